@@ -1,15 +1,21 @@
-use pyo3::{pyclass, pymethods, PyResult};
+use pyo3::exceptions::PyValueError;
+use pyo3::{pyclass, pymethods, PyErr, PyResult};
 
 use crate::ecore;
 
-/// OCR Strategy for PDF parsing
+/// OCR Strategy for PDF parsing. The number of OCRed pages is reported in the
+/// `pdf:ocrPageCount` metadata
 #[pyclass(eq, eq_int)]
 #[derive(Clone, PartialEq)]
 #[allow(non_camel_case_types)]
 pub enum PdfOcrStrategy {
+    /// Only extract the text of the content streams
     NO_OCR,
+    /// Only extract the text found by OCR on every page
     OCR_ONLY,
+    /// Extract both the text of the content streams and the text found by OCR
     OCR_AND_TEXT_EXTRACTION,
+    /// Let Tika decide per page, pages with too little text are OCRed
     AUTO,
 }
 
@@ -26,6 +32,54 @@ impl From<PdfOcrStrategy> for ecore::PdfOcrStrategy {
     }
 }
 
+/// How the metadata of the embedded documents of a recursive extraction relates to the metadata
+/// of the container
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum MergePolicy {
+    /// Keep the metadata of every document separate
+    NONE,
+    /// Add the keys the container lacks, with the values of the first embedded document that
+    /// has them
+    FIRST_WINS,
+    /// Add every value of the embedded documents the container does not have yet
+    COLLECT,
+}
+
+impl From<MergePolicy> for ecore::MergePolicy {
+    fn from(policy: MergePolicy) -> Self {
+        match policy {
+            MergePolicy::NONE => ecore::MergePolicy::None,
+            MergePolicy::FIRST_WINS => ecore::MergePolicy::FirstWins,
+            MergePolicy::COLLECT => ecore::MergePolicy::Collect,
+        }
+    }
+}
+
+/// A parser that documents are retried with when the parser of their detected type fails
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum ParserKind {
+    /// The plain text parser, which reads any bytes as text
+    PLAIN_TEXT,
+    /// The lenient HTML parser
+    HTML,
+    /// The XML parser
+    XML,
+}
+
+impl From<ParserKind> for ecore::ParserKind {
+    fn from(kind: ParserKind) -> Self {
+        match kind {
+            ParserKind::PLAIN_TEXT => ecore::ParserKind::PlainText,
+            ParserKind::HTML => ecore::ParserKind::Html,
+            ParserKind::XML => ecore::ParserKind::Xml,
+        }
+    }
+}
+
 /// PDF parsing configuration settings
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
@@ -83,6 +137,16 @@ impl PdfParserConfig {
         Ok(Self(inner))
     }
 
+    /// If true, describe the images drawn on the pages without extracting them. The number of
+    /// images is set to `extractous:pdf_image_count` in the metadata, and the recursive
+    /// extraction returns every image as an embedded document without content, with its page,
+    /// box, pixel size, type and alternate description in the metadata.
+    /// Default: false.
+    pub fn set_extract_inline_image_metadata(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_inline_image_metadata(val);
+        Ok(Self(inner))
+    }
+
     /// If the PDF contains marked content, try to extract text and its marked structure.
     /// Default: false.
     pub fn set_extract_marked_content(&self, val: bool) -> PyResult<Self> {
@@ -90,13 +154,60 @@ impl PdfParserConfig {
         Ok(Self(inner))
     }
 
-    /// If the PDF contains annotations, try to extract the text of the annotations.
+    /// If the PDF contains annotations, try to extract the text of the annotations, appended to
+    /// the content of their page. Sticky notes and highlights come with their author, subject
+    /// and comment.
     /// Default: true.
     pub fn set_extract_annotation_text(&self, val: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_extract_annotation_text(val);
         Ok(Self(inner))
     }
 
+    /// If the PDF contains an AcroForm, extract the values of its fields, to the content and to
+    /// the metadata under `extractous:form_field:` followed by the fully qualified field name.
+    /// Check boxes and radio buttons come with their export values.
+    /// Default: true.
+    pub fn set_extract_acro_form_content(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_acro_form_content(val);
+        Ok(Self(inner))
+    }
+
+    /// If true, the text of each page is sorted by position, top to bottom then left to right,
+    /// instead of following the order of the content stream, so the output only depends on
+    /// where the text is drawn.
+    /// Default: false.
+    pub fn set_sort_by_position(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_sort_by_position(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets a directory of TrueType and OpenType fonts that PDFBox uses for the fonts a PDF
+    /// document does not embed, such as CJK fonts extracted as boxes or wrong characters
+    /// otherwise. Only affects the PDF parser, the font files must be readable. The fonts stay
+    /// registered for the whole process.
+    /// Default: None.
+    pub fn set_font_dir(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_font_dir(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the password used to open encrypted PDF documents, embedded ones included. A
+    /// document that can not be opened with it, or without a password when none is set, fails
+    /// to extract.
+    /// Default: None.
+    pub fn set_password(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_password(val);
+        Ok(Self(inner))
+    }
+
+    /// Restricts the parsing to the pages from start to end, 1-based and inclusive. The range is
+    /// clamped to the page count of each document, embedded ones included.
+    /// Default: every page.
+    pub fn set_page_range(&self, start: u32, end: u32) -> PyResult<Self> {
+        let inner = self.0.clone().set_page_range(start, end);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -123,14 +234,18 @@ impl OfficeParserConfig {
         Self(ecore::OfficeParserConfig::new())
     }
 
-    /// Sets whether MSOffice parsers should extract macros.
+    /// Sets whether MSOffice parsers should extract macros. Each VBA module then becomes an
+    /// embedded document of the extract_*_recursive functions, of type `application/x-vba` and
+    /// with `X-TIKA:embedded_resource_type` set to `MACRO`, whose content is the source of the
+    /// module, left as it is by the content options of the extractor.
     /// Default: false.
     pub fn set_extract_macros(&self, val: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_extract_macros(val);
         Ok(Self(inner))
     }
 
-    /// Sets whether the docx parser should include deleted content.
+    /// Sets whether the docx parser should include deleted content, the text deleted with track
+    /// changes on. The xml output tags it as `<del class="deleted">`.
     /// Default: false.
     pub fn set_include_deleted_content(&self, val: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_include_deleted_content(val);
@@ -140,7 +255,8 @@ impl OfficeParserConfig {
     /// With track changes on for the docx parser, when a section is moved, the content is stored in
     /// both the "moveFrom" section and in the "moveTo" section. If you'd like to include the
     /// section both in its original location (moveFrom) and in its new location (moveTo),
-    /// set this to true. Default: false
+    /// set this to true. The xml output tags the moveFrom content as `<del class="moved">`.
+    /// Default: false
     pub fn set_include_move_from_content(&self, val: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_include_move_from_content(val);
         Ok(Self(inner))
@@ -189,6 +305,15 @@ impl OfficeParserConfig {
         Ok(Self(inner))
     }
 
+    /// Whether to add the reviewer comments of docx, xlsx and pptx files to the metadata. Each
+    /// comment adds one value to `extractous:comment_author`, `extractous:comment_date` and
+    /// `extractous:comment_text`, an empty one when the author or the time is not recorded.
+    /// Default: false
+    pub fn set_include_comments(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_include_comments(val);
+        Ok(Self(inner))
+    }
+
     /// Microsoft Excel files can sometimes contain phonetic (furigana) strings.
     /// This sets whether the parser will concatenate the phonetic runs to the original text.
     /// This is currently only supported by the xls and xlsx parsers (not the xlsb parser).
@@ -207,6 +332,15 @@ impl OfficeParserConfig {
         Ok(Self(inner))
     }
 
+    /// Sets the password used to open encrypted Office documents, embedded ones included. A
+    /// document that can not be opened with it, or without a password when none is set, fails
+    /// to extract.
+    /// Default: None.
+    pub fn set_password(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_password(val);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -240,7 +374,9 @@ impl TesseractOcrConfig {
         Ok(Self(inner))
     }
 
-    /// Sets the DPI (dots per inch) of the image to be processed.
+    /// Sets the DPI (dots per inch) the PDF pages are rendered at for OCR. Text rendered at a
+    /// low density is too coarse to be recognized. Must be between 150 and 1200, the extraction
+    /// fails otherwise.
     /// Default: 300.
     pub fn set_density(&self, val: i32) -> PyResult<Self> {
         let inner = self.0.clone().set_density(val);
@@ -271,13 +407,193 @@ impl TesseractOcrConfig {
         Ok(Self(inner))
     }
 
-    /// Sets the maximum time in seconds that Tesseract should spend on OCR.
-    /// Default: 120.
+    /// Sets the tesseract languages to be used for OCR together, such as `["eng", "ara"]`.
+    /// Unlike `set_language`, each language is checked against the installed language packs,
+    /// and a `ValueError` naming the first one missing is raised, as well as when tesseract can
+    /// not be run.
+    pub fn set_languages(&self, languages: Vec<String>) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_languages(languages)
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("{}", e)))?;
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum time in seconds that Tesseract should spend on OCR, which applies to
+    /// each image and each PDF page separately. When Tesseract runs out of time, the image or
+    /// page yields no text, the rest of the document is extracted as usual and the metadata
+    /// gets `X-TIKA:ocr_timed_out` set to `["true"]`.
+    /// Default: 130.
     pub fn set_timeout_seconds(&self, val: i32) -> PyResult<Self> {
         let inner = self.0.clone().set_timeout_seconds(val);
         Ok(Self(inner))
     }
 
+    /// Sets the directory of the tesseract executable, for a Tesseract installed outside of the
+    /// `PATH`. Raises a `ValueError` when the directory has no tesseract executable.
+    /// Default: "", the tesseract found on the `PATH`.
+    pub fn set_tesseract_path(&self, val: &str) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_tesseract_path(val)
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("{}", e)))?;
+        Ok(Self(inner))
+    }
+
+    /// Sets the directory of the tesseract language data, the `*.traineddata` files.
+    /// Default: "", the location the tesseract executable defaults to.
+    pub fn set_tessdata_path(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_tessdata_path(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the tesseract page segmentation mode (`--psm`), from 0 to 13, the extraction fails
+    /// otherwise. 1 is automatic with orientation and script detection, 3 fully automatic and
+    /// 6 a single uniform block of text, which suits forms and tables. Mode 0 yields no text.
+    /// Default: 1.
+    pub fn set_page_seg_mode(&self, val: u8) -> PyResult<Self> {
+        let inner = self.0.clone().set_page_seg_mode(val);
+        Ok(Self(inner))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Recursive extraction configuration settings
+///
+/// These settings control how embedded documents are walked by the `extract_*_recursive`
+/// functions.
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct RecursiveConfig(ecore::RecursiveConfig);
+
+impl From<RecursiveConfig> for ecore::RecursiveConfig {
+    fn from(config: RecursiveConfig) -> Self {
+        config.0
+    }
+}
+
+impl Default for RecursiveConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[pymethods]
+impl RecursiveConfig {
+    /// Creates a new instance of RecursiveConfig with default settings.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::RecursiveConfig::new())
+    }
+
+    /// Sets the maximum depth of embedded documents that are descended into. Depth 1 are the
+    /// documents directly embedded in the container. Leaf resources (images) one level below
+    /// the limit are still returned when extract_leaf_resources is enabled.
+    /// A negative value means no limit.
+    /// Default: -1.
+    pub fn set_max_container_depth(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_container_depth(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets whether leaf resources such as embedded images are returned as embedded documents.
+    /// Default: true.
+    pub fn set_extract_leaf_resources(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_leaf_resources(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum length of the content kept for each document of a recursive extraction,
+    /// the container included. Documents cut at this length have `extractous:content_truncated`
+    /// set to `true` in their metadata.
+    /// A negative value means no limit.
+    /// Default: -1.
+    pub fn set_per_document_max_length(&self, val: i32) -> PyResult<Self> {
+        let inner = self.0.clone().set_per_document_max_length(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets how the metadata of the embedded documents is merged into the metadata of the
+    /// container. The keys that describe a document itself, such as Content-Type, are never
+    /// merged.
+    /// Default: MergePolicy.NONE.
+    pub fn set_merge_embedded_metadata(&self, val: MergePolicy) -> PyResult<Self> {
+        let inner = self.0.clone().set_merge_embedded_metadata(val.into());
+        Ok(Self(inner))
+    }
+
+    /// Sets whether the length and SHA-256 digest of the raw bytes of every document, the
+    /// container included, are recorded in its metadata under `X-Content-Length` and
+    /// `X-Content-SHA256` (lowercase hex).
+    /// Default: False.
+    pub fn set_record_content_digests(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_record_content_digests(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets the maximum total size, in UTF-8 bytes, of the content of all the documents of a
+    /// recursive extraction. The document that uses up the budget is cut, with
+    /// `extractous:content_truncated` set, the documents after it are skipped, and the container
+    /// gets `extractous:output_truncated` set to "true".
+    /// Default: no limit.
+    pub fn set_max_total_output_bytes(&self, val: u64) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_total_output_bytes(val);
+        Ok(Self(inner))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Configuration of how the `extract_url*` functions fetch the documents
+#[pyclass]
+#[derive(Clone, PartialEq)]
+pub struct UrlFetchConfig(ecore::UrlFetchConfig);
+
+impl From<UrlFetchConfig> for ecore::UrlFetchConfig {
+    fn from(config: UrlFetchConfig) -> Self {
+        config.0
+    }
+}
+
+impl Default for UrlFetchConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[pymethods]
+impl UrlFetchConfig {
+    /// Creates a new instance of UrlFetchConfig with default settings.
+    #[new]
+    pub fn new() -> Self {
+        Self(ecore::UrlFetchConfig::new())
+    }
+
+    /// Sets the User-Agent header sent to http and https servers, for the document and for its
+    /// robots.txt, and the agent whose rules are followed when respect_robots is enabled.
+    /// Default: None, the default agent is sent and only the `User-agent: *` rules are followed.
+    pub fn set_user_agent(&self, val: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_user_agent(val);
+        Ok(Self(inner))
+    }
+
+    /// Sets whether the robots.txt of the site is fetched and honored before fetching an http
+    /// or https url. A url the rules disallow for the user agent is not fetched and the
+    /// extraction raises an error. A missing robots.txt allows everything and an unreachable
+    /// one, a server error, disallows everything.
+    /// Default: False.
+    pub fn set_respect_robots(&self, val: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_respect_robots(val);
+        Ok(Self(inner))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -1,11 +1,20 @@
-use crate::{ecore, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
-use pyo3::exceptions::PyTypeError;
+use crate::{
+    ecore, OfficeParserConfig, ParserKind, PdfParserConfig, RecursiveConfig, TesseractOcrConfig,
+    UrlFetchConfig,
+};
+use ecore::MetadataExt;
+use pyo3::exceptions::{PyTimeoutError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
+use pyo3::types::PyBytes;
 use pyo3::types::PyDict;
 use pyo3::types::PyList;
 use std::collections::HashMap;
 use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
 
 // PyO3 supports unit-only enums (which contain only unit variants)
 // These simple enums behave similarly to Python's enumerations (enum.Enum)
@@ -17,6 +26,10 @@ pub enum CharSet {
     UTF_8,
     US_ASCII,
     UTF_16BE,
+    UTF_16LE,
+    ISO_8859_1,
+    WINDOWS_1252,
+    SHIFT_JIS,
 }
 
 impl From<CharSet> for ecore::CharSet {
@@ -25,10 +38,77 @@ impl From<CharSet> for ecore::CharSet {
             CharSet::UTF_8 => ecore::CharSet::UTF_8,
             CharSet::US_ASCII => ecore::CharSet::US_ASCII,
             CharSet::UTF_16BE => ecore::CharSet::UTF_16BE,
+            CharSet::UTF_16LE => ecore::CharSet::UTF_16LE,
+            CharSet::ISO_8859_1 => ecore::CharSet::ISO_8859_1,
+            CharSet::WINDOWS_1252 => ecore::CharSet::WINDOWS_1252,
+            CharSet::SHIFT_JIS => ecore::CharSet::SHIFT_JIS,
         }
     }
 }
 
+/// Writing scripts supported by the script filter
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Hangul,
+    Cjk,
+}
+
+impl From<Script> for ecore::Script {
+    fn from(script: Script) -> Self {
+        match script {
+            Script::Latin => ecore::Script::Latin,
+            Script::Cyrillic => ecore::Script::Cyrillic,
+            Script::Greek => ecore::Script::Greek,
+            Script::Arabic => ecore::Script::Arabic,
+            Script::Hebrew => ecore::Script::Hebrew,
+            Script::Devanagari => ecore::Script::Devanagari,
+            Script::Thai => ecore::Script::Thai,
+            Script::Hangul => ecore::Script::Hangul,
+            Script::Cjk => ecore::Script::Cjk,
+        }
+    }
+}
+
+impl From<ecore::Script> for Script {
+    fn from(script: ecore::Script) -> Self {
+        match script {
+            ecore::Script::Latin => Script::Latin,
+            ecore::Script::Cyrillic => Script::Cyrillic,
+            ecore::Script::Greek => Script::Greek,
+            ecore::Script::Arabic => Script::Arabic,
+            ecore::Script::Hebrew => Script::Hebrew,
+            ecore::Script::Devanagari => Script::Devanagari,
+            ecore::Script::Thai => Script::Thai,
+            ecore::Script::Hangul => Script::Hangul,
+            ecore::Script::Cjk => Script::Cjk,
+        }
+    }
+}
+
+/// Returns the Script most of the letters of the text are written in, or None when it has no
+/// letters of the supported scripts. Han, Hiragana, Katakana and Bopomofo count as Script.Cjk,
+/// Hangul as Script.Hangul
+#[pyfunction]
+pub fn dominant_script(text: &str) -> Option<Script> {
+    ecore::dominant_script(text).map(Script::from)
+}
+
+/// Returns the 64-bit SimHash fingerprint of the text: the bits of the FNV-1a hashes of its
+/// distinct lowercased tokens, set where most hashes have them set. Near-duplicate texts get
+/// fingerprints that differ in few bits
+#[pyfunction]
+pub fn simhash(text: &str) -> u64 {
+    ecore::simhash(text)
+}
+
 /// StreamReader represents a stream of bytes
 ///
 /// Can be used to perform buffered reading.
@@ -98,6 +178,13 @@ pub struct PyDocument {
     pub content: String,
     // Store dict as generic Py<PyAny> to avoid lifetime issues; expose as property
     metadata: Py<PyAny>,
+    /// Entities of the content, filled when Extractor.set_extract_entities(True) is set
+    #[pyo3(get)]
+    pub entities: Vec<PyEntity>,
+    /// Index in RecursiveExtraction.documents of the document this one is embedded in, None for
+    /// the container and outside of RecursiveExtraction
+    #[pyo3(get)]
+    pub parent: Option<usize>,
 }
 
 #[pymethods]
@@ -110,6 +197,713 @@ impl PyDocument {
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
             .map(|b| b.clone())
     }
+
+    /// Original file name of the document (resourceName), such as the name of an archive entry.
+    /// The embedded documents of a recursive extraction always have one
+    pub fn resource_name(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.first_value(py, "resourceName")
+    }
+
+    /// Path of an embedded document in its container (X-TIKA:embedded_resource_path), such as
+    /// "/inner.zip/nested.txt", None for the container
+    pub fn embedded_path(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.first_value(py, "X-TIKA:embedded_resource_path")
+    }
+}
+
+impl PyDocument {
+    fn first_value(&self, py: Python<'_>, key: &str) -> PyResult<Option<String>> {
+        let Some(values) = self.metadata(py)?.get_item(key)? else {
+            return Ok(None);
+        };
+        let values: Vec<String> = values.extract()?;
+        Ok(values.into_iter().next())
+    }
+}
+
+/// Kinds of entities found by find_entities
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum EntityKind {
+    Email,
+    Phone,
+    Url,
+}
+
+impl From<ecore::EntityKind> for EntityKind {
+    fn from(kind: ecore::EntityKind) -> Self {
+        match kind {
+            ecore::EntityKind::Email => EntityKind::Email,
+            ecore::EntityKind::Phone => EntityKind::Phone,
+            ecore::EntityKind::Url => EntityKind::Url,
+        }
+    }
+}
+
+/// An email address, phone number or URL found in a text. start and end are character offsets,
+/// text[start:end] is the entity as written and value its normalized form
+#[pyclass(name = "Entity")]
+#[derive(Clone)]
+pub struct PyEntity {
+    #[pyo3(get)]
+    pub kind: EntityKind,
+    #[pyo3(get)]
+    pub value: String,
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+}
+
+/// Converts the byte offsets of the entities of text into the character offsets python strings
+/// are indexed with. The entities are ordered by position, so the text is walked once.
+fn entities_to_py(text: &str, entities: &[ecore::Entity]) -> Vec<PyEntity> {
+    let mut byte_offset = 0;
+    let mut char_offset = 0;
+    let mut to_char_offset = |offset: usize| {
+        char_offset += text[byte_offset..offset].chars().count();
+        byte_offset = offset;
+        char_offset
+    };
+    entities
+        .iter()
+        .map(|entity| PyEntity {
+            kind: entity.kind.into(),
+            value: entity.value.clone(),
+            start: to_char_offset(entity.start),
+            end: to_char_offset(entity.end),
+        })
+        .collect()
+}
+
+/// Returns the email addresses, phone numbers and URLs of the text as a list of Entity, ordered
+/// by position. The scanner is heuristic and tuned against false positives
+#[pyfunction]
+pub fn find_entities(text: &str) -> Vec<PyEntity> {
+    entities_to_py(text, &ecore::find_entities(text))
+}
+
+/// A digital signature found in a document
+#[pyclass(name = "SignatureInfo")]
+pub struct PySignatureInfo {
+    /// Name of the signer
+    #[pyo3(get)]
+    pub signer: Option<String>,
+    /// Signing time as an ISO-8601 string
+    #[pyo3(get)]
+    pub signed_at: Option<String>,
+    /// Whether the signature matches the signed content, None if it could not be checked
+    #[pyo3(get)]
+    pub valid: Option<bool>,
+}
+
+impl From<ecore::SignatureInfo> for PySignatureInfo {
+    fn from(info: ecore::SignatureInfo) -> Self {
+        Self {
+            signer: info.signer,
+            signed_at: info.signed_at,
+            valid: info.valid,
+        }
+    }
+}
+
+/// A font referenced by a PDF document
+#[pyclass(name = "FontInfo")]
+pub struct PyFontInfo {
+    /// PostScript name of the font, None for the fonts without one
+    #[pyo3(get)]
+    pub name: Option<String>,
+    /// Subtype of the font: Type0, Type1, MMType1, Type3 or TrueType
+    #[pyo3(get)]
+    pub subtype: String,
+    /// Whether the font program is embedded in the document
+    #[pyo3(get)]
+    pub embedded: bool,
+}
+
+impl From<ecore::FontInfo> for PyFontInfo {
+    fn from(font: ecore::FontInfo) -> Self {
+        Self {
+            name: font.name,
+            subtype: font.subtype,
+            embedded: font.embedded,
+        }
+    }
+}
+
+/// The PDF/A conformance and the accessibility tagging a PDF document declares, returned by
+/// Extractor.pdf_conformance_info
+#[pyclass(name = "PdfConformance")]
+pub struct PyPdfConformance {
+    /// Declared PDF/A part and conformance level, e.g. "2B", None if none is declared
+    #[pyo3(get)]
+    pub pdfa_level: Option<String>,
+    /// Whether the document is marked as tagged
+    #[pyo3(get)]
+    pub tagged: bool,
+    /// Whether the document has a structure tree
+    #[pyo3(get)]
+    pub has_structure_tree: bool,
+}
+
+impl From<ecore::PdfConformance> for PyPdfConformance {
+    fn from(info: ecore::PdfConformance) -> Self {
+        Self {
+            pdfa_level: info.pdfa_level,
+            tagged: info.tagged,
+            has_structure_tree: info.has_structure_tree,
+        }
+    }
+}
+
+/// A revision from the history an Office document keeps, returned by
+/// Extractor.extract_revisions
+#[pyclass(name = "Revision")]
+pub struct PyRevision {
+    /// Name of the user who made the revision
+    #[pyo3(get)]
+    pub author: Option<String>,
+    /// Time of the revision as an ISO-8601 string, None for the save history of Word documents
+    #[pyo3(get)]
+    pub timestamp: Option<String>,
+    /// Path the document was saved to, only recorded by the save history of Word documents
+    #[pyo3(get)]
+    pub location: Option<String>,
+}
+
+impl From<ecore::Revision> for PyRevision {
+    fn from(revision: ecore::Revision) -> Self {
+        Self {
+            author: revision.author,
+            timestamp: revision.timestamp,
+            location: revision.location,
+        }
+    }
+}
+
+/// A word of a PDF document with its bounding box. Coordinates are in PDF points (1/72 inch)
+/// with the origin at the top-left corner of the displayed page, y growing downwards
+#[pyclass(name = "WordBox")]
+pub struct PyWordBox {
+    #[pyo3(get)]
+    pub text: String,
+    /// 1-based page number
+    #[pyo3(get)]
+    pub page: u32,
+    #[pyo3(get)]
+    pub x: f32,
+    #[pyo3(get)]
+    pub y: f32,
+    #[pyo3(get)]
+    pub width: f32,
+    #[pyo3(get)]
+    pub height: f32,
+}
+
+impl From<ecore::WordBox> for PyWordBox {
+    fn from(word: ecore::WordBox) -> Self {
+        Self {
+            text: word.text,
+            page: word.page,
+            x: word.x,
+            y: word.y,
+            width: word.width,
+            height: word.height,
+        }
+    }
+}
+
+/// A section of a document, returned by Extractor.extract_file_sections. heading is None and
+/// level 0 for the content that precedes the first heading
+#[pyclass(name = "Section")]
+#[derive(Clone)]
+pub struct PySection {
+    #[pyo3(get)]
+    pub heading: Option<String>,
+    #[pyo3(get)]
+    pub level: u8,
+    #[pyo3(get)]
+    pub content: String,
+    #[pyo3(get)]
+    pub subsections: Vec<PySection>,
+}
+
+impl From<ecore::Section> for PySection {
+    fn from(section: ecore::Section) -> Self {
+        Self {
+            heading: section.heading,
+            level: section.level,
+            content: section.content,
+            subsections: section
+                .subsections
+                .into_iter()
+                .map(PySection::from)
+                .collect(),
+        }
+    }
+}
+
+/// A table of a document, returned by Extractor.extract_file_tables. header is the list of the
+/// column names, None when the table has no recognizable header row
+#[pyclass(name = "Table")]
+#[derive(Clone)]
+pub struct PyTable {
+    #[pyo3(get)]
+    pub header: Option<Vec<String>>,
+    #[pyo3(get)]
+    pub rows: Vec<Vec<String>>,
+}
+
+impl From<ecore::Table> for PyTable {
+    fn from(table: ecore::Table) -> Self {
+        Self {
+            header: table.header,
+            rows: table.rows,
+        }
+    }
+}
+
+/// A sentence of the text of a file, returned by Extractor.extract_file_sentences. start and end
+/// are the character offsets of the sentence in the text returned by extract_file_to_string
+#[pyclass(name = "Sentence")]
+pub struct PySentence {
+    #[pyo3(get)]
+    pub text: String,
+    #[pyo3(get)]
+    pub start: usize,
+    #[pyo3(get)]
+    pub end: usize,
+}
+
+/// Converts the byte offsets of the sentences of the text, in order, to character offsets
+fn sentences_to_py(text: &str, sentences: &[ecore::Sentence]) -> Vec<PySentence> {
+    let mut byte_offset = 0;
+    let mut char_offset = 0;
+    let mut to_char_offset = |offset: usize| {
+        char_offset += text[byte_offset..offset].chars().count();
+        byte_offset = offset;
+        char_offset
+    };
+    sentences
+        .iter()
+        .map(|sentence| PySentence {
+            text: sentence.text.clone(),
+            start: to_char_offset(sentence.start),
+            end: to_char_offset(sentence.end),
+        })
+        .collect()
+}
+
+/// The OCR text of one image of a document, returned by Extractor.extract_image_ocr. page is the
+/// 1-based page the image is drawn on, None outside of paginated formats such as PDF
+#[pyclass(name = "ImageOcr")]
+pub struct PyImageOcr {
+    #[pyo3(get)]
+    pub image_index: usize,
+    #[pyo3(get)]
+    pub page: Option<u32>,
+    #[pyo3(get)]
+    pub text: String,
+}
+
+impl From<ecore::ImageOcr> for PyImageOcr {
+    fn from(image: ecore::ImageOcr) -> Self {
+        Self {
+            image_index: image.image_index,
+            page: image.page,
+            text: image.text,
+        }
+    }
+}
+
+/// A contact of a vCard file, returned by Extractor.extract_vcard
+#[pyclass(name = "Contact")]
+pub struct PyContact {
+    #[pyo3(get)]
+    pub name: Option<String>,
+    #[pyo3(get)]
+    pub emails: Vec<String>,
+    #[pyo3(get)]
+    pub phones: Vec<String>,
+}
+
+impl From<ecore::Contact> for PyContact {
+    fn from(contact: ecore::Contact) -> Self {
+        Self {
+            name: contact.name,
+            emails: contact.emails,
+            phones: contact.phones,
+        }
+    }
+}
+
+/// An event of an iCalendar file, returned by Extractor.extract_icalendar. Dates are kept as
+/// written in the calendar, e.g. "20240105T090000Z"
+#[pyclass(name = "CalendarEvent")]
+pub struct PyCalendarEvent {
+    #[pyo3(get)]
+    pub summary: Option<String>,
+    #[pyo3(get)]
+    pub start: Option<String>,
+    #[pyo3(get)]
+    pub end: Option<String>,
+    #[pyo3(get)]
+    pub location: Option<String>,
+}
+
+impl From<ecore::CalendarEvent> for PyCalendarEvent {
+    fn from(event: ecore::CalendarEvent) -> Self {
+        Self {
+            summary: event.summary,
+            start: event.start,
+            end: event.end,
+            location: event.location,
+        }
+    }
+}
+
+/// Chat export formats recognized by Extractor.extract_chat
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum ChatFormat {
+    WhatsApp,
+    Slack,
+}
+
+impl From<ecore::ChatFormat> for ChatFormat {
+    fn from(format: ecore::ChatFormat) -> Self {
+        match format {
+            ecore::ChatFormat::WhatsApp => ChatFormat::WhatsApp,
+            ecore::ChatFormat::Slack => ChatFormat::Slack,
+        }
+    }
+}
+
+/// A message of a chat export. Timestamps are kept as written in the export, e.g.
+/// "12/31/23, 9:41 PM" for WhatsApp or "1704067200.000100" for Slack
+#[pyclass(name = "ChatMessage")]
+#[derive(Clone)]
+pub struct PyChatMessage {
+    #[pyo3(get)]
+    pub sender: Option<String>,
+    #[pyo3(get)]
+    pub timestamp: Option<String>,
+    #[pyo3(get)]
+    pub text: String,
+}
+
+impl From<ecore::ChatMessage> for PyChatMessage {
+    fn from(message: ecore::ChatMessage) -> Self {
+        Self {
+            sender: message.sender,
+            timestamp: message.timestamp,
+            text: message.text,
+        }
+    }
+}
+
+/// A chat export, returned by Extractor.extract_chat. When the format is not recognized, format
+/// is None, messages is empty and text holds the extracted plain text
+#[pyclass(name = "Chat")]
+pub struct PyChat {
+    #[pyo3(get)]
+    pub format: Option<ChatFormat>,
+    #[pyo3(get)]
+    pub messages: Vec<PyChatMessage>,
+    #[pyo3(get)]
+    pub text: String,
+}
+
+impl From<ecore::Chat> for PyChat {
+    fn from(chat: ecore::Chat) -> Self {
+        Self {
+            format: chat.format.map(ChatFormat::from),
+            messages: chat.messages.into_iter().map(PyChatMessage::from).collect(),
+            text: chat.text,
+        }
+    }
+}
+
+/// The core and extended properties of an Office document, returned by
+/// Extractor.extract_office_properties. Missing properties are None, dates are ISO-8601 strings
+#[pyclass(name = "OfficeProperties")]
+pub struct PyOfficeProperties {
+    #[pyo3(get)]
+    pub title: Option<String>,
+    #[pyo3(get)]
+    pub subject: Option<String>,
+    #[pyo3(get)]
+    pub creator: Option<String>,
+    #[pyo3(get)]
+    pub keywords: Option<String>,
+    #[pyo3(get)]
+    pub description: Option<String>,
+    #[pyo3(get)]
+    pub category: Option<String>,
+    #[pyo3(get)]
+    pub language: Option<String>,
+    #[pyo3(get)]
+    pub last_modified_by: Option<String>,
+    #[pyo3(get)]
+    pub revision: Option<u32>,
+    #[pyo3(get)]
+    pub created: Option<String>,
+    #[pyo3(get)]
+    pub modified: Option<String>,
+    #[pyo3(get)]
+    pub last_printed: Option<String>,
+    #[pyo3(get)]
+    pub application: Option<String>,
+    #[pyo3(get)]
+    pub app_version: Option<String>,
+    #[pyo3(get)]
+    pub company: Option<String>,
+    #[pyo3(get)]
+    pub manager: Option<String>,
+    #[pyo3(get)]
+    pub template: Option<String>,
+    #[pyo3(get)]
+    pub total_editing_time: Option<u64>,
+    #[pyo3(get)]
+    pub page_count: Option<u32>,
+    #[pyo3(get)]
+    pub word_count: Option<u32>,
+    #[pyo3(get)]
+    pub character_count: Option<u32>,
+    #[pyo3(get)]
+    pub character_count_with_spaces: Option<u32>,
+    #[pyo3(get)]
+    pub paragraph_count: Option<u32>,
+    #[pyo3(get)]
+    pub line_count: Option<u32>,
+    #[pyo3(get)]
+    pub slide_count: Option<u32>,
+}
+
+impl From<ecore::OfficeProperties> for PyOfficeProperties {
+    fn from(properties: ecore::OfficeProperties) -> Self {
+        Self {
+            title: properties.title,
+            subject: properties.subject,
+            creator: properties.creator,
+            keywords: properties.keywords,
+            description: properties.description,
+            category: properties.category,
+            language: properties.language,
+            last_modified_by: properties.last_modified_by,
+            revision: properties.revision,
+            created: properties.created,
+            modified: properties.modified,
+            last_printed: properties.last_printed,
+            application: properties.application,
+            app_version: properties.app_version,
+            company: properties.company,
+            manager: properties.manager,
+            template: properties.template,
+            total_editing_time: properties.total_editing_time,
+            page_count: properties.page_count,
+            word_count: properties.word_count,
+            character_count: properties.character_count,
+            character_count_with_spaces: properties.character_count_with_spaces,
+            paragraph_count: properties.paragraph_count,
+            line_count: properties.line_count,
+            slide_count: properties.slide_count,
+        }
+    }
+}
+
+/// Iterator over the paragraphs of a document, returned by Extractor.extract_file_paragraphs.
+/// The document is parsed in the background while the paragraphs are consumed
+#[pyclass]
+pub struct ParagraphIterator {
+    paragraphs: Mutex<Option<ecore::Paragraphs>>,
+}
+
+#[pymethods]
+impl ParagraphIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> Option<String> {
+        // Waiting for the next paragraph can take a while, let other python threads run
+        py.detach(|| {
+            let mut paragraphs = self.paragraphs.lock().unwrap_or_else(|e| e.into_inner());
+            paragraphs.as_mut().and_then(|p| p.next())
+        })
+    }
+
+    /// Consumes the remaining paragraphs and returns the metadata of the document as dict
+    pub fn finish<'py>(&self, py: Python<'py>) -> PyResult<Py<PyAny>> {
+        let paragraphs = self
+            .paragraphs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("finish() was already called")
+            })?;
+        let metadata = py
+            .detach(|| paragraphs.finish())
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok(py_metadata.into())
+    }
+}
+
+/// 逐个返回递归提取文档的迭代器，由 Extractor.extract_file_recursive_iter 返回。
+/// 容器文档最先返回，只有解析前已知的元数据，内容为空；之后文件在后台解析，每个嵌套文档
+/// 解析完成后立即返回，最后再返回内容完整的容器文档。解析失败时抛出异常
+#[pyclass]
+pub struct RecursiveDocumentIterator {
+    documents: Mutex<ecore::RecursiveDocuments>,
+}
+
+#[pymethods]
+impl RecursiveDocumentIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<Py<PyDocument>>> {
+        // 等待下一个文档可能较久，期间释放 GIL
+        let next = py.detach(|| {
+            self.documents
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .next()
+        });
+        match next {
+            Some(Ok(doc)) => Ok(Some(document_to_py(py, &doc)?)),
+            Some(Err(e)) => Err(PyErr::new::<PyTypeError, _>(format!("{:?}", e))),
+            None => Ok(None),
+        }
+    }
+}
+
+type DirectoryResult = (String, ecore::ExtractResult<(String, ecore::Metadata)>);
+
+/// Iterator over the files of a directory tree, returned by Extractor.extract_directory and
+/// Extractor.extract_files_streaming. Yields the errors of the walk first, then (path, Document
+/// or exception) tuples in the order the extractions finish. Dropping the iterator stops the
+/// workers once their current file is done
+#[pyclass]
+pub struct DirectoryIterator {
+    /// Directories that could not be read, yielded before the files
+    errors: Mutex<std::vec::IntoIter<DirectoryResult>>,
+    results: Mutex<mpsc::Receiver<DirectoryResult>>,
+    total: usize,
+    done: AtomicUsize,
+    on_progress: Option<Py<PyAny>>,
+}
+
+#[pymethods]
+impl DirectoryIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<(String, Py<PyAny>)>> {
+        if self.done.load(Ordering::SeqCst) >= self.total {
+            return Ok(None);
+        }
+        let error = self.errors.lock().unwrap_or_else(|e| e.into_inner()).next();
+        let received = match error {
+            Some(error) => Some(error),
+            // Waiting for the next file can take a while, let other python threads run
+            None => py.detach(|| {
+                self.results
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .recv()
+                    .ok()
+            }),
+        };
+        let Some((path, result)) = received else {
+            return Ok(None);
+        };
+        let done = self.done.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let item = match result {
+            Ok((content, metadata)) => {
+                let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+                let document = PyDocument {
+                    content,
+                    metadata: py_metadata.into(),
+                    entities: Vec::new(),
+                    parent: None,
+                };
+                Py::new(py, document)?.into_any()
+            }
+            Err(e) => PyErr::new::<PyTypeError, _>(format!("{:?}", e))
+                .into_value(py)
+                .into_any(),
+        };
+        if let Some(on_progress) = &self.on_progress {
+            on_progress.call1(py, (done, self.total, &path))?;
+        }
+        Ok(Some((path, item)))
+    }
+
+    /// Number of files the iterator yields
+    #[getter]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+/// Collects the files under dir whose path relative to root matches the pattern, in sorted
+/// order. Symbolic links to directories are not followed. Directories that can not be read are
+/// returned as errors
+fn walk_directory(
+    root: &Path,
+    dir: &Path,
+    pattern: &glob::Pattern,
+    files: &mut Vec<PathBuf>,
+    errors: &mut Vec<DirectoryResult>,
+) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            let error = ecore::Error::IoError(format!("Could not read directory: {}", e));
+            errors.push((dir.to_string_lossy().into_owned(), Err(error)));
+            return;
+        }
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    for path in paths {
+        let Ok(file_type) = std::fs::symlink_metadata(&path).map(|m| m.file_type()) else {
+            continue;
+        };
+        if file_type.is_dir() {
+            walk_directory(root, &path, pattern, files, errors);
+        } else if path.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if pattern.matches_path_with(relative, options) {
+                files.push(path);
+            }
+        }
+    }
+}
+
+/// The bodies and attachments of an email message, returned by Extractor.extract_mime_message.
+/// text_plain and text_html are None when the message has no such body
+#[pyclass(name = "MimeMessage")]
+pub struct PyMimeMessage {
+    #[pyo3(get)]
+    pub text_plain: Option<String>,
+    #[pyo3(get)]
+    pub text_html: Option<String>,
+    /// Every other part of the message, extracted as a Document
+    #[pyo3(get)]
+    pub attachments: Vec<Py<PyDocument>>,
 }
 
 /// Python-visible RecursiveExtraction (documents + helpers)
@@ -141,6 +935,17 @@ impl PyRecursiveExtraction {
     pub fn total_count(&self) -> usize {
         self.docs.len()
     }
+
+    /// Indexes in documents of the documents directly embedded in documents[index]. Calling it
+    /// recursively from the container, index 0, rebuilds the whole hierarchy
+    pub fn children(&self, index: usize, py: Python<'_>) -> Vec<usize> {
+        self.docs
+            .iter()
+            .enumerate()
+            .filter(|(_, doc)| doc.borrow(py).parent == Some(index))
+            .map(|(child, _)| child)
+            .collect()
+    }
 }
 
 /// `Extractor` is the entry for all extract APIs
@@ -171,6 +976,14 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the encoding to use for when extracting text to a stream by its name, for the
+    /// charsets CharSet does not list. An unsupported name fails the stream extractions.
+    /// Not used for extract_to_string functions.
+    pub fn set_encoding_name(&self, name: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_encoding_name(name);
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the PDF parser
     pub fn set_pdf_config(&self, config: PdfParserConfig) -> PyResult<Self> {
         let inner = self.0.clone().set_pdf_config(config.into());
@@ -189,6 +1002,265 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the configuration for fetching the documents of the extract_url* functions: the user
+    /// agent, and whether the robots.txt of the site is honored
+    pub fn set_url_fetch_config(&self, config: UrlFetchConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_url_fetch_config(config.into());
+        Ok(Self(inner))
+    }
+
+    /// Set the configuration for the recursive extraction.
+    /// Used only for the extract_*_recursive functions
+    pub fn set_recursive_config(&self, config: RecursiveConfig) -> PyResult<Self> {
+        let inner = self.0.clone().set_recursive_config(config.into());
+        Ok(Self(inner))
+    }
+
+    /// Set how many of the documents directly embedded in the container are parsed at the same
+    /// time by the extract_*_recursive functions, each one with its own embedded documents. The
+    /// documents are returned in the same order as a sequential extraction.
+    /// extract_file_recursive_to_ndjson ignores it
+    /// Default: 1, sequential
+    pub fn set_recursive_parallelism(&self, parallelism: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_recursive_parallelism(parallelism);
+        Ok(Self(inner))
+    }
+
+    /// Set the media types of the embedded documents the extract_*_recursive functions parse and
+    /// return, such as "application/pdf", or a wildcard, such as "image/*". The other embedded
+    /// documents are skipped before they are parsed, together with the documents embedded in them
+    /// Default: none, every embedded document is parsed
+    pub fn set_embedded_mime_filter(&self, allow: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_embedded_mime_filter(allow);
+        Ok(Self(inner))
+    }
+
+    /// Set the maximum number of embedded documents the extract_*_recursive functions parse.
+    /// The documents past it are left out and the container gets
+    /// "extractous:embedded_truncated" set to "true"
+    /// Default: no limit
+    pub fn set_max_embedded_count(&self, max: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_embedded_count(max);
+        Ok(Self(inner))
+    }
+
+    /// Set the maximum total size, in bytes, of the raw embedded documents the
+    /// extract_*_recursive functions decompress. The document that reaches it is cut, the
+    /// documents after it are left out and the container gets "extractous:embedded_truncated"
+    /// set to "true"
+    /// Default: no limit
+    pub fn set_max_embedded_bytes(&self, max: u64) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_embedded_bytes(max);
+        Ok(Self(inner))
+    }
+
+    /// Keep only the text written in the given script. Long runs of text in other scripts are
+    /// removed from the extracted string. Not applied to streams and xml output.
+    /// Default: off
+    pub fn set_script_filter(&self, script: Script) -> PyResult<Self> {
+        let inner = self.0.clone().set_script_filter(script.into());
+        Ok(Self(inner))
+    }
+
+    /// Set the minimum number of characters of another script a run must contain to be removed
+    /// Default: 20
+    pub fn set_script_filter_threshold(&self, threshold: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_script_filter_threshold(threshold);
+        Ok(Self(inner))
+    }
+
+    /// Set whether each line of plain text documents (text files, logs, source code, ...) is
+    /// prefixed with its line number in the source and a tab. Other formats and the xml output
+    /// are left unchanged
+    /// Default: False
+    pub fn set_emit_line_numbers(&self, emit_line_numbers: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_emit_line_numbers(emit_line_numbers);
+        Ok(Self(inner))
+    }
+
+    /// Set the maximum nesting depth of the elements produced while parsing. Documents nested
+    /// deeper, such as maliciously crafted HTML or XML, fail to parse instead of exhausting the
+    /// stack. 0 means no limit
+    /// Default: 100
+    pub fn set_max_element_depth(&self, max_depth: u32) -> PyResult<Self> {
+        let inner = self.0.clone().set_max_element_depth(max_depth);
+        Ok(Self(inner))
+    }
+
+    /// Set whether large jobs, such as OCR of big scanned PDF files, buffer their data in
+    /// temporary files instead of memory. Lowers the memory use at the cost of disk I/O
+    /// Default: False
+    pub fn set_ocr_spill_to_disk(&self, spill_to_disk: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_ocr_spill_to_disk(spill_to_disk);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the text a reader can not see is left out of the extracted content:
+    /// invisible or white filled PDF text and HTML elements hidden by the hidden attribute or an
+    /// inline display: none / visibility: hidden style. Best-effort
+    /// Default: False
+    pub fn set_exclude_hidden_text(&self, exclude: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_exclude_hidden_text(exclude);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the charset a document declares in its content, such as the meta charset of
+    /// an HTML page, is used to decode it. When False the declaration is ignored and the charset
+    /// is detected from the bytes instead, which fixes pages that declare the wrong charset
+    /// Default: True
+    pub fn set_trust_declared_charset(&self, trust: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_trust_declared_charset(trust);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the charset of the text documents is detected with ICU, falling back to
+    /// UTF-8 when the detection is ambiguous. The charset used is recorded in Content-Encoding.
+    /// Default: false
+    pub fn set_encoding_auto(&self, auto: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_encoding_auto(auto);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the lines repeated on more than half of the pages of a document, such as
+    /// running headers and footers, are kept only once, where they first appear. Numbers are
+    /// ignored when comparing lines, so "Page 2 of 10" footers are removed too. Applies to PDF
+    /// pages and presentation slides, for the *_to_string and *_recursive methods only
+    /// Default: False
+    pub fn set_dedup_repeated_lines(&self, dedup: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_dedup_repeated_lines(dedup);
+        Ok(Self(inner))
+    }
+
+    /// Set the media types, such as "application/x-foo", and the file extensions, such as ".dat"
+    /// or "log", of the documents that are always parsed as plain text, whatever their content is
+    /// detected as. Entries containing a "/" are media types, the others extensions
+    /// Default: none
+    pub fn set_force_text_types(&self, types: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_force_text_types(types);
+        Ok(Self(inner))
+    }
+
+    /// Set the size in bytes above which the extract_*_to_string methods stream the content
+    /// from the java side in chunks of this size, instead of building it as a single java string
+    /// first. This lowers the peak memory use on large documents, the returned content is the same
+    /// Default: the content is always built as a single java string
+    pub fn set_string_streaming_threshold(&self, threshold: usize) -> PyResult<Self> {
+        let inner = self.0.clone().set_string_streaming_threshold(threshold);
+        Ok(Self(inner))
+    }
+
+    /// Set the parsers a document is retried with, in order, when the parser of its detected type
+    /// fails on it. The content of the first one that succeeds is returned, with its name in the
+    /// "extractous:fallback_parser" metadata. Encrypted and empty documents are not retried, nor
+    /// are the documents parsed successfully without content
+    /// Default: none
+    pub fn set_parser_fallback_chain(&self, parsers: Vec<ParserKind>) -> PyResult<Self> {
+        let parsers = parsers.into_iter().map(ParserKind::into).collect();
+        let inner = self.0.clone().set_parser_fallback_chain(parsers);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the parsers each document went through, from the auto-detecting parser down to
+    /// the parser of its format, are listed by class name in the "extractous:parser_chain"
+    /// metadata. A fallback parser that took over comes after the parser that failed
+    /// Default: False
+    pub fn set_record_parser_chain(&self, record: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_record_parser_chain(record);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the ZIP based documents (archives, OOXML documents, JARs, ...) whose central
+    /// directory is corrupt or missing are parsed from the entries that can still be read,
+    /// instead of failing. The number of entries recovered is set in the
+    /// "extractous:zip_recovered_entries" metadata
+    /// Default: False
+    pub fn set_zip_recovery(&self, zip_recovery: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_zip_recovery(zip_recovery);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the superscripts and subscripts (<sup> and <sub> elements) are kept in the
+    /// text output, as Unicode superscript or subscript characters when they all have one,
+    /// H₂O and x², and between markers otherwise, ^{st} and _{eq}
+    /// Default: False
+    pub fn set_preserve_scripts(&self, preserve_scripts: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_preserve_scripts(preserve_scripts);
+        Ok(Self(inner))
+    }
+
+    /// Set a soft limit, in seconds, on the time spent parsing a document. Once it has elapsed,
+    /// the extraction stops and returns the content gathered so far, with
+    /// "extractous:content_partial" set to "true" in the metadata, instead of failing like the
+    /// timeout argument of the extract methods. The budget is checked as content is produced, so
+    /// a long OCR step finishes first. extract_string_max_length still applies
+    /// Default: no budget
+    pub fn set_soft_time_budget(&self, seconds: f64) -> PyResult<Self> {
+        let budget = Duration::try_from_secs_f64(seconds).map_err(|_| {
+            PyErr::new::<PyValueError, _>(format!("invalid time budget: {} seconds", seconds))
+        })?;
+        let inner = self.0.clone().set_soft_time_budget(budget);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the email addresses, phone numbers and URLs of the content are returned in
+    /// the entities of each Document of the *_recursive methods, with character offsets into its
+    /// content. Not computed for the xml output. For the *_to_string methods, call
+    /// find_entities on the returned content
+    /// Default: False
+    pub fn set_extract_entities(&self, extract_entities: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_entities(extract_entities);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the dominant script of the content, see dominant_script, is reported in the
+    /// extractous:dominant_script metadata key, e.g. Cjk for Chinese or Japanese text. Applies to
+    /// the *_to_string and *_recursive methods, and is skipped when the output is xml
+    /// Default: False
+    pub fn set_detect_script(&self, detect_script: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_detect_script(detect_script);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the language of the content, see detect_language, is reported in the
+    /// language metadata key as an ISO 639-1 code, e.g. de. Applies to the *_to_string and
+    /// *_recursive methods, and is skipped when the output is xml
+    /// Default: False
+    pub fn set_detect_language(&self, detect_language: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_detect_language(detect_language);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the SimHash fingerprint of the content, see simhash, is stored in the
+    /// "X-SimHash" metadata as 16 lowercase hex digits, to cluster near-duplicate documents.
+    /// Applies to the *_to_string and *_recursive methods, and is skipped when the output is xml
+    /// Default: False
+    pub fn set_compute_simhash(&self, compute_simhash: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_compute_simhash(compute_simhash);
+        Ok(Self(inner))
+    }
+
+    /// Set whether the file methods follow a path that is a symbolic link, when False such
+    /// paths are rejected. Either way directories, FIFOs, sockets and devices are rejected with
+    /// an InvalidInput error before anything is read
+    /// Default: True
+    pub fn set_follow_symlinks(&self, follow_symlinks: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_follow_symlinks(follow_symlinks);
+        Ok(Self(inner))
+    }
+
+    /// Set the renaming of metadata keys, a dict from the key reported by the parsers to the key
+    /// to return, e.g. {"dc:title": "doc_title"}. The keys not in the dict are left as they are.
+    /// When a key is renamed to a key the metadata already has, the values of both are kept.
+    /// Applied to the metadata returned by every method
+    /// Default: {}, no renaming
+    pub fn set_metadata_key_map(
+        &self,
+        metadata_key_map: HashMap<String, String>,
+    ) -> PyResult<Self> {
+        let inner = self.0.clone().set_metadata_key_map(metadata_key_map);
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the parse as xml
     pub fn set_xml_output(&self, xml_output: bool) -> PyResult<Self> {
         let inner = self.0.clone().set_xml_output(xml_output);
@@ -204,15 +1276,19 @@ impl Extractor {
 
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text
     /// the stream is decoded using the extractor's default `encoding` and tika metadata.
+    #[pyo3(signature = (filename, /, *, timeout=None, retries=0))]
     pub fn extract_file<'py>(
         &self,
         filename: &str,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, Py<PyAny>)> {
-        let (reader, metadata) = self
-            .0
-            .extract_file(filename)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let (reader, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file(&filename)
+        })?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
@@ -227,24 +1303,24 @@ impl Extractor {
     }
 
     // Optional-override APIs: None -> use Extractor defaults; Some(x) -> use x
-    #[pyo3(signature = (filename, /, *, encoding=None, as_xml=None, extract_embedded=None))]
+    #[pyo3(signature = (filename, /, *, encoding=None, as_xml=None, extract_embedded=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_file_opt<'py>(
         &self,
         filename: &str,
         encoding: Option<CharSet>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, Py<PyAny>)> {
-        let (reader, metadata) = self
-            .0
-            .extract_file_opt(
-                filename,
-                encoding.map(|c| c.into()),
-                as_xml,
-                extract_embedded,
-            )
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let encoding = encoding.map(|c| c.into());
+        let (reader, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file_opt(&filename, encoding, as_xml, extract_embedded)
+        })?;
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((
             StreamReader {
@@ -258,16 +1334,19 @@ impl Extractor {
 
     /// Extracts text from a bytearray. Returns a tuple with stream of the extracted text
     /// the stream is decoded using the extractor's default `encoding` and tika metadata.
+    #[pyo3(signature = (buffer, /, *, timeout=None, retries=0))]
     pub fn extract_bytes<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, Py<PyAny>)> {
         let slice = buffer.to_vec();
-        let (reader, metadata) = self
-            .0
-            .extract_bytes(&slice)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let (reader, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes(&slice)
+        })?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
@@ -281,20 +1360,24 @@ impl Extractor {
         ))
     }
 
-    #[pyo3(signature = (buffer, /, *, encoding=None, as_xml=None, extract_embedded=None))]
+    #[pyo3(signature = (buffer, /, *, encoding=None, as_xml=None, extract_embedded=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_bytes_opt<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
         encoding: Option<CharSet>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, Py<PyAny>)> {
         let slice = buffer.to_vec();
-        let (reader, metadata) = self
-            .0
-            .extract_bytes_opt(&slice, encoding.map(|c| c.into()), as_xml, extract_embedded)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let encoding = encoding.map(|c| c.into());
+        let (reader, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes_opt(&slice, encoding, as_xml, extract_embedded)
+        })?;
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((
             StreamReader {
@@ -308,15 +1391,18 @@ impl Extractor {
 
     /// Extracts text from a url. Returns a tuple with string that is of maximum length
     /// of the extractor's default `extract_string_max_length` and tika metdata.
+    #[pyo3(signature = (url, /, *, timeout=None, retries=0))]
     pub fn extract_url<'py>(
         &self,
         url: &str,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, Py<PyAny>)> {
-        let (reader, metadata) = self
-            .0
-            .extract_url(&url)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let (reader, metadata) =
+            run_extraction(py, timeout, retries, move || extractor.extract_url(&url))?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
@@ -329,19 +1415,24 @@ impl Extractor {
             py_metadata.into(),
         ))
     }
-    #[pyo3(signature = (url, /, *, encoding=None, as_xml=None, extract_embedded=None))]
+    #[pyo3(signature = (url, /, *, encoding=None, as_xml=None, extract_embedded=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_url_opt<'py>(
         &self,
         url: &str,
         encoding: Option<CharSet>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, Py<PyAny>)> {
-        let (reader, metadata) = self
-            .0
-            .extract_url_opt(url, encoding.map(|c| c.into()), as_xml, extract_embedded)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let encoding = encoding.map(|c| c.into());
+        let (reader, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_url_opt(&url, encoding, as_xml, extract_embedded)
+        })?;
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((
             StreamReader {
@@ -352,207 +1443,861 @@ impl Extractor {
             py_metadata.into(),
         ))
     }
+    /// Extracts the content of a file straight into the file at out_path, created or truncated,
+    /// without holding it in memory as a whole. Returns the metadata as dict.
+    pub fn extract_file_to_path<'py>(
+        &self,
+        filename: &str,
+        out_path: &str,
+        py: Python<'py>,
+    ) -> PyResult<Py<PyAny>> {
+        let metadata = py
+            .detach(|| {
+                let mut out = std::fs::File::create(out_path)
+                    .map_err(|e| ecore::Error::IoError(e.to_string()))?;
+                self.0.extract_file_to_writer(filename, &mut out)
+            })
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok(py_metadata.into())
+    }
+
     /// Extracts text from a file path. Returns a tuple with string that is of maximum length
     /// of the extractor's default `extract_string_max_length` and the metadata as dict.
+    #[pyo3(signature = (filename, /, *, timeout=None, retries=0))]
     pub fn extract_file_to_string<'py>(
         &self,
         filename: &str,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(String, Py<PyAny>)> {
-        let (content, metadata) = self
-            .0
-            .extract_file_to_string(filename)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let (content, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file_to_string(&filename)
+        })?;
 
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((content, py_metadata.into()))
     }
-    #[pyo3(signature = (filename, /, *, max_length=None, as_xml=None, extract_embedded=None))]
+    #[pyo3(signature = (filename, /, *, max_length=None, as_xml=None, extract_embedded=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_file_to_string_opt<'py>(
         &self,
         filename: &str,
         max_length: Option<i32>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(String, Py<PyAny>)> {
-        let (content, metadata) = self
-            .0
-            .extract_file_to_string_opt(filename, max_length, as_xml, extract_embedded)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let (content, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file_to_string_opt(&filename, max_length, as_xml, extract_embedded)
+        })?;
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((content, py_metadata.into()))
     }
     /// Extracts text from a bytearray. string that is of maximum length
     /// of the extractor's default `extract_string_max_length` and the metadata as dict.
+    #[pyo3(signature = (buffer, /, *, timeout=None, retries=0))]
     pub fn extract_bytes_to_string<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(String, Py<PyAny>)> {
-        let (content, metadata) = self
-            .0
-            .extract_bytes_to_string(&buffer.to_vec())
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let slice = buffer.to_vec();
+        let (content, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes_to_string(&slice)
+        })?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((content, py_metadata.into()))
     }
-    #[pyo3(signature = (buffer, /, *, max_length=None, as_xml=None, extract_embedded=None))]
+    #[pyo3(signature = (buffer, /, *, max_length=None, as_xml=None, extract_embedded=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_bytes_to_string_opt<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
         max_length: Option<i32>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(String, Py<PyAny>)> {
-        let (content, metadata) = self
-            .0
-            .extract_bytes_to_string_opt(&buffer.to_vec(), max_length, as_xml, extract_embedded)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let slice = buffer.to_vec();
+        let (content, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes_to_string_opt(&slice, max_length, as_xml, extract_embedded)
+        })?;
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((content, py_metadata.into()))
     }
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
     /// of the extractor's default `extract_string_max_length` and the metadata as dict.
+    #[pyo3(signature = (url, /, *, timeout=None, retries=0))]
     pub fn extract_url_to_string<'py>(
         &self,
         url: &str,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
-    ) -> PyResult<(String, Py<PyAny>)> {
-        let (content, metadata) = self
-            .0
-            .extract_url_to_string(url)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+    ) -> PyResult<(String, Py<PyAny>)> {
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let (content, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_url_to_string(&url)
+        })?;
 
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((content, py_metadata.into()))
     }
 
-    #[pyo3(signature = (url, /, *, max_length=None, as_xml=None, extract_embedded=None))]
+    #[pyo3(signature = (url, /, *, max_length=None, as_xml=None, extract_embedded=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_url_to_string_opt<'py>(
         &self,
         url: &str,
         max_length: Option<i32>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<(String, Py<PyAny>)> {
-        let (content, metadata) = self
-            .0
-            .extract_url_to_string_opt(url, max_length, as_xml, extract_embedded)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let (content, metadata) = run_extraction(py, timeout, retries, move || {
+            extractor.extract_url_to_string_opt(&url, max_length, as_xml, extract_embedded)
+        })?;
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
         Ok((content, py_metadata.into()))
     }
 
     /// 递归提取：文件路径，返回 RecursiveExtraction（Document 列表）
+    #[pyo3(signature = (filename, /, *, timeout=None, retries=0))]
     pub fn extract_file_recursive<'py>(
         &self,
         filename: &str,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<Py<PyRecursiveExtraction>> {
-        let extraction = self
-            .0
-            .extract_file_recursive(filename)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let extraction = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file_recursive(&filename)
+        })?;
 
         let docs = recursive_to_py(py, &extraction)?;
         Py::new(py, PyRecursiveExtraction { docs })
     }
-    #[pyo3(signature = (filename, /, *, max_length=None, as_xml=None))]
+    #[pyo3(signature = (filename, /, *, max_length=None, as_xml=None, timeout=None, retries=0))]
     pub fn extract_file_recursive_opt<'py>(
         &self,
         filename: &str,
         max_length: Option<i32>,
         as_xml: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<Py<PyRecursiveExtraction>> {
-        let extraction = self
-            .0
-            .extract_file_recursive_opt(filename, max_length, as_xml)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let extraction = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file_recursive_opt(&filename, max_length, as_xml)
+        })?;
         let docs = recursive_to_py(py, &extraction)?;
         Py::new(py, PyRecursiveExtraction { docs })
     }
 
+    /// 递归提取文件，每个文档解析完成后立即写为 out_path 文件中的一行 JSON，返回写入的行数。
+    /// 内存中最多只保留一个文档，适合超大归档。每行包含 content、metadata 和 embedded_path，
+    /// 容器文档最后写出，其 embedded_path 为 None
+    pub fn extract_file_recursive_to_ndjson(
+        &self,
+        filename: &str,
+        out_path: &str,
+        py: Python<'_>,
+    ) -> PyResult<usize> {
+        py.detach(|| {
+            let out = std::fs::File::create(out_path)
+                .map_err(|e| ecore::Error::IoError(e.to_string()))?;
+            self.0.extract_file_recursive_to_ndjson(filename, out)
+        })
+        .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// 将文件中每个嵌套文档的原始字节按其文件名写入 out_dir，返回写入的文件路径。
+    /// 嵌套在嵌套文档中的文档同样写出；重名的文件加 _1、_2 等后缀，不会覆盖已有文件；
+    /// 文件名中的路径只保留最后一段，不会写到 out_dir 之外
+    pub fn extract_file_embedded_to_dir(
+        &self,
+        filename: &str,
+        out_dir: &str,
+        py: Python<'_>,
+    ) -> PyResult<Vec<String>> {
+        let paths = py
+            .detach(|| {
+                self.0
+                    .extract_file_embedded_to_dir(filename, Path::new(out_dir))
+            })
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(paths
+            .into_iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    /// 递归提取文件，返回逐个产出 Document 的迭代器。文件在后台解析，内存中只保留少量文档，
+    /// 适合包含大量附件的文件。容器文档最先返回（内容为空），嵌套文档按解析结束的顺序返回，
+    /// 最后再返回内容完整的容器文档
+    pub fn extract_file_recursive_iter(
+        &self,
+        filename: &str,
+    ) -> PyResult<RecursiveDocumentIterator> {
+        let documents = self
+            .0
+            .extract_file_recursive_iter(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(RecursiveDocumentIterator {
+            documents: Mutex::new(documents),
+        })
+    }
+
     /// 递归提取：字节数组，返回 RecursiveExtraction（Document 列表）
+    #[pyo3(signature = (buffer, /, *, timeout=None, retries=0))]
     pub fn extract_bytes_recursive<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<Py<PyRecursiveExtraction>> {
         let slice = buffer.to_vec();
-        let extraction = self
-            .0
-            .extract_bytes_recursive(&slice)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let extraction = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes_recursive(&slice)
+        })?;
 
         let docs = recursive_to_py(py, &extraction)?;
         Py::new(py, PyRecursiveExtraction { docs })
     }
-    #[pyo3(signature = (buffer, /, *, max_length=None, as_xml=None))]
+    #[pyo3(signature = (buffer, /, *, max_length=None, as_xml=None, timeout=None, retries=0))]
+    #[allow(clippy::too_many_arguments)]
     pub fn extract_bytes_recursive_opt<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
         max_length: Option<i32>,
         as_xml: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<Py<PyRecursiveExtraction>> {
         let slice = buffer.to_vec();
-        let extraction = self
-            .0
-            .extract_bytes_recursive_opt(&slice, max_length, as_xml)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let extraction = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes_recursive_opt(&slice, max_length, as_xml)
+        })?;
         let docs = recursive_to_py(py, &extraction)?;
         Py::new(py, PyRecursiveExtraction { docs })
     }
 
     /// 递归提取：URL，返回 RecursiveExtraction（Document 列表）
+    #[pyo3(signature = (url, /, *, timeout=None, retries=0))]
     pub fn extract_url_recursive<'py>(
         &self,
         url: &str,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<Py<PyRecursiveExtraction>> {
-        let extraction = self
-            .0
-            .extract_url_recursive(url)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let extraction = run_extraction(py, timeout, retries, move || {
+            extractor.extract_url_recursive(&url)
+        })?;
 
         let docs = recursive_to_py(py, &extraction)?;
         Py::new(py, PyRecursiveExtraction { docs })
     }
 
-    #[pyo3(signature = (url, /, *, max_length=None, as_xml=None))]
+    #[pyo3(signature = (url, /, *, max_length=None, as_xml=None, timeout=None, retries=0))]
     pub fn extract_url_recursive_opt<'py>(
         &self,
         url: &str,
         max_length: Option<i32>,
         as_xml: Option<bool>,
+        timeout: Option<f64>,
+        retries: u32,
         py: Python<'py>,
     ) -> PyResult<Py<PyRecursiveExtraction>> {
-        let extraction = self
-            .0
-            .extract_url_recursive_opt(url, max_length, as_xml)
-            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let extraction = run_extraction(py, timeout, retries, move || {
+            extractor.extract_url_recursive_opt(&url, max_length, as_xml)
+        })?;
         let docs = recursive_to_py(py, &extraction)?;
         Py::new(py, PyRecursiveExtraction { docs })
     }
 
+    /// Extracts a file paragraph by paragraph. Returns an iterator yielding the text of each
+    /// paragraph as soon as the parser has produced it, so huge documents can be processed with
+    /// little memory. Call finish() on the iterator to get the metadata.
+    pub fn extract_file_paragraphs(&self, filename: &str) -> PyResult<ParagraphIterator> {
+        let paragraphs = self
+            .0
+            .extract_file_paragraphs(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(ParagraphIterator {
+            paragraphs: Mutex::new(Some(paragraphs)),
+        })
+    }
+
+    /// Extracts every file of the directory tree under root whose path relative to root matches
+    /// the glob pattern, e.g. "**/*.pdf" for the PDF files at any depth or "*.txt" for the text
+    /// files of root only. The files are extracted to string in parallel by worker threads,
+    /// os.cpu_count() by default, without holding the GIL.
+    /// Returns an iterator yielding a (path, Document) tuple per file, or (path, exception) for
+    /// the files that failed, in the order the extractions finish, so a failure does not stop
+    /// the walk. The documents have no entities. on_progress, when given, is called with
+    /// (done, total, path) each time the iterator yields a file
+    #[pyo3(signature = (root, pattern="**/*", *, on_progress=None, workers=None))]
+    pub fn extract_directory(
+        &self,
+        root: &str,
+        pattern: &str,
+        on_progress: Option<Py<PyAny>>,
+        workers: Option<usize>,
+        py: Python<'_>,
+    ) -> PyResult<DirectoryIterator> {
+        let pattern = glob::Pattern::new(pattern).map_err(|e| {
+            PyErr::new::<PyValueError, _>(format!("invalid pattern {:?}: {}", pattern, e))
+        })?;
+        if workers == Some(0) {
+            return Err(PyErr::new::<PyValueError, _>("workers must be at least 1"));
+        }
+        let root = Path::new(root);
+        if !root.is_dir() {
+            return Err(PyErr::new::<PyTypeError, _>(format!(
+                "not a directory: {}",
+                root.display()
+            )));
+        }
+
+        let mut files = Vec::new();
+        let mut errors = Vec::new();
+        py.detach(|| walk_directory(root, root, &pattern, &mut files, &mut errors));
+
+        let total = files.len() + errors.len();
+        let files: Vec<String> = files
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let files: Vec<&str> = files.iter().map(String::as_str).collect();
+        // The core workers give a panicking file its own error and stop once the receiver is
+        // dropped with the iterator
+        let results = match workers {
+            Some(workers) => self.0.extract_files_streaming_with_workers(&files, workers),
+            None => self.0.extract_files_streaming(&files),
+        };
+
+        Ok(DirectoryIterator {
+            errors: Mutex::new(errors.into_iter()),
+            results: Mutex::new(results),
+            total,
+            done: AtomicUsize::new(0),
+            on_progress,
+        })
+    }
+
+    /// Extracts the files to string in parallel, one worker thread per CPU, without holding the
+    /// GIL. Returns an iterator yielding a (path, Document) tuple per file, or (path, exception)
+    /// for the files that failed, in the order the extractions finish rather than the order of
+    /// the paths. The documents have no entities
+    pub fn extract_files_streaming(&self, file_paths: Vec<String>) -> DirectoryIterator {
+        let file_paths: Vec<&str> = file_paths.iter().map(String::as_str).collect();
+        DirectoryIterator {
+            errors: Mutex::new(Vec::new().into_iter()),
+            results: Mutex::new(self.0.extract_files_streaming(&file_paths)),
+            total: file_paths.len(),
+            done: AtomicUsize::new(0),
+            on_progress: None,
+        }
+    }
+
+    /// Extracts the files to string in parallel, one worker thread per CPU, without holding the
+    /// GIL. Returns a list with a Document per file, or the exception for the files that
+    /// failed, in the order of the paths. The documents have no entities
+    pub fn extract_files_to_string(
+        &self,
+        file_paths: Vec<String>,
+        py: Python<'_>,
+    ) -> PyResult<Vec<Py<PyAny>>> {
+        let results = py.detach(|| {
+            let file_paths: Vec<&str> = file_paths.iter().map(String::as_str).collect();
+            self.0.extract_files_to_string(&file_paths)
+        });
+        results
+            .into_iter()
+            .map(|result| match result {
+                Ok((content, metadata)) => {
+                    let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+                    let document = PyDocument {
+                        content,
+                        metadata: py_metadata.into(),
+                        entities: Vec::new(),
+                        parent: None,
+                    };
+                    Ok(Py::new(py, document)?.into_any())
+                }
+                Err(e) => Ok(PyErr::new::<PyTypeError, _>(format!("{:?}", e))
+                    .into_value(py)
+                    .into_any()),
+            })
+            .collect()
+    }
+
+    /// Parses a file and calls the methods of the handler object with the XHTML SAX events:
+    /// start_document(), end_document(), start_element(name, attributes), end_element(name)
+    /// and characters(text). Methods the handler does not define are skipped.
+    /// Returns the metadata as dict.
+    pub fn extract_file_with_handler<'py>(
+        &self,
+        filename: &str,
+        handler: Bound<'py, PyAny>,
+        py: Python<'py>,
+    ) -> PyResult<Py<PyAny>> {
+        let mut py_handler = PyContentHandler {
+            handler,
+            error: None,
+        };
+        let result = self.0.extract_file_with_handler(filename, &mut py_handler);
+        if let Some(err) = py_handler.error {
+            return Err(err);
+        }
+        let metadata = result.map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok(py_metadata.into())
+    }
+
+    /// Extracts the metadata of a file only, without its content. Much faster than
+    /// `extract_file_to_string` on large documents when the text is not needed.
+    #[pyo3(signature = (filename, /, *, timeout=None, retries=0))]
+    pub fn extract_file_metadata<'py>(
+        &self,
+        filename: &str,
+        timeout: Option<f64>,
+        retries: u32,
+        py: Python<'py>,
+    ) -> PyResult<Py<PyAny>> {
+        let extractor = self.0.clone();
+        let filename = filename.to_string();
+        let metadata = run_extraction(py, timeout, retries, move || {
+            extractor.extract_file_metadata(&filename)
+        })?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok(py_metadata.into())
+    }
+
+    /// Same as `extract_file_metadata` for a bytearray
+    #[pyo3(signature = (buffer, /, *, timeout=None, retries=0))]
+    pub fn extract_bytes_metadata<'py>(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        timeout: Option<f64>,
+        retries: u32,
+        py: Python<'py>,
+    ) -> PyResult<Py<PyAny>> {
+        let extractor = self.0.clone();
+        let slice = buffer.to_vec();
+        let metadata = run_extraction(py, timeout, retries, move || {
+            extractor.extract_bytes_metadata(&slice)
+        })?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok(py_metadata.into())
+    }
+
+    /// Same as `extract_file_metadata` for a URL
+    #[pyo3(signature = (url, /, *, timeout=None, retries=0))]
+    pub fn extract_url_metadata<'py>(
+        &self,
+        url: &str,
+        timeout: Option<f64>,
+        retries: u32,
+        py: Python<'py>,
+    ) -> PyResult<Py<PyAny>> {
+        let extractor = self.0.clone();
+        let url = url.to_string();
+        let metadata = run_extraction(py, timeout, retries, move || {
+            extractor.extract_url_metadata(&url)
+        })?;
+
+        let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
+        Ok(py_metadata.into())
+    }
+
+    /// Checks the configuration for settings that contradict each other or have no effect.
+    /// Returns the list of warning messages, empty when the configuration is consistent
+    pub fn validate(&self) -> Vec<String> {
+        match self.0.validate() {
+            Ok(()) => Vec::new(),
+            Err(warnings) => warnings.iter().map(|w| w.to_string()).collect(),
+        }
+    }
+
+    /// Returns the digital signatures of a PDF or OOXML file as a list of SignatureInfo.
+    /// The list is empty for unsigned documents and other formats
+    pub fn signature_info(&self, filename: &str) -> PyResult<Vec<PySignatureInfo>> {
+        let signatures = self
+            .0
+            .signature_info(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(signatures.into_iter().map(PySignatureInfo::from).collect())
+    }
+
+    /// Returns the words of a PDF file with their bounding boxes as a list of WordBox,
+    /// e.g. to build a highlightable text layer aligned to the rendered pages.
+    /// No OCR is done, so scanned pages without a text layer yield no words
+    pub fn extract_pdf_word_boxes(&self, filename: &str) -> PyResult<Vec<PyWordBox>> {
+        let words = self
+            .0
+            .extract_pdf_word_boxes(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(words.into_iter().map(PyWordBox::from).collect())
+    }
+
+    /// Returns the fonts referenced by a PDF file as a list of FontInfo, each listed once.
+    /// Fonts that are not embedded often explain a poor text extraction
+    pub fn list_fonts(&self, filename: &str) -> PyResult<Vec<PyFontInfo>> {
+        let fonts = self
+            .0
+            .list_fonts(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(fonts.into_iter().map(PyFontInfo::from).collect())
+    }
+
+    /// Returns the PDF/A conformance level a PDF file declares and whether it is tagged for
+    /// accessibility as a PdfConformance. The declarations are not validated
+    pub fn pdf_conformance_info(&self, filename: &str) -> PyResult<PyPdfConformance> {
+        let info = self
+            .0
+            .pdf_conformance_info(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(PyPdfConformance::from(info))
+    }
+
+    /// Returns the sections of a file following its heading hierarchy as a list of Section.
+    /// Each heading starts a section with its content and the sections of the lower level
+    /// headings that follow it as subsections. A document without headings yields a single section
+    pub fn extract_file_sections(
+        &self,
+        filename: &str,
+        py: Python<'_>,
+    ) -> PyResult<Vec<PySection>> {
+        let sections = py
+            .detach(|| self.0.extract_file_sections(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(sections.into_iter().map(PySection::from).collect())
+    }
+
+    /// Returns the tables of a file as a list of Table, in document order. The header row comes
+    /// from the th cells of a table, or is guessed from a first row of names above a column of
+    /// numbers. The other rows are returned in rows
+    pub fn extract_file_tables(&self, filename: &str, py: Python<'_>) -> PyResult<Vec<PyTable>> {
+        let tables = py
+            .detach(|| self.0.extract_file_tables(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(tables.into_iter().map(PyTable::from).collect())
+    }
+
+    /// Returns the sentences of the text of a file as a list of Sentence, with their character
+    /// offsets in the text returned by extract_file_to_string. Blank lines end a sentence, lines
+    /// wrapped in the middle of a sentence are joined and common abbreviations such as "Dr." do
+    /// not end one
+    pub fn extract_file_sentences(
+        &self,
+        filename: &str,
+        py: Python<'_>,
+    ) -> PyResult<Vec<PySentence>> {
+        let (content, _metadata) = py
+            .detach(|| self.0.extract_file_to_string(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(sentences_to_py(&content, &ecore::split_sentences(&content)))
+    }
+
+    /// Returns the OCR text of each image of the document separately, as a list of ImageOcr with
+    /// the page the image is drawn on. The pages of PDF documents are not OCRed, only their
+    /// images. Requires tesseract
+    pub fn extract_image_ocr(&self, filename: &str, py: Python<'_>) -> PyResult<Vec<PyImageOcr>> {
+        let images = py
+            .detach(|| self.0.extract_image_ocr(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(images.into_iter().map(PyImageOcr::from).collect())
+    }
+
+    /// Returns the contacts of a vCard (.vcf) file as a list of Contact with their name,
+    /// email addresses and phone numbers
+    pub fn extract_vcard(&self, filename: &str) -> PyResult<Vec<PyContact>> {
+        let contacts = self
+            .0
+            .extract_vcard(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(contacts.into_iter().map(PyContact::from).collect())
+    }
+
+    /// Returns the events of an iCalendar (.ics) file as a list of CalendarEvent with their
+    /// summary, start, end and location
+    pub fn extract_icalendar(&self, filename: &str) -> PyResult<Vec<PyCalendarEvent>> {
+        let events = self
+            .0
+            .extract_icalendar(filename)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(events.into_iter().map(PyCalendarEvent::from).collect())
+    }
+
+    /// Returns the messages of a WhatsApp or Slack chat export as a Chat with the sender,
+    /// timestamp and text of each message. Other files are returned as plain text
+    pub fn extract_chat(&self, filename: &str, py: Python<'_>) -> PyResult<PyChat> {
+        let chat = py
+            .detach(|| self.0.extract_chat(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(PyChat::from(chat))
+    }
+
+    /// Returns the core and extended properties of an Office document (doc, xls, ppt, docx,
+    /// xlsx, pptx, ...) as an OfficeProperties with typed fields such as word_count or company
+    pub fn extract_office_properties(
+        &self,
+        filename: &str,
+        py: Python<'_>,
+    ) -> PyResult<PyOfficeProperties> {
+        py.detach(|| self.0.extract_office_properties(filename))
+            .map(PyOfficeProperties::from)
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Returns the revision history an Office file keeps apart from its content as a list of
+    /// Revision: the save history of Word 97-2003 documents and the revision headers of Excel
+    /// shared workbooks. The other Office formats, and the documents whose history was not kept,
+    /// return an empty list
+    pub fn extract_revisions(&self, filename: &str, py: Python<'_>) -> PyResult<Vec<PyRevision>> {
+        let revisions = py
+            .detach(|| self.0.extract_revisions(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(revisions.into_iter().map(PyRevision::from).collect())
+    }
+
+    /// Returns the names of the streams of an OLE compound document (.doc, .xls, .ppt, .msg, ...),
+    /// as stored, control characters included, e.g. "\x05SummaryInformation". The streams
+    /// nested in storages are named by their path, joined with "/"
+    pub fn list_ole_streams(&self, filename: &str, py: Python<'_>) -> PyResult<Vec<String>> {
+        py.detach(|| self.0.list_ole_streams(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Returns the raw bytes of a stream of an OLE compound document, named as by
+    /// list_ole_streams
+    pub fn get_ole_stream<'py>(
+        &self,
+        filename: &str,
+        name: &str,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        let data = py
+            .detach(|| self.0.get_ole_stream(filename, name))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        Ok(PyBytes::new(py, &data))
+    }
+
+    /// Splits an email message (.eml) into its text/plain body, its text/html body and its
+    /// attachments, following the nested multipart bodies. Each attachment is extracted as a
+    /// Document with the extractor configuration, its file name in the resourceName metadata
+    pub fn extract_mime_message(&self, filename: &str, py: Python<'_>) -> PyResult<PyMimeMessage> {
+        let message = py
+            .detach(|| self.0.extract_mime_message(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
+        let attachments = message
+            .attachments
+            .iter()
+            .map(|doc| document_to_py(py, doc))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyMimeMessage {
+            text_plain: message.text_plain,
+            text_html: message.text_html,
+            attachments,
+        })
+    }
+
+    /// Returns the language a text is written in as a (language, confidence) tuple. The language
+    /// is an ISO 639-1 code, e.g. "en", or an empty string when none is detected, and the
+    /// confidence is between 0 and 1
+    pub fn detect_language(&self, text: &str, py: Python<'_>) -> PyResult<(String, f32)> {
+        py.detach(|| self.0.detect_language(text))
+            .map(|result| (result.language, result.confidence))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Returns the media type of a file, such as "application/pdf", without parsing it. It is
+    /// detected from the content and the file extension
+    pub fn detect_mime_file(&self, filename: &str, py: Python<'_>) -> PyResult<String> {
+        py.detach(|| self.0.detect_mime_file(filename))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Returns the media type of a bytearray, such as "application/pdf", without parsing it. The
+    /// bytearray may hold the first few kilobytes of a document only
+    pub fn detect_mime_bytes(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        py: Python<'_>,
+    ) -> PyResult<String> {
+        let slice = buffer.to_vec();
+        py.detach(|| self.0.detect_mime_bytes(&slice))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
+    /// Returns the media types a bytearray could be as a list of (mime type, confidence) tuples,
+    /// ordered by decreasing confidence. The first entry is the detected type
+    pub fn detect_mime_candidates(
+        &self,
+        buffer: &Bound<'_, PyByteArray>,
+        py: Python<'_>,
+    ) -> PyResult<Vec<(String, f32)>> {
+        let slice = buffer.to_vec();
+        py.detach(|| self.0.detect_mime_candidates(&slice))
+            .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
 }
 
-/// Converts HashMap<String, Vec<String> to PyDict
+/// Forwards the SAX events to the methods of a python handler object. Methods the object does
+/// not define are skipped. The first python exception stops the forwarding and is kept to be
+/// raised once the parse is done.
+struct PyContentHandler<'py> {
+    handler: Bound<'py, PyAny>,
+    error: Option<PyErr>,
+}
+
+impl<'py> PyContentHandler<'py> {
+    fn handle<F>(&mut self, method: &str, call: F)
+    where
+        F: FnOnce(&Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>>,
+    {
+        if self.error.is_some() {
+            return;
+        }
+        match self.handler.hasattr(method) {
+            Ok(true) => {
+                if let Err(e) = call(&self.handler) {
+                    self.error = Some(e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+impl ecore::ContentHandler for PyContentHandler<'_> {
+    fn start_document(&mut self) {
+        self.handle("start_document", |h| h.call_method0("start_document"));
+    }
+
+    fn end_document(&mut self) {
+        self.handle("end_document", |h| h.call_method0("end_document"));
+    }
+
+    fn start_element(&mut self, name: &str, attributes: &[(String, String)]) {
+        self.handle("start_element", |h| {
+            let attrs = PyDict::new(h.py());
+            for (key, value) in attributes {
+                attrs.set_item(key, value)?;
+            }
+            h.call_method1("start_element", (name, attrs))
+        });
+    }
+
+    fn end_element(&mut self, name: &str) {
+        self.handle("end_element", |h| h.call_method1("end_element", (name,)));
+    }
+
+    fn characters(&mut self, text: &str) {
+        self.handle("characters", |h| h.call_method1("characters", (text,)));
+    }
+}
+
+/// Runs an extraction with the timeout and retry policy of the python `extract_*` methods.
+///
+/// Io errors, e.g. a failed download, are retried up to `retries` times. Other errors are
+/// deterministic and returned right away. When `timeout` seconds elapse before the extraction
+/// has finished a `TimeoutError` is raised: the extraction runs under
+/// [`ecore::run_with_timeout`], which interrupts the parse and drops its result.
+fn run_extraction<T, F>(
+    py: Python<'_>,
+    timeout: Option<f64>,
+    retries: u32,
+    extract: F,
+) -> PyResult<T>
+where
+    T: Send + 'static,
+    F: Fn() -> ecore::ExtractResult<T> + Send + Sync + 'static,
+{
+    let timeout = timeout
+        .map(|secs| {
+            Duration::try_from_secs_f64(secs).map_err(|_| {
+                PyErr::new::<PyValueError, _>(format!("invalid timeout: {} seconds", secs))
+            })
+        })
+        .transpose()?;
+    let extract = Arc::new(extract);
+
+    let mut attempt = 0;
+    loop {
+        let result = match timeout {
+            None => py.detach(|| extract()),
+            Some(timeout) => {
+                let worker = Arc::clone(&extract);
+                py.detach(move || ecore::run_with_timeout(timeout, move || worker()))
+            }
+        };
+
+        match result {
+            Err(ecore::Error::Timeout(timeout)) => {
+                return Err(PyErr::new::<PyTimeoutError, _>(format!(
+                    "extraction did not finish within {:?}",
+                    timeout
+                )))
+            }
+            Err(ecore::Error::IoError(_)) if attempt < retries => attempt += 1,
+            result => return result.map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e))),
+        }
+    }
+}
+
+/// Converts HashMap<String, Vec<String> to PyDict. Keys are inserted in sorted order, so the
+/// dict iterates and serializes the same way on every run
 fn metadata_hashmap_to_pydict<'py>(
     py: Python<'py>,
     hashmap: &HashMap<String, Vec<String>>,
 ) -> Result<Bound<'py, PyDict>, PyErr> {
     let pydict = PyDict::new(py);
-    for (key, value) in hashmap {
+    for (key, value) in hashmap.sorted_entries() {
         pydict
             .set_item(key, value)
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
@@ -567,19 +2312,24 @@ fn recursive_to_py(
 ) -> Result<Vec<Py<PyDocument>>, PyErr> {
     let mut out: Vec<Py<PyDocument>> = Vec::with_capacity(extraction.documents.len());
     for doc in &extraction.documents {
-        let py_metadata = metadata_hashmap_to_pydict(py, &doc.metadata)?;
-        let pydoc = Py::new(
-            py,
-            PyDocument {
-                content: doc.content.clone(),
-                metadata: py_metadata.into(),
-            },
-        )?;
-        out.push(pydoc);
+        out.push(document_to_py(py, doc)?);
     }
     Ok(out)
 }
 
+fn document_to_py(py: Python, doc: &ecore::Document) -> PyResult<Py<PyDocument>> {
+    let py_metadata = metadata_hashmap_to_pydict(py, &doc.metadata)?;
+    Py::new(
+        py,
+        PyDocument {
+            content: doc.content.clone(),
+            metadata: py_metadata.into(),
+            entities: entities_to_py(&doc.content, &doc.entities),
+            parent: doc.parent,
+        },
+    )
+}
+
 /// Get JVM memory usage statistics
 ///
 /// Returns a dictionary with memory information:
@@ -609,6 +2359,54 @@ pub fn get_jvm_memory_usage(py: Python) -> PyResult<Py<PyAny>> {
     Ok(dict.into())
 }
 
+/// The memory usage of the VM in bytes, returned by get_jvm_memory_stats
+#[pyclass(name = "JvmMemoryStats")]
+pub struct PyJvmMemoryStats {
+    /// Size of the objects on the heap, garbage not yet collected included
+    #[pyo3(get)]
+    pub heap_used: u64,
+    /// Size of the heap reserved from the operating system
+    #[pyo3(get)]
+    pub heap_committed: u64,
+    /// Size the heap can grow to, None when it is not bounded
+    #[pyo3(get)]
+    pub heap_max: Option<u64>,
+    /// Size of the memory used outside of the heap
+    #[pyo3(get)]
+    pub non_heap_used: u64,
+    /// Number of collections run by all the garbage collectors since the VM started
+    #[pyo3(get)]
+    pub gc_count: u64,
+}
+
+impl From<ecore::JvmMemoryStats> for PyJvmMemoryStats {
+    fn from(stats: ecore::JvmMemoryStats) -> Self {
+        Self {
+            heap_used: stats.heap_used,
+            heap_committed: stats.heap_committed,
+            heap_max: stats.heap_max,
+            non_heap_used: stats.non_heap_used,
+            gc_count: stats.gc_count,
+        }
+    }
+}
+
+/// Get the current memory usage of the VM as a JvmMemoryStats, e.g. to export it as gauges
+///
+/// # Example
+/// ```python
+/// from extractous import get_jvm_memory_stats
+///
+/// stats = get_jvm_memory_stats()
+/// print(f"{stats.heap_used} of {stats.heap_max} bytes, {stats.gc_count} collections")
+/// ```
+#[pyfunction]
+pub fn get_jvm_memory_stats() -> PyResult<PyJvmMemoryStats> {
+    ecore::get_jvm_memory_stats()
+        .map(PyJvmMemoryStats::from)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{:?}", e)))
+}
+
 /// Trigger JVM garbage collection manually
 ///
 /// NOTE: This is a suggestion to the JVM, not a guarantee that GC will run immediately.
@@ -638,3 +2436,104 @@ pub fn trigger_jvm_gc(py: Python) -> PyResult<Py<PyAny>> {
 
     Ok(dict.into())
 }
+
+/// Behavior of the shared VM in processes forked after it was initialized
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum ForkBehavior {
+    /// Raise an error in the child until reinit_after_fork() is called
+    Error,
+    /// Transparently create a new VM in the child on its first call
+    Reinitialize,
+}
+
+impl From<ForkBehavior> for ecore::ForkBehavior {
+    fn from(behavior: ForkBehavior) -> Self {
+        match behavior {
+            ForkBehavior::Error => ecore::ForkBehavior::Error,
+            ForkBehavior::Reinitialize => ecore::ForkBehavior::Reinitialize,
+        }
+    }
+}
+
+/// Set the behavior of the shared VM in processes forked after it was initialized,
+/// e.g. the workers of a preforking server such as Gunicorn.
+/// Default: ForkBehavior.Error
+#[pyfunction]
+pub fn set_fork_behavior(behavior: ForkBehavior) {
+    ecore::set_fork_behavior(behavior.into());
+}
+
+/// Set the size of the heap of the shared VM, in MiB, to fit the memory limits of a container.
+/// Must be called before the first extraction of the process, the VM can not be reconfigured
+/// once created. Sizes left to None keep the GraalVM defaults.
+///
+/// # Example
+/// ```python
+/// from extractous import configure_vm
+///
+/// configure_vm(max_heap_mb=512, initial_heap_mb=64)
+/// ```
+#[pyfunction]
+#[pyo3(signature = (*, max_heap_mb=None, initial_heap_mb=None))]
+pub fn configure_vm(max_heap_mb: Option<u32>, initial_heap_mb: Option<u32>) -> PyResult<()> {
+    let mut options = ecore::VmOptions::new();
+    if let Some(max) = max_heap_mb {
+        options = options.set_max_heap_mb(max);
+    }
+    if let Some(initial) = initial_heap_mb {
+        options = options.set_initial_heap_mb(initial);
+    }
+    ecore::configure_vm(options)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{:?}", e)))
+}
+
+/// Create a new VM for the current process if the VM was inherited through fork().
+/// Call it from the post-fork hook of a preforking server.
+///
+/// # Example
+/// ```python
+/// # gunicorn.conf.py
+/// import extractous
+///
+/// def post_fork(server, worker):
+///     extractous.reinit_after_fork()
+/// ```
+#[pyfunction]
+pub fn reinit_after_fork() -> PyResult<()> {
+    ecore::reinit_after_fork()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{:?}", e)))
+}
+
+/// Set the maximum number of Tesseract invocations that can run at the same time across all
+/// extractions of the process. Extractions that need OCR wait for a free slot.
+/// Default: the number of available CPUs
+///
+/// # Example
+/// ```python
+/// from extractous import set_max_ocr_concurrency
+///
+/// set_max_ocr_concurrency(2)
+/// ```
+#[pyfunction]
+pub fn set_max_ocr_concurrency(max: usize) -> PyResult<()> {
+    ecore::set_max_ocr_concurrency(max)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{:?}", e)))
+}
+
+/// Set the maximum total size, in bytes, of the buffers that the byte-based extractions of the
+/// process hand over to the VM at the same time. Extractions that would go over the limit wait
+/// until enough of the running ones are done, a buffer larger than the limit runs alone. The
+/// streams of extract_bytes count until they are closed.
+/// Default: no limit
+///
+/// # Example
+/// ```python
+/// from extractous import set_max_direct_buffer_bytes
+///
+/// set_max_direct_buffer_bytes(512 * 1024 * 1024)
+/// ```
+#[pyfunction]
+pub fn set_max_direct_buffer_bytes(max_bytes: usize) {
+    ecore::set_max_direct_buffer_bytes(max_bytes)
+}
@@ -54,9 +54,31 @@ pub use config::*;
 #[pymodule]
 fn _extractous(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CharSet>()?;
+    m.add_class::<Script>()?;
     m.add_class::<StreamReader>()?;
+    m.add_class::<ParagraphIterator>()?;
+    m.add_class::<DirectoryIterator>()?;
     m.add_class::<PyDocument>()?;
+    m.add_class::<EntityKind>()?;
+    m.add_class::<PyEntity>()?;
     m.add_class::<PyRecursiveExtraction>()?;
+    m.add_class::<RecursiveDocumentIterator>()?;
+    m.add_class::<PySignatureInfo>()?;
+    m.add_class::<PyWordBox>()?;
+    m.add_class::<PyFontInfo>()?;
+    m.add_class::<PyPdfConformance>()?;
+    m.add_class::<PyMimeMessage>()?;
+    m.add_class::<PySection>()?;
+    m.add_class::<PyTable>()?;
+    m.add_class::<PySentence>()?;
+    m.add_class::<PyImageOcr>()?;
+    m.add_class::<PyContact>()?;
+    m.add_class::<PyCalendarEvent>()?;
+    m.add_class::<ChatFormat>()?;
+    m.add_class::<PyChatMessage>()?;
+    m.add_class::<PyChat>()?;
+    m.add_class::<PyOfficeProperties>()?;
+    m.add_class::<PyRevision>()?;
     m.add_class::<Extractor>()?;
 
     // Config
@@ -64,10 +86,32 @@ fn _extractous(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PdfParserConfig>()?;
     m.add_class::<OfficeParserConfig>()?;
     m.add_class::<TesseractOcrConfig>()?;
+    m.add_class::<RecursiveConfig>()?;
+    m.add_class::<UrlFetchConfig>()?;
+    m.add_class::<MergePolicy>()?;
+    m.add_class::<ParserKind>()?;
+
+    m.add_function(wrap_pyfunction!(find_entities, m)?)?;
+    m.add_function(wrap_pyfunction!(dominant_script, m)?)?;
+    m.add_function(wrap_pyfunction!(simhash, m)?)?;
 
     // JVM memory management functions
     m.add_function(wrap_pyfunction!(get_jvm_memory_usage, m)?)?;
+    m.add_function(wrap_pyfunction!(get_jvm_memory_stats, m)?)?;
+    m.add_class::<PyJvmMemoryStats>()?;
     m.add_function(wrap_pyfunction!(trigger_jvm_gc, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_vm, m)?)?;
+
+    // Fork handling
+    m.add_class::<ForkBehavior>()?;
+    m.add_function(wrap_pyfunction!(set_fork_behavior, m)?)?;
+    m.add_function(wrap_pyfunction!(reinit_after_fork, m)?)?;
+
+    // OCR
+    m.add_function(wrap_pyfunction!(set_max_ocr_concurrency, m)?)?;
+
+    // Native memory
+    m.add_function(wrap_pyfunction!(set_max_direct_buffer_bytes, m)?)?;
 
     Ok(())
 }
@@ -1,7 +1,8 @@
 import json
+import sys
 import pytest
 
-from extractous import Extractor
+from extractous import Extractor, set_max_direct_buffer_bytes
 from utils import calculate_similarity_percent, cosine_similarity, read_to_string, read_file_to_bytearray, \
     is_expected_metadata_contained, extract_body_text
 
@@ -139,3 +140,76 @@ def test_extract_bytes_to_stream_as_xml(file_name, target_dist, metadata_dist):
     percent_similarity = calculate_similarity_percent(metadata, expected_metadata)
     assert percent_similarity >= metadata_dist, \
         f"The metadata similarity is lower than expected. Current {percent_similarity}% | filename: {file_name}"
+
+
+def test_extract_bytes_max_element_depth():
+    depth = 400
+    html = "<html><body>" + "<div>" * depth + "deep text" + "</div>" * depth + "</body></html>"
+
+    extractor = Extractor().set_max_element_depth(100)
+    with pytest.raises(Exception, match="nesting"):
+        extractor.extract_bytes_to_string(bytearray(html, "utf-8"))
+
+    extractor = Extractor().set_max_element_depth(1000)
+    result, _metadata = extractor.extract_bytes_to_string(bytearray(html, "utf-8"))
+    assert "deep text" in result
+
+
+def test_detect_mime_candidates():
+    extractor = Extractor()
+    file_bytes = read_file_to_bytearray("../../test_files/documents/category-level.docx")
+    candidates = extractor.detect_mime_candidates(file_bytes)
+
+    assert candidates[0] == ("application/vnd.openxmlformats-officedocument.wordprocessingml.document", 1.0)
+    zip_confidence = dict(candidates)["application/zip"]
+    assert zip_confidence < 1.0
+
+
+def test_detect_mime_bytes_truncated():
+    extractor = Extractor()
+    file_bytes = read_file_to_bytearray("../../test_files/documents/2022_Q3_AAPL.pdf")
+
+    assert extractor.detect_mime_bytes(file_bytes[:4096]) == "application/pdf"
+
+
+def test_extract_bytes_emit_line_numbers():
+    log = bytearray("INFO service started\nWARN disk usage at 91%\n", "utf-8")
+    extractor = Extractor().set_emit_line_numbers(True)
+    result, _metadata = extractor.extract_bytes_to_string(log)
+
+    assert result.splitlines() == ["1\tINFO service started", "2\tWARN disk usage at 91%"]
+
+
+def test_extract_bytes_max_direct_buffer_bytes():
+    file_bytes = read_file_to_bytearray("../../test_files/documents/simple.odt")
+    set_max_direct_buffer_bytes(len(file_bytes) // 2)
+    try:
+        # Larger than the limit, the buffer is handed over once no other one is in use
+        result, _metadata = Extractor().extract_bytes_to_string(file_bytes)
+        assert len(result) > 0
+    finally:
+        set_max_direct_buffer_bytes(sys.maxsize)
+
+
+def test_extract_bytes_zip_recovery():
+    file_bytes = read_file_to_bytearray("../../test_files/documents/nested-archive.zip")
+    # Cut off in the middle of an entry, which leaves no central directory
+    truncated = file_bytes[:250]
+
+    result, metadata = Extractor().set_zip_recovery(True).extract_bytes_to_string(truncated)
+    assert "This text is a top level attachment." in result
+    assert metadata["extractous:zip_recovered_entries"] == ["3"]
+
+
+def test_extract_bytes_encoding_name():
+    html = bytearray('<html><head><meta charset="utf-8"></head><body><p>Привет</p></body></html>', "utf-8")
+    reader, _metadata = Extractor().set_encoding_name("KOI8-R").extract_bytes(html)
+    result = b""
+    chunk = reader.read(4096)
+    while len(chunk) > 0:
+        result += chunk
+        chunk = reader.read(4096)
+    assert "Привет".encode("koi8-r") in result
+
+    with pytest.raises(Exception, match="charset"):
+        Extractor().set_encoding_name("no-such-charset").extract_bytes(html)
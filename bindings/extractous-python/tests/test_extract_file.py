@@ -1,7 +1,8 @@
 import json
+import os
 import pytest
 
-from extractous import Extractor
+from extractous import Extractor, OfficeParserConfig, ParserKind
 from utils import calculate_similarity_percent, cosine_similarity, is_expected_metadata_contained, read_to_string, extract_body_text
 
 TEST_CASES = [
@@ -135,3 +136,285 @@ def test_extract_file_to_stream_as_xml(file_name, target_dist, metadata_dist):
     percent_similarity = calculate_similarity_percent(metadata, expected_metadata)
     assert percent_similarity >= metadata_dist, \
         f"The metadata similarity is lower than expected. Current {percent_similarity}% | filename: {file_name}"
+
+
+def test_extract_file_to_string_timeout():
+    extractor = Extractor()
+    with pytest.raises(TimeoutError):
+        extractor.extract_file_to_string("../../test_files/documents/2022_Q3_AAPL.pdf", timeout=0.000001)
+
+    # A generous timeout returns the regular result
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/simple.odt", timeout=60)
+    assert len(result) > 0
+
+
+def test_extract_file_to_string_retries_io_errors():
+    extractor = Extractor()
+    with pytest.raises(TypeError, match="IoError"):
+        extractor.extract_file_to_string("../../test_files/documents/missing.odt", retries=2)
+
+def test_extract_file_rejects_special_files(tmp_path):
+    with pytest.raises(TypeError, match="InvalidInput"):
+        Extractor().extract_file_to_string(str(tmp_path))
+
+    link = tmp_path / "link.odt"
+    link.symlink_to(os.path.abspath("../../test_files/documents/simple.odt"))
+    result, _ = Extractor().extract_file_to_string(str(link))
+    assert len(result) > 0
+    with pytest.raises(TypeError, match="symbolic link"):
+        Extractor().set_follow_symlinks(False).extract_file_to_string(str(link))
+
+def test_extract_file_sections():
+    extractor = Extractor()
+    sections = extractor.extract_file_sections("../../test_files/documents/sections.html")
+
+    assert [section.heading for section in sections] == [None, "Overview", "Outlook"]
+    overview = sections[1]
+    assert overview.level == 1
+    assert overview.content == "The year in numbers."
+    assert [sub.heading for sub in overview.subsections] == ["Revenue", "Costs"]
+    assert overview.subsections[0].subsections[0].heading == "By region"
+
+
+def test_extract_file_tables():
+    extractor = Extractor()
+    tables = extractor.extract_file_tables("../../test_files/documents/tables.html")
+
+    assert [table.header for table in tables] == [
+        ["Region", "Q1", "Q2"],
+        ["Product", "Units", "Price"],
+        None,
+    ]
+    assert tables[0].rows == [["North", "1,200", "1,350"], ["South", "980", "1,020"]]
+    assert tables[2].rows[0] == ["Prepared by", "Finance team"]
+
+def test_extract_file_sentences():
+    extractor = Extractor()
+    path = "../../test_files/documents/sections.html"
+    sentences = extractor.extract_file_sentences(path)
+    content, _ = extractor.extract_file_to_string(path)
+
+    texts = [sentence.text for sentence in sentences]
+    assert "Prepared by the finance team." in texts
+    assert "Overview" in texts
+    for sentence in sentences:
+        assert content[sentence.start:sentence.end] == sentence.text
+
+def test_extract_office_properties():
+    extractor = Extractor()
+    properties = extractor.extract_office_properties("../../test_files/documents/science-exploration-1p.pptx")
+
+    assert properties.company == "LM ES&S"
+    assert properties.title == "Slide 1"
+    assert properties.revision == 122
+    assert properties.word_count == 3969
+    assert properties.manager is None
+
+
+def test_extract_revisions():
+    extractor = Extractor()
+    revisions = extractor.extract_revisions("../../test_files/documents/shared-workbook.xlsx")
+
+    assert [revision.author for revision in revisions] == ["Alice Martin", "Bob Chen"]
+    assert revisions[1].timestamp == "2024-03-05T16:40:03"
+    assert revisions[1].location is None
+    assert extractor.extract_revisions("../../test_files/documents/category-level.docx") == []
+
+def test_extract_file_tracked_changes_as_xml():
+    office_config = OfficeParserConfig().set_include_deleted_content(True)
+    extractor = Extractor().set_office_config(office_config).set_xml_output(True)
+    xml, _metadata = extractor.extract_file_to_string("../../test_files/documents/tracked-changes.docx")
+
+    assert '<del class="deleted">without any limit </del>' in xml
+
+def test_extract_file_office_comments():
+    office_config = OfficeParserConfig().set_include_comments(True)
+    extractor = Extractor().set_office_config(office_config)
+    _content, metadata = extractor.extract_file_to_string("../../test_files/documents/commented.docx")
+
+    assert metadata["extractous:comment_author"] == ["Alice Reviewer", "Bob Counsel"]
+    assert metadata["extractous:comment_date"] == ["2024-03-05T10:15:30Z", ""]
+    assert metadata["extractous:comment_text"][1] == "Add a notice period."
+
+def test_ole_streams():
+    extractor = Extractor()
+    path = "../../test_files/documents/simple.doc"
+    names = extractor.list_ole_streams(path)
+
+    assert "WordDocument" in names
+    assert "\x05SummaryInformation" in names
+    assert extractor.get_ole_stream(path, "WordDocument")[:2] == b"\xec\xa5"
+    with pytest.raises(TypeError):
+        extractor.list_ole_streams("../../test_files/documents/category-level.docx")
+
+def test_extract_svg_labels():
+    extractor = Extractor()
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/labeled-chart.svg")
+
+    assert "First quarter" in result
+    assert "Third quarter" in result
+    assert "steelblue" not in result
+    assert metadata["dc:title"] == ["Quarterly revenue"]
+
+
+def test_extract_file_distrust_declared_charset():
+    # The page declares iso-8859-1 but is encoded in UTF-8
+    path = "../../test_files/documents/wrong-charset.html"
+    result, _ = Extractor().extract_file_to_string(path)
+    assert "crÃ¨me" in result
+
+    extractor = Extractor().set_trust_declared_charset(False)
+    result, _ = extractor.extract_file_to_string(path)
+    assert "Our café serves crème brûlée" in result
+
+
+def test_extract_file_dedup_repeated_lines():
+    path = "../../test_files/documents/running-footer.pdf"
+    result, _ = Extractor().extract_file_to_string(path)
+    assert result.count("Northwind Traders - Confidential") == 4
+
+    extractor = Extractor().set_dedup_repeated_lines(True)
+    result, _ = extractor.extract_file_to_string(path)
+    assert result.count("Northwind Traders - Confidential") == 1
+    assert "Page 1 of 4" in result
+    assert "Page 4 of 4" not in result
+    assert "The board approved the dividend." in result
+
+
+def test_extract_file_force_text_types():
+    path = "../../test_files/documents/records.dat"
+    result, _ = Extractor().extract_file_to_string(path)
+    assert "ORDER-1001" not in result
+
+    extractor = Extractor().set_force_text_types([".dat"])
+    result, metadata = extractor.extract_file_to_string(path)
+    assert "ORDER-1001" in result
+    assert metadata["Content-Type"][0].startswith("text/plain")
+
+
+def test_extract_file_parser_fallback_chain():
+    path = "../../test_files/documents/truncated.xml"
+    with pytest.raises(Exception):
+        Extractor().extract_file_to_string(path)
+
+    extractor = Extractor().set_parser_fallback_chain([ParserKind.PLAIN_TEXT])
+    result, metadata = extractor.extract_file_to_string(path)
+    assert "Midnight Rain" in result
+    assert metadata["extractous:fallback_parser"] == ["PlainText"]
+
+
+def test_extract_file_record_parser_chain():
+    path = "../../test_files/documents/table-multi-row-column-cells-actual.csv"
+    _, metadata = Extractor().extract_file_to_string(path)
+    assert "extractous:parser_chain" not in metadata
+
+    _, metadata = Extractor().set_record_parser_chain(True).extract_file_to_string(path)
+    assert metadata["extractous:parser_chain"] == [
+        "org.apache.tika.parser.AutoDetectParser",
+        "org.apache.tika.parser.DefaultParser",
+        "org.apache.tika.parser.csv.TextAndCSVParser",
+    ]
+
+def test_extract_file_preserve_scripts():
+    path = "../../test_files/documents/chemistry.html"
+    result, _ = Extractor().extract_file_to_string(path)
+    assert "CH4 + 2O2" in result
+
+    result, _ = Extractor().set_preserve_scripts(True).extract_file_to_string(path)
+    assert "CH₄ + 2O₂ → CO₂ + 2H₂O" in result
+    assert "x²" in result
+    assert "K_{eq}" in result
+
+
+def test_extract_file_metadata_key_map():
+    path = "../../test_files/documents/simple.odt"
+    _, expected = Extractor().extract_file_to_string(path)
+
+    extractor = Extractor().set_metadata_key_map({"Content-Type": "mime"})
+    _, metadata = extractor.extract_file_to_string(path)
+    assert metadata["mime"] == expected["Content-Type"]
+    assert "Content-Type" not in metadata
+    assert len(metadata) == len(expected)
+
+def test_extract_file_string_streaming_threshold():
+    path = "../../test_files/documents/2022_Q3_AAPL.pdf"
+    expected, _ = Extractor().extract_file_to_string(path)
+    result, _ = Extractor().set_string_streaming_threshold(1000).extract_file_to_string(path)
+    assert result == expected
+
+
+def test_extract_file_to_path(tmp_path):
+    path = "../../test_files/documents/simple.odt"
+    reader, expected_metadata = Extractor().extract_file(path)
+    expected = read_to_string(reader)
+    out_path = tmp_path / "simple.txt"
+    metadata = Extractor().extract_file_to_path(path, str(out_path))
+    assert out_path.read_text(encoding="utf-8") == expected
+    assert metadata["Content-Type"] == expected_metadata["Content-Type"]
+
+
+def test_extract_directory(tmp_path):
+    (tmp_path / "reports").mkdir()
+    (tmp_path / "reports" / "q1.txt").write_text("First quarter revenue", encoding="utf-8")
+    (tmp_path / "notes.txt").write_text("Meeting notes", encoding="utf-8")
+    (tmp_path / "image.png").write_bytes(b"\x89PNG")
+
+    progress = []
+    iterator = Extractor().extract_directory(
+        str(tmp_path), "**/*.txt", on_progress=lambda done, total, path: progress.append((done, total, path))
+    )
+    results = dict(iterator)
+
+    assert sorted(results) == sorted(str(tmp_path / name) for name in ["notes.txt", "reports/q1.txt"])
+    assert "First quarter revenue" in results[str(tmp_path / "reports" / "q1.txt")].content
+    assert [(done, total) for done, total, _ in progress] == [(1, 2), (2, 2)]
+
+
+def test_extract_files_streaming(tmp_path):
+    (tmp_path / "notes.txt").write_text("Meeting notes", encoding="utf-8")
+    paths = [str(tmp_path / "notes.txt"), str(tmp_path / "missing.txt"), "../../test_files/documents/simple.odt"]
+
+    results = dict(Extractor().extract_files_streaming(paths))
+
+    assert sorted(results) == sorted(paths)
+    assert "Meeting notes" in results[paths[0]].content
+    assert isinstance(results[paths[1]], Exception)
+    assert results[paths[2]].content
+
+
+def test_extract_directory_isolates_failures(tmp_path):
+    (tmp_path / "good.txt").write_text("Readable text", encoding="utf-8")
+    (tmp_path / "locked.txt").write_text("Locked text", encoding="utf-8")
+    os.chmod(tmp_path / "locked.txt", 0)
+    if os.access(tmp_path / "locked.txt", os.R_OK):
+        pytest.skip("running with permissions that can read any file")
+
+    results = dict(Extractor().extract_directory(str(tmp_path), "*.txt", workers=1))
+
+    # The file that failed does not stop the others
+    assert "Readable text" in results[str(tmp_path / "good.txt")].content
+    assert isinstance(results[str(tmp_path / "locked.txt")], Exception)
+
+
+def test_extract_file_metadata():
+    file_path = "../../test_files/documents/2022_Q3_AAPL.pdf"
+    _content, expected = Extractor().extract_file_to_string(file_path)
+
+    metadata = Extractor().extract_file_metadata(file_path)
+
+    assert metadata["Content-Type"] == expected["Content-Type"]
+    assert metadata["xmpTPg:NPages"] == expected["xmpTPg:NPages"]
+
+
+def test_extract_files_to_string():
+    paths = [
+        "../../test_files/documents/simple.odt",
+        "../../test_files/documents/missing.pdf",
+        "../../test_files/documents/simple.odt",
+    ]
+
+    results = Extractor().extract_files_to_string(paths)
+
+    assert len(results) == len(paths)
+    assert results[0].content and results[0].content == results[2].content
+    assert isinstance(results[1], Exception)
@@ -1,7 +1,9 @@
+import hashlib
+import json
 import pytest
 import os
 
-from extractous import Extractor
+from extractous import EntityKind, Extractor, MergePolicy, PdfParserConfig, RecursiveConfig
 from utils import read_file_to_bytearray
 
 # 根据当前工作目录确定测试文件路径
@@ -201,3 +203,147 @@ class TestExtractFileRecursive:
         assert result.total_count >= 1
         for doc in result.documents:
             assert len(doc.content) <= 10000
+
+    def test_extract_file_recursive_merge_embedded_metadata(self):
+        """测试将嵌入文档的元数据合并到容器中"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        plain = Extractor().extract_file_recursive(file_path)
+        config = RecursiveConfig().set_merge_embedded_metadata(MergePolicy.COLLECT)
+        merged = Extractor().set_recursive_config(config).extract_file_recursive(file_path)
+
+        container = merged.container().metadata
+        # 容器自身的内容类型不会被覆盖
+        assert container["Content-Type"] == plain.container().metadata["Content-Type"]
+        assert len(container) > len(plain.container().metadata)
+
+    def test_extract_file_recursive_content_digests(self):
+        """测试为每个文档记录原始字节长度和 SHA-256"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        config = RecursiveConfig().set_record_content_digests(True)
+        result = Extractor().set_recursive_config(config).extract_file_recursive(file_path)
+
+        with open(file_path, "rb") as f:
+            data = f.read()
+        container = result.container().metadata
+        assert container["X-Content-Length"] == [str(len(data))]
+        assert container["X-Content-SHA256"] == [hashlib.sha256(data).hexdigest()]
+        for doc in result.embedded_documents():
+            assert len(doc.metadata["X-Content-SHA256"][0]) == 64
+            assert int(doc.metadata["X-Content-Length"][0]) > 0
+
+    def test_extract_file_recursive_max_total_output_bytes(self):
+        """测试所有文档的内容合计不超过总输出预算"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        config = RecursiveConfig().set_max_total_output_bytes(40)
+        result = Extractor().set_recursive_config(config).extract_file_recursive(file_path)
+
+        assert sum(len(doc.content.encode("utf-8")) for doc in result.documents) <= 40
+        assert result.container().metadata["extractous:output_truncated"] == ["true"]
+
+    def test_extract_file_recursive_parallelism(self):
+        """测试并行解析顶层附件时保持顺序解析的结果顺序"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        sequential = Extractor().extract_file_recursive(file_path)
+        parallel = Extractor().set_recursive_parallelism(4).extract_file_recursive(file_path)
+
+        def summary(result):
+            return [
+                (doc.metadata.get("X-TIKA:embedded_resource_path"), doc.content)
+                for doc in result.documents
+            ]
+
+        assert summary(parallel) == summary(sequential)
+
+    def test_extract_file_recursive_embedded_mime_filter(self):
+        """测试按 MIME 类型过滤嵌套文档"""
+        extractor = Extractor().set_embedded_mime_filter(["image/*"])
+        result = extractor.extract_file_recursive(f"{TEST_FILES_BASE}/nested-archive.zip")
+
+        names = [doc.metadata["resourceName"][0] for doc in result.documents[1:]]
+        assert names == ["top.png"]
+
+    def test_extract_file_recursive_max_embedded_count(self):
+        """测试嵌套文档数量上限"""
+        extractor = Extractor().set_max_embedded_count(2)
+        result = extractor.extract_file_recursive(f"{TEST_FILES_BASE}/nested-archive.zip")
+
+        assert len(result.documents) == 3
+        assert result.container().metadata["extractous:embedded_truncated"] == ["true"]
+
+    def test_extract_bytes_recursive_entities(self):
+        """测试递归提取时返回内容中的实体"""
+        text = bytearray("Café: jane.doe@example.com, +1 555 0100, https://example.com/docs\n".encode("utf-8"))
+        extractor = Extractor().set_extract_entities(True)
+        result = extractor.extract_bytes_recursive(text)
+
+        container = result.container()
+        assert [entity.kind for entity in container.entities] == [EntityKind.Email, EntityKind.Phone, EntityKind.Url]
+        # 偏移量为字符偏移量
+        assert [container.content[entity.start:entity.end] for entity in container.entities] == [
+            "jane.doe@example.com", "+1 555 0100", "https://example.com/docs"
+        ]
+        assert container.entities[1].value == "+15550100"
+
+    def test_extract_file_recursive_to_ndjson(self, tmp_path):
+        """测试将递归提取结果流式写入 NDJSON 文件"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        out_path = tmp_path / "documents.ndjson"
+        count = Extractor().extract_file_recursive_to_ndjson(file_path, str(out_path))
+
+        lines = [json.loads(line) for line in out_path.read_text(encoding="utf-8").splitlines()]
+        assert count == len(lines) == Extractor().extract_file_recursive(file_path).total_count
+        # 容器文档最后写出
+        assert lines[-1]["embedded_path"] is None
+        assert all(line["embedded_path"] for line in lines[:-1])
+
+    def test_extract_file_recursive_hierarchy(self):
+        """测试嵌套文档的父子关系"""
+        result = Extractor().extract_file_recursive(f"{TEST_FILES_BASE}/nested-archive.zip")
+        names = [doc.metadata.get("resourceName", [None])[0] for doc in result.documents]
+        inner = names.index("inner.zip")
+        nested = names.index("nested.txt")
+
+        assert result.documents[0].parent is None
+        assert result.documents[nested].parent == inner
+        assert nested in result.children(inner)
+        assert inner in result.children(0)
+        assert result.documents[nested].resource_name() == "nested.txt"
+        assert result.documents[nested].embedded_path() == "/inner.zip/nested.txt"
+        assert result.documents[0].embedded_path() is None
+
+    def test_extract_file_recursive_iter(self):
+        """测试逐个返回递归提取的文档"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        documents = list(Extractor().extract_file_recursive_iter(file_path))
+
+        assert len(documents) == Extractor().extract_file_recursive(file_path).total_count + 1
+        # 容器文档最先返回，内容为空，内容完整的容器文档最后返回
+        assert "X-TIKA:embedded_resource_path" not in documents[0].metadata
+        assert documents[0].content == ""
+        assert "X-TIKA:embedded_resource_path" not in documents[-1].metadata
+        assert any("This text is a top level attachment." in doc.content for doc in documents)
+
+    def test_extract_file_embedded_to_dir(self, tmp_path):
+        """测试将嵌套文档的原始字节写入目录"""
+        file_path = f"{TEST_FILES_BASE}/nested-archive.zip"
+        paths = Extractor().extract_file_embedded_to_dir(file_path, str(tmp_path))
+
+        assert sorted(os.path.basename(path) for path in paths) == [
+            "inner.zip", "nested.png", "nested.txt", "top.png", "top.txt"
+        ]
+        assert (tmp_path / "inner.zip").read_bytes().startswith(b"PK")
+        # 重名的文件加后缀，不覆盖已有文件
+        again = Extractor().extract_file_embedded_to_dir(file_path, str(tmp_path))
+        assert str(tmp_path / "top_1.txt") in again
+
+    def test_extract_file_recursive_pdf_inline_image_metadata(self):
+        """测试 PDF 图片描述作为无内容的嵌套文档返回"""
+        pdf_config = PdfParserConfig().set_extract_inline_image_metadata(True)
+        result = Extractor().set_pdf_config(pdf_config).extract_file_recursive(
+            f"{TEST_FILES_BASE}/tagged-figure.pdf")
+
+        assert result.container().metadata["extractous:pdf_image_count"] == ["2"]
+        images = result.embedded_documents()
+        assert images[0].metadata["extractous:image_page"] == ["1"]
+        assert images[0].metadata["extractous:image_alt"] == ["Company logo"]
+        assert "extractous:image_alt" not in images[1].metadata
@@ -1,4 +1,8 @@
-from extractous import Extractor
+import threading
+from http.server import BaseHTTPRequestHandler, HTTPServer
+
+import pytest
+from extractous import Extractor, UrlFetchConfig
 from utils import read_to_string
 
 def test_extract_url_to_stream():
@@ -26,3 +30,35 @@ def test_extract_url_to_string_as_xml():
 
     assert "Google" in content
     assert len(metadata.keys()) > 0
+
+class RobotsSiteHandler(BaseHTTPRequestHandler):
+    """A site whose robots.txt disallows /private/ to TestBot"""
+
+    def do_GET(self):
+        if self.path == "/robots.txt":
+            body = b"User-agent: TestBot\nDisallow: /private/\n"
+        else:
+            body = b"A page of the test site"
+        self.send_response(200)
+        self.send_header("Content-Type", "text/plain")
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, *args):
+        pass
+
+def test_extract_url_to_string_respect_robots():
+    server = HTTPServer(("127.0.0.1", 0), RobotsSiteHandler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    base_url = f"http://127.0.0.1:{server.server_port}"
+    try:
+        config = UrlFetchConfig().set_user_agent("TestBot/1.0").set_respect_robots(True)
+        extractor = Extractor().set_url_fetch_config(config)
+
+        with pytest.raises(TypeError, match="DisallowedByRobots"):
+            extractor.extract_url_to_string(f"{base_url}/private/page.txt")
+        content, _metadata = extractor.extract_url_to_string(f"{base_url}/public/page.txt")
+        assert "A page of the test site" in content
+    finally:
+        server.shutdown()
@@ -1,3 +1,5 @@
+import os
+import shutil
 import sys
 
 import pytest
@@ -16,6 +18,7 @@ def test_ara_ocr_png():
         expected = file.read()
 
     assert cosine_similarity(result, expected) > 0.9
+    assert metadata["extractous:ocr_used"] == ["true"]
 
 
 @pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
@@ -52,3 +55,81 @@ def test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf():
     result, metadata = extractor.extract_file_to_string(test_file)
 
     assert result.strip() == ""
+
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_ocr_config_set_languages():
+    ocr_config = TesseractOcrConfig().set_languages(["eng", "ara"])
+    extractor = Extractor().set_ocr_config(ocr_config)
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/ara-ocr.png")
+    assert metadata["extractous:ocr_used"] == ["true"]
+
+    with pytest.raises(ValueError, match="xx_missing"):
+        TesseractOcrConfig().set_languages(["eng", "xx_missing"])
+    with pytest.raises(ValueError):
+        TesseractOcrConfig().set_languages([])
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_ocr_density_and_page_seg_mode_deu_ocr_pdf():
+    pdf_config = PdfParserConfig().set_ocr_strategy(PdfOcrStrategy.OCR_ONLY)
+    ocr_config = TesseractOcrConfig().set_language("deu").set_density(400).set_page_seg_mode(3)
+    extractor = Extractor().set_ocr_config(ocr_config).set_pdf_config(pdf_config)
+
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/deu-ocr.pdf")
+
+    with open("../../test_files/expected_result/deu-ocr.pdf.txt", "r", encoding="utf8") as file:
+        expected = file.read()
+
+    assert cosine_similarity(result, expected) > 0.9
+
+    extractor = Extractor().set_ocr_config(ocr_config.set_page_seg_mode(14)).set_pdf_config(pdf_config)
+    with pytest.raises(Exception):
+        extractor.extract_file_to_string("../../test_files/documents/deu-ocr.pdf")
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_ocr_timeout_deu_ocr_pdf():
+    # A page rendered at 600 dpi takes Tesseract far longer than a second
+    pdf_config = PdfParserConfig().set_ocr_strategy(PdfOcrStrategy.OCR_ONLY)
+    ocr_config = TesseractOcrConfig().set_language("deu").set_density(600).set_timeout_seconds(1)
+    extractor = Extractor().set_ocr_config(ocr_config).set_pdf_config(pdf_config)
+
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/deu-ocr.pdf")
+
+    assert metadata["X-TIKA:ocr_timed_out"] == ["true"]
+
+@pytest.mark.skipif(shutil.which("tesseract") is None, reason="tesseract is not on the PATH")
+def test_ocr_config_set_tesseract_path():
+    with pytest.raises(ValueError, match="tesseract"):
+        TesseractOcrConfig().set_tesseract_path("../../test_files/documents")
+
+    # The directory of the tesseract on the PATH, given explicitly
+    tesseract_dir = os.path.dirname(shutil.which("tesseract"))
+    ocr_config = TesseractOcrConfig().set_tesseract_path(tesseract_dir).set_language("ara")
+    result, metadata = Extractor().set_ocr_config(ocr_config).extract_file_to_string(
+        "../../test_files/documents/ara-ocr.png")
+
+    assert metadata["extractous:ocr_used"] == ["true"]
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_ocr_spill_to_disk_deu_ocr_pdf():
+    pdf_config = PdfParserConfig().set_ocr_strategy(PdfOcrStrategy.OCR_ONLY)
+    ocr_config = TesseractOcrConfig().set_language("deu")
+    extractor = Extractor().set_ocr_spill_to_disk(True).set_ocr_config(ocr_config).set_pdf_config(pdf_config)
+
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/deu-ocr.pdf")
+
+    with open("../../test_files/expected_result/deu-ocr.pdf.txt", "r", encoding="utf8") as file:
+        expected = file.read()
+
+    assert cosine_similarity(result, expected) > 0.9
+    assert metadata["extractous:ocr_used"] == ["true"]
+
+
+@pytest.mark.skipif(sys.platform.startswith("win"), reason="Test not supported on Windows")
+def test_extract_image_ocr_eng_ocr_pdf():
+    images = Extractor().extract_image_ocr("../../test_files/documents/eng-ocr.pdf")
+
+    assert len(images) > 0
+    assert [image.image_index for image in images] == list(range(len(images)))
+    assert images[0].page == 1
+    assert "Inventors Are Everywhere" in images[0].text
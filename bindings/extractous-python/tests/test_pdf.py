@@ -1,4 +1,4 @@
-from extractous import Extractor
+from extractous import Extractor, PdfParserConfig
 from utils import read_to_string, extract_body_text
 
 
@@ -58,3 +58,139 @@ def test_extract_bytes_as_xml():
     print(f"test_pdf:test_extract_bytes_as_xml result = {result_xml}")
     result_text = extract_body_text(result_xml)
     assert result_text.strip() == expected_result().strip()
+
+def test_signature_info_unsigned():
+    extractor = Extractor()
+    signatures = extractor.signature_info("tests/quarkus.pdf")
+
+    assert signatures == []
+
+def test_extract_file_paragraphs():
+    extractor = Extractor()
+    paragraphs = extractor.extract_file_paragraphs("tests/quarkus.pdf")
+
+    assert list(paragraphs) == ["Hello Quarkus"]
+    metadata = paragraphs.finish()
+    assert metadata["Content-Type"] == ["application/pdf"]
+
+def test_extract_pdf_word_boxes():
+    extractor = Extractor()
+    words = extractor.extract_pdf_word_boxes("tests/quarkus.pdf")
+
+    assert len(words) > 0
+    assert all(word.page >= 1 for word in words)
+    assert all(word.width > 0 and word.height > 0 for word in words)
+    assert "Quarkus" in [word.text for word in words]
+
+def test_list_fonts():
+    extractor = Extractor()
+    fonts = extractor.list_fonts("tests/quarkus.pdf")
+
+    assert len(fonts) > 0
+    assert all(font.subtype in ("Type0", "Type1", "MMType1", "Type3", "TrueType") for font in fonts)
+
+def test_pdf_conformance_info():
+    extractor = Extractor()
+    info = extractor.pdf_conformance_info("../../test_files/documents/pdfa-tagged.pdf")
+    assert info.pdfa_level == "2B"
+    assert info.tagged
+    assert info.has_structure_tree
+
+    info = extractor.pdf_conformance_info("tests/quarkus.pdf")
+    assert info.pdfa_level is None
+
+def test_extract_file_with_handler():
+    class TextCollector:
+        def __init__(self):
+            self.elements = []
+            self.text = ""
+
+        def start_element(self, name, attributes):
+            self.elements.append(name)
+
+        def characters(self, text):
+            self.text += text
+
+    handler = TextCollector()
+    extractor = Extractor()
+    metadata = extractor.extract_file_with_handler("tests/quarkus.pdf", handler)
+
+    assert "body" in handler.elements
+    assert "Hello Quarkus" in handler.text
+    assert len(metadata) > 0
+
+
+def test_extract_file_to_string_exclude_hidden_text():
+    extractor = Extractor().set_exclude_hidden_text(True)
+    result, metadata = extractor.extract_file_to_string("../../test_files/documents/hidden-text.pdf")
+
+    assert "Quarterly report for the visible reader." in result
+    assert "White keyword stuffing" not in result
+    assert "Invisible rendering mode" not in result
+
+
+def test_extract_file_to_string_soft_time_budget():
+    file_path = "../../test_files/documents/2022_Q3_AAPL.pdf"
+    full, metadata = Extractor().extract_file_to_string(file_path)
+    assert "extractous:content_partial" not in metadata
+
+    extractor = Extractor().set_soft_time_budget(0.001)
+    result, metadata = extractor.extract_file_to_string(file_path)
+
+    assert len(result) < len(full)
+    assert metadata["extractous:content_partial"] == ["true"]
+
+
+def test_extract_file_to_string_with_font_dir():
+    file_path = "../../test_files/documents/2022_Q3_AAPL.pdf"
+    expected, _metadata = Extractor().extract_file_to_string(file_path)
+
+    # No font in the directory, the fonts are mapped as without it
+    pdf_config = PdfParserConfig().set_font_dir("../../test_files/documents")
+    result, _metadata = Extractor().set_pdf_config(pdf_config).extract_file_to_string(file_path)
+
+    assert result == expected
+
+def test_extract_file_sort_by_position_is_deterministic():
+    extractor = Extractor().set_pdf_config(PdfParserConfig().set_sort_by_position(True))
+    results = [extractor.extract_file_to_string("tests/quarkus.pdf")[0] for _ in range(3)]
+
+    assert results == [expected_result()] * 3
+
+
+def test_extract_file_to_string_page_range():
+    file_path = "../../test_files/documents/2022_Q3_AAPL.pdf"
+    full, _metadata = Extractor().extract_file_to_string(file_path)
+
+    pdf_config = PdfParserConfig().set_page_range(1, 1)
+    result, metadata = Extractor().set_pdf_config(pdf_config).extract_file_to_string(file_path)
+    assert 0 < len(result.strip()) and len(result) < len(full)
+    assert metadata["xmpTPg:NPages"] == ["1"]
+
+    # Clamped to the page count
+    pdf_config = PdfParserConfig().set_page_range(0, 1000)
+    result, _metadata = Extractor().set_pdf_config(pdf_config).extract_file_to_string(file_path)
+    assert result == full
+
+
+def test_extract_file_to_string_form_fields():
+    file_path = "../../test_files/documents/filled-form.pdf"
+    _result, metadata = Extractor().extract_file_to_string(file_path)
+    assert metadata["extractous:form_field:applicant.name"] == ["Jane Doe"]
+    assert metadata["extractous:form_field:subscribed"] == ["Yes"]
+    assert metadata["extractous:form_field:contact"] == ["Phone"]
+
+    pdf_config = PdfParserConfig().set_extract_acro_form_content(False)
+    _result, metadata = Extractor().set_pdf_config(pdf_config).extract_file_to_string(file_path)
+    assert not any(key.startswith("extractous:form_field:") for key in metadata)
+
+
+def test_extract_file_to_string_annotations():
+    file_path = "../../test_files/documents/annotated-contract.pdf"
+    result, _metadata = Extractor().extract_file_to_string(file_path)
+    assert "Alice Reviewer" in result
+    assert "Cap the liability at the contract value." in result
+
+    pdf_config = PdfParserConfig().set_extract_annotation_text(False)
+    result, _metadata = Extractor().set_pdf_config(pdf_config).extract_file_to_string(file_path)
+    assert "Cap the liability at the contract value." not in result
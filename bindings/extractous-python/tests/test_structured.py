@@ -0,0 +1,99 @@
+from extractous import ChatFormat, EntityKind, Extractor, Script, dominant_script, find_entities, simhash
+
+
+def test_extract_vcard():
+    extractor = Extractor()
+    contacts = extractor.extract_vcard("../../test_files/documents/contacts.vcf")
+
+    assert [contact.name for contact in contacts] == ["Alice Martin", "Bob Chen", "Dr. Claire Dubois"]
+    assert contacts[0].emails == ["alice.martin@example.com"]
+    assert contacts[1].emails == ["bob.chen@example.org", "b.chen@example.com"]
+    assert contacts[2].emails == ["claire.dubois@example.fr"]
+    assert contacts[1].phones == ["+1 555 0102"]
+
+
+def test_extract_icalendar():
+    extractor = Extractor()
+    events = extractor.extract_icalendar("../../test_files/documents/calendar.ics")
+
+    assert [event.summary for event in events] == ["Quarterly planning", "Company offsite"]
+    assert events[0].start == "20240115T090000Z"
+    assert events[0].end == "20240115T110000Z"
+    assert events[1].location == "Lakeside Lodge, Building 2"
+
+
+def test_extract_chat_whatsapp():
+    extractor = Extractor()
+    chat = extractor.extract_chat("../../test_files/documents/whatsapp-chat.txt")
+
+    assert chat.format == ChatFormat.WhatsApp
+    assert [message.sender for message in chat.messages] == [
+        None, "Alice Martin", "Bob Chen", "Alice Martin", "Bob Chen"
+    ]
+    assert chat.messages[1].timestamp == "12/31/23, 9:05\u202fPM"
+    assert chat.messages[2].text == "Yes, 10:30 in room B.\nI will bring the printed contracts."
+
+
+def test_extract_chat_plain_text_fallback():
+    extractor = Extractor()
+    chat = extractor.extract_chat("../../test_files/documents/contacts.vcf")
+
+    assert chat.format is None
+    assert chat.messages == []
+    assert "Alice Martin" in chat.text
+
+
+def test_extract_mime_message():
+    extractor = Extractor()
+    message = extractor.extract_mime_message("../../test_files/documents/multipart-alternative.eml")
+
+    assert "The quarterly orders are attached." in message.text_plain
+    assert "<b>quarterly orders</b>" in message.text_html
+    assert len(message.attachments) == 1
+    assert message.attachments[0].metadata["resourceName"] == ["orders.csv"]
+    assert "ORDER-2002" in message.attachments[0].content
+
+
+def test_find_entities():
+    text = "Zoë: Zoe@Example.com or 555-0199, see www.example.org."
+    entities = find_entities(text)
+
+    assert [(entity.kind, entity.value) for entity in entities] == [
+        (EntityKind.Email, "zoe@example.com"),
+        (EntityKind.Phone, "5550199"),
+        (EntityKind.Url, "http://www.example.org"),
+    ]
+    assert text[entities[0].start:entities[0].end] == "Zoe@Example.com"
+
+
+def test_dominant_script():
+    assert dominant_script("自然语言处理需要先进行分词。") == Script.Cjk
+    assert dominant_script("한국어 텍스트입니다") == Script.Hangul
+    assert dominant_script("2024-03-04") is None
+
+    extractor = Extractor().set_detect_script(True)
+    _result, metadata = extractor.extract_bytes_to_string(bytearray("日本語のテキストです".encode("utf-8")))
+    assert metadata["extractous:dominant_script"] == ["Cjk"]
+
+
+def test_detect_language():
+    extractor = Extractor()
+    language, confidence = extractor.detect_language(
+        "Die Quartalszahlen zeigen ein deutliches Wachstum im Geschäft mit Cloud-Diensten.")
+    assert language == "de"
+    assert 0.5 < confidence <= 1.0
+
+    extractor = Extractor().set_detect_language(True)
+    text = "Les résultats trimestriels montrent une forte croissance des services en ligne."
+    _result, metadata = extractor.extract_bytes_to_string(bytearray(text.encode("utf-8")))
+    assert metadata["language"] == ["fr"]
+
+
+def test_compute_simhash():
+    text = "Quarterly revenue grew by twelve percent, driven by demand for cloud services."
+    extractor = Extractor().set_compute_simhash(True)
+    _result, metadata = extractor.extract_bytes_to_string(bytearray(text.encode("utf-8")))
+
+    assert metadata["X-SimHash"] == [format(simhash(text), "016x")]
+    assert simhash(text.upper()) == simhash(text)
+    assert bin(simhash(text) ^ simhash(text.replace("twelve", "eleven"))).count("1") < 16
@@ -0,0 +1,32 @@
+import subprocess
+import sys
+
+from extractous import Extractor, get_jvm_memory_stats
+
+# The vm must be configured before it is created, so the checks run in a fresh interpreter
+SCRIPT = """
+from extractous import Extractor, configure_vm, get_jvm_memory_usage
+
+configure_vm(max_heap_mb=512, initial_heap_mb=64)
+Extractor().extract_file_to_string("../../test_files/documents/simple.odt")
+assert get_jvm_memory_usage()["maxMemoryMB"] <= 512
+
+try:
+    configure_vm(max_heap_mb=1024)
+except RuntimeError as e:
+    assert "VmAlreadyCreated" in str(e)
+else:
+    raise AssertionError("the vm was reconfigured")
+"""
+
+
+def test_configure_vm():
+    subprocess.run([sys.executable, "-c", SCRIPT], check=True)
+
+
+def test_get_jvm_memory_stats():
+    Extractor().extract_file_to_string("../../test_files/documents/simple.odt")
+    stats = get_jvm_memory_stats()
+    assert stats.heap_used > 0
+    assert stats.heap_committed >= stats.heap_used
+    assert stats.gc_count >= 0
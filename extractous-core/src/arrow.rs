@@ -0,0 +1,168 @@
+use crate::Metadata;
+use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt32Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::sync::{Arc, OnceLock};
+
+/// Returns the schema of the record batches built by [`to_arrow`], one row per extracted
+/// document. The schema is stable: columns are only ever added at the end.
+///
+/// | column       | type   | nullable | value                                                  |
+/// |--------------|--------|----------|--------------------------------------------------------|
+/// | `content`    | Utf8   | no       | extracted content                                      |
+/// | `mime_type`  | Utf8   | yes      | detected `Content-Type`, without its parameters        |
+/// | `page_count` | UInt32 | yes      | `xmpTPg:NPages` (PDF) or `meta:page-count` (Office)    |
+/// | `title`      | Utf8   | yes      | `dc:title`                                             |
+/// | `author`     | Utf8   | yes      | `dc:creator`, or `meta:author` when missing            |
+/// | `language`   | Utf8   | yes      | `dc:language`                                          |
+/// | `created`    | Utf8   | yes      | `dcterms:created`, as written in the metadata          |
+/// | `modified`   | Utf8   | yes      | `dcterms:modified`, as written in the metadata         |
+///
+/// Metadata fields are the first non blank value of the key, trimmed, or null when there is
+/// none. Dates are usually ISO-8601 strings such as `2024-07-11T19:20:35Z`.
+pub fn arrow_schema() -> SchemaRef {
+    static SCHEMA: OnceLock<SchemaRef> = OnceLock::new();
+    SCHEMA
+        .get_or_init(|| {
+            Arc::new(Schema::new(vec![
+                Field::new("content", DataType::Utf8, false),
+                Field::new("mime_type", DataType::Utf8, true),
+                Field::new("page_count", DataType::UInt32, true),
+                Field::new("title", DataType::Utf8, true),
+                Field::new("author", DataType::Utf8, true),
+                Field::new("language", DataType::Utf8, true),
+                Field::new("created", DataType::Utf8, true),
+                Field::new("modified", DataType::Utf8, true),
+            ]))
+        })
+        .clone()
+}
+
+/// Converts `(content, metadata)` extraction results, as returned by
+/// [`crate::Extractor::extract_file_to_string`], to a record batch of the [`arrow_schema`]
+/// with one row per result, in order.
+pub fn to_arrow(results: &[(String, Metadata)]) -> RecordBatch {
+    let text_column = |keys: &[&str]| -> ArrayRef {
+        Arc::new(
+            results
+                .iter()
+                .map(|(_, metadata)| first_value(metadata, keys))
+                .collect::<StringArray>(),
+        )
+    };
+
+    let content: ArrayRef = Arc::new(
+        results
+            .iter()
+            .map(|(content, _)| Some(content.as_str()))
+            .collect::<StringArray>(),
+    );
+    let mime_type: ArrayRef = Arc::new(
+        results
+            .iter()
+            .map(|(_, metadata)| {
+                first_value(metadata, &["Content-Type"])
+                    .map(|value| value.split(';').next().unwrap_or_default().trim())
+            })
+            .collect::<StringArray>(),
+    );
+    let page_count: ArrayRef = Arc::new(
+        results
+            .iter()
+            .map(|(_, metadata)| {
+                first_value(metadata, &["xmpTPg:NPages", "meta:page-count"])
+                    .and_then(|value| value.parse::<u32>().ok())
+            })
+            .collect::<UInt32Array>(),
+    );
+
+    let columns = vec![
+        content,
+        mime_type,
+        page_count,
+        text_column(&["dc:title"]),
+        text_column(&["dc:creator", "meta:author"]),
+        text_column(&["dc:language"]),
+        text_column(&["dcterms:created"]),
+        text_column(&["dcterms:modified"]),
+    ];
+    RecordBatch::try_new(arrow_schema(), columns)
+        .expect("columns are built to match the arrow schema")
+}
+
+/// Returns the first non blank value of the first key that has one
+fn first_value<'a>(metadata: &'a Metadata, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| {
+        metadata
+            .get(*key)?
+            .iter()
+            .map(|value| value.trim())
+            .find(|value| !value.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arrow_schema, to_arrow};
+    use crate::Metadata;
+    use arrow_array::{Array, StringArray, UInt32Array};
+
+    fn metadata(entries: &[(&str, &str)]) -> Metadata {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+            .collect()
+    }
+
+    #[test]
+    fn converts_results_to_record_batch() {
+        let results = vec![
+            (
+                "Quarterly report".to_string(),
+                metadata(&[
+                    ("Content-Type", "application/pdf"),
+                    ("xmpTPg:NPages", "3"),
+                    ("dc:title", " Report "),
+                    ("dc:creator", "Alice"),
+                ]),
+            ),
+            (
+                "Notes".to_string(),
+                metadata(&[
+                    ("Content-Type", "text/plain; charset=UTF-8"),
+                    ("meta:author", "Bob"),
+                    ("dc:title", " "),
+                ]),
+            ),
+        ];
+
+        let batch = to_arrow(&results);
+        assert_eq!(batch.schema(), arrow_schema());
+        assert_eq!(batch.num_rows(), 2);
+
+        let text = |name: &str| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(text("content").value(1), "Notes");
+        assert_eq!(text("mime_type").value(1), "text/plain");
+        assert_eq!(text("title").value(0), "Report");
+        assert!(text("title").is_null(1));
+        assert_eq!(text("author").value(0), "Alice");
+        assert_eq!(text("author").value(1), "Bob");
+        assert!(text("created").is_null(0));
+
+        let pages = batch
+            .column_by_name("page_count")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt32Array>()
+            .unwrap();
+        assert_eq!(pages.value(0), 3);
+        assert!(pages.is_null(1));
+    }
+}
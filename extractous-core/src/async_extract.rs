@@ -0,0 +1,134 @@
+use crate::errors::{Error, ExtractResult};
+use crate::{Extractor, Metadata, MetadataView, StreamReader, DEFAULT_BUF_SIZE};
+use std::future::Future;
+use std::io::{ErrorKind, Read};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::task::{spawn_blocking, JoinError, JoinHandle};
+
+/// Runs the extraction on the blocking thread pool of the tokio runtime with a copy of the
+/// extractor, so that the runtime threads do not wait on the VM
+pub(crate) async fn run_blocking<T, F>(extractor: &Extractor, extract: F) -> ExtractResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Extractor) -> ExtractResult<T> + Send + 'static,
+{
+    let extractor = extractor.clone();
+    spawn_blocking(move || extract(&extractor))
+        .await
+        .map_err(join_error)?
+}
+
+/// Resumes the panic of the blocking task on the awaiting task, as the synchronous methods
+/// would have panicked on the caller's thread
+fn join_error(error: JoinError) -> Error {
+    match error.try_into_panic() {
+        Ok(payload) => std::panic::resume_unwind(payload),
+        // Only when the runtime shuts down
+        Err(_) => Error::IoError("the extraction task was cancelled".to_string()),
+    }
+}
+
+/// [`AsyncRead`] adapter of a [`StreamReader`], returned by [`Extractor::extract_file_async`]
+/// and the other `*_async` stream methods, or made from any stream with
+/// [`AsyncStreamReader::new`].
+///
+/// Reading a [`StreamReader`] blocks on the VM while the document is parsed, so each chunk of
+/// up to [`DEFAULT_BUF_SIZE`] bytes is read on the blocking thread pool of the tokio runtime
+/// and then handed out from memory. Dropping the adapter while a chunk is being read waits for
+/// nothing: the read finishes on the pool and the stream is dropped there.
+pub struct AsyncStreamReader {
+    state: State,
+    metadata: Metadata,
+}
+
+enum State {
+    /// The stream, with the chunk read last and how much of it was handed out, none once the
+    /// task reading it was cancelled
+    Idle(Option<(StreamReader, Vec<u8>, usize)>),
+    Reading(JoinHandle<(StreamReader, Vec<u8>, std::io::Result<usize>)>),
+}
+
+impl AsyncStreamReader {
+    pub fn new(reader: StreamReader) -> Self {
+        let metadata = reader.metadata().clone();
+        Self {
+            state: State::Idle(Some((reader, Vec::new(), 0))),
+            metadata,
+        }
+    }
+
+    /// Returns the metadata of the document, see [`StreamReader::metadata`]
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the media type of the document, see [`StreamReader::content_type`]
+    pub fn content_type(&self) -> Option<&str> {
+        MetadataView::new(&self.metadata).content_type()
+    }
+}
+
+impl From<StreamReader> for AsyncStreamReader {
+    fn from(reader: StreamReader) -> Self {
+        Self::new(reader)
+    }
+}
+
+impl AsyncRead for AsyncStreamReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        loop {
+            match &mut self.state {
+                State::Idle(idle) => {
+                    let Some((_, chunk, pos)) = idle.as_mut() else {
+                        return Poll::Ready(Err(std::io::Error::other(
+                            "the extraction task was cancelled",
+                        )));
+                    };
+                    if *pos < chunk.len() {
+                        let len = buf.remaining().min(chunk.len() - *pos);
+                        buf.put_slice(&chunk[*pos..*pos + len]);
+                        *pos += len;
+                        return Poll::Ready(Ok(()));
+                    }
+                    let (mut reader, mut chunk, _) = idle.take().expect("the stream is idle");
+                    self.state = State::Reading(spawn_blocking(move || {
+                        chunk.resize(DEFAULT_BUF_SIZE, 0);
+                        let result = reader.read(&mut chunk);
+                        (reader, chunk, result)
+                    }));
+                }
+                State::Reading(handle) => {
+                    let (reader, mut chunk, result) = match ready!(Pin::new(handle).poll(cx)) {
+                        Ok(read) => read,
+                        Err(error) => {
+                            self.state = State::Idle(None);
+                            return Poll::Ready(Err(join_error(error).into()));
+                        }
+                    };
+                    let read = match result {
+                        Ok(read) => read,
+                        Err(error) => {
+                            self.state = State::Idle(Some((reader, Vec::new(), 0)));
+                            if error.kind() == ErrorKind::Interrupted {
+                                continue;
+                            }
+                            return Poll::Ready(Err(error));
+                        }
+                    };
+                    chunk.truncate(read);
+                    self.state = State::Idle(Some((reader, chunk, 0)));
+                    if read == 0 {
+                        // The end of the stream
+                        return Poll::Ready(Ok(()));
+                    }
+                }
+            }
+        }
+    }
+}
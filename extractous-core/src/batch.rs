@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+use crate::errors::{Error, ExtractResult};
+use crate::{Extractor, Metadata};
+
+/// The result of one file of [`Extractor::extract_files_streaming`], with its path
+pub type FileResult = (String, ExtractResult<(String, Metadata)>);
+
+type Queue = Arc<Mutex<VecDeque<(usize, String)>>>;
+
+/// Extracts the files to string on `workers` worker threads and sends each result as soon as it
+/// is done
+pub(crate) fn extract_files_streaming(
+    extractor: &Extractor,
+    file_paths: &[&str],
+    workers: usize,
+) -> Receiver<FileResult> {
+    let (sender, receiver) = channel();
+    run_workers(
+        extractor,
+        file_paths,
+        workers,
+        move |_index, path, result| sender.send((path, result)).is_ok(),
+    );
+    receiver
+}
+
+/// Extracts the files to string on worker threads and returns the results in the order of
+/// `file_paths` once they are all done
+pub(crate) fn extract_files_to_string(
+    extractor: &Extractor,
+    file_paths: &[&str],
+) -> Vec<ExtractResult<(String, Metadata)>> {
+    let (sender, receiver) = channel();
+    run_workers(
+        extractor,
+        file_paths,
+        available_workers(),
+        move |index, _path, result| sender.send((index, result)).is_ok(),
+    );
+
+    let mut results: Vec<_> = file_paths.iter().map(|_| None).collect();
+    // The senders are dropped with the workers, which closes the channel
+    for (index, result) in receiver {
+        results[index] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .unwrap_or_else(|| Err(Error::Unknown("The extraction did not finish".to_string())))
+        })
+        .collect()
+}
+
+/// One worker per available CPU
+pub(crate) fn available_workers() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Starts `workers` workers, at least one and at most one per file, that take the files from a
+/// shared queue. `send` gets the index, path and result of each file, and returns false when
+/// nobody waits for the results anymore, which stops the workers once their current file is
+/// done.
+fn run_workers<S>(extractor: &Extractor, file_paths: &[&str], workers: usize, send: S)
+where
+    S: Fn(usize, String, ExtractResult<(String, Metadata)>) -> bool + Clone + Send + 'static,
+{
+    let queue: Queue = Arc::new(Mutex::new(
+        file_paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| (index, path.to_string()))
+            .collect(),
+    ));
+    let workers = workers.max(1).min(file_paths.len());
+
+    let mut started = 0;
+    for _ in 0..workers {
+        let extractor = extractor.clone();
+        let queue = Arc::clone(&queue);
+        let send = send.clone();
+        let spawned = thread::Builder::new()
+            .name("extractous-batch".to_string())
+            .spawn(move || work(&extractor, &queue, send));
+        if spawned.is_ok() {
+            started += 1;
+        }
+    }
+    if started == 0 {
+        // Every file still gets its result
+        while let Some((index, path)) = next_path(&queue) {
+            let error = Error::Unknown("Could not start the extraction threads".to_string());
+            send(index, path, Err(error));
+        }
+    }
+}
+
+fn work<S>(extractor: &Extractor, queue: &Queue, send: S)
+where
+    S: Fn(usize, String, ExtractResult<(String, Metadata)>) -> bool,
+{
+    while let Some((index, path)) = next_path(queue) {
+        // A panic fails its file only, the worker goes on with the next one
+        let result =
+            panic::catch_unwind(AssertUnwindSafe(|| extractor.extract_file_to_string(&path)))
+                .unwrap_or_else(|_| Err(Error::Unknown("The extraction panicked".to_string())));
+        if !send(index, path, result) {
+            // The receiver was dropped, nobody waits for the remaining files
+            break;
+        }
+    }
+}
+
+fn next_path(queue: &Queue) -> Option<(usize, String)> {
+    queue
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .pop_front()
+}
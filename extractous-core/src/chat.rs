@@ -0,0 +1,243 @@
+use serde_json::Value;
+
+/// Chat export formats recognized by [`crate::Extractor::extract_chat`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatFormat {
+    /// The `.txt` transcript written by WhatsApp's "Export chat", in the Android
+    /// `date, time - sender: text` or the iOS `[date, time] sender: text` layout
+    WhatsApp,
+    /// The JSON file of one channel and day of a Slack workspace export
+    Slack,
+}
+
+/// A message of a chat export
+///
+/// Timestamps are returned as written in the export: the locale dependent date and time of
+/// WhatsApp, e.g. `12/31/23, 9:41 PM`, or the `ts` epoch seconds of Slack, e.g.
+/// `1704067200.000100`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChatMessage {
+    /// Sender of the message, `None` for the system messages such as members joining
+    pub sender: Option<String>,
+    pub timestamp: Option<String>,
+    /// Text of the message, with its line breaks
+    pub text: String,
+}
+
+/// The messages of a chat export, or its plain text when the format is not recognized
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Chat {
+    /// Format of the export, `None` when it is not recognized
+    pub format: Option<ChatFormat>,
+    /// Messages in the order of the export, empty when the format is not recognized
+    pub messages: Vec<ChatMessage>,
+    /// Text extracted by the parsers when the format is not recognized, empty otherwise
+    pub text: String,
+}
+
+/// Returns the format of the export and its messages, or `None` when the text is neither a
+/// WhatsApp transcript nor a Slack export
+pub(crate) fn parse_chat(text: &str) -> Option<(ChatFormat, Vec<ChatMessage>)> {
+    let text = text.trim_start_matches('\u{feff}');
+    if let Some(messages) = parse_slack(text) {
+        return Some((ChatFormat::Slack, messages));
+    }
+    parse_whatsapp(text).map(|messages| (ChatFormat::WhatsApp, messages))
+}
+
+/// Parses a WhatsApp transcript. A transcript starts with a message header, the lines that are
+/// not one continue the text of the previous message.
+fn parse_whatsapp(text: &str) -> Option<Vec<ChatMessage>> {
+    let mut messages: Vec<ChatMessage> = Vec::new();
+    for line in text.lines() {
+        match parse_whatsapp_header(line) {
+            Some(message) => messages.push(message),
+            None => match messages.last_mut() {
+                Some(message) => {
+                    message.text.push('\n');
+                    message.text.push_str(line);
+                }
+                None if line.trim().is_empty() => {}
+                None => return None,
+            },
+        }
+    }
+    for message in &mut messages {
+        message.text.truncate(message.text.trim_end().len());
+    }
+    (!messages.is_empty()).then_some(messages)
+}
+
+/// Parses the first line of a WhatsApp message, `12/31/23, 9:41 PM - Alice: text` on Android or
+/// `[31.12.23, 21:41:05] Alice: text` on iOS
+fn parse_whatsapp_header(line: &str) -> Option<ChatMessage> {
+    // iOS marks the lines of system messages and attachments with a left-to-right mark
+    let line = line.trim_start_matches(['\u{200e}', '\u{200f}']);
+    let (timestamp, rest) = match line.strip_prefix('[') {
+        Some(bracketed) => {
+            let (timestamp, rest) = bracketed.split_once(']')?;
+            (timestamp, rest.strip_prefix(' ')?)
+        }
+        None => line.split_once(" - ")?,
+    };
+    if !is_whatsapp_timestamp(timestamp) {
+        return None;
+    }
+
+    let rest = rest.trim_start_matches(['\u{200e}', '\u{200f}']);
+    let (sender, text) = match rest.split_once(": ") {
+        Some((sender, text)) if !sender.trim().is_empty() => (Some(sender.trim()), text),
+        _ => (None, rest),
+    };
+    Some(ChatMessage {
+        sender: sender.map(str::to_string),
+        timestamp: Some(timestamp.trim().to_string()),
+        text: text
+            .trim_start_matches(['\u{200e}', '\u{200f}'])
+            .to_string(),
+    })
+}
+
+/// Whether the text is a WhatsApp date and time: a date of three numbers, a comma or a space,
+/// and a time of two or three numbers, followed by an optional AM/PM marker in any language
+fn is_whatsapp_timestamp(timestamp: &str) -> bool {
+    let Some((date, time)) = timestamp
+        .split_once(", ")
+        .or_else(|| timestamp.split_once(' '))
+    else {
+        return false;
+    };
+
+    let date_parts: Vec<&str> = date.split(['/', '.', '-']).collect();
+    let valid_date = date_parts.len() == 3 && date_parts.iter().all(|part| is_number(part, 4));
+
+    // The marker is separated by a space, a no-break space or a narrow no-break space
+    let clock = time
+        .trim()
+        .split([' ', '\u{a0}', '\u{202f}'])
+        .next()
+        .unwrap_or_default();
+    let time_parts: Vec<&str> = clock.split(':').collect();
+    let valid_time =
+        (2..=3).contains(&time_parts.len()) && time_parts.iter().all(|part| is_number(part, 2));
+
+    valid_date && valid_time
+}
+
+fn is_number(text: &str, max_digits: usize) -> bool {
+    (1..=max_digits).contains(&text.len()) && text.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parses a Slack export, an array of message objects with at least a `ts`
+fn parse_slack(text: &str) -> Option<Vec<ChatMessage>> {
+    if !text.trim_start().starts_with('[') {
+        return None;
+    }
+    let Value::Array(items) = serde_json::from_str(text).ok()? else {
+        return None;
+    };
+    if items.is_empty() {
+        return None;
+    }
+
+    let mut messages = Vec::with_capacity(items.len());
+    for item in &items {
+        let Value::Object(object) = item else {
+            return None;
+        };
+        let timestamp = object.get("ts").and_then(Value::as_str)?;
+        if object.get("type").and_then(Value::as_str) != Some("message") {
+            continue;
+        }
+        let field = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let profile = object.get("user_profile");
+        // Prefer the names people see in the client over the member id
+        let sender = field(profile.and_then(|profile| profile.get("real_name")))
+            .or_else(|| field(profile.and_then(|profile| profile.get("display_name"))))
+            .or_else(|| field(object.get("user_name")))
+            .or_else(|| field(object.get("username")))
+            .or_else(|| field(object.get("user")));
+        messages.push(ChatMessage {
+            sender,
+            timestamp: Some(timestamp.to_string()),
+            text: object
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+    Some(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_chat, ChatFormat, ChatMessage};
+
+    fn message(sender: Option<&str>, timestamp: &str, text: &str) -> ChatMessage {
+        ChatMessage {
+            sender: sender.map(str::to_string),
+            timestamp: Some(timestamp.to_string()),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_whatsapp_ios_transcript() {
+        let text = "[31.12.23, 21:41:05] Alice: \u{200e}Messages are end-to-end encrypted.\n\
+                    [31.12.23, 21:42:10] Bob: See you at 10: bring the slides\nand the charts\n";
+        assert_eq!(
+            parse_chat(text),
+            Some((
+                ChatFormat::WhatsApp,
+                vec![
+                    message(
+                        Some("Alice"),
+                        "31.12.23, 21:41:05",
+                        "Messages are end-to-end encrypted."
+                    ),
+                    message(
+                        Some("Bob"),
+                        "31.12.23, 21:42:10",
+                        "See you at 10: bring the slides\nand the charts"
+                    ),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_slack_export() {
+        let text = r#"[
+            {"type": "message", "user": "U01", "text": "Hello", "ts": "1704067200.000100",
+             "user_profile": {"real_name": "Alice Martin", "display_name": "alice"}},
+            {"type": "message", "subtype": "bot_message", "username": "deploy-bot",
+             "text": "Deployed", "ts": "1704067260.000200"}
+        ]"#;
+        assert_eq!(
+            parse_chat(text),
+            Some((
+                ChatFormat::Slack,
+                vec![
+                    message(Some("Alice Martin"), "1704067200.000100", "Hello"),
+                    message(Some("deploy-bot"), "1704067260.000200", "Deployed"),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_other_text() {
+        assert_eq!(
+            parse_chat("Meeting notes\n12/31/23, 9:41 PM - Alice: hi"),
+            None
+        );
+        assert_eq!(parse_chat(r#"[{"name": "not a message"}]"#), None);
+    }
+}
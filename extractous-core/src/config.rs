@@ -1,12 +1,25 @@
+use crate::ocr_languages::{is_executable, list_ocr_languages, tesseract_executable};
+use crate::{Error, ExtractResult};
+use std::fmt;
+use std::time::Duration;
 use strum_macros::{Display, EnumString};
 
-/// OCR Strategy for PDF parsing
+/// OCR Strategy for PDF parsing, the variants map to Tika's `PDFParserConfig.OCR_STRATEGY`.
+///
+/// OCR needs Tesseract, see [`TesseractOcrConfig`](crate::TesseractOcrConfig). The number of
+/// pages that went through OCR is reported in the `pdf:ocrPageCount` metadata, so a document
+/// whose text is empty although pages were OCRed can be told apart from one that was never OCRed.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
 #[allow(non_camel_case_types)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PdfOcrStrategy {
+    /// Only extracts the text of the content streams, image-only pages yield no text
     NO_OCR,
+    /// Renders every page and only extracts the text found by OCR
     OCR_ONLY,
+    /// Extracts the text of the content streams and the text found by OCR on every page
     OCR_AND_TEXT_EXTRACTION,
+    /// Lets Tika decide per page: pages with too little extractable text are OCRed
     #[default]
     AUTO,
 }
@@ -14,13 +27,52 @@ pub enum PdfOcrStrategy {
 /// PDF parsing configuration settings
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PdfParserConfig {
     pub(crate) ocr_strategy: PdfOcrStrategy,
     pub(crate) extract_inline_images: bool,
     pub(crate) extract_unique_inline_images_only: bool,
+    pub(crate) extract_inline_image_metadata: bool,
     pub(crate) extract_marked_content: bool,
     pub(crate) extract_annotation_text: bool,
+    pub(crate) extract_acro_form_content: bool,
+    pub(crate) sort_by_position: bool,
+    pub(crate) font_dir: Option<String>,
+    // Secret, neither printed nor serialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) password: Option<String>,
+    pub(crate) page_range: Option<(u32, u32)>,
+}
+
+impl fmt::Debug for PdfParserConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PdfParserConfig")
+            .field("ocr_strategy", &self.ocr_strategy)
+            .field("extract_inline_images", &self.extract_inline_images)
+            .field(
+                "extract_unique_inline_images_only",
+                &self.extract_unique_inline_images_only,
+            )
+            .field(
+                "extract_inline_image_metadata",
+                &self.extract_inline_image_metadata,
+            )
+            .field("extract_marked_content", &self.extract_marked_content)
+            .field("extract_annotation_text", &self.extract_annotation_text)
+            .field("extract_acro_form_content", &self.extract_acro_form_content)
+            .field("sort_by_position", &self.sort_by_position)
+            .field("font_dir", &self.font_dir)
+            .field("password", &redacted(&self.password))
+            .field("page_range", &self.page_range)
+            .finish()
+    }
+}
+
+/// Stands in for a password in the `Debug` output, telling only whether one is set
+fn redacted(password: &Option<String>) -> Option<&'static str> {
+    password.as_ref().map(|_| "***")
 }
 
 impl Default for PdfParserConfig {
@@ -29,8 +81,14 @@ impl Default for PdfParserConfig {
             ocr_strategy: PdfOcrStrategy::AUTO,
             extract_inline_images: false,
             extract_unique_inline_images_only: false,
+            extract_inline_image_metadata: false,
             extract_marked_content: false,
             extract_annotation_text: true,
+            extract_acro_form_content: true,
+            sort_by_position: false,
+            font_dir: None,
+            password: None,
+            page_range: None,
         }
     }
 }
@@ -77,6 +135,29 @@ impl PdfParserConfig {
         self
     }
 
+    /// If true, describe the images drawn on the pages of the PDF, without extracting them.
+    /// The number of images, an image drawn several times counting once per drawing, is set to
+    /// `extractous:pdf_image_count` in the metadata of the document. The `*_recursive` methods
+    /// also return every image as an embedded document without content, whose metadata holds:
+    /// - `extractous:image_page`: the page it is drawn on, 1-based
+    /// - `extractous:image_box`: the box it is drawn in, `x,y,width,height` in points from the
+    ///   bottom left corner of the page
+    /// - `tiff:ImageWidth` and `tiff:ImageLength`: its size in pixels
+    /// - `extractous:image_type`: the type it is stored as, such as `image/jpeg`
+    /// - `extractous:image_alt`: its alternate description in tagged PDF documents, the `Alt`,
+    ///   or failing that the `ActualText`, of the structure element it belongs to, usually a
+    ///   `Figure`, when there is one
+    ///
+    /// The embedded documents are detected as `application/octet-stream`, which matters to
+    /// [`Extractor::set_embedded_mime_filter`](crate::Extractor::set_embedded_mime_filter).
+    /// This is independent of [`set_extract_inline_images`](Self::set_extract_inline_images),
+    /// and opens the document a second time after parsing it.
+    /// Default: false.
+    pub fn set_extract_inline_image_metadata(mut self, val: bool) -> Self {
+        self.extract_inline_image_metadata = val;
+        self
+    }
+
     /// If the PDF contains marked content, try to extract text and its marked structure.
     /// Default: false.
     pub fn set_extract_marked_content(mut self, val: bool) -> Self {
@@ -84,18 +165,83 @@ impl PdfParserConfig {
         self
     }
 
-    /// If the PDF contains annotations, try to extract the text of the annotations.
+    /// If the PDF contains annotations, try to extract the text of the annotations. The text is
+    /// appended to the content of the page the annotation is on. For the markup annotations,
+    /// such as sticky notes and highlights, it is their author, subject and comment, in that
+    /// order, each when the annotation has it. The popup annotations only display the comment
+    /// of their parent annotation and add no text of their own.
     /// Default: true.
     pub fn set_extract_annotation_text(mut self, val: bool) -> Self {
         self.extract_annotation_text = val;
         self
     }
+
+    /// If the PDF contains an AcroForm, extract the values of its fields. The fields are written
+    /// to the content as name and value text, and added to the metadata keyed by their fully
+    /// qualified name, see [`MetadataView::pdf_form_fields`](crate::MetadataView::pdf_form_fields).
+    /// Check boxes and radio buttons come with their export values.
+    /// Default: true.
+    pub fn set_extract_acro_form_content(mut self, val: bool) -> Self {
+        self.extract_acro_form_content = val;
+        self
+    }
+
+    /// If true, the text of each page is sorted by position, top to bottom then left to right,
+    /// instead of following the order of the content stream. The output then only depends on
+    /// where the text is drawn, so it stays the same for documents whose writers emit the same
+    /// page in a different order, such as successive versions of a document, at the cost of
+    /// sorting the characters of every page. The pages themselves are always in document order.
+    /// Default: false.
+    pub fn set_sort_by_position(mut self, val: bool) -> Self {
+        self.sort_by_position = val;
+        self
+    }
+
+    /// Sets a directory of TrueType and OpenType fonts (.ttf, .otf, .ttc, .otc), searched
+    /// recursively, that PDFBox uses for the fonts a document does not embed. A font of the
+    /// directory is used when its name matches the requested one, and CJK fonts also stand in
+    /// for the non-embedded CJK fonts no installed font matches, instead of the last resort
+    /// fallback. This improves the text of PDF documents whose characters are otherwise
+    /// extracted as boxes or wrong characters.
+    /// It only affects the PDF parser. The font files must be readable by the process, the
+    /// others are skipped. PDFBox maps fonts process wide, so once a directory has been used,
+    /// its fonts are used by every extraction.
+    /// Default: None.
+    pub fn set_font_dir(mut self, dir: &str) -> Self {
+        self.font_dir = Some(dir.to_string());
+        self
+    }
+
+    /// Sets the password used to open encrypted PDF documents, embedded ones included. A
+    /// document that can not be opened with it, or without a password when none is set, fails
+    /// with [`Error::EncryptedDocument`](crate::Error::EncryptedDocument). PDF documents that
+    /// are only protected by an owner password are parsed without it.
+    /// The password is shown as `***` by `Debug` and left out of the serialized config.
+    /// Default: None.
+    pub fn set_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Restricts the parsing to the pages from `start` to `end`, 1-based and inclusive. The range
+    /// is clamped to the page count of each document, so a range past the last page yields no
+    /// text, and a `start` of 0 counts as 1. The pages outside the range are dropped before the
+    /// document is parsed, so their content costs nothing, but the document level content such
+    /// as bookmarks and form fields is still extracted. Embedded PDF documents are cut the same
+    /// way.
+    /// Default: every page.
+    pub fn set_page_range(mut self, start: u32, end: u32) -> Self {
+        self.page_range = Some((start, end));
+        self
+    }
 }
 
 /// Microsoft Office parser configuration settings
 ///
 /// These settings are used to configure the behavior of the MSOffice parsing.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OfficeParserConfig {
     pub(crate) extract_macros: bool,
     pub(crate) include_deleted_content: bool,
@@ -107,6 +253,41 @@ pub struct OfficeParserConfig {
     pub(crate) include_slide_master_content: bool,
     pub(crate) concatenate_phonetic_runs: bool,
     pub(crate) extract_all_alternatives_from_msg: bool,
+    pub(crate) include_comments: bool,
+    // Secret, neither printed nor serialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) password: Option<String>,
+}
+
+impl fmt::Debug for OfficeParserConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OfficeParserConfig")
+            .field("extract_macros", &self.extract_macros)
+            .field("include_deleted_content", &self.include_deleted_content)
+            .field("include_move_from_content", &self.include_move_from_content)
+            .field(
+                "include_shape_based_content",
+                &self.include_shape_based_content,
+            )
+            .field(
+                "include_headers_and_footers",
+                &self.include_headers_and_footers,
+            )
+            .field("include_missing_rows", &self.include_missing_rows)
+            .field("include_slide_notes", &self.include_slide_notes)
+            .field(
+                "include_slide_master_content",
+                &self.include_slide_master_content,
+            )
+            .field("concatenate_phonetic_runs", &self.concatenate_phonetic_runs)
+            .field(
+                "extract_all_alternatives_from_msg",
+                &self.extract_all_alternatives_from_msg,
+            )
+            .field("include_comments", &self.include_comments)
+            .field("password", &redacted(&self.password))
+            .finish()
+    }
 }
 
 impl Default for OfficeParserConfig {
@@ -122,6 +303,8 @@ impl Default for OfficeParserConfig {
             include_slide_master_content: true,
             concatenate_phonetic_runs: true,
             extract_all_alternatives_from_msg: false,
+            include_comments: false,
+            password: None,
         }
     }
 }
@@ -132,14 +315,26 @@ impl OfficeParserConfig {
         Self::default()
     }
 
-    /// Sets whether MSOffice parsers should extract macros.
+    /// Sets whether MSOffice parsers should extract macros. The VBA modules of the binary and
+    /// the macro enabled OOXML files then become embedded documents, one per module, with the
+    /// `resourceName` of the module and `X-TIKA:embedded_resource_type` set to `MACRO`. The
+    /// `*_recursive` methods return them like the other embedded documents, with the
+    /// `application/x-vba` content type, and their content is the source of the module as
+    /// stored, only decoded: the content options of the extractor, such as
+    /// [`Extractor::set_dedup_repeated_lines`](crate::Extractor::set_dedup_repeated_lines) or
+    /// [`Extractor::set_emit_line_numbers`](crate::Extractor::set_emit_line_numbers), leave it
+    /// as it is. The other methods add their text to the content of the file. Without this
+    /// setting, no macro is extracted whatever the recursion settings.
     /// Default: false.
     pub fn set_extract_macros(mut self, val: bool) -> Self {
         self.extract_macros = val;
         self
     }
 
-    /// Sets whether the docx parser should include deleted content.
+    /// Sets whether the docx parser should include deleted content, the text deleted with track
+    /// changes on. In the xml output the deleted text is tagged as
+    /// `<del class="deleted">...</del>`, within each paragraph it is in, while the text output
+    /// has it in place, like the rest of the text.
     /// Default: false.
     pub fn set_include_deleted_content(mut self, val: bool) -> Self {
         self.include_deleted_content = val;
@@ -149,7 +344,8 @@ impl OfficeParserConfig {
     /// With track changes on for the docx parser, when a section is moved, the content is stored in
     /// both the "moveFrom" section and in the "moveTo" section. If you'd like to include the
     /// section both in its original location (moveFrom) and in its new location (moveTo),
-    /// set this to true. Default: false
+    /// set this to true. In the xml output the moveFrom content is tagged as
+    /// `<del class="moved">...</del>`. Default: false
     pub fn set_include_move_from_content(mut self, val: bool) -> Self {
         self.include_move_from_content = val;
         self
@@ -198,6 +394,20 @@ impl OfficeParserConfig {
         self
     }
 
+    /// Whether to add the reviewer comments of docx, xlsx and pptx files, and of their macro
+    /// enabled variants, to the metadata: the comments of Word, the cell notes of Excel and the
+    /// comments of PowerPoint. Each comment adds one value to `extractous:comment_author`,
+    /// `extractous:comment_date` and `extractous:comment_text`, an empty one for an author or a
+    /// time the file does not record, so that the values of the same index belong together, see
+    /// [`MetadataView::office_comments`](crate::MetadataView::office_comments). Excel does not
+    /// record the time of its notes. The comment text in the content is left as the parser
+    /// writes it. The binary Office formats and encrypted files yield no comments.
+    /// Default: false
+    pub fn set_include_comments(mut self, val: bool) -> Self {
+        self.include_comments = val;
+        self
+    }
+
     /// Microsoft Excel files can sometimes contain phonetic (furigana) strings.
     /// This sets whether the parser will concatenate the phonetic runs to the original text.
     /// This is currently only supported by the xls and xlsx parsers (not the xlsb parser).
@@ -215,12 +425,25 @@ impl OfficeParserConfig {
         self.extract_all_alternatives_from_msg = val;
         self
     }
+
+    /// Sets the password used to open encrypted Office documents, the OOXML ones (docx, xlsx,
+    /// pptx...) and the legacy ones that support it, embedded ones included. A document that can
+    /// not be opened with it, or without a password when none is set, fails with
+    /// [`Error::EncryptedDocument`](crate::Error::EncryptedDocument).
+    /// The password is shown as `***` by `Debug` and left out of the serialized config.
+    /// Default: None.
+    pub fn set_password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
 }
 
 /// Tesseract OCR configuration settings
 ///
 /// These settings are used to configure the behavior of the optical image recognition.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct TesseractOcrConfig {
     pub(crate) density: i32,
     pub(crate) depth: i32,
@@ -228,6 +451,9 @@ pub struct TesseractOcrConfig {
     pub(crate) enable_image_preprocessing: bool,
     pub(crate) apply_rotation: bool,
     pub(crate) language: String,
+    pub(crate) page_seg_mode: u8,
+    pub(crate) tesseract_path: String,
+    pub(crate) tessdata_path: String,
 }
 
 impl Default for TesseractOcrConfig {
@@ -239,6 +465,9 @@ impl Default for TesseractOcrConfig {
             enable_image_preprocessing: false,
             apply_rotation: false,
             language: "eng".to_string(),
+            page_seg_mode: 1,
+            tesseract_path: String::new(),
+            tessdata_path: String::new(),
         }
     }
 }
@@ -256,7 +485,10 @@ impl TesseractOcrConfig {
         self
     }
 
-    /// Sets the DPI (dots per inch) of the image to be processed.
+    /// Sets the DPI (dots per inch) the PDF pages are rendered at for OCR. Text rendered at a
+    /// low density is too coarse to be recognized, scans of small print need more than the
+    /// default, at the cost of speed and memory. Must be between 150 and 1200, the extraction
+    /// fails otherwise.
     /// Default: 300.
     pub fn set_density(mut self, val: i32) -> Self {
         self.density = val;
@@ -287,10 +519,314 @@ impl TesseractOcrConfig {
         self
     }
 
-    /// Sets the maximum time in seconds that Tesseract should spend on OCR.
-    /// Default: 120.
+    /// Sets the tesseract languages to be used for OCR together, such as `["eng", "ara"]` for
+    /// documents that mix English and Arabic. Unlike [`set_language`](Self::set_language), each
+    /// language is checked against the language packs of the tesseract installation set so far,
+    /// see [`set_tesseract_path`](Self::set_tesseract_path), and the first one missing
+    /// fails with [`Error::OcrLanguageUnavailable`](crate::Error::OcrLanguageUnavailable), so
+    /// that OCR does not silently produce nothing. Fails with
+    /// [`Error::InvalidInput`](crate::Error::InvalidInput) when there is no language, and with
+    /// [`Error::IoError`](crate::Error::IoError) when tesseract can not be run.
+    pub fn set_languages(self, languages: Vec<String>) -> ExtractResult<Self> {
+        if languages.is_empty() {
+            return Err(Error::InvalidInput(
+                "at least one OCR language is required".to_string(),
+            ));
+        }
+        let available = list_ocr_languages(
+            &tesseract_executable(&self.tesseract_path),
+            &self.tessdata_path,
+        )?;
+        if let Some(missing) = languages.iter().find(|lang| !available.contains(lang)) {
+            return Err(Error::OcrLanguageUnavailable(missing.clone()));
+        }
+        Ok(self.set_language(&languages.join("+")))
+    }
+
+    /// Sets the maximum time in seconds that Tesseract should spend on OCR, which applies to
+    /// each image and each PDF page separately. When Tesseract runs out of time, the image or
+    /// page yields no text but the rest of the document is extracted as usual, and the metadata
+    /// of the document gets `X-TIKA:ocr_timed_out` set to `true`, so that a pathological
+    /// page does not stall or fail a whole batch.
+    /// Default: 130.
     pub fn set_timeout_seconds(mut self, val: i32) -> Self {
         self.timeout_seconds = val;
         self
     }
+
+    /// Sets the directory of the tesseract executable, for a Tesseract installed outside of the
+    /// `PATH`. Fails with [`Error::OcrEngineNotFound`](crate::Error::OcrEngineNotFound) when the
+    /// directory has no tesseract executable, rather than at the first extraction that needs OCR.
+    /// Default: "", the tesseract found on the `PATH`.
+    pub fn set_tesseract_path(mut self, val: &str) -> ExtractResult<Self> {
+        let executable = tesseract_executable(val);
+        if !val.is_empty() && !is_executable(&executable) {
+            return Err(Error::OcrEngineNotFound(executable.display().to_string()));
+        }
+        self.tesseract_path = val.to_string();
+        Ok(self)
+    }
+
+    /// Sets the directory of the tesseract language data, the `*.traineddata` files, for
+    /// language packs installed outside of the default location of the tesseract executable.
+    /// Default: "", the location the tesseract executable defaults to, or `TESSDATA_PREFIX`.
+    pub fn set_tessdata_path(mut self, val: &str) -> Self {
+        self.tessdata_path = val.to_string();
+        self
+    }
+
+    /// Sets the tesseract page segmentation mode (`--psm`), how the layout of the image is
+    /// analysed into blocks of text. The modes range from 0 to 13, the extraction fails
+    /// otherwise, and the most useful are:
+    /// * 1: automatic segmentation with orientation and script detection
+    /// * 3: fully automatic segmentation, without orientation and script detection
+    /// * 4: a single column of text of variable sizes
+    /// * 6: a single uniform block of text, which suits forms and tables that the automatic
+    ///   modes split into scattered fragments
+    /// * 11: sparse text, as much text as possible in no particular order
+    ///
+    /// Mode 0 only detects orientation and script and yields no text. See the
+    /// [tesseract documentation](https://tesseract-ocr.github.io/tessdoc/ImproveQuality.html#page-segmentation-method)
+    /// for all of them.
+    /// Default: 1.
+    pub fn set_page_seg_mode(mut self, val: u8) -> Self {
+        self.page_seg_mode = val;
+        self
+    }
+}
+
+/// How the metadata of the embedded documents of a recursive extraction relates to the metadata
+/// of the container. Only the container, the first document of the extraction, is changed.
+/// The keys that describe a document itself (`Content-Type`, `Content-Length`, `resourceName`,
+/// the `X-TIKA:` and `extractous:` keys, ...) are never merged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MergePolicy {
+    /// Keep the metadata of every document separate
+    #[default]
+    None,
+    /// Add the keys the container lacks, with the values of the first embedded document, in
+    /// document order, that has them
+    FirstWins,
+    /// Add every value of the embedded documents the container does not have yet, so that for
+    /// example `dcterms:created` lists the creation dates of all parts
+    Collect,
+}
+
+/// Recursive extraction configuration settings
+///
+/// These settings control which embedded documents are descended into when extracting
+/// recursively. Sub-containers (archives, attachments, embedded office documents...) and leaf
+/// resources (embedded images) are controlled separately, so for example the top-level
+/// attachments of an email archive and their images can be extracted without recursing into
+/// attachments that are themselves containers.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RecursiveConfig {
+    pub(crate) max_container_depth: i32,
+    pub(crate) extract_leaf_resources: bool,
+    pub(crate) per_document_max_length: i32,
+    pub(crate) merge_embedded_metadata: MergePolicy,
+    pub(crate) record_content_digests: bool,
+    pub(crate) max_total_output_bytes: Option<u64>,
+    // Set through Extractor::set_recursive_parallelism
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) parallelism: usize,
+    // Set through Extractor::set_embedded_mime_filter
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) embedded_mime_filter: Vec<String>,
+    // Set through Extractor::set_max_embedded_count
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) max_embedded_count: Option<usize>,
+    // Set through Extractor::set_max_embedded_bytes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) max_embedded_bytes: Option<u64>,
+}
+
+impl Default for RecursiveConfig {
+    fn default() -> Self {
+        Self {
+            max_container_depth: -1,
+            extract_leaf_resources: true,
+            per_document_max_length: -1,
+            merge_embedded_metadata: MergePolicy::None,
+            record_content_digests: false,
+            max_total_output_bytes: None,
+            parallelism: 1,
+            embedded_mime_filter: Vec::new(),
+            max_embedded_count: None,
+            max_embedded_bytes: None,
+        }
+    }
+}
+
+impl RecursiveConfig {
+    /// Creates a new instance of RecursiveConfig with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum depth of embedded documents that are descended into. Depth 1 are the
+    /// documents directly embedded in the container, depth 2 the documents embedded in those,
+    /// and so on. Embedded documents deeper than this are skipped, except leaf resources
+    /// (images) one level below the limit when `extract_leaf_resources` is enabled.
+    /// A negative value means no limit.
+    /// Default: -1
+    pub fn set_max_container_depth(mut self, val: i32) -> Self {
+        self.max_container_depth = val;
+        self
+    }
+
+    /// Sets whether leaf resources such as embedded images are surfaced as embedded documents.
+    /// Default: true
+    pub fn set_extract_leaf_resources(mut self, val: bool) -> Self {
+        self.extract_leaf_resources = val;
+        self
+    }
+
+    /// Sets the maximum length of the content kept for each document of a recursive extraction,
+    /// the container included. Unlike `Extractor::set_extract_string_max_length`, which caps the
+    /// content of all documents combined, this gives every embedded document its own budget so a
+    /// single large attachment can't use it all up. Documents cut at this length have
+    /// `extractous:content_truncated` set to `true` in their metadata.
+    /// A negative value means no limit.
+    /// Default: -1
+    pub fn set_per_document_max_length(mut self, val: i32) -> Self {
+        self.per_document_max_length = val;
+        self
+    }
+
+    /// Sets how the metadata of the embedded documents is merged into the metadata of the
+    /// container, see [`MergePolicy`].
+    /// Default: MergePolicy::None
+    pub fn set_merge_embedded_metadata(mut self, policy: MergePolicy) -> Self {
+        self.merge_embedded_metadata = policy;
+        self
+    }
+
+    /// Sets whether the length and the SHA-256 digest of the raw bytes of every document, the
+    /// container included, are recorded in its metadata, under `X-Content-Length` in bytes and
+    /// `X-Content-SHA256` as lowercase hex. Together with the path, type and content of each
+    /// document this makes a complete manifest of an archive from a single extraction. The
+    /// bytes that are not in a file already are spooled to a temporary file to be hashed.
+    /// Default: false
+    pub fn set_record_content_digests(mut self, val: bool) -> Self {
+        self.record_content_digests = val;
+        self
+    }
+
+    /// Sets the maximum total size, in UTF-8 bytes, of the content of all the documents of a
+    /// recursive extraction, a memory ceiling on top of the per-document and total length limits.
+    /// The documents take their share as their content is produced: the document that uses up the
+    /// budget is cut there, with `extractous:content_truncated` set to `true`, the documents after
+    /// it are not extracted at all, and the container gets `extractous:output_truncated` set to
+    /// `true`, see [`RecursiveExtraction::is_output_truncated`](crate::RecursiveExtraction::is_output_truncated).
+    /// Only the text counts, not the markup of the xml output nor the metadata. With
+    /// `Extractor::set_recursive_parallelism`, which documents are cut depends on the order the
+    /// workers produce their content in.
+    /// Default: no limit
+    pub fn set_max_total_output_bytes(mut self, val: u64) -> Self {
+        self.max_total_output_bytes = Some(val);
+        self
+    }
+}
+
+/// A parser that documents are retried with when the parser of their detected type fails, see
+/// [`Extractor::set_parser_fallback_chain`](crate::Extractor::set_parser_fallback_chain)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum ParserKind {
+    /// The plain text parser, which detects the charset and reads any bytes as text
+    PlainText,
+    /// The lenient HTML parser, which makes do with unclosed and misnested tags
+    Html,
+    /// The XML parser, which outputs the text content of well-formed XML
+    Xml,
+}
+
+/// Configuration of how the url methods, such as
+/// [`Extractor::extract_url`](crate::Extractor::extract_url), fetch the documents
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct UrlFetchConfig {
+    pub(crate) user_agent: Option<String>,
+    pub(crate) respect_robots: bool,
+}
+
+impl UrlFetchConfig {
+    /// Creates a new instance of UrlFetchConfig with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `User-Agent` header sent to http and https servers, for the document and for its
+    /// robots.txt, and the agent whose rules are followed when
+    /// [`UrlFetchConfig::set_respect_robots`] is enabled.
+    /// Default: None, the default agent of the VM is sent and only the rules for all agents
+    /// (`User-agent: *`) are followed.
+    pub fn set_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets whether the robots.txt of the site is fetched and honored before fetching an http
+    /// or https url. When the rules for the user agent disallow the url, the extraction fails
+    /// with [`Error::DisallowedByRobots`](crate::Error::DisallowedByRobots) without fetching
+    /// it. The rules are evaluated as RFC 9309 describes: the groups whose `User-agent` line
+    /// names the product token of the user agent, `MyBot` for `MyBot/1.0`, apply, or else the
+    /// `*` groups, and the longest matching `Allow` or `Disallow` path wins. A missing robots.txt
+    /// allows everything and an unreachable one, a server error, disallows everything. Up to five
+    /// redirects of the robots.txt on the same host are followed, such as from http to https. The
+    /// robots.txt is fetched again on every extraction.
+    /// Default: false
+    pub fn set_respect_robots(mut self, respect_robots: bool) -> Self {
+        self.respect_robots = respect_robots;
+        self
+    }
+}
+
+/// Default maximum nesting depth of the elements produced by the parsers, same as Tika's
+pub const DEFAULT_MAX_ELEMENT_DEPTH: u32 = 100;
+
+/// Extractor wide options that apply to every parse, whatever the format of the document.
+/// They are set through the `Extractor` setters and passed to the java side as a single
+/// `ai.yobix.ParseOptions` object.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParseOptions {
+    pub(crate) max_element_depth: u32,
+    pub(crate) ocr_spill_to_disk: bool,
+    pub(crate) exclude_hidden_text: bool,
+    pub(crate) soft_time_budget: Option<Duration>,
+    pub(crate) trust_declared_charset: bool,
+    pub(crate) encoding_auto: bool,
+    pub(crate) dedup_repeated_lines: bool,
+    pub(crate) force_text_types: Vec<String>,
+    pub(crate) string_streaming_threshold: Option<usize>,
+    pub(crate) parser_fallbacks: Vec<ParserKind>,
+    pub(crate) record_parser_chain: bool,
+    pub(crate) zip_recovery: bool,
+    pub(crate) preserve_scripts: bool,
+    pub(crate) url_fetch: UrlFetchConfig,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_element_depth: DEFAULT_MAX_ELEMENT_DEPTH,
+            ocr_spill_to_disk: false,
+            exclude_hidden_text: false,
+            soft_time_budget: None,
+            trust_declared_charset: true,
+            encoding_auto: false,
+            dedup_repeated_lines: false,
+            force_text_types: Vec::new(),
+            string_streaming_threshold: None,
+            parser_fallbacks: Vec::new(),
+            record_parser_chain: false,
+            zip_recovery: false,
+            preserve_scripts: false,
+            url_fetch: UrlFetchConfig::default(),
+        }
+    }
 }
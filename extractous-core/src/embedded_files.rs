@@ -0,0 +1,155 @@
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::errors::{Error, ExtractResult};
+
+/// Longest file name written, in bytes, below the 255 bytes most file systems accept so that the
+/// collision suffix still fits
+const MAX_NAME_BYTES: usize = 200;
+
+/// Picks the files the embedded documents of [`Extractor::extract_file_embedded_to_dir`] are
+/// written to: each one is created empty and new in the output directory, from the sanitized
+/// resource name of the document, with a `_1`, `_2`, ... suffix when the name is taken.
+///
+/// [`Extractor::extract_file_embedded_to_dir`]: crate::Extractor::extract_file_embedded_to_dir
+pub(crate) struct EmbeddedFileNames<'d> {
+    out_dir: &'d Path,
+    count: usize,
+}
+
+impl<'d> EmbeddedFileNames<'d> {
+    pub(crate) fn new(out_dir: &'d Path) -> Self {
+        Self { out_dir, count: 0 }
+    }
+
+    /// Creates the file of the next embedded document. A document without a usable name is
+    /// named `embedded-<n>` followed by the extension of its detected type.
+    pub(crate) fn create(&mut self, name: Option<&str>, extension: &str) -> ExtractResult<PathBuf> {
+        self.count += 1;
+        let name = name
+            .and_then(sanitize_file_name)
+            .unwrap_or_else(|| format!("embedded-{}{}", self.count, extension));
+        let (stem, extension) = split_extension(&name);
+
+        for suffix in 0.. {
+            let file_name = if suffix == 0 {
+                name.clone()
+            } else {
+                format!("{}_{}{}", stem, suffix, extension)
+            };
+            let path = self.out_dir.join(file_name);
+            // create_new never follows nor overwrites an existing file or link
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(path),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => {
+                    return Err(Error::IoError(format!(
+                        "Could not create {}: {}",
+                        path.display(),
+                        e
+                    )))
+                }
+            }
+        }
+        unreachable!()
+    }
+}
+
+/// Returns the name a file can safely be created with in the output directory, or `None` when
+/// nothing is left of it. Only the last component of a path is kept, so that
+/// `../../etc/passwd` becomes `passwd`, and the control characters and the characters Windows
+/// forbids are replaced.
+fn sanitize_file_name(name: &str) -> Option<String> {
+    let last = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let mut sanitized: String = last
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Windows drops the trailing dots and spaces
+    sanitized.truncate(sanitized.trim_end_matches(['.', ' ']).len());
+    let sanitized = sanitized.trim_start();
+    if sanitized.is_empty() {
+        return None;
+    }
+
+    if sanitized.len() <= MAX_NAME_BYTES {
+        return Some(sanitized.to_string());
+    }
+    // Shortened from the end of the stem, the extension is kept
+    let (stem, extension) = split_extension(sanitized);
+    let extension = if extension.len() < MAX_NAME_BYTES / 2 {
+        extension
+    } else {
+        ""
+    };
+    let mut end = MAX_NAME_BYTES - extension.len();
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    Some(format!("{}{}", &stem[..end], extension))
+}
+
+/// Splits `report.final.pdf` into `report.final` and `.pdf`. A name starting with its only dot,
+/// such as `.bashrc`, has no extension.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_file_name_test() {
+        assert_eq!(sanitize_file_name("report.pdf").unwrap(), "report.pdf");
+        assert_eq!(sanitize_file_name("../../etc/passwd").unwrap(), "passwd");
+        assert_eq!(sanitize_file_name("..\\..\\boot.ini").unwrap(), "boot.ini");
+        assert_eq!(sanitize_file_name("/abs/path.txt").unwrap(), "path.txt");
+        assert_eq!(sanitize_file_name("a:b*c?.txt").unwrap(), "a_b_c_.txt");
+        assert_eq!(sanitize_file_name("tab\there.txt").unwrap(), "tab_here.txt");
+        assert_eq!(sanitize_file_name("name. . ").unwrap(), "name");
+        assert_eq!(sanitize_file_name(".bashrc").unwrap(), ".bashrc");
+        assert_eq!(sanitize_file_name(".."), None);
+        assert_eq!(sanitize_file_name("."), None);
+        assert_eq!(sanitize_file_name("dir/"), None);
+        assert_eq!(sanitize_file_name(""), None);
+
+        let long = format!("{}.pdf", "é".repeat(150));
+        let shortened = sanitize_file_name(&long).unwrap();
+        assert!(shortened.len() <= MAX_NAME_BYTES);
+        assert!(shortened.ends_with("é.pdf"));
+    }
+
+    #[test]
+    fn create_suffixes_taken_names() {
+        let dir = std::env::temp_dir().join(format!("extractous-embedded-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut names = EmbeddedFileNames::new(&dir);
+
+        let first = names.create(Some("a.txt"), "").unwrap();
+        let second = names.create(Some("sub/a.txt"), "").unwrap();
+        let third = names.create(Some("../a.txt"), "").unwrap();
+        let anonymous = names.create(None, ".png").unwrap();
+        let unusable = names.create(Some(".."), "").unwrap();
+
+        let file_name = |path: &PathBuf| path.file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(file_name(&first), "a.txt");
+        assert_eq!(file_name(&second), "a_1.txt");
+        assert_eq!(file_name(&third), "a_2.txt");
+        assert_eq!(file_name(&anonymous), "embedded-4.png");
+        assert_eq!(file_name(&unusable), "embedded-5");
+        assert!([first, second, third, anonymous, unusable]
+            .iter()
+            .all(|path| path.parent() == Some(dir.as_path())));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
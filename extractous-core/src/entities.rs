@@ -0,0 +1,484 @@
+use strum_macros::{Display, EnumString};
+
+/// Kinds of entities recognized by [`find_entities`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EntityKind {
+    Email,
+    Phone,
+    Url,
+}
+
+/// An email address, phone number or URL found in a text.
+///
+/// `start` and `end` are the byte offsets of the entity as written in the text, so that
+/// `&text[start..end]` is the matched span, while `value` is its normalized form:
+/// - emails are lowercased, e.g. `John.Doe@Example.COM` gives `john.doe@example.com`
+/// - phone numbers keep their digits and the leading `+` only, e.g. `+1 (555) 010-0100` gives
+///   `+15550100100`
+/// - URLs get a lowercase scheme and host, and `http://` is added to the ones starting with
+///   `www.`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entity {
+    pub kind: EntityKind,
+    pub value: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Returns the email addresses, phone numbers and URLs of the text, ordered by position and
+/// without overlaps: an email address in a URL is part of the URL.
+///
+/// The scanner is heuristic and tuned against false positives. Phone numbers need 7 to 15
+/// digits, and a leading `+`, an area code in parentheses, a `-` or `.` separator or a leading
+/// `0` trunk prefix; dates, IPv4 addresses, decimals and year ranges are skipped. URLs need an
+/// `http://` or `https://` scheme or a `www.` prefix and end at the first whitespace or non
+/// ASCII character.
+pub fn find_entities(text: &str) -> Vec<Entity> {
+    let mut candidates = find_urls(text);
+    candidates.extend(find_emails(text));
+    candidates.extend(find_phones(text));
+    // On the same start, URLs win over emails, which win over phones
+    candidates.sort_by_key(|entity| (entity.start, priority(entity.kind)));
+
+    let mut entities: Vec<Entity> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if entities
+            .last()
+            .is_some_and(|last| candidate.start < last.end)
+        {
+            continue;
+        }
+        entities.push(candidate);
+    }
+    entities
+}
+
+fn priority(kind: EntityKind) -> u8 {
+    match kind {
+        EntityKind::Url => 0,
+        EntityKind::Email => 1,
+        EntityKind::Phone => 2,
+    }
+}
+
+/// Letters, digits and the bytes of non ASCII characters, which are taken as letters
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || !byte.is_ascii()
+}
+
+fn find_urls(text: &str) -> Vec<Entity> {
+    let bytes = text.as_bytes();
+    // ASCII lowercasing keeps the byte offsets
+    let lower = text.to_ascii_lowercase();
+    let mut urls = Vec::new();
+
+    for prefix in ["https://", "http://", "www."] {
+        for (start, _) in lower.match_indices(prefix) {
+            if start > 0 && (is_word_byte(bytes[start - 1]) || bytes[start - 1] == b'.') {
+                continue;
+            }
+            let mut end = start;
+            while end < bytes.len() && is_url_byte(bytes[end]) {
+                end += 1;
+            }
+            let end = trim_url_end(&text[start..end]) + start;
+
+            let host_start = start + prefix.len();
+            let host_end = text[host_start..end]
+                .find(['/', '?', '#'])
+                .map_or(end, |i| host_start + i);
+            let host = &lower[host_start..host_end];
+            // www. must be followed by a domain, the schemes by any host
+            let valid_host = match prefix {
+                "www." => host.contains('.') && !host.ends_with('.'),
+                _ => host.bytes().any(|byte| byte.is_ascii_alphanumeric()),
+            };
+            if !valid_host {
+                continue;
+            }
+
+            let scheme = if prefix == "www." { "http://" } else { "" };
+            let value = format!(
+                "{}{}{}",
+                scheme,
+                &lower[start..host_end],
+                &text[host_end..end]
+            );
+            urls.push(Entity {
+                kind: EntityKind::Url,
+                value,
+                start,
+                end,
+            });
+        }
+    }
+    urls
+}
+
+fn is_url_byte(byte: u8) -> bool {
+    byte.is_ascii_graphic()
+        && !matches!(
+            byte,
+            b'<' | b'>' | b'"' | b'`' | b'{' | b'}' | b'|' | b'\\' | b'^'
+        )
+}
+
+/// Returns the length of the URL without the trailing punctuation of the sentence around it.
+/// Closing brackets are kept when the URL opens them, as in wiki links.
+fn trim_url_end(url: &str) -> usize {
+    let mut end = url.len();
+    while let Some(&last) = url.as_bytes()[..end].last() {
+        let opening = match last {
+            b'.' | b',' | b';' | b':' | b'!' | b'?' | b'\'' | b'*' => {
+                end -= 1;
+                continue;
+            }
+            b')' => b'(',
+            b']' => b'[',
+            _ => break,
+        };
+        let span = &url.as_bytes()[..end];
+        let opened = span.iter().filter(|&&byte| byte == opening).count();
+        let closed = span.iter().filter(|&&byte| byte == last).count();
+        if closed > opened {
+            end -= 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+fn find_emails(text: &str) -> Vec<Entity> {
+    let bytes = text.as_bytes();
+    let mut emails = Vec::new();
+
+    for (at, _) in text.match_indices('@') {
+        let mut start = at;
+        while start > 0 && is_local_byte(bytes[start - 1]) {
+            start -= 1;
+        }
+        while start < at && bytes[start] == b'.' {
+            start += 1;
+        }
+        let mut end = at + 1;
+        while end < bytes.len()
+            && (bytes[end].is_ascii_alphanumeric() || matches!(bytes[end], b'.' | b'-'))
+        {
+            end += 1;
+        }
+        while end > at + 1 && matches!(bytes[end - 1], b'.' | b'-') {
+            end -= 1;
+        }
+
+        let local = &text[start..at];
+        let domain = &text[at + 1..end];
+        if local.is_empty() || local.ends_with('.') || local.contains("..") || !is_domain(domain) {
+            continue;
+        }
+        emails.push(Entity {
+            kind: EntityKind::Email,
+            value: text[start..end].to_ascii_lowercase(),
+            start,
+            end,
+        });
+    }
+    emails
+}
+
+fn is_local_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_' | b'%' | b'+' | b'-')
+}
+
+/// Whether the text is a domain name with at least two labels and an alphabetic top level one
+fn is_domain(domain: &str) -> bool {
+    let labels: Vec<&str> = domain.split('.').collect();
+    let Some(tld) = labels.last() else {
+        return false;
+    };
+    labels.len() >= 2
+        && tld.len() >= 2
+        && tld.bytes().all(|byte| byte.is_ascii_alphabetic())
+        && labels
+            .iter()
+            .all(|label| !label.is_empty() && !label.starts_with('-') && !label.ends_with('-'))
+}
+
+/// A phone number candidate: digit groups with their separators
+struct PhoneCandidate {
+    end: usize,
+    plus: bool,
+    parens: bool,
+    /// Whether the first group starts with a 0, the trunk prefix of national numbers
+    leading_zero: bool,
+    groups: Vec<usize>,
+    separators: Vec<u8>,
+}
+
+fn find_phones(text: &str) -> Vec<Entity> {
+    let bytes = text.as_bytes();
+    let mut phones = Vec::new();
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let byte = bytes[start];
+        let starts_number = byte == b'+' || byte == b'(' || byte.is_ascii_digit();
+        let after_boundary = start == 0
+            || !(is_word_byte(bytes[start - 1])
+                || matches!(bytes[start - 1], b'+' | b'.' | b'-' | b'/'));
+        if !starts_number || !after_boundary {
+            start += 1;
+            continue;
+        }
+        let Some(candidate) = scan_phone(bytes, start) else {
+            start += 1;
+            continue;
+        };
+        if is_phone(&candidate) {
+            let digits: String = text[start..candidate.end]
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect();
+            phones.push(Entity {
+                kind: EntityKind::Phone,
+                value: if candidate.plus {
+                    format!("+{}", digits)
+                } else {
+                    digits
+                },
+                start,
+                end: candidate.end,
+            });
+        }
+        start = candidate.end.max(start + 1);
+    }
+    phones
+}
+
+/// Reads the digit groups from start, an area code in parentheses included, each group being
+/// separated from the next by a single space, `-` or `.`
+fn scan_phone(bytes: &[u8], start: usize) -> Option<PhoneCandidate> {
+    let digits_end = |from: usize| {
+        let mut end = from;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        end
+    };
+
+    let mut pos = start;
+    let plus = bytes[pos] == b'+';
+    if plus {
+        pos += 1;
+    }
+    let mut candidate = PhoneCandidate {
+        end: pos,
+        plus,
+        parens: false,
+        leading_zero: false,
+        groups: Vec::new(),
+        separators: Vec::new(),
+    };
+
+    loop {
+        if bytes.get(pos) == Some(&b'(') {
+            let end = digits_end(pos + 1);
+            if end == pos + 1 || bytes.get(end) != Some(&b')') {
+                break;
+            }
+            if candidate.groups.is_empty() {
+                candidate.leading_zero = bytes[pos + 1] == b'0';
+            }
+            candidate.groups.push(end - pos - 1);
+            candidate.parens = true;
+            pos = end + 1;
+        } else {
+            let end = digits_end(pos);
+            if end == pos {
+                break;
+            }
+            if candidate.groups.is_empty() {
+                candidate.leading_zero = bytes[pos] == b'0';
+            }
+            candidate.groups.push(end - pos);
+            pos = end;
+        }
+        candidate.end = pos;
+
+        let separator = match bytes.get(pos) {
+            Some(&byte @ (b' ' | b'-' | b'.')) => byte,
+            _ => break,
+        };
+        match bytes.get(pos + 1) {
+            Some(next) if next.is_ascii_digit() || *next == b'(' => {
+                candidate.separators.push(separator);
+                pos += 1;
+            }
+            _ => break,
+        }
+    }
+
+    if candidate.groups.is_empty()
+        || bytes
+            .get(candidate.end)
+            .is_some_and(|&byte| is_word_byte(byte))
+    {
+        return None;
+    }
+    Some(candidate)
+}
+
+fn is_phone(candidate: &PhoneCandidate) -> bool {
+    let digits: usize = candidate.groups.iter().sum();
+    if !(7..=15).contains(&digits) {
+        return false;
+    }
+    if candidate.plus || candidate.parens {
+        return true;
+    }
+
+    let groups = &candidate.groups;
+    let separators = &candidate.separators;
+    if groups.len() == 1 {
+        // A bare number: an amount, an id, ...
+        return false;
+    }
+    let same_separator = separators.windows(2).all(|pair| pair[0] == pair[1]);
+    let separator = separators[0];
+    if separator == b'.'
+        && (groups.len() == 2 || (groups.len() == 4 && groups.iter().all(|&len| len <= 3)))
+    {
+        // A decimal or an IPv4 address
+        return false;
+    }
+    if groups.len() == 3
+        && same_separator
+        && (groups[..] == [4, 2, 2] || (groups[0] <= 2 && groups[1] <= 2 && groups[2] == 4))
+    {
+        // A date
+        return false;
+    }
+
+    // Only the national formats starting with a trunk prefix use spaces alone, e.g. 020 7946 0000
+    if separators.iter().all(|&separator| separator == b' ') && !candidate.leading_zero {
+        return false;
+    }
+    // Two groups are a local number such as 555-0100, not a year range such as 2019-2020
+    groups.len() > 2 || groups[..] == [3, 4] || candidate.leading_zero
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_entities, Entity, EntityKind};
+
+    fn found(text: &str) -> Vec<(EntityKind, &str, String)> {
+        find_entities(text)
+            .into_iter()
+            .map(
+                |Entity {
+                     kind,
+                     value,
+                     start,
+                     end,
+                 }| (kind, &text[start..end], value),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn finds_emails() {
+        assert_eq!(
+            found("Write to John.Doe@Example.COM. Or (ops+alerts@mail.example.org)!"),
+            vec![
+                (
+                    EntityKind::Email,
+                    "John.Doe@Example.COM",
+                    "john.doe@example.com".to_string()
+                ),
+                (
+                    EntityKind::Email,
+                    "ops+alerts@mail.example.org",
+                    "ops+alerts@mail.example.org".to_string()
+                ),
+            ]
+        );
+        assert!(found("user@localhost, a@b.c, @handle, x@-bad.com").is_empty());
+    }
+
+    #[test]
+    fn finds_urls() {
+        assert_eq!(
+            found("See HTTPS://Example.com/Path?q=1, www.example.org. (https://en.wikipedia.org/wiki/Rust_(language))"),
+            vec![
+                (
+                    EntityKind::Url,
+                    "HTTPS://Example.com/Path?q=1",
+                    "https://example.com/Path?q=1".to_string()
+                ),
+                (
+                    EntityKind::Url,
+                    "www.example.org",
+                    "http://www.example.org".to_string()
+                ),
+                (
+                    EntityKind::Url,
+                    "https://en.wikipedia.org/wiki/Rust_(language)",
+                    "https://en.wikipedia.org/wiki/Rust_(language)".to_string()
+                ),
+            ]
+        );
+        // The email of a URL is part of it
+        assert_eq!(
+            found("https://example.com/share?to=jane@example.com")
+                .into_iter()
+                .map(|(kind, _, _)| kind)
+                .collect::<Vec<_>>(),
+            vec![EntityKind::Url]
+        );
+    }
+
+    #[test]
+    fn finds_phones() {
+        assert_eq!(
+            found("Call +1 (555) 010-0100, 555-0199 or 020 7946 0000 or +4915112345678."),
+            vec![
+                (
+                    EntityKind::Phone,
+                    "+1 (555) 010-0100",
+                    "+15550100100".to_string()
+                ),
+                (EntityKind::Phone, "555-0199", "5550199".to_string()),
+                (
+                    EntityKind::Phone,
+                    "020 7946 0000",
+                    "02079460000".to_string()
+                ),
+                (
+                    EntityKind::Phone,
+                    "+4915112345678",
+                    "+4915112345678".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_numbers_that_are_not_phones() {
+        let text = "Revenue 1234567 in 2019-2020, 2020 2021 2022, on 2024-05-01 and 01.05.2024, \
+                    pi 3.14159265, host 192.168.100.200, at 10:00:05, id A555-0100";
+        assert!(found(text).is_empty());
+    }
+
+    #[test]
+    fn offsets_index_into_the_text() {
+        let text = "Café Zürich: info@café.example.com, contact@zurich.example.ch";
+        let entities = find_entities(text);
+        assert_eq!(entities.len(), 1);
+        assert_eq!(
+            &text[entities[0].start..entities[0].end],
+            "contact@zurich.example.ch"
+        );
+    }
+}
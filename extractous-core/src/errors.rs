@@ -1,5 +1,6 @@
 use std::io;
 use std::str::Utf8Error;
+use std::time::Duration;
 
 /// Represent errors returned by extractous
 #[derive(thiserror::Error, Debug)]
@@ -13,6 +14,16 @@ pub enum Error {
     #[error("{0}")]
     ParseError(String),
 
+    #[error("{0}")]
+    InvalidInput(String),
+
+    /// The document is encrypted and could not be opened, either because no password was set
+    /// or because the password is wrong, see `PdfParserConfig::set_password` and
+    /// `OfficeParserConfig::set_password`. The methods returning a `StreamReader` parse while
+    /// the stream is read, so they report it as a read error instead.
+    #[error("{0}")]
+    EncryptedDocument(String),
+
     #[error("{0}")]
     Utf8Error(#[from] Utf8Error),
 
@@ -21,6 +32,34 @@ pub enum Error {
 
     #[error("{0}")]
     JniEnvCall(&'static str),
+
+    #[error("the shared vm was created by process {0} and cannot be used after fork(), call reinit_after_fork() in the child process")]
+    VmForked(u32),
+
+    #[error("the extraction did not finish within {0:?}")]
+    Timeout(Duration),
+
+    /// The extraction was cancelled with its `CancellationToken`
+    #[error("the extraction was cancelled")]
+    Cancelled,
+
+    #[error("the shared vm is already created and cannot be reconfigured, call configure_vm() before the first extraction")]
+    VmAlreadyCreated,
+
+    /// The tesseract language pack of the language is not installed, see
+    /// `TesseractOcrConfig::set_languages`
+    #[error("the tesseract language pack {0} is not installed")]
+    OcrLanguageUnavailable(String),
+
+    /// No tesseract executable was found at the path, see
+    /// `TesseractOcrConfig::set_tesseract_path`
+    #[error("no tesseract executable found at {0}")]
+    OcrEngineNotFound(String),
+
+    /// The robots.txt of the site disallows fetching the url, see
+    /// `UrlFetchConfig::set_respect_robots`
+    #[error("fetching {0} is disallowed by the robots.txt of the site")]
+    DisallowedByRobots(String),
 }
 
 // Implement the conversion from our Error type to io::Error
@@ -34,6 +73,14 @@ impl From<Error> for io::Error {
             Error::ParseError(msg) => {
                 io::Error::new(io::ErrorKind::Other, format!("Parse error: {}", msg))
             }
+            Error::EncryptedDocument(msg) => io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("Encrypted document: {}", msg),
+            ),
+            Error::InvalidInput(msg) => io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid input: {}", msg),
+            ),
             Error::Utf8Error(e) => {
                 io::Error::new(io::ErrorKind::Other, format!("UTF8 error: {}", e))
             }
@@ -41,6 +88,16 @@ impl From<Error> for io::Error {
             Error::JniEnvCall(msg) => {
                 io::Error::new(io::ErrorKind::Other, format!("JNI env call error: {}", msg))
             }
+            Error::VmForked(_) | Error::VmAlreadyCreated | Error::Cancelled => {
+                io::Error::other(err.to_string())
+            }
+            Error::Timeout(_) => io::Error::new(io::ErrorKind::TimedOut, err.to_string()),
+            Error::OcrLanguageUnavailable(_) | Error::OcrEngineNotFound(_) => {
+                io::Error::new(io::ErrorKind::NotFound, err.to_string())
+            }
+            Error::DisallowedByRobots(_) => {
+                io::Error::new(io::ErrorKind::PermissionDenied, err.to_string())
+            }
             _ => io::Error::new(io::ErrorKind::Other, "Unknown error"),
         }
     }
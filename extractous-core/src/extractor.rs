@@ -1,30 +1,136 @@
-use crate::errors::ExtractResult;
+use crate::batch;
+use crate::chat;
+use crate::embedded_files::EmbeddedFileNames;
+use crate::entities::{find_entities, Entity};
+use crate::errors::{Error, ExtractResult};
+use crate::file_check::{check_input_file, path_to_str};
+use crate::image_ocr;
+use crate::language::{language_sample, LANGUAGE_KEY};
+use crate::line_numbers;
+use crate::metadata_view::MetadataView;
+use crate::mime_message::MimePart;
+use crate::ndjson;
+use crate::office_properties;
+use crate::repeated_lines;
+use crate::robots;
+use crate::script_filter::{
+    dominant_script, filter_script, Script, DEFAULT_SCRIPT_FILTER_THRESHOLD, DOMINANT_SCRIPT_KEY,
+};
+use crate::sections;
+use crate::sentences::split_sentences;
+use crate::simhash::{format_simhash, simhash, SIMHASH_KEY};
+use crate::structured;
+use crate::tables;
 use crate::tika;
-use crate::tika::JReaderInputStream;
-use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
-use std::collections::HashMap;
+use crate::tika::{DirectBufferPermit, JReaderInputStream};
+use crate::timeout;
+use crate::{
+    CalendarEvent, CancellationToken, Chat, Contact, FileResult, ImageOcr, LanguageResult,
+    MergePolicy, MimeMessage, OfficeParserConfig, OfficeProperties, Paragraphs, ParseOptions,
+    ParserKind, PdfOcrStrategy, PdfParserConfig, RecursiveConfig, RecursiveDocuments, Section,
+    Sentence, Table, TesseractOcrConfig, UrlFetchConfig, DEFAULT_BUF_SIZE,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::time::Duration;
 use strum_macros::{Display, EnumString};
 
 /// Metadata type alias
 pub type Metadata = HashMap<String, Vec<String>>;
 
+/// Deterministic views over [`Metadata`]. `Metadata` is a `HashMap`, so its iteration order
+/// changes from run to run; use these when the metadata is serialized, compared to golden files
+/// or diffed across extractions.
+pub trait MetadataExt {
+    /// Returns the entries sorted by key
+    fn sorted_entries(&self) -> Vec<(&String, &Vec<String>)>;
+
+    /// Returns a copy of the metadata as a map that iterates in key order
+    fn to_sorted(&self) -> BTreeMap<String, Vec<String>>;
+
+    /// Returns typed accessors over the canonical keys, such as the content type or the page
+    /// count
+    fn view(&self) -> MetadataView<'_>;
+}
+
+impl MetadataExt for Metadata {
+    fn sorted_entries(&self) -> Vec<(&String, &Vec<String>)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+
+    fn to_sorted(&self) -> BTreeMap<String, Vec<String>> {
+        self.iter()
+            .map(|(key, values)| (key.clone(), values.clone()))
+            .collect()
+    }
+
+    fn view(&self) -> MetadataView<'_> {
+        MetadataView::new(self)
+    }
+}
+
 /// 单个文档（容器文档或嵌套文档）
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Document {
     /// 文档内容文本
     pub content: String,
     /// 文档元数据
     pub metadata: Metadata,
+    /// 内容中的邮箱、电话和 URL，仅在 `Extractor::set_extract_entities(true)` 时填充，
+    /// 偏移量指向 `content`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub entities: Vec<Entity>,
+    /// 直接包含本文档的文档在 `RecursiveExtraction::documents` 中的下标，容器文档为 `None`。
+    /// 仅由 `RecursiveExtraction::new` 填充，逐个交付文档的方法中始终为 `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub parent: Option<usize>,
 }
 
 impl Document {
     pub fn new(content: String, metadata: Metadata) -> Self {
-        Self { content, metadata }
+        Self {
+            content,
+            metadata,
+            entities: Vec::new(),
+            parent: None,
+        }
+    }
+
+    /// 文档的原始文件名（`resourceName`），如压缩包中条目的文件名。递归提取的嵌套文档
+    /// 总有文件名：没有名称的嵌入资源使用其嵌套路径的最后一段，如 `embedded-3`
+    pub fn resource_name(&self) -> Option<&str> {
+        first_value(self, RESOURCE_NAME_KEY)
+    }
+
+    /// 嵌套文档在容器中的路径（`X-TIKA:embedded_resource_path`），如 `/inner.zip/nested.txt`，
+    /// 容器文档为 `None`
+    pub fn embedded_path(&self) -> Option<&str> {
+        first_value(self, EMBEDDED_RESOURCE_PATH_KEY)
     }
 }
 
+/// 文档的原始文件名
+const RESOURCE_NAME_KEY: &str = "resourceName";
+
+/// 嵌套文档在容器中的编号路径，如 `/1/3`，由 Tika 的嵌套文档编号组成
+const EMBEDDED_ID_PATH_KEY: &str = "X-TIKA:embedded_id_path";
+/// 嵌套文档在容器中的名称路径，如 `/inner.zip/nested.txt`
+const EMBEDDED_RESOURCE_PATH_KEY: &str = "X-TIKA:embedded_resource_path";
+/// 嵌套文档的类型，如 `ATTACHMENT`、`INLINE`，VBA 宏模块为 `MACRO`
+const EMBEDDED_RESOURCE_TYPE_KEY: &str = "X-TIKA:embedded_resource_type";
+/// 宏模块的媒体类型，代替 Tika 给出的 `text/x-vbasic`
+const MACRO_CONTENT_TYPE: &str = "application/x-vba";
+
 /// 递归提取结果，包含容器文档及其所有嵌套文档
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RecursiveExtraction {
     /// 文档列表：
     /// - documents[0]: 容器文档本身
@@ -33,8 +139,11 @@ pub struct RecursiveExtraction {
 }
 
 impl RecursiveExtraction {
+    /// 创建递归提取结果，并根据嵌套文档的路径填充各文档的 `parent`
     pub fn new(documents: Vec<Document>) -> Self {
-        Self { documents }
+        let mut extraction = Self { documents };
+        extraction.link_parents();
+        extraction
     }
 
     /// 获取容器文档（第一个文档）
@@ -55,37 +164,266 @@ impl RecursiveExtraction {
     pub fn total_count(&self) -> usize {
         self.documents.len()
     }
+
+    /// 获取直接嵌套在 `documents[index]` 中的文档的下标，按 `documents` 中的顺序。
+    /// 从容器文档（下标 0）开始递归调用即可还原整个嵌套结构
+    pub fn children(&self, index: usize) -> Vec<usize> {
+        self.documents
+            .iter()
+            .enumerate()
+            .filter(|(_, doc)| doc.parent == Some(index))
+            .map(|(child, _)| child)
+            .collect()
+    }
+
+    /// 按 Tika 记录的嵌套路径找到每个嵌套文档的父文档：优先使用编号路径，文件名可能重复；
+    /// 没有路径或找不到父文档（如父文档因输出预算未被提取）时挂在容器文档下
+    fn link_parents(&mut self) {
+        let Some((container, embedded)) = self.documents.split_first_mut() else {
+            return;
+        };
+        container.parent = None;
+        for key in [EMBEDDED_ID_PATH_KEY, EMBEDDED_RESOURCE_PATH_KEY] {
+            // 文件名路径可能重复，取最先出现的文档，与 Tika 先输出父文档的顺序一致
+            let mut indexes: HashMap<String, usize> = HashMap::new();
+            for (i, doc) in embedded.iter().enumerate() {
+                if let Some(path) = first_value(doc, key) {
+                    indexes.entry(path.to_string()).or_insert(i + 1);
+                }
+            }
+            for doc in embedded.iter_mut().filter(|doc| doc.parent.is_none()) {
+                doc.parent = first_value(doc, key)
+                    .and_then(|path| path.rsplit_once('/'))
+                    .and_then(|(parent_path, _)| indexes.get(parent_path).copied());
+            }
+        }
+        for doc in embedded.iter_mut() {
+            doc.parent.get_or_insert(0);
+        }
+    }
+
+    /// 总输出预算（`RecursiveConfig::set_max_total_output_bytes`）是否已耗尽，
+    /// 即是否有文档被截断或未被提取
+    pub fn is_output_truncated(&self) -> bool {
+        self.container()
+            .and_then(|doc| doc.metadata.get("extractous:output_truncated"))
+            .is_some_and(|values| values.iter().any(|value| value == "true"))
+    }
+
+    /// 嵌套文档数量或字节数上限（`Extractor::set_max_embedded_count`、
+    /// `Extractor::set_max_embedded_bytes`）是否已达到，即是否有嵌套文档被截断或未被提取
+    pub fn is_embedded_truncated(&self) -> bool {
+        self.container()
+            .and_then(|doc| doc.metadata.get("extractous:embedded_truncated"))
+            .is_some_and(|values| values.iter().any(|value| value == "true"))
+    }
+
+    /// 按 MergePolicy 将嵌套文档的元数据合并到容器文档的元数据中
+    pub(crate) fn merge_embedded_metadata(&mut self, policy: MergePolicy) {
+        if policy == MergePolicy::None {
+            return;
+        }
+        let Some((container, embedded)) = self.documents.split_first_mut() else {
+            return;
+        };
+        for doc in embedded.iter() {
+            for (key, values) in doc.metadata.iter() {
+                if is_document_own_key(key) {
+                    continue;
+                }
+                match policy {
+                    MergePolicy::FirstWins => {
+                        container
+                            .metadata
+                            .entry(key.clone())
+                            .or_insert_with(|| values.clone());
+                    }
+                    MergePolicy::Collect => {
+                        let merged = container.metadata.entry(key.clone()).or_default();
+                        for value in values {
+                            if !merged.contains(value) {
+                                merged.push(value.clone());
+                            }
+                        }
+                    }
+                    MergePolicy::None => {}
+                }
+            }
+        }
+    }
+}
+
+fn first_value<'d>(doc: &'d Document, key: &str) -> Option<&'d str> {
+    doc.metadata
+        .get(key)
+        .and_then(|values| values.first())
+        .map(String::as_str)
+}
+
+/// 描述文档自身的元数据键（类型、大小、名称、解析信息等），合并时跳过
+fn is_document_own_key(key: &str) -> bool {
+    key.starts_with("X-TIKA:")
+        || key.starts_with("extractous:")
+        || matches!(
+            key,
+            "Content-Type"
+                | "Content-Length"
+                | "Content-Encoding"
+                | "resourceName"
+                | "embeddedRelationshipId"
+                | "embeddedResourceType"
+        )
+}
+
+/// A digital signature found in a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureInfo {
+    /// Name of the signer, taken from the signature dictionary or the signing certificate
+    pub signer: Option<String>,
+    /// Signing time as an ISO-8601 string
+    pub signed_at: Option<String>,
+    /// Whether the signature matches the signed content. `None` if the signature could not be
+    /// checked. The certificate chain is never verified
+    pub valid: Option<bool>,
+}
+
+/// A font referenced by a PDF document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontInfo {
+    /// PostScript name of the font, e.g. `ABCDEF+Helvetica-Bold` for a subset. `None` for the
+    /// fonts without one, such as some Type3 fonts
+    pub name: Option<String>,
+    /// Subtype of the font dictionary: `Type0`, `Type1`, `MMType1`, `Type3` or `TrueType`
+    pub subtype: String,
+    /// Whether the font program is embedded in the document. Text drawn with a font that is not
+    /// embedded is more likely to extract poorly
+    pub embedded: bool,
+}
+
+/// The PDF/A conformance and the accessibility tagging a PDF document declares, see
+/// [`Extractor::pdf_conformance_info`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfConformance {
+    /// Part of the PDF/A standard followed by the conformance level, as declared by the
+    /// `pdfaid:part` and `pdfaid:conformance` properties of the XMP metadata, e.g. `1B`, `2U`,
+    /// or `4` for PDF/A-4, which has no conformance level. `None` if no PDF/A part is declared
+    pub pdfa_level: Option<String>,
+    /// Whether the `MarkInfo` dictionary of the document catalog marks the document as tagged
+    pub tagged: bool,
+    /// Whether the document catalog has a structure tree, which holds the tags
+    pub has_structure_tree: bool,
+}
+
+/// A revision from the history an Office document keeps, see [`Extractor::extract_revisions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// Name of the user who made the revision
+    pub author: Option<String>,
+    /// Time of the revision as an ISO-8601 string, e.g. `2024-03-05T10:15:30Z`. Not recorded by
+    /// the save history of Word documents
+    pub timestamp: Option<String>,
+    /// Path the document was saved to. Only recorded by the save history of Word documents
+    pub location: Option<String>,
+}
+
+/// A word of a PDF document with its bounding box.
+///
+/// Coordinates are in PDF points (1/72 inch) with the origin at the top-left corner of the page
+/// as displayed, i.e. after the page rotation has been applied. `x` grows to the right and `y`
+/// grows downwards, so `(x, y)` is the top-left corner of the box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordBox {
+    pub text: String,
+    /// 1-based page number
+    pub page: u32,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
 }
 
 /// CharSet enum of all supported encodings
+///
+/// Displays as the canonical Java name of the charset, e.g. `UTF-8`, which is the name handed to
+/// the parser. Parses from either that name or the variant name.
 #[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
 #[allow(non_camel_case_types)]
 pub enum CharSet {
     #[default]
+    #[strum(to_string = "UTF-8", serialize = "UTF_8")]
     UTF_8,
+    #[strum(to_string = "US-ASCII", serialize = "US_ASCII")]
     US_ASCII,
+    #[strum(to_string = "UTF-16BE", serialize = "UTF_16BE")]
     UTF_16BE,
+    #[strum(to_string = "UTF-16LE", serialize = "UTF_16LE")]
+    UTF_16LE,
+    /// Latin-1
+    #[strum(to_string = "ISO-8859-1", serialize = "ISO_8859_1")]
+    ISO_8859_1,
+    /// The Windows superset of Latin-1, with typographic quotes and dashes in 0x80-0x9F
+    #[strum(to_string = "windows-1252", serialize = "WINDOWS_1252")]
+    WINDOWS_1252,
+    /// Japanese
+    #[strum(to_string = "Shift_JIS", serialize = "SHIFT_JIS")]
+    SHIFT_JIS,
 }
 
-/// StreamReader implements std::io::Read
+/// StreamReader implements std::io::Read and std::io::BufRead
 ///
-/// Can be used to perform buffered reading. For example:
+/// It is buffered already, so it does not need to be wrapped in a `BufReader`. For example:
 /// ```rust
 /// use extractous::{CharSet, Extractor};
-/// use std::io::BufReader;
 /// use std::io::prelude::*;
 ///
 /// let extractor = Extractor::new();
-/// let (reader, metadata) = extractor.extract_file("README.md").unwrap();
+/// let (mut reader, metadata) = extractor.extract_file("README.md").unwrap();
 ///
-/// let mut buf_reader = BufReader::new(reader);
 /// let mut content = String::new();
-/// buf_reader.read_to_string(&mut content).unwrap();
+/// reader.read_to_string(&mut content).unwrap();
 /// println!("{}", content);
 /// ```
 ///
 pub struct StreamReader {
-    pub(crate) inner: JReaderInputStream,
+    pub(crate) inner: BufReader<JReaderInputStream>,
+    // The VM reads the buffer of extract_bytes until the stream is done
+    pub(crate) _buffer_permit: Option<DirectBufferPermit>,
+    // The buffer of extract_bytes_with_source, kept alive until the stream is done
+    pub(crate) _source: Option<Arc<[u8]>>,
+    // A copy of the metadata returned next to the stream
+    pub(crate) metadata: Metadata,
+}
+
+impl StreamReader {
+    /// Returns the metadata of the document, the same as the one returned next to the stream,
+    /// so that the stream can be passed on without losing track of what it was extracted from
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the media type of the document, the first value of `Content-Type`, such as
+    /// `application/pdf`
+    pub fn content_type(&self) -> Option<&str> {
+        MetadataView::new(&self.metadata).content_type()
+    }
+
+    /// Returns an iterator over the lines of the extracted text, without their line ending,
+    /// which is the same as [`BufRead::lines`] without importing the trait. For example:
+    /// ```rust
+    /// use extractous::Extractor;
+    ///
+    /// let (reader, _metadata) = Extractor::new().extract_file("README.md").unwrap();
+    /// for line in reader.lines() {
+    ///     println!("{}", line.unwrap());
+    /// }
+    /// ```
+    ///
+    /// The lines are decoded as UTF-8, a line that is not fails with
+    /// [`std::io::ErrorKind::InvalidData`], so the stream must be extracted with the default
+    /// [`CharSet::UTF_8`] encoding.
+    pub fn lines(self) -> std::io::Lines<Self> {
+        BufRead::lines(self)
+    }
 }
 
 impl std::io::Read for StreamReader {
@@ -94,6 +432,99 @@ impl std::io::Read for StreamReader {
     }
 }
 
+impl BufRead for StreamReader {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+/// The original input of an extraction, returned next to the stream by
+/// [`Extractor::extract_file_with_source`] and [`Extractor::extract_bytes_with_source`] so that
+/// it can be stored or processed again without reading it a second time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceInput {
+    /// Path of the extracted file
+    Path(PathBuf),
+    /// The extracted bytes, shared with the stream rather than copied
+    Bytes(Arc<[u8]>),
+}
+
+/// Receives the XHTML SAX events of a parse, see [`Extractor::extract_file_with_handler`].
+/// All methods have empty default implementations, so only the needed events have to be handled.
+///
+/// For example, collecting the text of every table cell:
+/// ```rust
+/// use extractous::{ContentHandler, Extractor};
+///
+/// #[derive(Default)]
+/// struct Cells {
+///     in_cell: bool,
+///     cells: Vec<String>,
+/// }
+///
+/// impl ContentHandler for Cells {
+///     fn start_element(&mut self, name: &str, _attributes: &[(String, String)]) {
+///         if name == "td" {
+///             self.in_cell = true;
+///             self.cells.push(String::new());
+///         }
+///     }
+///     fn end_element(&mut self, name: &str) {
+///         if name == "td" {
+///             self.in_cell = false;
+///         }
+///     }
+///     fn characters(&mut self, text: &str) {
+///         if self.in_cell {
+///             self.cells.last_mut().unwrap().push_str(text);
+///         }
+///     }
+/// }
+///
+/// let mut cells = Cells::default();
+/// Extractor::new()
+///     .extract_file_with_handler("README.md", &mut cells)
+///     .unwrap();
+/// ```
+pub trait ContentHandler {
+    fn start_document(&mut self) {}
+    fn end_document(&mut self) {}
+    /// Called for every element with its local name and its `(name, value)` attributes
+    fn start_element(&mut self, _name: &str, _attributes: &[(String, String)]) {}
+    fn end_element(&mut self, _name: &str) {}
+    /// Called with chunks of text content. A text node may be split across several calls
+    fn characters(&mut self, _text: &str) {}
+}
+
+/// A configuration combination reported by [`Extractor::validate`] that is contradictory or has
+/// no effect
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    #[error("the OCR config is not used for PDF files because the PDF OCR strategy is NO_OCR")]
+    OcrConfigUnusedForPdf,
+
+    #[error("the script filter is skipped because the output is xml")]
+    ScriptFilterWithXmlOutput,
+
+    #[error("the script filter threshold has no effect because no script filter is set")]
+    ScriptFilterThresholdWithoutFilter,
+
+    #[error(
+        "extract_unique_inline_images_only has no effect because extract_inline_images is disabled"
+    )]
+    UniqueInlineImagesWithoutInlineImages,
+
+    #[error("the per-document max length ({per_document}) is not lower than the extract string max length ({total}), so it never applies")]
+    PerDocumentMaxLengthNotLower { per_document: i32, total: i32 },
+
+    #[error("recursive extraction returns no embedded documents because the max container depth is 0 and leaf resources are disabled")]
+    NoEmbeddedDocuments,
+}
+
 /// Extractor for extracting text from different file formats
 ///
 /// The Extractor uses the builder pattern to set configurations. This allows configuring and
@@ -107,27 +538,59 @@ impl std::io::Read for StreamReader {
 /// println!("{}", text);
 /// ```
 ///
+/// Besides the metadata reported by the parsers, the metadata returned by the `*_to_string`,
+/// `*_recursive` and `extract_file_with_handler` methods contains `extractous:ocr_used`, set to
+/// `true` when any of the document's content came from OCR rather than from a text layer, and
+/// `X-TIKA:ocr_timed_out`, set to `true` only when OCR ran out of time on some of it, see
+/// [`TesseractOcrConfig::set_timeout_seconds`](crate::TesseractOcrConfig::set_timeout_seconds).
+/// In recursive results they are set for each document separately.
+///
 #[derive(Debug, Clone)]
 pub struct Extractor {
     extract_string_max_length: i32,
-    encoding: CharSet,
+    // The java name of the charset, see `set_encoding_name`
+    encoding: String,
     pdf_config: PdfParserConfig,
     office_config: OfficeParserConfig,
     ocr_config: TesseractOcrConfig,
+    recursive_config: RecursiveConfig,
     xml_output: bool,
     extract_embedded: bool,
+    script_filter: Option<Script>,
+    script_filter_threshold: usize,
+    emit_line_numbers: bool,
+    extract_entities: bool,
+    detect_script: bool,
+    detect_language: bool,
+    compute_simhash: bool,
+    follow_symlinks: bool,
+    timeout: Option<Duration>,
+    metadata_key_map: HashMap<String, String>,
+    parse_options: ParseOptions,
 }
 
 impl Default for Extractor {
     fn default() -> Self {
         Self {
             extract_string_max_length: -1, // -1 means no limit
-            encoding: CharSet::UTF_8,
+            encoding: CharSet::UTF_8.to_string(),
             pdf_config: PdfParserConfig::default(),
             office_config: OfficeParserConfig::default(),
             ocr_config: TesseractOcrConfig::default(),
+            recursive_config: RecursiveConfig::default(),
             xml_output: false,
             extract_embedded: true,
+            script_filter: None,
+            script_filter_threshold: DEFAULT_SCRIPT_FILTER_THRESHOLD,
+            emit_line_numbers: false,
+            extract_entities: false,
+            detect_script: false,
+            detect_language: false,
+            compute_simhash: false,
+            follow_symlinks: true,
+            timeout: None,
+            metadata_key_map: HashMap::new(),
+            parse_options: ParseOptions::default(),
         }
     }
 }
@@ -148,7 +611,17 @@ impl Extractor {
     /// Not used for extract_to_string functions.
     /// Default: CharSet::UTF_8
     pub fn set_encoding(mut self, encoding: CharSet) -> Self {
-        self.encoding = encoding;
+        self.encoding = encoding.to_string();
+        self
+    }
+
+    /// Set the encoding to use for when extracting text to a stream by its name, for the
+    /// charsets [`CharSet`] does not list, e.g. `"KOI8-R"` or `"GB18030"`. Any name or alias
+    /// known to Java is accepted. The name is checked when extracting: an unsupported one makes
+    /// the stream extractions fail with [`Error::ParseError`](crate::Error::ParseError).
+    /// Not used for extract_to_string functions.
+    pub fn set_encoding_name(mut self, name: &str) -> Self {
+        self.encoding = name.to_string();
         self
     }
 
@@ -170,6 +643,88 @@ impl Extractor {
         self
     }
 
+    /// Set the configuration for fetching the documents of the url methods, such as
+    /// [`Extractor::extract_url`]: the user agent, and whether the robots.txt of the site is
+    /// honored
+    pub fn set_url_fetch_config(mut self, config: UrlFetchConfig) -> Self {
+        self.parse_options.url_fetch = config;
+        self
+    }
+
+    /// Set the configuration for walking embedded documents. Used only for the
+    /// extract_*_recursive functions
+    pub fn set_recursive_config(mut self, config: RecursiveConfig) -> Self {
+        // Keep what is set through the Extractor setters
+        let RecursiveConfig {
+            parallelism,
+            embedded_mime_filter,
+            max_embedded_count,
+            max_embedded_bytes,
+            ..
+        } = std::mem::take(&mut self.recursive_config);
+        self.recursive_config = RecursiveConfig {
+            parallelism,
+            embedded_mime_filter,
+            max_embedded_count,
+            max_embedded_bytes,
+            ..config
+        };
+        self
+    }
+
+    /// Set how many of the documents directly embedded in the container are parsed at the same
+    /// time by the extract_*_recursive functions, each one together with its own embedded
+    /// documents. This speeds up archives of many independent documents, such as a ZIP of PDF
+    /// files. The documents are returned in the same order as a sequential extraction, the
+    /// container first, but their `X-TIKA:embedded_id` are numbered differently. The
+    /// `max_length` and the soft time budget apply to each concurrently parsed document
+    /// separately. `extract_file_recursive_to_ndjson`, which writes the documents as they are
+    /// parsed, ignores it. 0 and 1 mean sequential.
+    /// Default: 1
+    pub fn set_recursive_parallelism(mut self, parallelism: usize) -> Self {
+        self.recursive_config.parallelism = parallelism;
+        self
+    }
+
+    /// Set the media types of the embedded documents the extract_*_recursive functions parse and
+    /// return, such as `application/pdf`, or a whole top-level type with a wildcard, such as
+    /// `image/*`. The embedded documents whose detected type matches none of them are skipped on
+    /// the java side, before they are parsed, together with the documents embedded in them, so
+    /// that for example the thumbnails and fonts of a presentation cost nothing. Types are
+    /// compared without their parameters and ignoring the case. The container is always returned,
+    /// and the rules of the recursive config still apply to the documents that match.
+    /// Default: none, every embedded document is parsed
+    pub fn set_embedded_mime_filter(mut self, allow: Vec<String>) -> Self {
+        self.recursive_config.embedded_mime_filter = allow;
+        self
+    }
+
+    /// Set the maximum number of embedded documents the extract_*_recursive functions parse, a
+    /// defense against the ZIP bombs of many entries. The embedded documents past it are not
+    /// parsed, the ones collected so far are returned, and the container gets
+    /// `extractous:embedded_truncated` set to `true`, see
+    /// [`RecursiveExtraction::is_embedded_truncated`]. The documents skipped by the recursive
+    /// config and the MIME filter do not count. With `set_recursive_parallelism`, which documents
+    /// are left out depends on the order the workers parse them in.
+    /// Default: no limit
+    pub fn set_max_embedded_count(mut self, max: usize) -> Self {
+        self.recursive_config.max_embedded_count = Some(max);
+        self
+    }
+
+    /// Set the maximum total size, in bytes, of the raw embedded documents the
+    /// extract_*_recursive functions decompress, a defense against the ZIP bombs of entries that
+    /// inflate to huge sizes. The bytes are counted as they are read, so the document that reaches
+    /// the limit is cut there, possibly failing to parse, and the documents after it are not
+    /// parsed. The bytes of a document embedded in an embedded document count again, as they are
+    /// decompressed again. As with `set_max_embedded_count`, the container gets
+    /// `extractous:embedded_truncated` set to `true`.
+    /// Default: no limit
+    pub fn set_max_embedded_bytes(mut self, max: u64) -> Self {
+        self.recursive_config.max_embedded_bytes = Some(max);
+        self
+    }
+
     /// Set the configuration for the parse as xml (global default). Per-call overrides exist via *_opt APIs.
     pub fn set_xml_output(mut self, xml_output: bool) -> Self {
         self.xml_output = xml_output;
@@ -183,18 +738,358 @@ impl Extractor {
         self
     }
 
+    /// Keep only the text written in the given script. Runs of text in other scripts with at
+    /// least `script_filter_threshold` characters are removed from the extracted content.
+    /// Used only for the extract_*_to_string and extract_*_recursive functions, and skipped
+    /// when the output is xml.
+    /// Default: off
+    pub fn set_script_filter(mut self, script: Script) -> Self {
+        self.script_filter = Some(script);
+        self
+    }
+
+    /// Set the minimum number of characters of another script a run must contain to be removed
+    /// by the script filter. Shorter runs such as foreign names or words are kept.
+    /// Default: 20
+    pub fn set_script_filter_threshold(mut self, threshold: usize) -> Self {
+        self.script_filter_threshold = threshold;
+        self
+    }
+
+    /// Set whether each line of plain text documents (text files, logs, source code, ...) is
+    /// prefixed with its line number in the source, followed by a tab, so that the extracted
+    /// content can cite exact locations. Other formats, whose lines do not map to the source, and
+    /// the xml output are left unchanged. Applies to the `*_to_string` and `*_recursive`
+    /// methods, like the script filter. The numbers stay those of the source when the repeated
+    /// lines are removed, see [`Extractor::set_dedup_repeated_lines`].
+    /// Default: false
+    pub fn set_emit_line_numbers(mut self, emit_line_numbers: bool) -> Self {
+        self.emit_line_numbers = emit_line_numbers;
+        self
+    }
+
+    /// Set whether the email addresses, phone numbers and URLs of the extracted content are
+    /// returned as structured entities with their offsets, see [`find_entities`] for what is
+    /// recognized and how the values are normalized. The entities are in the `entities` of each
+    /// [`Document`] of the `*_recursive` methods and point into its `content`, after the script
+    /// filter and the line numbers. They are not computed for the xml output. For the
+    /// `*_to_string` methods, call [`find_entities`] on the returned content.
+    /// Default: false
+    pub fn set_extract_entities(mut self, extract_entities: bool) -> Self {
+        self.extract_entities = extract_entities;
+        self
+    }
+
+    /// Set whether the script most of the extracted text is written in, see [`dominant_script`],
+    /// is reported in the `extractous:dominant_script` metadata key, e.g. `Cjk` for Chinese or
+    /// Japanese text that needs a word segmenter rather than a whitespace tokenizer. The key is
+    /// left out when the text has no letters. Applies to the `*_to_string` and `*_recursive`
+    /// methods, for each document separately, and is skipped when the output is xml. The
+    /// script is detected on the content after the script filter.
+    /// Default: false
+    pub fn set_detect_script(mut self, detect_script: bool) -> Self {
+        self.detect_script = detect_script;
+        self
+    }
+
+    /// Set whether the language of the extracted text, see [`Extractor::detect_language`], is
+    /// reported in the `language` metadata key as an ISO 639-1 code, e.g. `de`, to route each
+    /// document to an analyzer of its language. The key is left out when no language is
+    /// detected, and a detection that fails leaves the extraction as it is. Applies to the
+    /// `*_to_string` and `*_recursive` methods, for each document separately, and is skipped
+    /// when the output is xml. The language is detected on the content after the script filter.
+    /// Default: false
+    pub fn set_detect_language(mut self, detect_language: bool) -> Self {
+        self.detect_language = detect_language;
+        self
+    }
+
+    /// Set whether a SimHash fingerprint of the extracted text, see [`simhash`], is stored in the
+    /// `X-SimHash` metadata key as 16 lowercase hex digits, to cluster near-duplicate documents
+    /// downstream without reading their content again. The fingerprint is computed on the content
+    /// after the script filter and before the line numbers. Applies to the `*_to_string` and
+    /// `*_recursive` methods, for each document separately, and is skipped when the output is
+    /// xml. Hashing every token of the content has a cost, hence the default.
+    /// Default: false
+    pub fn set_compute_simhash(mut self, compute_simhash: bool) -> Self {
+        self.compute_simhash = compute_simhash;
+        self
+    }
+
+    /// Set whether the file methods follow a path that is a symbolic link to the file it points
+    /// to. When false such paths are rejected. Either way the file methods only read regular
+    /// files: directories, FIFOs, sockets and devices fail with [`Error::InvalidInput`] before
+    /// anything is read, instead of failing late or blocking like a FIFO without a writer.
+    /// Default: true
+    pub fn set_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Set the renaming of metadata keys, from the key reported by the parsers to the key to
+    /// return, e.g. `dc:title` to `doc_title`, so that the metadata comes out in the schema of the
+    /// application. The keys not in the map are left as they are. The keys added by the extractor,
+    /// such as `extractous:ocr_used`, are renamed too. When a key is renamed to a key the metadata
+    /// already has, the values of both are kept, those of the key already there first.
+    ///
+    /// The renaming is applied last, to the metadata returned by every method, for each document
+    /// separately in recursive results. The methods that read the metadata themselves, such as
+    /// [`Extractor::extract_office_properties`], see the keys reported by the parsers.
+    /// Default: empty, no renaming
+    pub fn set_metadata_key_map(mut self, metadata_key_map: HashMap<String, String>) -> Self {
+        self.metadata_key_map = metadata_key_map;
+        self
+    }
+
+    /// Set the maximum nesting depth of the elements produced while parsing, the `html` and
+    /// `body` elements of the XHTML output included. Parsing a document nested deeper, such as a
+    /// maliciously crafted HTML or XML file, fails with `Error::ParseError` instead of exhausting
+    /// the stack. Streaming extractions report the error when reading the stream, and embedded
+    /// documents of a recursive extraction record it in their metadata. 0 means no limit.
+    /// Default: 100
+    pub fn set_max_element_depth(mut self, max_depth: u32) -> Self {
+        self.parse_options.max_element_depth = max_depth;
+        self
+    }
+
+    /// Set whether large jobs buffer their data in temporary files instead of memory. This
+    /// matters most for OCR of large scanned PDF files: every page is rendered from the loaded
+    /// document, which PDFBox otherwise keeps entirely in memory. When enabled, the PDF documents
+    /// and the byte and url inputs are kept in temporary files, which lowers the memory use at
+    /// the cost of disk I/O, so it suits memory constrained hosts with fast disks. The rendered
+    /// page images are handed to tesseract through temporary files either way.
+    /// Default: false, everything stays in memory
+    pub fn set_ocr_spill_to_disk(mut self, spill_to_disk: bool) -> Self {
+        self.parse_options.ocr_spill_to_disk = spill_to_disk;
+        self
+    }
+
+    /// Set whether the text a reader can not see is left out of the extracted content. This is
+    /// best-effort and covers PDF text drawn in the invisible rendering mode or filled in white,
+    /// which assumes a white page, and HTML elements hidden by the `hidden` attribute or an
+    /// inline `display: none` or `visibility: hidden` style. Style sheets are not evaluated.
+    /// PDF files are rewritten to a temporary file before parsing, so enabling it costs an extra
+    /// pass over every PDF document. Encrypted PDF files that can not be opened without a
+    /// password are parsed as they are.
+    /// Default: false
+    pub fn set_exclude_hidden_text(mut self, exclude: bool) -> Self {
+        self.parse_options.exclude_hidden_text = exclude;
+        self
+    }
+
+    /// Set a soft limit on the time spent parsing a document, its embedded documents included.
+    /// Once the budget has elapsed, the extraction stops gracefully and returns the content
+    /// gathered so far instead of an error. The metadata of the documents that were cut, as
+    /// returned by the `*_to_string` and `*_recursive` methods, contains
+    /// `extractous:content_partial` set to `true`. The budget is only checked when the
+    /// parser produces content, so a parser busy without producing any, such as tesseract
+    /// running on a scanned page, finishes that step first: the budget is a target, not a hard
+    /// deadline. `max_length` still applies, the extraction ends at whichever limit is reached
+    /// first, and the content cut by the length limit is not flagged partial.
+    /// Default: no budget
+    pub fn set_soft_time_budget(mut self, budget: Duration) -> Self {
+        self.parse_options.soft_time_budget = Some(budget);
+        self
+    }
+
+    /// Set a hard limit on the time an extraction can take. The extraction then runs on a
+    /// worker thread, and fails with [`Error::Timeout`] once the timeout has elapsed, dropping
+    /// whatever it produced so far. The java thread of the parse is interrupted, which ends it at
+    /// the next content the parser produces; a parser that spins without producing any keeps its
+    /// worker busy in the background until it is done. Unlike [`Extractor::set_soft_time_budget`],
+    /// the caller never waits longer than the timeout.
+    ///
+    /// Applies to the file, bytes and url methods: the stream, `*_to_string` and `*_recursive`
+    /// ones and their `*_opt` variants, and to the methods built on them. See
+    /// [`Extractor::extract_file_opt_with_timeout`] for a timeout of a single call. For the
+    /// stream methods it bounds the start of the parse, until the stream is returned, not the
+    /// reading of the stream. The bytes methods copy the bytes, so that the abandoned parse never
+    /// reads memory the caller has freed. It does not apply to the reader methods, to sessions,
+    /// nor to the methods that hand out the content while it is parsed, such as
+    /// [`Extractor::extract_file_recursive_to_ndjson`] and
+    /// [`Extractor::extract_file_recursive_iter`].
+    /// Default: no timeout
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set whether the charset a document declares in its content, such as the
+    /// `<meta charset>` or `<meta http-equiv="Content-Type">` tag of an HTML page, is used to
+    /// decode it. When false the declaration is ignored and the charset is detected from the
+    /// bytes of the document instead, which fixes the mojibake of pages that declare the wrong
+    /// charset. A byte order mark is still honored.
+    /// Default: true, the declaration is trusted as Tika does
+    pub fn set_trust_declared_charset(mut self, trust: bool) -> Self {
+        self.parse_options.trust_declared_charset = trust;
+        self
+    }
+
+    /// Set whether the charset of the text documents, such as plain text, CSV and HTML, is
+    /// detected with the ICU charset detection rather than Tika's detectors, for corpora that mix
+    /// encodings. A document that is valid UTF-8 is read as UTF-8, and when the detection is
+    /// ambiguous it falls back to UTF-8 instead of guessing. The charset used is recorded in the
+    /// `Content-Encoding` metadata key. The charset declared by an HTML page still comes first,
+    /// unless [`Extractor::set_trust_declared_charset`] is false. This is about reading the
+    /// documents: the streams are still written in the charset of [`Extractor::set_encoding`].
+    /// Default: false
+    pub fn set_encoding_auto(mut self, auto: bool) -> Self {
+        self.parse_options.encoding_auto = auto;
+        self
+    }
+
+    /// Set whether the lines repeated on most pages of a document, such as running headers and
+    /// footers, are kept only once, where they first appear. A line is repeated when it is found
+    /// on more than half of the pages of a document of at least 3 pages. Lines are compared
+    /// trimmed, with their whitespace collapsed and their numbers ignored, so that the
+    /// `Page 2 of 10` footers are removed as well. The pages are the ones of PDF documents and
+    /// the slides of presentations, the form feeds of plain text are treated as page breaks too
+    /// and removed. Applies to the `*_to_string` and `*_recursive` methods, before the script
+    /// filter, but not to the xml output and the streams.
+    /// Default: false
+    pub fn set_dedup_repeated_lines(mut self, dedup: bool) -> Self {
+        self.parse_options.dedup_repeated_lines = dedup;
+        self
+    }
+
+    /// Set the media types, such as `application/x-foo`, and the file extensions, such as `.dat`
+    /// or `log`, of the documents that are always parsed as plain text, whatever their content is
+    /// detected as. This onboards in-house text formats that are detected as
+    /// `application/octet-stream`, without a custom Tika config. Entries containing a `/` are
+    /// media types, compared to the detected type without its parameters, the others are
+    /// extensions, compared to the file or resource name ignoring the case. The charset is then
+    /// detected by the text parser. Applies to embedded documents too.
+    /// Default: none
+    pub fn set_force_text_types(mut self, types: Vec<String>) -> Self {
+        self.parse_options.force_text_types = types;
+        self
+    }
+
+    /// Set the size in bytes above which the `*_to_string` methods stream the content from the
+    /// java side instead of building it as a single java string first. The content is then
+    /// written in UTF-8 chunks of this size, which are moved into the returned `String` one at a
+    /// time, so that a large document is not held twice in full at the end of the parse. The
+    /// returned content is the same. Applies to the xml output as well.
+    /// Default: None, the content is always built as a single java string
+    pub fn set_string_streaming_threshold(mut self, threshold: usize) -> Self {
+        self.parse_options.string_streaming_threshold = Some(threshold);
+        self
+    }
+
+    /// Set the parsers a document is retried with, in order, when the parser of its detected type
+    /// fails on it, e.g. [`ParserKind::PlainText`] for XML documents the XML parser chokes on.
+    /// The content of the first parser that succeeds is returned, with the name of that parser in
+    /// the `extractous:fallback_parser` metadata key and the error of the detected parser in
+    /// `X-TIKA:EXCEPTION:warn`. When every parser fails, the error of the detected parser is
+    /// returned. Applies to embedded documents too.
+    ///
+    /// Only recoverable failures trigger a fallback: encrypted and empty documents still fail, and
+    /// a document parsed successfully is never retried, even when no content was found. The
+    /// content of each attempt is held until it succeeded, so that a failed attempt leaves no
+    /// partial content behind. The streams therefore only start once a document is parsed, and the
+    /// max length and soft time budget are only checked as the content of that attempt is handed
+    /// over.
+    /// Default: none
+    pub fn set_parser_fallback_chain(mut self, parsers: Vec<ParserKind>) -> Self {
+        self.parse_options.parser_fallbacks = parsers;
+        self
+    }
+
+    /// Set whether the parsers each document went through are recorded in the
+    /// `extractous:parser_chain` metadata key, as class names from the outermost to the parser of
+    /// the document format, e.g. `org.apache.tika.parser.AutoDetectParser`,
+    /// `org.apache.tika.parser.DefaultParser`, `org.apache.tika.parser.csv.TextAndCSVParser`.
+    /// It is the `X-TIKA:Parsed-By` list Tika keeps, led by the auto-detecting parser and without
+    /// the wrappers the extractor adds around the Tika parsers. When a fallback parser took over
+    /// (see [`Extractor::set_parser_fallback_chain`]), it comes after the parser that failed.
+    /// The key is set by every extract method, on the embedded documents too, and is already in
+    /// the metadata the stream methods return before the content is read.
+    /// Default: false
+    pub fn set_record_parser_chain(mut self, record: bool) -> Self {
+        self.parse_options.record_parser_chain = record;
+        self
+    }
+
+    /// Set whether the ZIP based documents (archives, OOXML documents, JARs, ...) whose central
+    /// directory is corrupt or missing, such as truncated files, are recovered instead of
+    /// failing. When the parse of such a document fails, the entries that can still be read from
+    /// their local file headers are copied to a new archive, which is parsed instead, and
+    /// `extractous:zip_recovered_entries` is set to the number of entries recovered. The entry
+    /// cut short by a truncation is kept with the data up to the cut. Applies to the embedded
+    /// archives too.
+    /// Default: false
+    pub fn set_zip_recovery(mut self, zip_recovery: bool) -> Self {
+        self.parse_options.zip_recovery = zip_recovery;
+        self
+    }
+
+    /// Set whether the superscripts and subscripts of the documents are kept in the text output
+    /// instead of flattening into inline text, `H₂O` and `x²` rather than `H2O` and `x2`. The text
+    /// of the `<sup>` and `<sub>` elements of the XHTML output is written with the Unicode
+    /// superscript or subscript characters when all of its characters have one, the digits,
+    /// `+ - = ( )` and a few letters, and between markers otherwise: `^{st}` for a superscript and
+    /// `_{eq}` for a subscript. Only the formats whose parser reports superscripts and subscripts,
+    /// such as HTML, have any. Applies to the text output of the stream, `*_to_string` and
+    /// `*_recursive` methods, the xml output keeps the elements as they are.
+    /// Default: false
+    pub fn set_preserve_scripts(mut self, preserve_scripts: bool) -> Self {
+        self.parse_options.preserve_scripts = preserve_scripts;
+        self
+    }
+
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_file(&self, file_path: &str) -> ExtractResult<(StreamReader, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let file_path = file_path.to_string();
+            return self.with_timeout(timeout, move |extractor| extractor.extract_file(&file_path));
+        }
+        check_input_file(file_path, self.follow_symlinks)?;
         tika::parse_file(
             file_path,
             &self.encoding,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             self.xml_output,
             self.extract_embedded,
         )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
+    }
+
+    /// Same as [`Extractor::extract_file`], for a path built with [`Path`] or [`PathBuf`].
+    /// Fails with [`Error::IoError`] if the path is not valid unicode.
+    pub fn extract_path<P: AsRef<Path>>(&self, path: P) -> ExtractResult<(StreamReader, Metadata)> {
+        self.extract_file(path_to_str(path.as_ref())?)
+    }
+
+    /// Extracts the content of the file straight into `out`, such as a file or a socket, and
+    /// returns the metadata. The stream of [`Extractor::extract_file`] is copied in chunks of
+    /// [`DEFAULT_BUF_SIZE`] bytes as it is parsed, so the content is never held in memory as a
+    /// whole. The content is the same as the one of the stream, text or xml in the configured
+    /// encoding. A failure of the parse while the stream is read returns a `ParseError` and a
+    /// failure to write an `IoError`, in both cases after the content copied so far. `out` is
+    /// flushed at the end.
+    pub fn extract_file_to_writer<W: Write>(
+        &self,
+        file_path: &str,
+        out: &mut W,
+    ) -> ExtractResult<Metadata> {
+        let (mut reader, metadata) = self.extract_file(file_path)?;
+        let mut buffer = vec![0; DEFAULT_BUF_SIZE];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::ParseError(e.to_string())),
+            };
+            out.write_all(&buffer[..read])
+                .map_err(|e| Error::IoError(e.to_string()))?;
+        }
+        out.flush().map_err(|e| Error::IoError(e.to_string()))?;
+        Ok(metadata)
     }
 
     /// Extracts to stream using optional overrides. If an option is None, uses Extractor defaults.
@@ -205,32 +1100,125 @@ impl Extractor {
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
     ) -> ExtractResult<(StreamReader, Metadata)> {
-        let eff_encoding = encoding.unwrap_or(self.encoding);
+        if let Some(timeout) = self.timeout {
+            return self.extract_file_opt_with_timeout(
+                file_path,
+                encoding,
+                as_xml,
+                extract_embedded,
+                timeout,
+            );
+        }
+        let eff_encoding = encoding.map_or_else(|| self.encoding.clone(), |e| e.to_string());
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         let eff_extract_embedded = extract_embedded.unwrap_or(self.extract_embedded);
+        check_input_file(file_path, self.follow_symlinks)?;
         tika::parse_file(
             file_path,
             &eff_encoding,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             eff_as_xml,
             eff_extract_embedded,
         )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
+    }
+
+    /// Same as [`Extractor::extract_file_opt`], with a timeout for this call only that overrides
+    /// the one of [`Extractor::set_timeout`]
+    pub fn extract_file_opt_with_timeout(
+        &self,
+        file_path: &str,
+        encoding: Option<CharSet>,
+        as_xml: Option<bool>,
+        extract_embedded: Option<bool>,
+        timeout: Duration,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        let file_path = file_path.to_string();
+        self.with_timeout(timeout, move |extractor| {
+            extractor.extract_file_opt(&file_path, encoding, as_xml, extract_embedded)
+        })
+    }
+
+    /// Same as [`Extractor::extract_file`], and also returns the path of the file as a
+    /// [`SourceInput::Path`], for pipelines that keep the original next to the extracted text.
+    pub fn extract_file_with_source(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(StreamReader, Metadata, SourceInput)> {
+        let (reader, metadata) = self.extract_file(file_path)?;
+        Ok((
+            reader,
+            metadata,
+            SourceInput::Path(PathBuf::from(file_path)),
+        ))
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_bytes(&self, buffer: &[u8]) -> ExtractResult<(StreamReader, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let buffer = Arc::<[u8]>::from(buffer);
+            return self.with_timeout(timeout, move |extractor| {
+                extractor
+                    .extract_bytes_with_source(buffer)
+                    .map(|(reader, metadata, _)| (reader, metadata))
+            });
+        }
         tika::parse_bytes(
             buffer,
             &self.encoding,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
+            self.xml_output,
+            self.extract_embedded,
+        )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
+    }
+
+    /// Same as [`Extractor::extract_bytes`], and also returns the bytes as a
+    /// [`SourceInput::Bytes`], for pipelines that keep the original next to the extracted text.
+    ///
+    /// The stream and the returned input share the same buffer, which the VM reads in place, so
+    /// the bytes are held once and stay in memory until both are dropped. Passing an `Arc<[u8]>`
+    /// shares it as is, while a `Vec<u8>` or a slice is copied once into a new shared buffer.
+    pub fn extract_bytes_with_source(
+        &self,
+        buffer: impl Into<Arc<[u8]>>,
+    ) -> ExtractResult<(StreamReader, Metadata, SourceInput)> {
+        let buffer = buffer.into();
+        let (mut reader, metadata) = self.extract_bytes(&buffer)?;
+        reader._source = Some(Arc::clone(&buffer));
+        Ok((reader, metadata, SourceInput::Bytes(buffer)))
+    }
+
+    /// Extracts text from any reader, for inputs such as network streams or decompressors that
+    /// are neither a file nor a buffer in memory. Returns a tuple with stream of the extracted
+    /// text and metadata. The stream is decoded using the extractor's `encoding`.
+    ///
+    /// The reader is pulled by the VM in chunks while the stream is consumed, on a VM thread,
+    /// and dropped once the parse is over. Its errors and panics fail the parse with an IO
+    /// error. Note that some formats, for example ZIP based ones, need random access and are
+    /// spooled to a temporary file first.
+    pub fn extract_reader<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        tika::parse_reader(
+            reader,
+            &self.encoding,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
             self.xml_output,
             self.extract_embedded,
         )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
     }
 
     /// Extracts bytes to stream using optional overrides. If an option is None, uses Extractor defaults.
@@ -241,7 +1229,16 @@ impl Extractor {
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
     ) -> ExtractResult<(StreamReader, Metadata)> {
-        let eff_encoding = encoding.unwrap_or(self.encoding);
+        if let Some(timeout) = self.timeout {
+            let buffer = Arc::<[u8]>::from(buffer);
+            return self.with_timeout(timeout, move |extractor| {
+                let (mut reader, metadata) =
+                    extractor.extract_bytes_opt(&buffer, encoding, as_xml, extract_embedded)?;
+                reader._source = Some(buffer);
+                Ok((reader, metadata))
+            });
+        }
+        let eff_encoding = encoding.map_or_else(|| self.encoding.clone(), |e| e.to_string());
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         let eff_extract_embedded = extract_embedded.unwrap_or(self.extract_embedded);
         tika::parse_bytes(
@@ -250,23 +1247,32 @@ impl Extractor {
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             eff_as_xml,
             eff_extract_embedded,
         )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
     }
 
     /// Extracts text from an url. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_url(&self, url: &str) -> ExtractResult<(StreamReader, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let url = url.to_string();
+            return self.with_timeout(timeout, move |extractor| extractor.extract_url(&url));
+        }
+        self.check_robots(url)?;
         tika::parse_url(
             url,
             &self.encoding,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             self.xml_output,
             self.extract_embedded,
         )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
     }
 
     /// Extracts url to stream using optional overrides. If an option is None, uses Extractor defaults.
@@ -277,32 +1283,64 @@ impl Extractor {
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
     ) -> ExtractResult<(StreamReader, Metadata)> {
-        let eff_encoding = encoding.unwrap_or(self.encoding);
+        if let Some(timeout) = self.timeout {
+            let url = url.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_url_opt(&url, encoding, as_xml, extract_embedded)
+            });
+        }
+        let eff_encoding = encoding.map_or_else(|| self.encoding.clone(), |e| e.to_string());
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         let eff_extract_embedded = extract_embedded.unwrap_or(self.extract_embedded);
+        self.check_robots(url)?;
         tika::parse_url(
             url,
             &eff_encoding,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             eff_as_xml,
             eff_extract_embedded,
         )
+        .map(|stream| self.rename_stream_metadata_keys(stream))
     }
 
     /// Extracts text from a file path. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
     pub fn extract_file_to_string(&self, file_path: &str) -> ExtractResult<(String, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let file_path = file_path.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_file_to_string(&file_path)
+            });
+        }
+        check_input_file(file_path, self.follow_symlinks)?;
         tika::parse_file_to_string(
             file_path,
             self.extract_string_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             self.xml_output,
             self.extract_embedded,
         )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, self.xml_output),
+                self.rename_metadata_keys(metadata),
+            )
+        })
+    }
+
+    /// Same as [`Extractor::extract_file_to_string`], for a path built with [`Path`] or
+    /// [`PathBuf`]. Fails with [`Error::IoError`] if the path is not valid unicode.
+    pub fn extract_path_to_string<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> ExtractResult<(String, Metadata)> {
+        self.extract_file_to_string(path_to_str(path.as_ref())?)
     }
 
     /// String extraction with optional overrides (max_length, as_xml, extract_embedded)
@@ -313,32 +1351,64 @@ impl Extractor {
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
     ) -> ExtractResult<(String, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let file_path = file_path.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_file_to_string_opt(
+                    &file_path,
+                    max_length,
+                    as_xml,
+                    extract_embedded,
+                )
+            });
+        }
         let eff_max_length = max_length.unwrap_or(self.extract_string_max_length);
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         let eff_extract_embedded = extract_embedded.unwrap_or(self.extract_embedded);
+        check_input_file(file_path, self.follow_symlinks)?;
         tika::parse_file_to_string(
             file_path,
             eff_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             eff_as_xml,
             eff_extract_embedded,
         )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, eff_as_xml),
+                self.rename_metadata_keys(metadata),
+            )
+        })
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
     pub fn extract_bytes_to_string(&self, buffer: &[u8]) -> ExtractResult<(String, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let buffer = buffer.to_vec();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_bytes_to_string(&buffer)
+            });
+        }
         tika::parse_bytes_to_string(
             buffer,
             self.extract_string_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             self.xml_output,
             self.extract_embedded,
         )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, self.xml_output),
+                self.rename_metadata_keys(metadata),
+            )
+        })
     }
 
     pub fn extract_bytes_to_string_opt(
@@ -348,6 +1418,12 @@ impl Extractor {
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
     ) -> ExtractResult<(String, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let buffer = buffer.to_vec();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_bytes_to_string_opt(&buffer, max_length, as_xml, extract_embedded)
+            });
+        }
         let eff_max_length = max_length.unwrap_or(self.extract_string_max_length);
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         let eff_extract_embedded = extract_embedded.unwrap_or(self.extract_embedded);
@@ -357,43 +1433,176 @@ impl Extractor {
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             eff_as_xml,
             eff_extract_embedded,
         )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, eff_as_xml),
+                self.rename_metadata_keys(metadata),
+            )
+        })
+    }
+    /// Extracts text from any reader. Returns a tuple with string that is of maximum length
+    /// of the extractor's `extract_string_max_length` and metadata.
+    ///
+    /// The reader is read on the calling thread, so it does not need to be `Send`. A panic it
+    /// raises is resumed once the parse is over.
+    pub fn extract_reader_to_string<R: Read>(
+        &self,
+        reader: R,
+    ) -> ExtractResult<(String, Metadata)> {
+        tika::parse_reader_to_string(
+            reader,
+            self.extract_string_max_length,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            self.xml_output,
+            self.extract_embedded,
+        )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, self.xml_output),
+                self.rename_metadata_keys(metadata),
+            )
+        })
     }
+
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and metadata.
     pub fn extract_url_to_string(&self, url: &str) -> ExtractResult<(String, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let url = url.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_url_to_string(&url)
+            });
+        }
+        self.check_robots(url)?;
         tika::parse_url_to_string(
             url,
             self.extract_string_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             self.xml_output,
             self.extract_embedded,
         )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, self.xml_output),
+                self.rename_metadata_keys(metadata),
+            )
+        })
     }
 
-    pub fn extract_url_to_string_opt(
+    /// Same as [`Extractor::extract_file_to_string`], and fails with [`Error::Cancelled`] as soon
+    /// as the token is cancelled, from another thread, such as the handler of a client that went
+    /// away. The extraction runs on a worker thread whose parse is interrupted on cancellation,
+    /// as on a timeout, see [`crate::run_cancellable`]. The timeout of
+    /// [`Extractor::set_timeout`] applies as well, whichever comes first.
+    pub fn extract_file_to_string_cancellable(
         &self,
-        url: &str,
-        max_length: Option<i32>,
+        file_path: &str,
+        token: &CancellationToken,
+    ) -> ExtractResult<(String, Metadata)> {
+        let file_path = file_path.to_string();
+        self.with_cancellation(token, move |extractor| {
+            extractor.extract_file_to_string(&file_path)
+        })
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_string`], cancelled with the token as
+    /// [`Extractor::extract_file_to_string_cancellable`]. The bytes are copied, so that the
+    /// cancelled parse never reads memory the caller has freed.
+    pub fn extract_bytes_to_string_cancellable(
+        &self,
+        buffer: &[u8],
+        token: &CancellationToken,
+    ) -> ExtractResult<(String, Metadata)> {
+        let buffer = buffer.to_vec();
+        self.with_cancellation(token, move |extractor| {
+            extractor.extract_bytes_to_string(&buffer)
+        })
+    }
+
+    /// Same as [`Extractor::extract_url_to_string`], cancelled with the token as
+    /// [`Extractor::extract_file_to_string_cancellable`]
+    pub fn extract_url_to_string_cancellable(
+        &self,
+        url: &str,
+        token: &CancellationToken,
+    ) -> ExtractResult<(String, Metadata)> {
+        let url = url.to_string();
+        self.with_cancellation(token, move |extractor| {
+            extractor.extract_url_to_string(&url)
+        })
+    }
+
+    pub fn extract_url_to_string_opt(
+        &self,
+        url: &str,
+        max_length: Option<i32>,
         as_xml: Option<bool>,
         extract_embedded: Option<bool>,
     ) -> ExtractResult<(String, Metadata)> {
+        if let Some(timeout) = self.timeout {
+            let url = url.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_url_to_string_opt(&url, max_length, as_xml, extract_embedded)
+            });
+        }
         let eff_max_length = max_length.unwrap_or(self.extract_string_max_length);
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         let eff_extract_embedded = extract_embedded.unwrap_or(self.extract_embedded);
+        self.check_robots(url)?;
         tika::parse_url_to_string(
             url,
             eff_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.parse_options,
             eff_as_xml,
             eff_extract_embedded,
         )
+        .map(|(content, mut metadata)| {
+            (
+                self.filter_content(content, &mut metadata, eff_as_xml),
+                self.rename_metadata_keys(metadata),
+            )
+        })
+    }
+
+    /// Returns a session that runs `*_to_string` extractions with the configuration of this
+    /// extractor. The session keeps the calling thread attached to the VM and builds the java
+    /// config objects once, which saves their cost on every call of a tight, high volume loop.
+    /// Later changes to the extractor do not reach sessions created before them.
+    ///
+    /// ```no_run
+    /// use extractous::Extractor;
+    ///
+    /// let extractor = Extractor::new();
+    /// let mut session = extractor.session().unwrap();
+    /// for path in ["a.pdf", "b.docx"] {
+    ///     let (content, _metadata) = session.extract_file_to_string(path).unwrap();
+    ///     println!("{}", content);
+    /// }
+    /// ```
+    pub fn session(&self) -> ExtractResult<ExtractSession<'_>> {
+        let inner = tika::ParseSession::new(
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+        )?;
+        Ok(ExtractSession {
+            extractor: self,
+            inner,
+        })
     }
 
     /// 递归提取文件内容，包括所有嵌套文档
@@ -417,14 +1626,33 @@ impl Extractor {
     /// }
     /// ```
     pub fn extract_file_recursive(&self, file_path: &str) -> ExtractResult<RecursiveExtraction> {
+        if let Some(timeout) = self.timeout {
+            let file_path = file_path.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_file_recursive(&file_path)
+            });
+        }
+        check_input_file(file_path, self.follow_symlinks)?;
         tika::parse_file_recursive(
             file_path,
             self.extract_string_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
             self.xml_output,
         )
+        .map(|extraction| self.filter_extraction(extraction, self.xml_output))
+    }
+
+    /// Same as [`Extractor::extract_file_recursive`], for a path built with [`Path`] or
+    /// [`PathBuf`]. Fails with [`Error::IoError`] if the path is not valid unicode.
+    pub fn extract_path_recursive<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> ExtractResult<RecursiveExtraction> {
+        self.extract_file_recursive(path_to_str(path.as_ref())?)
     }
     pub fn extract_file_recursive_opt(
         &self,
@@ -432,27 +1660,135 @@ impl Extractor {
         max_length: Option<i32>,
         as_xml: Option<bool>,
     ) -> ExtractResult<RecursiveExtraction> {
+        if let Some(timeout) = self.timeout {
+            let file_path = file_path.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_file_recursive_opt(&file_path, max_length, as_xml)
+            });
+        }
         let eff_max_length = max_length.unwrap_or(self.extract_string_max_length);
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
+        check_input_file(file_path, self.follow_symlinks)?;
         tika::parse_file_recursive(
             file_path,
             eff_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
             eff_as_xml,
         )
+        .map(|extraction| self.filter_extraction(extraction, eff_as_xml))
     }
+
+    /// 递归提取文件，并将每个文档（容器文档及所有嵌套文档）写为 `out` 中的一行 JSON，
+    /// 返回写入的行数。
+    ///
+    /// 与 [`Extractor::extract_file_recursive`] 不同，文档在解析完成后立即写出，内存中
+    /// 最多只保留一个文档，适合内存无法容纳的超大归档。每行的格式为：
+    /// ```json
+    /// {"content": "...", "metadata": {"Content-Type": ["..."], ...}, "embedded_path": "/a.zip/b.pdf"}
+    /// ```
+    /// - `metadata` 按键排序，不包含已单独成字段的 `X-TIKA:content`
+    /// - `embedded_path` 为嵌套文档在容器中的路径，容器文档为 `null`
+    /// - 嵌套文档按解析结束的顺序写出，容器文档在其所有嵌套文档之后、最后写出
+    /// - 容器文档写出时嵌套文档已写出，因此不应用 `RecursiveConfig` 的元数据合并策略
+    ///
+    /// 写入是同步的：`out` 写入阻塞时解析也随之暂停。每个文档写完后都会 flush 到 `out`，
+    /// 结束时再 flush `out` 本身。写入失败会中止解析并返回 `IoError`，已写出的行保持完整。
+    pub fn extract_file_recursive_to_ndjson(
+        &self,
+        file_path: &str,
+        out: impl Write,
+    ) -> ExtractResult<usize> {
+        let mut out = BufWriter::new(out);
+        let mut count = 0;
+        let mut sink = |doc: Document| -> ExtractResult<()> {
+            ndjson::write_document(&mut out, &doc)
+                .and_then(|_| out.flush())
+                .map_err(|e| Error::IoError(e.to_string()))?;
+            count += 1;
+            Ok(())
+        };
+        check_input_file(file_path, self.follow_symlinks)?;
+        self.extract_file_recursive_to_sink(file_path, &mut sink)?;
+        out.flush().map_err(|e| Error::IoError(e.to_string()))?;
+        Ok(count)
+    }
+
+    /// 递归提取文件，返回逐个产出文档的迭代器，适合嵌套文档多到无法同时放入内存的文件，
+    /// 如包含数百个附件的邮箱文件。
+    ///
+    /// 文件在后台线程中解析，每个文档解析完成后立即由迭代器返回，解析最多领先迭代器
+    /// 几个文档，因此内存占用与嵌套文档的数量无关：
+    /// - 容器文档最先返回，此时只有解析开始时已知的元数据（`Content-Type`），内容为空；
+    ///   随后嵌套文档按解析结束的顺序返回；最后再返回一次容器文档，带完整的内容和元数据，
+    ///   因为其内容在解析结束时才完整。因此返回的文档数比
+    ///   [`RecursiveExtraction::total_count`] 多一个
+    /// - 不应用 `RecursiveConfig` 的元数据合并策略
+    /// - 解析失败时最后返回该错误，之后迭代结束
+    ///
+    /// 提前丢弃迭代器会在下一个文档完成时中止解析。文件不存在等错误在调用时直接返回。
+    pub fn extract_file_recursive_iter(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<RecursiveDocuments> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        RecursiveDocuments::spawn(self.clone(), file_path)
+    }
+
+    /// 解析开始时的容器文档：只有检测出的 `Content-Type`，内容为空
+    pub(crate) fn recursive_start_document(&self, file_path: &str) -> ExtractResult<Document> {
+        let mime = tika::detect_mime_file(file_path)?;
+        let metadata = Metadata::from([("Content-Type".to_string(), vec![mime])]);
+        let mut doc = Document::new(String::new(), metadata);
+        self.filter_document(&mut doc, self.xml_output);
+        Ok(doc)
+    }
+
+    /// 递归解析文件，每个文档经过过滤后立即交给 sink
+    pub(crate) fn extract_file_recursive_to_sink(
+        &self,
+        file_path: &str,
+        sink: &mut dyn FnMut(Document) -> ExtractResult<()>,
+    ) -> ExtractResult<()> {
+        let mut filtered = |mut doc: Document| -> ExtractResult<()> {
+            self.filter_document(&mut doc, self.xml_output);
+            sink(doc)
+        };
+        tika::parse_file_recursive_to_sink(
+            file_path,
+            self.extract_string_max_length,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
+            self.xml_output,
+            &mut filtered,
+        )
+    }
+
     /// 递归提取字节数组内容，包括所有嵌套文档
     pub fn extract_bytes_recursive(&self, buffer: &[u8]) -> ExtractResult<RecursiveExtraction> {
+        if let Some(timeout) = self.timeout {
+            let buffer = buffer.to_vec();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_bytes_recursive(&buffer)
+            });
+        }
         tika::parse_bytes_recursive(
             buffer,
             self.extract_string_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
             self.xml_output,
         )
+        .map(|extraction| self.filter_extraction(extraction, self.xml_output))
     }
     pub fn extract_bytes_recursive_opt(
         &self,
@@ -460,6 +1796,12 @@ impl Extractor {
         max_length: Option<i32>,
         as_xml: Option<bool>,
     ) -> ExtractResult<RecursiveExtraction> {
+        if let Some(timeout) = self.timeout {
+            let buffer = buffer.to_vec();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_bytes_recursive_opt(&buffer, max_length, as_xml)
+            });
+        }
         let eff_max_length = max_length.unwrap_or(self.extract_string_max_length);
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
         tika::parse_bytes_recursive(
@@ -468,20 +1810,33 @@ impl Extractor {
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
             eff_as_xml,
         )
+        .map(|extraction| self.filter_extraction(extraction, eff_as_xml))
     }
 
     /// 递归提取 URL 内容，包括所有嵌套文档
     pub fn extract_url_recursive(&self, url: &str) -> ExtractResult<RecursiveExtraction> {
+        if let Some(timeout) = self.timeout {
+            let url = url.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_url_recursive(&url)
+            });
+        }
+        self.check_robots(url)?;
         tika::parse_url_recursive(
             url,
             self.extract_string_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
             self.xml_output,
         )
+        .map(|extraction| self.filter_extraction(extraction, self.xml_output))
     }
 
     pub fn extract_url_recursive_opt(
@@ -490,23 +1845,857 @@ impl Extractor {
         max_length: Option<i32>,
         as_xml: Option<bool>,
     ) -> ExtractResult<RecursiveExtraction> {
+        if let Some(timeout) = self.timeout {
+            let url = url.to_string();
+            return self.with_timeout(timeout, move |extractor| {
+                extractor.extract_url_recursive_opt(&url, max_length, as_xml)
+            });
+        }
         let eff_max_length = max_length.unwrap_or(self.extract_string_max_length);
         let eff_as_xml = as_xml.unwrap_or(self.xml_output);
+        self.check_robots(url)?;
         tika::parse_url_recursive(
             url,
             eff_max_length,
             &self.pdf_config,
             &self.office_config,
             &self.ocr_config,
+            &self.recursive_config,
+            &self.parse_options,
             eff_as_xml,
         )
+        .map(|extraction| self.filter_extraction(extraction, eff_as_xml))
+    }
+
+    /// Returns the digital signatures of a PDF or OOXML (docx, xlsx, pptx, ...) file.
+    /// An empty list is returned for unsigned documents and for other formats.
+    ///
+    /// PDF signatures are checked against the signed byte ranges, OOXML signatures are only
+    /// reported, so their `valid` field is `None`.
+    pub fn signature_info(&self, file_path: &str) -> ExtractResult<Vec<SignatureInfo>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::get_signature_info(file_path)
+    }
+
+    /// Extracts a file paragraph by paragraph. The document is parsed on a background thread
+    /// and the text of each paragraph is yielded as soon as the parser has produced it, so huge
+    /// documents can be processed with little memory. Paragraphs are delimited by the block
+    /// elements (`<p>`, headings, list items, table cells, ...) of the XHTML parser output.
+    ///
+    /// Dropping the iterator early discards the remaining paragraphs, but the parse still runs to
+    /// the end in the background. Use [`Paragraphs::finish`] to get the metadata of the document.
+    pub fn extract_file_paragraphs(&self, file_path: &str) -> ExtractResult<Paragraphs> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        Paragraphs::spawn(self.clone(), file_path)
+    }
+
+    /// Returns the sections of a file following its heading hierarchy: each heading (`h1` to `h6`
+    /// of the XHTML parser output) starts a section holding its content and, as subsections, the
+    /// sections of the lower level headings that follow it. Content that precedes the first
+    /// heading is returned as a leading section without heading, so a document without headings
+    /// yields a single section.
+    pub fn extract_file_sections(&self, file_path: &str) -> ExtractResult<Vec<Section>> {
+        sections::extract_file_sections(self, file_path)
+    }
+
+    /// Returns the tables of a file (`table` elements of the XHTML parser output), in document
+    /// order, with the text of their cells. The header row is taken from the `th` cells or the
+    /// `thead` of a table when it has them. Otherwise the first row is taken as the header when it
+    /// holds distinct non-numeric names above a column of numbers, as in spreadsheets and CSV
+    /// files. The cells of a nested table belong to that table only.
+    pub fn extract_file_tables(&self, file_path: &str) -> ExtractResult<Vec<Table>> {
+        tables::extract_file_tables(self, file_path)
+    }
+
+    /// Returns the sentences of the text of a file, as extracted by
+    /// [`Extractor::extract_file_to_string`], with their byte offsets in that text. Sentences are
+    /// split on the Unicode sentence boundaries (UAX #29), adjusted for extracted text: blank lines
+    /// end a sentence, lines wrapped in the middle of a sentence are joined, and common
+    /// abbreviations such as `Dr.` or `e.g.` do not end one.
+    pub fn extract_file_sentences(&self, file_path: &str) -> ExtractResult<Vec<Sentence>> {
+        let (content, _metadata) = self.extract_file_to_string(file_path)?;
+        Ok(split_sentences(&content))
+    }
+
+    /// Returns the OCR text of each image of the document separately, with the page it is drawn
+    /// on, instead of merged into the body, so that the text of a figure can be attached to its
+    /// caption. The images of PDF documents are extracted as embedded documents and the pages
+    /// themselves are not OCRed, whatever the PDF OCR strategy is. Other formats report their
+    /// embedded images, without page. Images that the OCR parser skips, such as those smaller
+    /// than its minimum size, are not reported. Requires tesseract, like any OCR.
+    pub fn extract_image_ocr(&self, file_path: &str) -> ExtractResult<Vec<ImageOcr>> {
+        let pdf_config = self
+            .pdf_config
+            .clone()
+            .set_ocr_strategy(PdfOcrStrategy::NO_OCR)
+            .set_extract_inline_images(true);
+        let extractor = self
+            .clone()
+            .set_pdf_config(pdf_config)
+            .set_extract_embedded(true);
+        image_ocr::extract_image_ocr(&extractor, file_path)
+    }
+
+    /// Extracts the files to string, as [`Extractor::extract_file_to_string`] does, on a pool of
+    /// worker threads, one per available CPU, and returns a receiver of the `(path, result)` of
+    /// each file, sent as soon as its extraction is done. The results come in the order the
+    /// extractions finish, not in the order of `file_paths`, so the fast documents can be processed
+    /// while the slow ones are still being parsed. Every file gets exactly one result: a file that
+    /// fails, or whose extraction panics, only fails its own result. The receiver is closed once
+    /// all the files are done.
+    ///
+    /// Returns right away, the extractions run in the background. Dropping the receiver stops the
+    /// workers once their current file is done.
+    pub fn extract_files_streaming(&self, file_paths: &[&str]) -> Receiver<FileResult> {
+        batch::extract_files_streaming(self, file_paths, batch::available_workers())
+    }
+
+    /// Same as [`Extractor::extract_files_streaming`], on `workers` worker threads instead of
+    /// one per available CPU. A count of 0 starts a single worker.
+    pub fn extract_files_streaming_with_workers(
+        &self,
+        file_paths: &[&str],
+        workers: usize,
+    ) -> Receiver<FileResult> {
+        batch::extract_files_streaming(self, file_paths, workers)
+    }
+
+    /// Extracts the files to string, as [`Extractor::extract_file_to_string`] does, on a pool of
+    /// worker threads, one per available CPU, and returns their results in the order of
+    /// `file_paths` once all of them are done. A file that fails, or whose extraction panics,
+    /// only fails its own result. See [`Extractor::extract_files_streaming`] to get each result
+    /// as soon as it is done.
+    ///
+    /// Each worker attaches itself to the shared VM isolate. The isolate accepts any number of
+    /// attached threads and runs their parses in parallel, so the pool is bounded by the CPUs
+    /// rather than by the VM: the parses are CPU bound, and each one keeps its document in the
+    /// isolate heap while it runs. Running more batches, or other extractions, at the same time
+    /// is safe but adds to that heap. OCR stays bounded separately by
+    /// [`crate::set_max_ocr_concurrency`].
+    pub fn extract_files_to_string(
+        &self,
+        file_paths: &[&str],
+    ) -> Vec<ExtractResult<(String, Metadata)>> {
+        batch::extract_files_to_string(self, file_paths)
+    }
+
+    /// Extracts the files, in order, and returns their content and metadata as a record batch
+    /// of the [`crate::arrow_schema`], one row per file. The first file that fails to extract
+    /// fails the whole batch.
+    #[cfg(feature = "arrow")]
+    pub fn extract_files_to_arrow(
+        &self,
+        file_paths: &[&str],
+    ) -> ExtractResult<arrow_array::RecordBatch> {
+        let results = file_paths
+            .iter()
+            .map(|file_path| self.extract_file_to_string(file_path))
+            .collect::<ExtractResult<Vec<_>>>()?;
+        Ok(crate::to_arrow(&results))
+    }
+
+    /// Extracts the file and returns its content, metadata and detected type as a single JSON
+    /// object, `{"content": ..., "metadata": {...}, "content_type": ...}`, a quick way to a JSON
+    /// record for scripts and logs. The content is the one of
+    /// [`Extractor::extract_file_to_string`], the metadata values are arrays of strings and
+    /// `content_type` is the first `Content-Type` value, or null when none was reported.
+    #[cfg(feature = "serde")]
+    pub fn extract_file_to_json(&self, file_path: &str) -> ExtractResult<serde_json::Value> {
+        let (content, metadata) = self.extract_file_to_string(file_path)?;
+        Ok(crate::json::to_json(content, &metadata))
+    }
+
+    /// Same as [`Extractor::extract_file`], without blocking the async runtime: the extraction
+    /// runs on the blocking thread pool of tokio, with [`tokio::task::spawn_blocking`], and the
+    /// stream is an [`AsyncRead`](tokio::io::AsyncRead). Must be awaited within a tokio runtime.
+    ///
+    /// The VM is used from whatever pool thread picks the work up. Every call, and every chunk
+    /// read from the stream, attaches its thread to the VM for its duration and detaches it
+    /// afterwards, so the pool threads keep no VM state between calls, at the cost of attaching
+    /// each time. The pool grows up to its limit, 512 threads by default, so it is the
+    /// concurrency of the callers, or a semaphore, that bounds the extractions running at once.
+    #[cfg(feature = "async")]
+    pub async fn extract_file_async(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(crate::AsyncStreamReader, Metadata)> {
+        let file_path = file_path.to_string();
+        crate::async_extract::run_blocking(self, move |extractor| {
+            extractor.extract_file(&file_path)
+        })
+        .await
+        .map(|(reader, metadata)| (reader.into(), metadata))
+    }
+
+    /// Same as [`Extractor::extract_bytes`], without blocking the async runtime, see
+    /// [`Extractor::extract_file_async`]. The buffer is shared with the stream as with
+    /// [`Extractor::extract_bytes_with_source`].
+    #[cfg(feature = "async")]
+    pub async fn extract_bytes_async(
+        &self,
+        buffer: impl Into<Arc<[u8]>>,
+    ) -> ExtractResult<(crate::AsyncStreamReader, Metadata)> {
+        let buffer = buffer.into();
+        crate::async_extract::run_blocking(self, move |extractor| {
+            extractor.extract_bytes_with_source(buffer)
+        })
+        .await
+        .map(|(reader, metadata, _)| (reader.into(), metadata))
+    }
+
+    /// Same as [`Extractor::extract_url`], without blocking the async runtime, see
+    /// [`Extractor::extract_file_async`]
+    #[cfg(feature = "async")]
+    pub async fn extract_url_async(
+        &self,
+        url: &str,
+    ) -> ExtractResult<(crate::AsyncStreamReader, Metadata)> {
+        let url = url.to_string();
+        crate::async_extract::run_blocking(self, move |extractor| extractor.extract_url(&url))
+            .await
+            .map(|(reader, metadata)| (reader.into(), metadata))
+    }
+
+    /// Same as [`Extractor::extract_file_to_string`], without blocking the async runtime, see
+    /// [`Extractor::extract_file_async`]
+    #[cfg(feature = "async")]
+    pub async fn extract_file_to_string_async(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(String, Metadata)> {
+        let file_path = file_path.to_string();
+        crate::async_extract::run_blocking(self, move |extractor| {
+            extractor.extract_file_to_string(&file_path)
+        })
+        .await
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_string`], without blocking the async runtime, see
+    /// [`Extractor::extract_file_async`]
+    #[cfg(feature = "async")]
+    pub async fn extract_bytes_to_string_async(
+        &self,
+        buffer: impl Into<Arc<[u8]>>,
+    ) -> ExtractResult<(String, Metadata)> {
+        let buffer = buffer.into();
+        crate::async_extract::run_blocking(self, move |extractor| {
+            extractor.extract_bytes_to_string(&buffer)
+        })
+        .await
+    }
+
+    /// Same as [`Extractor::extract_url_to_string`], without blocking the async runtime, see
+    /// [`Extractor::extract_file_async`]
+    #[cfg(feature = "async")]
+    pub async fn extract_url_to_string_async(
+        &self,
+        url: &str,
+    ) -> ExtractResult<(String, Metadata)> {
+        let url = url.to_string();
+        crate::async_extract::run_blocking(self, move |extractor| {
+            extractor.extract_url_to_string(&url)
+        })
+        .await
+    }
+
+    /// Returns the media type of the file, such as `application/pdf`, without parsing it. Only
+    /// the detectors run: the magic bytes at the start of the file, the structure of the
+    /// container formats such as the ZIP based office documents, and the file extension, which
+    /// decides when the content alone is not conclusive.
+    pub fn detect_mime_file(&self, file_path: &str) -> ExtractResult<String> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::detect_mime_file(file_path)
+    }
+
+    /// Returns the media type of the bytes, such as `application/pdf`, without parsing them.
+    /// Only the content is looked at, there is no file name to fall back on. The buffer may be
+    /// the first few kilobytes of a document only: the magic bytes are at its start, so most
+    /// formats are detected the same. The ZIP based formats are told apart by their entries,
+    /// so a truncated docx or xlsx may come out as `application/zip`.
+    pub fn detect_mime_bytes(&self, buffer: &[u8]) -> ExtractResult<String> {
+        tika::detect_mime_bytes(buffer)
+    }
+
+    /// Returns the language the text is written in, detected by the Optimaize detector bundled
+    /// with Tika, which knows about 70 languages. Only the first 20 000 characters are looked
+    /// at. Short texts, such as a title or a single sentence, are detected with a lower
+    /// confidence and are more often wrong. The language profiles are loaded on the first call,
+    /// which takes a moment, and the detections of the process run one at a time.
+    pub fn detect_language(&self, text: &str) -> ExtractResult<LanguageResult> {
+        tika::detect_language(language_sample(text))
+    }
+
+    /// Returns the media types the given bytes could be, as `(mime type, confidence)` pairs
+    /// ordered by decreasing confidence. The first entry is the type Tika detects. Its confidence
+    /// is 1.0 unless the magic bytes also match unrelated types, which are then listed as lower
+    /// confidence alternatives. Generic supertypes of the detected type, such as
+    /// `application/zip` for a docx file, are listed as well. Only the content is looked at, no
+    /// file name hint is available.
+    pub fn detect_mime_candidates(&self, buffer: &[u8]) -> ExtractResult<Vec<(String, f32)>> {
+        tika::detect_mime_candidates(buffer)
+    }
+
+    /// Returns the words of a PDF file with their bounding boxes, see [`WordBox`] for the
+    /// coordinate system. This is meant to build a text layer aligned to the rendered pages and is
+    /// heavier than a plain extraction. No OCR is done, so scanned pages without a text layer
+    /// yield no words. Files that are not PDF documents return a `ParseError`.
+    pub fn extract_pdf_word_boxes(&self, file_path: &str) -> ExtractResult<Vec<WordBox>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::get_pdf_word_boxes(file_path)
+    }
+
+    /// Returns the fonts referenced by a PDF file, with their subtype and whether they are
+    /// embedded. They are read from the resource dictionaries of the pages, of the forms and Type3
+    /// fonts they use, and of the appearance streams of their annotations. A font shared by several
+    /// pages is listed once, in the order it is first found. Files that are not PDF documents
+    /// return a `ParseError`.
+    pub fn list_fonts(&self, file_path: &str) -> ExtractResult<Vec<FontInfo>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::list_fonts(file_path)
+    }
+
+    /// Returns the PDF/A conformance level a PDF file declares in its XMP metadata and whether
+    /// it is tagged for accessibility, see [`PdfConformance`]. The declarations are reported as
+    /// they are, the document is not validated against them: a tagged document may still have
+    /// incomplete tags. Files that are not PDF documents return a `ParseError`.
+    pub fn pdf_conformance_info(&self, file_path: &str) -> ExtractResult<PdfConformance> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::pdf_conformance_info(file_path)
+    }
+
+    /// Returns the contacts of a vCard (.vcf) file with their name, email addresses and phone
+    /// numbers. The card is read directly, without going through the parsers, so the extractor
+    /// configuration does not apply. Files without any `BEGIN:VCARD` block yield no contacts.
+    pub fn extract_vcard(&self, file_path: &str) -> ExtractResult<Vec<Contact>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        let text = structured::read_text(file_path)?;
+        Ok(structured::parse_vcard(&text))
+    }
+
+    /// Returns the events of an iCalendar (.ics) file with their summary, start, end and
+    /// location. Like [`Extractor::extract_vcard`], the file is read directly and the extractor
+    /// configuration does not apply.
+    pub fn extract_icalendar(&self, file_path: &str) -> ExtractResult<Vec<CalendarEvent>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        let text = structured::read_text(file_path)?;
+        Ok(structured::parse_icalendar(&text))
+    }
+
+    /// Returns the messages of a chat export with their sender, timestamp and text, see
+    /// [`ChatFormat`] for the recognized formats. Like [`Extractor::extract_vcard`], exports
+    /// are read directly. Other files are extracted with the extractor configuration and
+    /// returned as plain text, without messages.
+    pub fn extract_chat(&self, file_path: &str) -> ExtractResult<Chat> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        let text = structured::read_text(file_path)?;
+        if let Some((format, messages)) = chat::parse_chat(&text) {
+            return Ok(Chat {
+                format: Some(format),
+                messages,
+                text: String::new(),
+            });
+        }
+        let (text, _metadata) = self.extract_file_to_string(file_path)?;
+        Ok(Chat {
+            format: None,
+            messages: Vec::new(),
+            text,
+        })
+    }
+
+    /// Returns the core and extended properties of an Office document (doc, xls, ppt, docx,
+    /// xlsx, pptx, ...) as typed fields, see [`OfficeProperties`]. The file is parsed with the
+    /// extractor configuration, embedded documents left aside. Other formats return a
+    /// `ParseError`.
+    pub fn extract_office_properties(&self, file_path: &str) -> ExtractResult<OfficeProperties> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        // The metadata as reported by the parsers, before the renaming of the keys
+        let (_content, metadata) = tika::parse_file_to_string(
+            file_path,
+            self.extract_string_max_length,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            false,
+            false,
+        )?;
+        office_properties::office_properties(&metadata)
+    }
+
+    /// Returns the revision history an Office file keeps apart from its content, in the order the
+    /// file records them:
+    /// - the save history of Word 97-2003 (.doc) documents, with the user and the path of each of
+    ///   the last saves
+    /// - the revision headers of Excel shared workbooks, with the user and the time of each
+    ///   revision
+    ///
+    /// The other Office formats keep no such history and return an empty list, as do documents
+    /// whose history was not kept. Neither format records a comment with its revisions. The
+    /// tracked changes of the content are not revisions in this sense. Files that are not Office
+    /// documents return a `ParseError`.
+    pub fn extract_revisions(&self, file_path: &str) -> ExtractResult<Vec<Revision>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::extract_revisions(file_path)
+    }
+
+    /// Writes the raw bytes of the documents embedded in a file, such as the attachments of an
+    /// email or the entries of an archive, to `out_dir`, which is created if needed, and returns
+    /// the paths written, in the order the documents were found. The documents embedded in an
+    /// embedded document are written too, all in `out_dir`. The text is not extracted, so this
+    /// holds no document in memory.
+    ///
+    /// Each file is named after the resource name of its document. Only the last component of
+    /// the name is used, so that names such as `../../etc/passwd` from a malicious archive stay
+    /// in `out_dir`, and the characters file systems reject are replaced by `_`. A name already
+    /// taken, by another document or a file that was there before, gets a `_1`, `_2`, ...
+    /// suffix: no existing file is overwritten. Documents without a name are written as
+    /// `embedded-<n>` with the extension of their detected type.
+    ///
+    /// An error stops the extraction, the files already written are left in place.
+    pub fn extract_file_embedded_to_dir(
+        &self,
+        file_path: &str,
+        out_dir: &Path,
+    ) -> ExtractResult<Vec<PathBuf>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        fs::create_dir_all(out_dir).map_err(|e| {
+            Error::IoError(format!("Could not create {}: {}", out_dir.display(), e))
+        })?;
+
+        let mut names = EmbeddedFileNames::new(out_dir);
+        let mut written = Vec::new();
+        let mut target = |name: Option<&str>, extension: &str| -> ExtractResult<PathBuf> {
+            let path = names.create(name, extension)?;
+            written.push(path.clone());
+            Ok(path)
+        };
+        tika::extract_file_embedded_to_dir(
+            file_path,
+            &self.pdf_config,
+            &self.office_config,
+            &self.parse_options,
+            &mut target,
+        )?;
+        Ok(written)
+    }
+
+    /// Returns the names of the streams of an OLE compound document, such as the Word, Excel and
+    /// PowerPoint 97-2003 files and the Outlook messages, storage by storage in the order of its
+    /// directory. The names are as stored, control characters included, e.g. `WordDocument` or
+    /// `\u{5}SummaryInformation`. The streams nested in storages are named by their path, the
+    /// names of the storages and of the stream joined with `/`, which OLE names can not contain.
+    /// Files that are not OLE compound documents return a `ParseError`.
+    pub fn list_ole_streams(&self, file_path: &str) -> ExtractResult<Vec<String>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::list_ole_streams(file_path)
+    }
+
+    /// Returns the raw bytes of a stream of an OLE compound document, named as by
+    /// [`Extractor::list_ole_streams`]. Files that are not OLE compound documents and names of
+    /// no stream of the document return a `ParseError`.
+    pub fn get_ole_stream(&self, file_path: &str, name: &str) -> ExtractResult<Vec<u8>> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::get_ole_stream(file_path, name)
+    }
+
+    /// Splits an email message (RFC822, `.eml`) into its bodies and attachments instead of
+    /// flattening it into one text. The nested multipart bodies are followed: the first inline
+    /// `text/plain` and `text/html` parts are the bodies, which for a `multipart/alternative` are
+    /// the two renditions of the same text, and every other part is an attachment. Each
+    /// attachment is extracted from its decoded bytes with the extractor configuration, its file
+    /// name in `resourceName`. An attachment that fails to parse is returned without content,
+    /// with the error in `X-TIKA:EXCEPTION:embedded_exception` and its declared `Content-Type`.
+    /// Files that are not email messages return a `ParseError`.
+    pub fn extract_mime_message(&self, file_path: &str) -> ExtractResult<MimeMessage> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        let parts = tika::split_mime_message(file_path)?;
+        let attachments = parts
+            .attachments
+            .into_iter()
+            .map(|part| self.extract_mime_part(part))
+            .collect();
+        Ok(MimeMessage {
+            text_plain: parts.text_plain,
+            text_html: parts.text_html,
+            attachments,
+        })
+    }
+
+    /// Parses a file and drives the given handler with the XHTML SAX events produced by the
+    /// parser, as they happen. Returns the metadata of the document.
+    ///
+    /// The events are delivered synchronously on the calling thread. If the handler panics the
+    /// parse is aborted and the panic is resumed once the parser has returned.
+    pub fn extract_file_with_handler<H: ContentHandler>(
+        &self,
+        file_path: &str,
+        handler: &mut H,
+    ) -> ExtractResult<Metadata> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::parse_file_with_handler(
+            file_path,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            self.extract_embedded,
+            handler,
+        )
+        .map(|metadata| self.rename_metadata_keys(metadata))
+    }
+
+    /// Extracts the metadata of a file only. The parser drops the content as it goes and OCR is
+    /// skipped, which makes it much faster than [`Extractor::extract_file_to_string`] on large
+    /// documents when the text is not needed.
+    pub fn extract_file_metadata(&self, file_path: &str) -> ExtractResult<Metadata> {
+        check_input_file(file_path, self.follow_symlinks)?;
+        tika::parse_file_metadata(
+            file_path,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            self.extract_embedded,
+        )
+        .map(|metadata| self.rename_metadata_keys(metadata))
+    }
+
+    /// Same as [`Extractor::extract_file_metadata`] for a byte buffer
+    pub fn extract_bytes_metadata(&self, buffer: &[u8]) -> ExtractResult<Metadata> {
+        tika::parse_bytes_metadata(
+            buffer,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            self.extract_embedded,
+        )
+        .map(|metadata| self.rename_metadata_keys(metadata))
+    }
+
+    /// Same as [`Extractor::extract_file_metadata`] for a URL
+    pub fn extract_url_metadata(&self, url: &str) -> ExtractResult<Metadata> {
+        self.check_robots(url)?;
+        tika::parse_url_metadata(
+            url,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            self.extract_embedded,
+        )
+        .map(|metadata| self.rename_metadata_keys(metadata))
+    }
+
+    /// Checks the assembled configuration for settings that contradict each other or have no
+    /// effect, which would otherwise silently produce surprising output. Nothing is extracted,
+    /// so this is cheap to call right after building the extractor.
+    pub fn validate(&self) -> Result<(), Vec<ConfigWarning>> {
+        let mut warnings = Vec::new();
+
+        if self.pdf_config.ocr_strategy == PdfOcrStrategy::NO_OCR
+            && self.ocr_config != TesseractOcrConfig::default()
+        {
+            warnings.push(ConfigWarning::OcrConfigUnusedForPdf);
+        }
+        if self.pdf_config.extract_unique_inline_images_only
+            && !self.pdf_config.extract_inline_images
+        {
+            warnings.push(ConfigWarning::UniqueInlineImagesWithoutInlineImages);
+        }
+
+        if self.script_filter.is_some() && self.xml_output {
+            warnings.push(ConfigWarning::ScriptFilterWithXmlOutput);
+        }
+        if self.script_filter.is_none()
+            && self.script_filter_threshold != DEFAULT_SCRIPT_FILTER_THRESHOLD
+        {
+            warnings.push(ConfigWarning::ScriptFilterThresholdWithoutFilter);
+        }
+
+        let per_document = self.recursive_config.per_document_max_length;
+        let total = self.extract_string_max_length;
+        if per_document >= 0 && total >= 0 && per_document >= total {
+            warnings.push(ConfigWarning::PerDocumentMaxLengthNotLower {
+                per_document,
+                total,
+            });
+        }
+        if self.recursive_config.max_container_depth == 0
+            && !self.recursive_config.extract_leaf_resources
+        {
+            warnings.push(ConfigWarning::NoEmbeddedDocuments);
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+
+    fn extract_mime_part(&self, part: MimePart) -> Document {
+        let mut doc = match tika::parse_bytes_to_string(
+            &part.data,
+            self.extract_string_max_length,
+            &self.pdf_config,
+            &self.office_config,
+            &self.ocr_config,
+            &self.parse_options,
+            self.xml_output,
+            self.extract_embedded,
+        ) {
+            Ok((content, metadata)) => Document::new(content, metadata),
+            Err(e) => {
+                let mut metadata = Metadata::new();
+                metadata.insert("Content-Type".to_string(), vec![part.mime_type]);
+                metadata.insert(
+                    "X-TIKA:EXCEPTION:embedded_exception".to_string(),
+                    vec![e.to_string()],
+                );
+                Document::new(String::new(), metadata)
+            }
+        };
+        if let Some(name) = part.name {
+            doc.metadata
+                .entry("resourceName".to_string())
+                .or_insert_with(|| vec![name]);
+        }
+        self.filter_document(&mut doc, self.xml_output);
+        doc
+    }
+
+    fn filter_content(&self, content: String, metadata: &mut Metadata, as_xml: bool) -> String {
+        if as_xml {
+            return content;
+        }
+        // The script filter keeps the line breaks of the text it removes
+        let content = match self.script_filter {
+            Some(script) => filter_script(&content, script, self.script_filter_threshold),
+            None => content,
+        };
+        // Numbered before the repeated lines are dropped, so that the numbers stay those of the
+        // source. The statistics below are computed on the text without them.
+        let numbered = (self.emit_line_numbers && line_numbers::is_plain_text(metadata))
+            .then(|| line_numbers::number_lines(&content));
+        let dedup = |text: String| {
+            if self.parse_options.dedup_repeated_lines {
+                repeated_lines::dedup_repeated_lines(&text)
+            } else {
+                text
+            }
+        };
+        let content = dedup(content);
+        if self.detect_script {
+            if let Some(script) = dominant_script(&content) {
+                metadata.insert(DOMINANT_SCRIPT_KEY.to_string(), vec![script.to_string()]);
+            }
+        }
+        if self.detect_language {
+            // Best effort, a failed detection leaves the metadata as it is
+            if let Ok(result) = tika::detect_language(language_sample(&content)) {
+                if !result.language.is_empty() {
+                    metadata.insert(LANGUAGE_KEY.to_string(), vec![result.language]);
+                }
+            }
+        }
+        if self.compute_simhash {
+            metadata.insert(
+                SIMHASH_KEY.to_string(),
+                vec![format_simhash(simhash(&content))],
+            );
+        }
+        numbered.map_or(content, dedup)
+    }
+
+    fn filter_extraction(
+        &self,
+        mut extraction: RecursiveExtraction,
+        as_xml: bool,
+    ) -> RecursiveExtraction {
+        extraction.merge_embedded_metadata(self.recursive_config.merge_embedded_metadata);
+        for doc in extraction.documents.iter_mut() {
+            self.filter_document(doc, as_xml);
+        }
+        extraction
+    }
+
+    fn filter_document(&self, doc: &mut Document, as_xml: bool) {
+        // The source of a macro module is kept verbatim, see OfficeParserConfig::set_extract_macros
+        let is_macro = first_value(doc, EMBEDDED_RESOURCE_TYPE_KEY) == Some("MACRO");
+        if is_macro {
+            doc.metadata.insert(
+                "Content-Type".to_string(),
+                vec![MACRO_CONTENT_TYPE.to_string()],
+            );
+        }
+        let filters_content = self.script_filter.is_some()
+            || self.emit_line_numbers
+            || self.detect_script
+            || self.detect_language
+            || self.compute_simhash
+            || self.parse_options.dedup_repeated_lines;
+        if filters_content && !as_xml && !is_macro {
+            let content = std::mem::take(&mut doc.content);
+            doc.content = self.filter_content(content, &mut doc.metadata, as_xml);
+        }
+        if self.extract_entities && !as_xml {
+            doc.entities = find_entities(&doc.content);
+        }
+        doc.metadata = self.rename_metadata_keys(std::mem::take(&mut doc.metadata));
+    }
+
+    /// Renames the keys of both the metadata returned with a stream and the copy the stream keeps
+    fn rename_stream_metadata_keys(
+        &self,
+        (mut reader, metadata): (StreamReader, Metadata),
+    ) -> (StreamReader, Metadata) {
+        let metadata = self.rename_metadata_keys(metadata);
+        if !self.metadata_key_map.is_empty() {
+            reader.metadata = metadata.clone();
+        }
+        (reader, metadata)
+    }
+
+    fn rename_metadata_keys(&self, mut metadata: Metadata) -> Metadata {
+        if self.metadata_key_map.is_empty() {
+            return metadata;
+        }
+        // Taken out first so that a key renamed to another renamed key, or two keys swapped,
+        // do not pick up each other's values
+        let renamed: Vec<(&String, Vec<String>)> = self
+            .metadata_key_map
+            .iter()
+            .filter_map(|(from, to)| metadata.remove(from).map(|values| (to, values)))
+            .collect();
+        for (to, values) in renamed {
+            metadata.entry(to.clone()).or_default().extend(values);
+        }
+        metadata
+    }
+
+    /// Fails with [`Error::DisallowedByRobots`] when the robots.txt of the site disallows the
+    /// url for the user agent, see [`UrlFetchConfig::set_respect_robots`]
+    fn check_robots(&self, url: &str) -> ExtractResult<()> {
+        let config = &self.parse_options.url_fetch;
+        if !config.respect_robots {
+            return Ok(());
+        }
+        let user_agent = config.user_agent.as_deref();
+        let robots_txt = tika::fetch_robots_txt(url, user_agent)?;
+        if robots::is_allowed(&robots_txt, user_agent, url) {
+            Ok(())
+        } else {
+            Err(Error::DisallowedByRobots(url.to_string()))
+        }
+    }
+
+    /// Runs the extraction on a worker thread with a copy of this extractor that has no
+    /// timeout, until the token is cancelled or the timeout of the extractor elapses
+    fn with_cancellation<T, F>(&self, token: &CancellationToken, extract: F) -> ExtractResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Extractor) -> ExtractResult<T> + Send + 'static,
+    {
+        let mut extractor = self.clone();
+        let timeout = extractor.timeout.take();
+        timeout::run_interruptible(timeout, Some(token), move || extract(&extractor))
+    }
+
+    /// Runs the extraction on a worker thread with a copy of this extractor that has no
+    /// timeout, see [`Extractor::set_timeout`]
+    fn with_timeout<T, F>(&self, timeout: Duration, extract: F) -> ExtractResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Extractor) -> ExtractResult<T> + Send + 'static,
+    {
+        let mut extractor = self.clone();
+        extractor.timeout = None;
+        timeout::run_with_timeout(timeout, move || extract(&extractor))
+    }
+}
+
+/// Runs extractions on the thread that created it, see [`Extractor::session`]. The methods behave
+/// like their [`Extractor`] counterparts.
+///
+/// A session holds the attachment of its thread to the VM, so it is neither `Send` nor `Sync`:
+/// create one session per thread.
+/// ```compile_fail
+/// fn assert_send<T: Send>() {}
+/// assert_send::<extractous::ExtractSession>();
+/// ```
+pub struct ExtractSession<'e> {
+    extractor: &'e Extractor,
+    inner: tika::ParseSession,
+}
+
+impl ExtractSession<'_> {
+    /// Same as [`Extractor::extract_file_to_string`]
+    pub fn extract_file_to_string(&mut self, file_path: &str) -> ExtractResult<(String, Metadata)> {
+        let extractor = self.extractor;
+        check_input_file(file_path, extractor.follow_symlinks)?;
+        self.inner
+            .parse_file_to_string(
+                file_path,
+                extractor.extract_string_max_length,
+                extractor.xml_output,
+                extractor.extract_embedded,
+            )
+            .map(|(content, mut metadata)| {
+                let content =
+                    extractor.filter_content(content, &mut metadata, extractor.xml_output);
+                (content, extractor.rename_metadata_keys(metadata))
+            })
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_string`]
+    pub fn extract_bytes_to_string(&mut self, buffer: &[u8]) -> ExtractResult<(String, Metadata)> {
+        let extractor = self.extractor;
+        self.inner
+            .parse_bytes_to_string(
+                buffer,
+                extractor.extract_string_max_length,
+                extractor.xml_output,
+                extractor.extract_embedded,
+            )
+            .map(|(content, mut metadata)| {
+                let content =
+                    extractor.filter_content(content, &mut metadata, extractor.xml_output);
+                (content, extractor.rename_metadata_keys(metadata))
+            })
+    }
+
+    /// Same as [`Extractor::extract_url_to_string`]
+    pub fn extract_url_to_string(&mut self, url: &str) -> ExtractResult<(String, Metadata)> {
+        let extractor = self.extractor;
+        extractor.check_robots(url)?;
+        self.inner
+            .parse_url_to_string(
+                url,
+                extractor.extract_string_max_length,
+                extractor.xml_output,
+                extractor.extract_embedded,
+            )
+            .map(|(content, mut metadata)| {
+                let content =
+                    extractor.filter_content(content, &mut metadata, extractor.xml_output);
+                (content, extractor.rename_metadata_keys(metadata))
+            })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::StreamReader;
-    use crate::Extractor;
+    use super::{
+        ConfigWarning, Document, Metadata, MetadataExt, RecursiveExtraction, StreamReader,
+        EMBEDDED_ID_PATH_KEY, EMBEDDED_RESOURCE_PATH_KEY, EMBEDDED_RESOURCE_TYPE_KEY,
+    };
+    use crate::{
+        Extractor, MergePolicy, PdfOcrStrategy, PdfParserConfig, RecursiveConfig, Script,
+        TesseractOcrConfig,
+    };
+    use std::collections::HashMap;
     use std::fs::File;
     use std::io::BufReader;
     use std::io::{self, Read};
@@ -616,4 +2805,205 @@ mod tests {
         assert!(content.len() > 0);
         assert!(metadata.len() > 0);
     }
+
+    #[test]
+    fn metadata_sorted_entries_test() {
+        let mut metadata = Metadata::new();
+        for key in ["dc:title", "Content-Type", "X-TIKA:Parsed-By", "author"] {
+            metadata.insert(key.to_string(), vec![key.to_uppercase()]);
+        }
+
+        let keys: Vec<&str> = metadata
+            .sorted_entries()
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            ["Content-Type", "X-TIKA:Parsed-By", "author", "dc:title"]
+        );
+
+        let sorted = metadata.to_sorted();
+        assert!(sorted.keys().map(String::as_str).eq(keys));
+        assert_eq!(sorted["author"], vec!["AUTHOR".to_string()]);
+    }
+
+    #[test]
+    fn validate_default_config_test() {
+        assert_eq!(Extractor::new().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_conflicting_config_test() {
+        let extractor = Extractor::new()
+            .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::NO_OCR))
+            .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
+            .set_xml_output(true)
+            .set_script_filter(Script::Latin)
+            .set_extract_string_max_length(1000)
+            .set_recursive_config(RecursiveConfig::new().set_per_document_max_length(5000));
+
+        assert_eq!(
+            extractor.validate(),
+            Err(vec![
+                ConfigWarning::OcrConfigUnusedForPdf,
+                ConfigWarning::ScriptFilterWithXmlOutput,
+                ConfigWarning::PerDocumentMaxLengthNotLower {
+                    per_document: 5000,
+                    total: 1000
+                },
+            ])
+        );
+    }
+
+    fn merge_test_extraction() -> RecursiveExtraction {
+        let doc = |entries: &[(&str, &str)]| {
+            let metadata: Metadata = entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+                .collect();
+            Document::new(String::new(), metadata)
+        };
+        RecursiveExtraction::new(vec![
+            doc(&[("Content-Type", "application/zip")]),
+            doc(&[
+                ("Content-Type", "application/pdf"),
+                ("dcterms:created", "2024-03-01T00:00:00Z"),
+                ("dc:creator", "Alice"),
+            ]),
+            doc(&[
+                ("Content-Type", "text/plain"),
+                ("dcterms:created", "2023-01-01T00:00:00Z"),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn merge_embedded_metadata_test() {
+        let mut extraction = merge_test_extraction();
+        extraction.merge_embedded_metadata(MergePolicy::None);
+        assert_eq!(extraction.documents[0].metadata.len(), 1);
+
+        let mut extraction = merge_test_extraction();
+        extraction.merge_embedded_metadata(MergePolicy::FirstWins);
+        let container = &extraction.documents[0].metadata;
+        assert_eq!(container["Content-Type"], vec!["application/zip"]);
+        assert_eq!(container["dcterms:created"], vec!["2024-03-01T00:00:00Z"]);
+        assert_eq!(container["dc:creator"], vec!["Alice"]);
+
+        let mut extraction = merge_test_extraction();
+        extraction.merge_embedded_metadata(MergePolicy::Collect);
+        let container = &extraction.documents[0].metadata;
+        assert_eq!(container["Content-Type"], vec!["application/zip"]);
+        assert_eq!(
+            container["dcterms:created"],
+            vec!["2024-03-01T00:00:00Z", "2023-01-01T00:00:00Z"]
+        );
+        // The embedded documents are left untouched
+        assert_eq!(extraction.documents[2].metadata.len(), 2);
+    }
+
+    #[test]
+    fn filter_extraction_keeps_macros_verbatim_test() {
+        // Latin code under a Cyrillic script filter, which would remove it from other documents
+        let source = "Attribute VB_Name = \"Module1\"\r\n\
+                      Sub AutoOpen()\r\n    Shell \"calc\"\r\n    Shell \"calc\"\r\n\
+                      End Sub\r\n";
+        let metadata: Metadata = [
+            ("Content-Type", "text/x-vbasic"),
+            (EMBEDDED_RESOURCE_TYPE_KEY, "MACRO"),
+            (EMBEDDED_ID_PATH_KEY, "/1"),
+        ]
+        .iter()
+        .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+        .collect();
+        let extraction = RecursiveExtraction::new(vec![
+            Document::new(
+                "Text of the container document\n".to_string(),
+                Metadata::new(),
+            ),
+            Document::new(source.to_string(), metadata),
+        ]);
+
+        let extractor = Extractor::new()
+            .set_script_filter(Script::Cyrillic)
+            .set_dedup_repeated_lines(true);
+        let extraction = extractor.filter_extraction(extraction, false);
+        assert_eq!(extraction.documents[0].content.trim(), "");
+        let module = &extraction.documents[1];
+        assert_eq!(module.content, source);
+        assert_eq!(
+            module.metadata.get("Content-Type"),
+            Some(&vec!["application/x-vba".to_string()])
+        );
+    }
+
+    #[test]
+    fn link_parents_test() {
+        let doc = |entries: &[(&str, &str)]| {
+            let metadata: Metadata = entries
+                .iter()
+                .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+                .collect();
+            Document::new(String::new(), metadata)
+        };
+        let extraction = RecursiveExtraction::new(vec![
+            doc(&[]),
+            doc(&[
+                (EMBEDDED_ID_PATH_KEY, "/1/2"),
+                (EMBEDDED_RESOURCE_PATH_KEY, "/a.zip/b.txt"),
+            ]),
+            // Same name as the first one, told apart by the id path
+            doc(&[
+                (EMBEDDED_ID_PATH_KEY, "/3/4"),
+                (EMBEDDED_RESOURCE_PATH_KEY, "/a.zip/b.txt"),
+            ]),
+            doc(&[
+                (EMBEDDED_ID_PATH_KEY, "/1"),
+                (EMBEDDED_RESOURCE_PATH_KEY, "/a.zip"),
+            ]),
+            doc(&[
+                (EMBEDDED_ID_PATH_KEY, "/3"),
+                (EMBEDDED_RESOURCE_PATH_KEY, "/a.zip"),
+            ]),
+            // Only the name path
+            doc(&[(EMBEDDED_RESOURCE_PATH_KEY, "/a.zip/c.txt")]),
+            doc(&[]),
+        ]);
+        let parents: Vec<_> = extraction.documents.iter().map(|doc| doc.parent).collect();
+        assert_eq!(
+            parents,
+            vec![None, Some(3), Some(4), Some(0), Some(0), Some(3), Some(0)]
+        );
+        assert_eq!(extraction.children(0), vec![3, 4, 6]);
+        assert_eq!(extraction.children(3), vec![1, 5]);
+        assert!(extraction.children(1).is_empty());
+    }
+
+    #[test]
+    fn rename_metadata_keys_test() {
+        let extractor = Extractor::new().set_metadata_key_map(HashMap::from([
+            ("dc:title".to_string(), "doc_title".to_string()),
+            ("a".to_string(), "b".to_string()),
+            ("b".to_string(), "a".to_string()),
+            ("dc:creator".to_string(), "author".to_string()),
+        ]));
+        let metadata = Metadata::from([
+            ("dc:title".to_string(), vec!["Report".to_string()]),
+            ("a".to_string(), vec!["1".to_string()]),
+            ("b".to_string(), vec!["2".to_string()]),
+            ("dc:creator".to_string(), vec!["Bob".to_string()]),
+            ("author".to_string(), vec!["Alice".to_string()]),
+            ("Content-Type".to_string(), vec!["text/plain".to_string()]),
+        ]);
+        let renamed = extractor.rename_metadata_keys(metadata);
+        assert_eq!(renamed["doc_title"], vec!["Report"]);
+        // Swapped keys keep their own values
+        assert_eq!(renamed["a"], vec!["2"]);
+        assert_eq!(renamed["b"], vec!["1"]);
+        // Merged into the key already there, after its values
+        assert_eq!(renamed["author"], vec!["Alice", "Bob"]);
+        assert_eq!(renamed["Content-Type"], vec!["text/plain"]);
+        assert_eq!(renamed.len(), 5);
+    }
 }
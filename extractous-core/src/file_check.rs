@@ -0,0 +1,125 @@
+use crate::errors::{Error, ExtractResult};
+use std::fs;
+use std::path::Path;
+
+/// Fails with [`Error::InvalidInput`] unless the path is a regular file, or a symbolic link to
+/// one when symbolic links are followed. Reading a FIFO blocks until a writer shows up and
+/// reading a directory or a device fails in ways that are hard to tell apart, so they are
+/// rejected before the file is handed to the parser.
+pub(crate) fn check_input_file(file_path: &str, follow_symlinks: bool) -> ExtractResult<()> {
+    let metadata = if follow_symlinks {
+        fs::metadata(file_path)
+    } else {
+        fs::symlink_metadata(file_path)
+    }
+    .map_err(|e| Error::IoError(format!("Could not open file: {}", e)))?;
+
+    let file_type = metadata.file_type();
+    if file_type.is_file() {
+        return Ok(());
+    }
+    let kind = if file_type.is_symlink() {
+        "a symbolic link"
+    } else if file_type.is_dir() {
+        "a directory"
+    } else {
+        special_file_kind(&file_type)
+    };
+    Err(Error::InvalidInput(format!(
+        "Not a regular file: {} is {}",
+        file_path, kind
+    )))
+}
+
+/// Returns the path as the UTF-8 string the parser is called with. Fails with
+/// [`Error::IoError`] for a path that is not valid unicode, which the VM could not open.
+pub(crate) fn path_to_str(path: &Path) -> ExtractResult<&str> {
+    path.to_str().ok_or_else(|| {
+        Error::IoError(format!(
+            "Could not open file: {} is not a valid UTF-8 path",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(unix)]
+fn special_file_kind(file_type: &fs::FileType) -> &'static str {
+    use std::os::unix::fs::FileTypeExt;
+
+    if file_type.is_fifo() {
+        "a FIFO"
+    } else if file_type.is_socket() {
+        "a socket"
+    } else if file_type.is_char_device() {
+        "a character device"
+    } else if file_type.is_block_device() {
+        "a block device"
+    } else {
+        "a special file"
+    }
+}
+
+#[cfg(not(unix))]
+fn special_file_kind(_file_type: &fs::FileType) -> &'static str {
+    "a special file"
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{check_input_file, path_to_str};
+    use crate::Error;
+    use std::ffi::{CString, OsStr};
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::symlink;
+    use std::path::{Path, PathBuf};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("extractous-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn assert_invalid(path: &str, follow_symlinks: bool, kind: &str) {
+        match check_input_file(path, follow_symlinks) {
+            Err(Error::InvalidInput(msg)) => assert!(msg.ends_with(kind), "{}", msg),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_special_files() {
+        let dir = scratch_dir("file-check");
+        let file = dir.join("report.txt");
+        fs::write(&file, "quarterly report").unwrap();
+        let link = dir.join("link.txt");
+        symlink(&file, &link).unwrap();
+        let fifo = dir.join("pipe");
+        let fifo_path = CString::new(fifo.to_str().unwrap()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(fifo_path.as_ptr(), 0o600) }, 0);
+
+        assert!(check_input_file(file.to_str().unwrap(), false).is_ok());
+        assert!(check_input_file(link.to_str().unwrap(), true).is_ok());
+        assert_invalid(link.to_str().unwrap(), false, "a symbolic link");
+        assert_invalid(dir.to_str().unwrap(), true, "a directory");
+        assert_invalid(fifo.to_str().unwrap(), true, "a FIFO");
+        assert_invalid("/dev/null", true, "a character device");
+        assert!(matches!(
+            check_input_file(dir.join("missing.txt").to_str().unwrap(), true),
+            Err(Error::IoError(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_non_unicode_paths() {
+        assert_eq!(
+            path_to_str(Path::new("docs/report.pdf")).unwrap(),
+            "docs/report.pdf"
+        );
+        let path = Path::new(OsStr::from_bytes(b"docs/r\xe9sum\xe9.pdf"));
+        assert!(matches!(path_to_str(path), Err(Error::IoError(_))));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::errors::ExtractResult;
+use crate::{ContentHandler, Extractor};
+
+/// The OCR text of one image of a document. Returned by [`Extractor::extract_image_ocr`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageOcr {
+    /// Position of the image among the OCRed images of the document, from 0
+    pub image_index: usize,
+    /// 1-based page the image is drawn on, `None` outside of paginated formats such as PDF
+    pub page: Option<u32>,
+    /// Text recognized in the image, trimmed
+    pub text: String,
+}
+
+pub(crate) fn extract_image_ocr(
+    extractor: &Extractor,
+    file_path: &str,
+) -> ExtractResult<Vec<ImageOcr>> {
+    let mut handler = ImageOcrHandler::default();
+    extractor.extract_file_with_handler(file_path, &mut handler)?;
+    Ok(handler.images)
+}
+
+enum Element {
+    Page,
+    Ocr,
+    Other,
+}
+
+/// Collects the `<div class="ocr">` elements written by the OCR parser for each embedded image,
+/// and the `<div class="page">` element they are in
+#[derive(Default)]
+struct ImageOcrHandler {
+    open: Vec<Element>,
+    pages: u32,
+    current: Option<ImageOcr>,
+    images: Vec<ImageOcr>,
+}
+
+impl ContentHandler for ImageOcrHandler {
+    fn start_element(&mut self, name: &str, attributes: &[(String, String)]) {
+        let class = attributes
+            .iter()
+            .find(|(attribute, _)| attribute == "class")
+            .map(|(_, value)| value.as_str());
+        let element = match (name, class) {
+            ("div", Some("page")) => {
+                self.pages += 1;
+                Element::Page
+            }
+            ("div", Some("ocr")) if self.current.is_none() => {
+                let in_page = self
+                    .open
+                    .iter()
+                    .any(|element| matches!(element, Element::Page));
+                self.current = Some(ImageOcr {
+                    image_index: self.images.len(),
+                    page: in_page.then_some(self.pages),
+                    text: String::new(),
+                });
+                Element::Ocr
+            }
+            _ => Element::Other,
+        };
+        self.open.push(element);
+    }
+
+    fn end_element(&mut self, _name: &str) {
+        if let Some(Element::Ocr) = self.open.pop() {
+            if let Some(mut image) = self.current.take() {
+                image.text = image.text.trim().to_string();
+                self.images.push(image);
+            }
+        }
+    }
+
+    fn characters(&mut self, text: &str) {
+        if let Some(image) = self.current.as_mut() {
+            image.text.push_str(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageOcr, ImageOcrHandler};
+    use crate::ContentHandler;
+
+    fn class(value: &str) -> Vec<(String, String)> {
+        vec![("class".to_string(), value.to_string())]
+    }
+
+    #[test]
+    fn collects_ocr_text_per_image_and_page() {
+        let mut handler = ImageOcrHandler::default();
+        handler.start_element("body", &[]);
+        handler.start_element("div", &class("page"));
+        handler.start_element("p", &[]);
+        handler.characters("Body text");
+        handler.end_element("p");
+        handler.end_element("div");
+        handler.start_element("div", &class("page"));
+        handler.start_element("div", &class("ocr"));
+        handler.characters(" Revenue ");
+        handler.characters("by quarter\n");
+        handler.end_element("div");
+        handler.end_element("div");
+        handler.start_element("div", &class("package-entry"));
+        handler.start_element("div", &class("ocr"));
+        handler.characters("Logo");
+        handler.end_element("div");
+        handler.end_element("div");
+        handler.end_element("body");
+
+        assert_eq!(
+            handler.images,
+            vec![
+                ImageOcr {
+                    image_index: 0,
+                    page: Some(2),
+                    text: "Revenue by quarter".to_string(),
+                },
+                ImageOcr {
+                    image_index: 1,
+                    page: None,
+                    text: "Logo".to_string(),
+                },
+            ]
+        );
+    }
+}
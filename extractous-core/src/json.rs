@@ -0,0 +1,53 @@
+use crate::Metadata;
+use serde_json::{json, Map, Value};
+
+/// Combines the content and metadata of an extraction into a single JSON object:
+/// `{"content": ..., "metadata": {...}, "content_type": ...}`. The metadata values are arrays,
+/// as a key can have several values, and `content_type` is the first `Content-Type` value, null
+/// when the parsers did not report one.
+pub(crate) fn to_json(content: String, metadata: &Metadata) -> Value {
+    let content_type = metadata
+        .get("Content-Type")
+        .and_then(|values| values.first())
+        .cloned();
+    let metadata: Map<String, Value> = metadata
+        .iter()
+        .map(|(key, values)| (key.clone(), json!(values)))
+        .collect();
+
+    json!({
+        "content": content,
+        "metadata": metadata,
+        "content_type": content_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_json;
+    use crate::Metadata;
+    use serde_json::Value;
+
+    #[test]
+    fn combines_content_and_metadata() {
+        let metadata = Metadata::from([
+            (
+                "Content-Type".to_string(),
+                vec!["text/plain; charset=UTF-8".to_string()],
+            ),
+            (
+                "dc:creator".to_string(),
+                vec!["Ada".to_string(), "Grace".to_string()],
+            ),
+        ]);
+
+        let value = to_json("Hello".to_string(), &metadata);
+        assert_eq!(value["content"], "Hello");
+        assert_eq!(value["content_type"], "text/plain; charset=UTF-8");
+        assert_eq!(value["metadata"]["dc:creator"][1], "Grace");
+
+        let value = to_json(String::new(), &Metadata::new());
+        assert_eq!(value["content_type"], Value::Null);
+        assert_eq!(value["metadata"], Value::Object(Default::default()));
+    }
+}
@@ -0,0 +1,51 @@
+/// Metadata key of the language detected when [`Extractor::set_detect_language`] is enabled
+///
+/// [`Extractor::set_detect_language`]: crate::Extractor::set_detect_language
+pub(crate) const LANGUAGE_KEY: &str = "language";
+
+// The detector looks at the first 20_000 characters at most, so there is no point in copying more
+// than this many bytes of a large document to the VM
+const SAMPLE_MAX_BYTES: usize = 80_000;
+
+/// The language a text is written in, see [`Extractor::detect_language`]
+///
+/// [`Extractor::detect_language`]: crate::Extractor::detect_language
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageResult {
+    /// ISO 639-1 code of the language, e.g. `en` or `de`, with the region for the variants of
+    /// Chinese, `zh-CN` or `zh-TW`. Empty when no language could be detected, e.g. for a text
+    /// without letters
+    pub language: String,
+    /// Probability of the language, between 0 and 1
+    pub confidence: f32,
+}
+
+/// Returns the start of the text that is handed to the detector
+pub(crate) fn language_sample(text: &str) -> &str {
+    if text.len() <= SAMPLE_MAX_BYTES {
+        return text;
+    }
+    let mut end = SAMPLE_MAX_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_sample_test() {
+        assert_eq!(language_sample("Guten Tag"), "Guten Tag");
+
+        let text = "é".repeat(SAMPLE_MAX_BYTES);
+        let sample = language_sample(&text);
+        assert_eq!(sample.len(), SAMPLE_MAX_BYTES);
+        assert!(sample.chars().all(|c| c == 'é'));
+
+        let text = format!("a{}", "é".repeat(SAMPLE_MAX_BYTES));
+        assert_eq!(language_sample(&text).len(), SAMPLE_MAX_BYTES - 1);
+    }
+}
@@ -79,15 +79,78 @@ pub use config::*;
 // extractor module is the main public api interface
 mod extractor;
 pub use extractor::*;
+mod batch;
+pub use batch::FileResult;
+mod timeout;
+pub use timeout::{run_cancellable, run_with_timeout, CancellationToken};
+mod embedded_files;
+mod file_check;
+mod language;
+pub use language::LanguageResult;
+mod line_numbers;
+mod metadata_view;
+pub use metadata_view::{MetadataView, OfficeComment};
+mod ndjson;
+mod recursive_iter;
+pub use recursive_iter::RecursiveDocuments;
+mod script_filter;
+pub use script_filter::{dominant_script, Script, DEFAULT_SCRIPT_FILTER_THRESHOLD};
+mod paragraphs;
+pub use paragraphs::Paragraphs;
+mod repeated_lines;
+mod robots;
+mod sections;
+pub use sections::Section;
+mod simhash;
+pub use simhash::simhash;
+mod sentences;
+pub use sentences::{split_sentences, Sentence};
+mod tables;
+pub use tables::Table;
+mod structured;
+pub use structured::{CalendarEvent, Contact};
+mod chat;
+pub use chat::{Chat, ChatFormat, ChatMessage};
+mod entities;
+pub use entities::{find_entities, Entity, EntityKind};
+mod image_ocr;
+pub use image_ocr::ImageOcr;
+mod ocr_languages;
+pub use ocr_languages::available_ocr_languages;
+mod office_properties;
+pub use office_properties::OfficeProperties;
+mod mime_message;
+pub use mime_message::MimeMessage;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "arrow")]
+pub use arrow::{arrow_schema, to_arrow};
+#[cfg(feature = "async")]
+mod async_extract;
+#[cfg(feature = "async")]
+pub use async_extract::AsyncStreamReader;
+#[cfg(feature = "serde")]
+mod json;
 
 // tika module, not exposed outside this crate
 mod tika {
+    mod buffer_gate;
+    mod embedded_target;
+    mod handler;
     mod jni_utils;
     mod parse;
+    mod reader;
+    mod sink;
     mod wrappers;
+    pub use buffer_gate::set_max_direct_buffer_bytes;
+    pub(crate) use buffer_gate::{acquire_direct_buffer, DirectBufferPermit};
     pub use parse::*;
     pub use wrappers::JReaderInputStream;
 }
 
 // Public utility functions for JVM memory management
-pub use tika::{get_jvm_memory_usage, trigger_jvm_gc};
+pub use tika::{
+    configure_vm, get_jvm_memory_stats, get_jvm_memory_usage, reinit_after_fork, set_fork_behavior,
+    set_max_direct_buffer_bytes, set_max_ocr_concurrency, trigger_jvm_gc, ForkBehavior,
+    JvmMemoryStats, VmOptions,
+};
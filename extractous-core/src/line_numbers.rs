@@ -0,0 +1,79 @@
+use crate::Metadata;
+
+/// Parsers whose output keeps the lines of the source text as they are
+const PLAIN_TEXT_PARSERS: [&str; 2] = [
+    "org.apache.tika.parser.csv.TextAndCSVParser",
+    "org.apache.tika.parser.txt.TXTParser",
+];
+
+/// Whether the document is plain text whose extracted lines are the lines of the source:
+/// `text/plain` and its subtypes handed to the text parser, such as source code or logs.
+/// CSV and TSV files go through the same parser but are rendered as tables.
+pub(crate) fn is_plain_text(metadata: &Metadata) -> bool {
+    let content_type = metadata
+        .get("Content-Type")
+        .and_then(|values| values.first())
+        .map(|value| value.split(';').next().unwrap_or_default().trim())
+        .unwrap_or_default();
+    if content_type == "text/plain" {
+        return true;
+    }
+    if matches!(content_type, "text/csv" | "text/tab-separated-values") {
+        return false;
+    }
+    metadata.get("X-TIKA:Parsed-By").is_some_and(|parsers| {
+        parsers
+            .iter()
+            .any(|parser| PLAIN_TEXT_PARSERS.contains(&parser.as_str()))
+    })
+}
+
+/// Prefixes every line with its 1-based number and a tab. The trailing empty lines are dropped,
+/// they come from the paragraph the parser wraps the text in rather than from the source.
+pub(crate) fn number_lines(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + text.len() / 8);
+    for (idx, line) in text.trim_end_matches(['\n', '\r']).lines().enumerate() {
+        out.push_str(&(idx + 1).to_string());
+        out.push('\t');
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_plain_text, number_lines};
+    use crate::Metadata;
+
+    fn metadata(content_type: &str, parser: &str) -> Metadata {
+        Metadata::from([
+            ("Content-Type".to_string(), vec![content_type.to_string()]),
+            ("X-TIKA:Parsed-By".to_string(), vec![parser.to_string()]),
+        ])
+    }
+
+    #[test]
+    fn numbers_lines() {
+        let text = "first\r\nsecond\n\nfourth\n\n";
+        assert_eq!(number_lines(text), "1\tfirst\n2\tsecond\n3\t\n4\tfourth\n");
+    }
+
+    #[test]
+    fn detects_plain_text() {
+        let text_parser = "org.apache.tika.parser.csv.TextAndCSVParser";
+        assert!(is_plain_text(&metadata(
+            "text/plain; charset=UTF-8",
+            text_parser
+        )));
+        assert!(is_plain_text(&metadata("text/x-python", text_parser)));
+        assert!(!is_plain_text(&metadata(
+            "text/csv; delimiter=comma",
+            text_parser
+        )));
+        assert!(!is_plain_text(&metadata(
+            "application/pdf",
+            "org.apache.tika.parser.pdf.PDFParser"
+        )));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::Metadata;
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Prefix of the metadata keys of the PDF form fields, set by the Java side
+const FORM_FIELD_PREFIX: &str = "extractous:form_field:";
+
+/// A reviewer comment of an Office document, see
+/// [`OfficeParserConfig::set_include_comments`](crate::OfficeParserConfig::set_include_comments)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OfficeComment<'a> {
+    pub author: Option<&'a str>,
+    /// The time as written in the document, usually ISO-8601
+    pub date: Option<&'a str>,
+    pub text: &'a str,
+}
+
+/// Typed accessors over the canonical Tika keys of a [`Metadata`], obtained with
+/// [`MetadataExt::view`](crate::MetadataExt::view). Keys with several values return their first
+/// value, and a missing key or a value that does not parse returns `None`.
+#[derive(Debug, Clone, Copy)]
+pub struct MetadataView<'a> {
+    metadata: &'a Metadata,
+}
+
+impl<'a> MetadataView<'a> {
+    pub fn new(metadata: &'a Metadata) -> Self {
+        Self { metadata }
+    }
+
+    /// Returns the first value of any key
+    pub fn first(&self, key: &str) -> Option<&'a str> {
+        self.metadata
+            .get(key)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    /// The detected media type, `Content-Type`, with its parameters such as the charset
+    pub fn content_type(&self) -> Option<&'a str> {
+        self.first("Content-Type")
+    }
+
+    /// The title, `dc:title`
+    pub fn title(&self) -> Option<&'a str> {
+        self.first("dc:title")
+    }
+
+    /// The author, `dc:creator`
+    pub fn author(&self) -> Option<&'a str> {
+        self.first("dc:creator")
+    }
+
+    /// The number of pages, `xmpTPg:NPages`, or `meta:page-count` for the office formats that
+    /// only report the latter
+    pub fn page_count(&self) -> Option<u32> {
+        self.first("xmpTPg:NPages")
+            .or_else(|| self.first("meta:page-count"))
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    /// The creation date, `dcterms:created`
+    pub fn created(&self) -> Option<SystemTime> {
+        self.first("dcterms:created").and_then(parse_timestamp)
+    }
+
+    /// The last modification date, `dcterms:modified`
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.first("dcterms:modified").and_then(parse_timestamp)
+    }
+
+    /// The reviewer comments of an Office document, from the `extractous:comment_author`,
+    /// `extractous:comment_date` and `extractous:comment_text` keys, in document order
+    pub fn office_comments(&self) -> Vec<OfficeComment<'a>> {
+        let values = |key: &str| self.metadata.get(key).map_or(&[][..], Vec::as_slice);
+        let non_empty = |value: &'a String| Some(value.as_str()).filter(|value| !value.is_empty());
+        let authors = values("extractous:comment_author");
+        let dates = values("extractous:comment_date");
+        values("extractous:comment_text")
+            .iter()
+            .enumerate()
+            .map(|(index, text)| OfficeComment {
+                author: authors.get(index).and_then(non_empty),
+                date: dates.get(index).and_then(non_empty),
+                text,
+            })
+            .collect()
+    }
+
+    /// The values of the PDF form fields by fully qualified field name, from the
+    /// `extractous:form_field:` keys, see
+    /// [`PdfParserConfig::set_extract_acro_form_content`](crate::PdfParserConfig::set_extract_acro_form_content)
+    pub fn pdf_form_fields(&self) -> BTreeMap<&'a str, &'a [String]> {
+        self.metadata
+            .iter()
+            .filter_map(|(key, values)| {
+                key.strip_prefix(FORM_FIELD_PREFIX)
+                    .map(|name| (name, values.as_slice()))
+            })
+            .collect()
+    }
+}
+
+/// Parses the ISO 8601 dates Tika reports, `2022-07-28T18:05:49Z` with optional fractional
+/// seconds and offset, or a date alone. A time without offset is taken as UTC.
+fn parse_timestamp(value: &str) -> Option<SystemTime> {
+    let value = value.trim();
+    let (date, time) = match value.find(['T', ' ']) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = parse_digits(parts.next()?, 4)?;
+    let month: i64 = parse_digits(parts.next()?, 2)?;
+    let day: i64 = parse_digits(parts.next()?, 2)?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+    let mut nanos = 0;
+
+    if let Some(time) = time {
+        let (clock, offset) = match time.find(['Z', 'z', '+', '-']) {
+            Some(index) => (&time[..index], &time[index..]),
+            None => (time, ""),
+        };
+        let (clock, fraction) = match clock.split_once('.') {
+            Some((clock, fraction)) => (clock, Some(fraction)),
+            None => (clock, None),
+        };
+        let mut fields = clock.splitn(3, ':');
+        let hour: i64 = parse_digits(fields.next()?, 2)?;
+        let minute: i64 = parse_digits(fields.next()?, 2)?;
+        let second: i64 = match fields.next() {
+            Some(second) => parse_digits(second, 2)?,
+            None => 0,
+        };
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        seconds += hour * 3_600 + minute * 60 + second - parse_offset(offset)?;
+
+        if let Some(fraction) = fraction {
+            if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            // Digits past the nanoseconds are dropped
+            let digits = &fraction[..fraction.len().min(9)];
+            nanos = digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32);
+        }
+    }
+
+    let since_epoch = Duration::new(seconds.unsigned_abs(), 0);
+    let whole = if seconds >= 0 {
+        UNIX_EPOCH.checked_add(since_epoch)?
+    } else {
+        UNIX_EPOCH.checked_sub(since_epoch)?
+    };
+    whole.checked_add(Duration::from_nanos(nanos.into()))
+}
+
+/// Parses a field of exactly `len` ASCII digits
+fn parse_digits(field: &str, len: usize) -> Option<i64> {
+    if field.len() != len || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    field.parse().ok()
+}
+
+/// Returns the offset from UTC in seconds of `Z`, `+02:00`, `-0530` or an empty zone
+fn parse_offset(offset: &str) -> Option<i64> {
+    let sign = match offset.chars().next() {
+        None | Some('Z') | Some('z') if offset.len() <= 1 => return Some(0),
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return None,
+    };
+    let digits = offset[1..].replace(':', "");
+    let hours = parse_digits(digits.get(..2)?, 2)?;
+    let minutes = match digits.get(2..) {
+        Some("") => 0,
+        Some(minutes) => parse_digits(minutes, 2)?,
+        None => return None,
+    };
+    Some(sign * (hours * 3_600 + minutes * 60))
+}
+
+/// Returns the number of days from 1970-01-01 to the given date of the proleptic Gregorian
+/// calendar, after Howard Hinnant's `days_from_civil`
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn parse_timestamp_test() {
+        assert_eq!(
+            parse_timestamp("2022-07-28T18:05:49Z"),
+            Some(at(1_659_031_549))
+        );
+        assert_eq!(
+            parse_timestamp("2022-07-28T20:05:49+02:00"),
+            Some(at(1_659_031_549))
+        );
+        assert_eq!(
+            parse_timestamp("2022-07-28T12:35:49-0530"),
+            Some(at(1_659_031_549))
+        );
+        assert_eq!(
+            parse_timestamp("2022-07-28T18:05:49"),
+            Some(at(1_659_031_549))
+        );
+        assert_eq!(
+            parse_timestamp("2022-07-28T18:05Z"),
+            Some(at(1_659_031_500))
+        );
+        assert_eq!(parse_timestamp("2022-07-28"), Some(at(1_658_966_400)));
+        assert_eq!(
+            parse_timestamp("2022-07-28T18:05:49.25Z"),
+            Some(at(1_659_031_549) + Duration::from_millis(250))
+        );
+        assert_eq!(
+            parse_timestamp("1969-12-31T23:59:59Z"),
+            Some(UNIX_EPOCH - Duration::from_secs(1))
+        );
+
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("D:20220728180549Z"), None);
+        assert_eq!(parse_timestamp("2022-13-01"), None);
+        assert_eq!(parse_timestamp("2022-07-28T25:00:00Z"), None);
+        assert_eq!(parse_timestamp("2022-07-28T18:05:49+2"), None);
+    }
+
+    #[test]
+    fn metadata_view_test() {
+        let metadata: Metadata = HashMap::from([
+            (
+                "Content-Type".to_string(),
+                vec!["application/pdf".to_string()],
+            ),
+            (
+                "dc:creator".to_string(),
+                vec!["Ada".to_string(), "Grace".to_string()],
+            ),
+            ("meta:page-count".to_string(), vec!["12".to_string()]),
+            ("xmpTPg:NPages".to_string(), vec![" 3 ".to_string()]),
+            (
+                "dcterms:modified".to_string(),
+                vec!["not a date".to_string()],
+            ),
+            (
+                "extractous:form_field:applicant.name".to_string(),
+                vec!["Jane Doe".to_string()],
+            ),
+            (
+                "extractous:comment_author".to_string(),
+                vec!["Alice".to_string(), "".to_string()],
+            ),
+            (
+                "extractous:comment_date".to_string(),
+                vec!["".to_string(), "2024-03-05T10:15:30Z".to_string()],
+            ),
+            (
+                "extractous:comment_text".to_string(),
+                vec!["First".to_string(), "Second".to_string()],
+            ),
+        ]);
+        let view = MetadataView::new(&metadata);
+
+        assert_eq!(view.content_type(), Some("application/pdf"));
+        assert_eq!(view.author(), Some("Ada"));
+        assert_eq!(view.title(), None);
+        assert_eq!(view.page_count(), Some(3));
+        assert_eq!(view.created(), None);
+        assert_eq!(view.modified(), None);
+        assert_eq!(
+            view.pdf_form_fields(),
+            BTreeMap::from([("applicant.name", &["Jane Doe".to_string()][..])])
+        );
+        assert_eq!(
+            view.office_comments(),
+            [
+                OfficeComment {
+                    author: Some("Alice"),
+                    date: None,
+                    text: "First",
+                },
+                OfficeComment {
+                    author: None,
+                    date: Some("2024-03-05T10:15:30Z"),
+                    text: "Second",
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use crate::Document;
+
+/// The bodies and attachments of an email message. Returned by
+/// [`Extractor::extract_mime_message`](crate::Extractor::extract_mime_message).
+#[derive(Debug, Clone, Default)]
+pub struct MimeMessage {
+    /// The first inline `text/plain` part, decoded, `None` if the message has none
+    pub text_plain: Option<String>,
+    /// The first inline `text/html` part, decoded, `None` if the message has none
+    pub text_html: Option<String>,
+    /// Every other part, extracted like a document of its own, in the order of the message
+    pub attachments: Vec<Document>,
+}
+
+/// A part of a message as split by `ai.yobix.MimeMessageSplitter`, before it is extracted
+pub(crate) struct MimePart {
+    pub(crate) name: Option<String>,
+    pub(crate) mime_type: String,
+    pub(crate) data: Vec<u8>,
+}
+
+/// A message as split by `ai.yobix.MimeMessageSplitter`
+pub(crate) struct MimeParts {
+    pub(crate) text_plain: Option<String>,
+    pub(crate) text_html: Option<String>,
+    pub(crate) attachments: Vec<MimePart>,
+}
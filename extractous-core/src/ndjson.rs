@@ -0,0 +1,72 @@
+use crate::{Document, MetadataExt};
+use serde_json::{json, Map, Value};
+use std::io::{self, Write};
+
+/// 内容在元数据中的键，NDJSON 中内容单独成字段，不再重复写入元数据
+const CONTENT_KEY: &str = "X-TIKA:content";
+
+/// 将文档写为一行 JSON：`{"content": ..., "metadata": {...}, "embedded_path": ...}`，
+/// 元数据按键排序，容器文档的 `embedded_path` 为 null
+pub(crate) fn write_document<W: Write>(out: &mut W, doc: &Document) -> io::Result<()> {
+    let metadata: Map<String, Value> = doc
+        .metadata
+        .sorted_entries()
+        .into_iter()
+        .filter(|(key, _)| key.as_str() != CONTENT_KEY)
+        .map(|(key, values)| (key.clone(), json!(values)))
+        .collect();
+    let embedded_path = doc.embedded_path();
+
+    let line = json!({
+        "content": doc.content,
+        "metadata": metadata,
+        "embedded_path": embedded_path,
+    });
+    serde_json::to_writer(&mut *out, &line)?;
+    out.write_all(b"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_document;
+    use crate::Document;
+    use serde_json::Value;
+
+    #[test]
+    fn writes_one_json_line_per_document() {
+        let container = Document::new(
+            "容器".to_string(),
+            [
+                ("X-TIKA:content", "容器"),
+                ("Content-Type", "application/zip"),
+            ]
+            .iter()
+            .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+            .collect(),
+        );
+        let embedded = Document::new(
+            "第一行\n第二行".to_string(),
+            [("X-TIKA:embedded_resource_path", "/a.txt")]
+                .iter()
+                .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+                .collect(),
+        );
+
+        let mut out = Vec::new();
+        write_document(&mut out, &embedded).unwrap();
+        write_document(&mut out, &container).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        // 内容中的换行被转义，每个文档恰好一行
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["content"], "第一行\n第二行");
+        assert_eq!(lines[0]["embedded_path"], "/a.txt");
+        assert_eq!(lines[1]["embedded_path"], Value::Null);
+        assert_eq!(lines[1]["metadata"]["Content-Type"][0], "application/zip");
+        assert!(lines[1]["metadata"].get("X-TIKA:content").is_none());
+    }
+}
@@ -0,0 +1,97 @@
+use crate::errors::{Error, ExtractResult};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Returns the languages of the tesseract language packs installed, as listed by
+/// `tesseract --list-langs`, which are the ones OCR can use. The tesseract found on the `PATH`
+/// is run, which honors `TESSDATA_PREFIX`. Fails with [`Error::IoError`] when it can not be run.
+pub fn available_ocr_languages() -> ExtractResult<Vec<String>> {
+    list_ocr_languages(&tesseract_executable(""), "")
+}
+
+/// Returns the tesseract executable of the directory, the one found on the `PATH` when the
+/// directory is empty
+pub(crate) fn tesseract_executable(dir: &str) -> PathBuf {
+    if dir.is_empty() {
+        PathBuf::from("tesseract")
+    } else if cfg!(windows) {
+        Path::new(dir).join("tesseract.exe")
+    } else {
+        Path::new(dir).join("tesseract")
+    }
+}
+
+/// Returns whether the path is a file that can be executed
+pub(crate) fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// Lists the languages installed for the tesseract executable, in the language data directory
+/// when it is not empty
+pub(crate) fn list_ocr_languages(
+    tesseract: &Path,
+    tessdata_dir: &str,
+) -> ExtractResult<Vec<String>> {
+    let mut command = Command::new(tesseract);
+    command.arg("--list-langs");
+    if !tessdata_dir.is_empty() {
+        command.arg("--tessdata-dir").arg(tessdata_dir);
+    }
+    let output = command
+        .output()
+        .map_err(|e| Error::IoError(format!("tesseract can not be run: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::IoError(format!(
+            "tesseract --list-langs failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    // Older versions print the list to stderr
+    let listing = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    Ok(parse_language_list(&String::from_utf8_lossy(&listing)))
+}
+
+/// Parses the output of `tesseract --list-langs`, a header line followed by one language per
+/// line
+fn parse_language_list(listing: &str) -> Vec<String> {
+    listing
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("List of available languages"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_language_list_test() {
+        let listing =
+            "List of available languages in \"/usr/share/tesseract-ocr/5/tessdata/\" (4):\n\
+                       ara\n\
+                       eng\n\
+                       osd\n\
+                       script/Arabic\n";
+        assert_eq!(
+            parse_language_list(listing),
+            ["ara", "eng", "osd", "script/Arabic"]
+        );
+        assert!(parse_language_list("").is_empty());
+    }
+}
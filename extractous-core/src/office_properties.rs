@@ -0,0 +1,155 @@
+use crate::errors::{Error, ExtractResult};
+use crate::Metadata;
+
+/// The core and extended properties of an Office document, as read by POI from the OLE2
+/// property sets of the legacy formats (doc, xls, ppt, ...) or the `docProps` parts of the
+/// OOXML ones (docx, xlsx, pptx, ...).
+///
+/// Properties missing from the document, or stored as an empty string, are `None`. Dates are
+/// ISO-8601 strings such as `2024-07-11T19:20:35Z`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OfficeProperties {
+    // Core properties
+    pub title: Option<String>,
+    pub subject: Option<String>,
+    /// Author of the document, the first one when several are listed
+    pub creator: Option<String>,
+    pub keywords: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub language: Option<String>,
+    pub last_modified_by: Option<String>,
+    pub revision: Option<u32>,
+    pub created: Option<String>,
+    pub modified: Option<String>,
+    pub last_printed: Option<String>,
+
+    // Extended properties
+    pub application: Option<String>,
+    pub app_version: Option<String>,
+    pub company: Option<String>,
+    pub manager: Option<String>,
+    pub template: Option<String>,
+    /// Total editing time as stored by the application: minutes for OOXML documents, the
+    /// unit of the writing application for the legacy formats
+    pub total_editing_time: Option<u64>,
+    pub page_count: Option<u32>,
+    pub word_count: Option<u32>,
+    pub character_count: Option<u32>,
+    pub character_count_with_spaces: Option<u32>,
+    pub paragraph_count: Option<u32>,
+    pub line_count: Option<u32>,
+    pub slide_count: Option<u32>,
+}
+
+impl OfficeProperties {
+    /// Maps the metadata keys under which Tika reports the POI properties
+    pub(crate) fn from_metadata(metadata: &Metadata) -> Self {
+        let text = |key: &str| {
+            metadata
+                .get(key)
+                .and_then(|values| values.iter().find(|value| !value.trim().is_empty()))
+                .map(|value| value.trim().to_string())
+        };
+        let number = |key: &str| text(key).and_then(|value| value.parse().ok());
+
+        Self {
+            title: text("dc:title"),
+            subject: text("cp:subject").or_else(|| text("dc:subject")),
+            creator: text("dc:creator"),
+            keywords: text("meta:keyword"),
+            description: text("dc:description"),
+            category: text("cp:category"),
+            language: text("dc:language"),
+            last_modified_by: text("meta:last-author"),
+            revision: number("cp:revision"),
+            created: text("dcterms:created"),
+            modified: text("dcterms:modified"),
+            last_printed: text("meta:print-date"),
+            application: text("extended-properties:Application"),
+            app_version: text("extended-properties:AppVersion"),
+            company: text("extended-properties:Company"),
+            manager: text("extended-properties:Manager"),
+            template: text("extended-properties:Template"),
+            total_editing_time: text("extended-properties:TotalTime")
+                .and_then(|value| value.parse().ok()),
+            page_count: number("meta:page-count"),
+            word_count: number("meta:word-count"),
+            character_count: number("meta:character-count"),
+            character_count_with_spaces: number("meta:character-count-with-spaces"),
+            paragraph_count: number("meta:paragraph-count"),
+            line_count: number("meta:line-count"),
+            slide_count: number("meta:slide-count"),
+        }
+    }
+}
+
+/// Whether the detected content type is one of the OLE2 or OOXML formats read by POI
+fn is_office_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    mime == "application/msword"
+        || mime.starts_with("application/vnd.ms-")
+        || mime.starts_with("application/vnd.openxmlformats-officedocument.")
+}
+
+/// Returns the properties of the Office document described by the metadata of its parse, or a
+/// `ParseError` for the other formats
+pub(crate) fn office_properties(metadata: &Metadata) -> ExtractResult<OfficeProperties> {
+    let content_type = metadata
+        .get("Content-Type")
+        .and_then(|values| values.first())
+        .map(String::as_str)
+        .unwrap_or_default();
+    if !is_office_type(content_type) {
+        return Err(Error::ParseError(format!(
+            "not an Office document: {}",
+            content_type
+        )));
+    }
+    Ok(OfficeProperties::from_metadata(metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{office_properties, OfficeProperties};
+    use crate::Metadata;
+
+    fn metadata(entries: &[(&str, &str)]) -> Metadata {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), vec![value.to_string()]))
+            .collect()
+    }
+
+    #[test]
+    fn maps_typed_properties() {
+        let metadata = metadata(&[
+            (
+                "Content-Type",
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            ),
+            ("dc:title", "Report"),
+            ("cp:revision", "12"),
+            ("meta:word-count", "288"),
+            ("extended-properties:Company", ""),
+            ("extended-properties:Template", "Normal.dotm"),
+        ]);
+        let properties = office_properties(&metadata).unwrap();
+        assert_eq!(
+            properties,
+            OfficeProperties {
+                title: Some("Report".to_string()),
+                revision: Some(12),
+                word_count: Some(288),
+                template: Some("Normal.dotm".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_other_formats() {
+        let metadata = metadata(&[("Content-Type", "application/pdf")]);
+        assert!(office_properties(&metadata).is_err());
+    }
+}
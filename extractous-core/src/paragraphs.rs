@@ -0,0 +1,147 @@
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread;
+
+use crate::errors::{Error, ExtractResult};
+use crate::{ContentHandler, Extractor, Metadata};
+
+/// Number of paragraphs buffered ahead of the consumer before the parser is paused
+const PARAGRAPH_BUFFER: usize = 64;
+
+/// Whether the given element of the XHTML parser output delimits a paragraph
+pub(crate) fn is_block_element(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "li"
+            | "dt"
+            | "dd"
+            | "td"
+            | "th"
+            | "pre"
+            | "blockquote"
+            | "div"
+            | "body"
+    )
+}
+
+enum Event {
+    Paragraph(String),
+    Done(ExtractResult<Metadata>),
+}
+
+/// Iterator over the paragraphs of a document, returned by [`Extractor::extract_file_paragraphs`].
+///
+/// The document is parsed on a background thread and each paragraph is handed over as soon as
+/// the parser has produced it, so only a few paragraphs are held in memory at any time. Once the
+/// iterator is exhausted, [`Paragraphs::finish`] returns the metadata of the document or the
+/// error that ended the parse early.
+pub struct Paragraphs {
+    receiver: Receiver<Event>,
+    result: Option<ExtractResult<Metadata>>,
+}
+
+impl Paragraphs {
+    pub(crate) fn spawn(extractor: Extractor, file_path: &str) -> ExtractResult<Self> {
+        // Report a missing file right away instead of through finish()
+        std::fs::metadata(file_path)
+            .map_err(|e| Error::IoError(format!("Could not open file: {}", e)))?;
+
+        let (sender, receiver) = sync_channel(PARAGRAPH_BUFFER);
+        let file_path = file_path.to_string();
+        thread::Builder::new()
+            .name("extractous-paragraphs".to_string())
+            .spawn(move || {
+                let mut handler = ParagraphHandler {
+                    sender: sender.clone(),
+                    text: String::new(),
+                    in_body: false,
+                };
+                let result = extractor.extract_file_with_handler(&file_path, &mut handler);
+                let _ = sender.send(Event::Done(result));
+            })
+            .map_err(|e| Error::Unknown(format!("Could not start the parse thread: {}", e)))?;
+
+        Ok(Self {
+            receiver,
+            result: None,
+        })
+    }
+
+    /// Consumes the remaining paragraphs and returns the metadata of the document, or the error
+    /// that stopped the parse
+    pub fn finish(mut self) -> ExtractResult<Metadata> {
+        for _ in self.by_ref() {}
+        self.result.take().unwrap_or_else(|| {
+            Err(Error::Unknown(
+                "The parse thread stopped unexpectedly".into(),
+            ))
+        })
+    }
+}
+
+impl Iterator for Paragraphs {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.result.is_some() {
+            return None;
+        }
+        match self.receiver.recv() {
+            Ok(Event::Paragraph(text)) => Some(text),
+            Ok(Event::Done(result)) => {
+                self.result = Some(result);
+                None
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+/// Collects the text of the body between block element boundaries
+struct ParagraphHandler {
+    sender: SyncSender<Event>,
+    text: String,
+    in_body: bool,
+}
+
+impl ParagraphHandler {
+    fn flush(&mut self) {
+        let paragraph = self.text.trim();
+        if !paragraph.is_empty() {
+            // A send error means the iterator was dropped, the rest of the document is discarded
+            let _ = self.sender.send(Event::Paragraph(paragraph.to_string()));
+        }
+        self.text.clear();
+    }
+}
+
+impl ContentHandler for ParagraphHandler {
+    fn start_element(&mut self, name: &str, _attributes: &[(String, String)]) {
+        if name == "body" {
+            self.in_body = true;
+        }
+        if is_block_element(name) {
+            self.flush();
+        }
+    }
+
+    fn end_element(&mut self, name: &str) {
+        if is_block_element(name) {
+            self.flush();
+        }
+        if name == "body" {
+            self.in_body = false;
+        }
+    }
+
+    fn characters(&mut self, text: &str) {
+        if self.in_body {
+            self.text.push_str(text);
+        }
+    }
+}
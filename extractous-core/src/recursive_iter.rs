@@ -0,0 +1,56 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread;
+
+use crate::errors::{Error, ExtractResult};
+use crate::{Document, Extractor};
+
+/// 解析线程领先迭代器缓存的文档数，缓存满时解析暂停，直到迭代器取走文档
+const DOCUMENT_BUFFER: usize = 2;
+
+/// 逐个返回递归提取结果的迭代器，由 [`Extractor::extract_file_recursive_iter`] 返回。
+///
+/// 第一个文档是解析开始时的容器文档：只有解析前已知的元数据（`Content-Type`），内容为空。
+/// 之后文件在后台线程中解析，每个嵌套文档解析完成后立即交给迭代器，最后返回内容完整的
+/// 容器文档。内存中只保留少量文档，与嵌套文档的数量无关。解析失败时最后返回一个错误，
+/// 之后迭代结束。
+pub struct RecursiveDocuments {
+    receiver: Receiver<ExtractResult<Document>>,
+}
+
+impl RecursiveDocuments {
+    pub(crate) fn spawn(extractor: Extractor, file_path: &str) -> ExtractResult<Self> {
+        let (sender, receiver) = sync_channel(DOCUMENT_BUFFER);
+        let file_path = file_path.to_string();
+        thread::Builder::new()
+            .name("extractous-recursive".to_string())
+            .spawn(move || {
+                let mut sink = |doc: Document| -> ExtractResult<()> {
+                    // 迭代器已被丢弃，返回错误以中止解析
+                    sender
+                        .send(Ok(doc))
+                        .map_err(|_| Error::Unknown("The iterator was dropped".to_string()))
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    sink(extractor.recursive_start_document(&file_path)?)?;
+                    extractor.extract_file_recursive_to_sink(&file_path, &mut sink)
+                }))
+                .unwrap_or_else(|_| Err(Error::Unknown("The extraction panicked".to_string())));
+                if let Err(error) = result {
+                    let _ = sender.send(Err(error));
+                }
+            })
+            .map_err(|e| Error::Unknown(format!("Could not start the parse thread: {}", e)))?;
+
+        Ok(Self { receiver })
+    }
+}
+
+impl Iterator for RecursiveDocuments {
+    type Item = ExtractResult<Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 解析线程结束时关闭通道
+        self.receiver.recv().ok()
+    }
+}
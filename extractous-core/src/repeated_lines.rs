@@ -0,0 +1,117 @@
+use std::collections::{HashMap, HashSet};
+
+/// Marks the end of a page in the text handed over by `ai.yobix.PageBreakMarker`
+const PAGE_BREAK: char = '\u{c}';
+
+/// Documents with fewer pages are left as they are, a line on both pages of a two pages letter
+/// is as likely to be content as a footer
+const MIN_PAGES: usize = 3;
+
+/// Keeps the first occurrence of the lines found on more than half of the pages, such as running
+/// headers and footers, drops the later ones and removes the page breaks. Lines are compared
+/// trimmed, with their runs of whitespace collapsed and their numbers ignored, so that
+/// `Page 2 of 10` and `Page 3 of 10` count as the same line.
+pub(crate) fn dedup_repeated_lines(text: &str) -> String {
+    let pages: Vec<&str> = text
+        .split(PAGE_BREAK)
+        .filter(|page| !page.trim().is_empty())
+        .collect();
+
+    let mut page_counts: HashMap<String, usize> = HashMap::new();
+    for page in &pages {
+        let keys: HashSet<String> = page.lines().filter_map(line_key).collect();
+        for key in keys {
+            *page_counts.entry(key).or_default() += 1;
+        }
+    }
+    let is_repeated = |key: &str| {
+        pages.len() >= MIN_PAGES
+            && page_counts
+                .get(key)
+                .is_some_and(|count| count * 2 > pages.len())
+    };
+
+    let mut out = String::with_capacity(text.len());
+    let mut seen = HashSet::new();
+    for line in text.split_inclusive('\n') {
+        let line_without_breaks = line.replace(PAGE_BREAK, "");
+        if let Some(key) = line_key(&line_without_breaks) {
+            if is_repeated(&key) && !seen.insert(key) {
+                continue;
+            }
+        }
+        out.push_str(&line_without_breaks);
+    }
+    out
+}
+
+/// The normalized line, None for a blank line. The number that
+/// [`crate::line_numbers::number_lines`] puts in front of the line is not part of it.
+fn line_key(line: &str) -> Option<String> {
+    let line = match line.split_once('\t') {
+        Some((number, rest))
+            if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            rest
+        }
+        _ => line,
+    };
+    let mut key = String::new();
+    for word in line.split_whitespace() {
+        if !key.is_empty() {
+            key.push(' ');
+        }
+        let mut in_number = false;
+        for c in word.chars() {
+            if c.is_ascii_digit() {
+                if !in_number {
+                    key.push('#');
+                }
+                in_number = true;
+            } else {
+                key.push(c);
+                in_number = false;
+            }
+        }
+    }
+    (!key.is_empty()).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dedup_repeated_lines;
+    use crate::line_numbers::number_lines;
+
+    #[test]
+    fn keeps_first_occurrence_of_running_footer() {
+        let text = "Report\n\nFirst page.\nACME  Confidential\nPage 1 of 3\n\u{c}\
+                    Second page.\nACME Confidential\nPage 2 of 3\n\u{c}\
+                    Third page.\nACME Confidential \nPage 3 of 3\n\u{c}\n";
+        assert_eq!(
+            dedup_repeated_lines(text),
+            "Report\n\nFirst page.\nACME  Confidential\nPage 1 of 3\n\
+             Second page.\n\
+             Third page.\n\n"
+        );
+    }
+
+    #[test]
+    fn keeps_lines_of_few_pages_or_less_than_half() {
+        // Only two pages
+        let text = "One\nFooter\n\u{c}Two\nFooter\n\u{c}";
+        assert_eq!(dedup_repeated_lines(text), "One\nFooter\nTwo\nFooter\n");
+
+        // On two of the five pages
+        let text = "A\nNote\n\u{c}B\nNote\n\u{c}C\n\u{c}D\n\u{c}E\n\u{c}";
+        assert_eq!(dedup_repeated_lines(text), "A\nNote\nB\nNote\nC\nD\nE\n");
+    }
+
+    #[test]
+    fn keeps_the_numbers_of_numbered_lines() {
+        let text = number_lines("One\n\nFooter\n\u{c}Two\n\nFooter\n\u{c}Three\n\nFooter\n");
+        assert_eq!(
+            dedup_repeated_lines(&text),
+            "1\tOne\n2\t\n3\tFooter\n4\tTwo\n5\t\n7\tThree\n8\t\n"
+        );
+    }
+}
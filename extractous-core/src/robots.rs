@@ -0,0 +1,239 @@
+/// Returns whether the robots.txt rules allow the user agent to fetch the url, as RFC 9309
+/// describes, see [`crate::UrlFetchConfig::set_respect_robots`]. Urls that are not http or https
+/// have no robots.txt and are always allowed.
+pub(crate) fn is_allowed(robots_txt: &str, user_agent: Option<&str>, url: &str) -> bool {
+    let Some(path) = url_path(url) else {
+        return true;
+    };
+    if path == "/robots.txt" {
+        return true;
+    }
+    let groups = parse_groups(robots_txt);
+    let token = user_agent.map(product_token);
+    let named: Vec<&Group> = groups
+        .iter()
+        .filter(|group| {
+            token
+                .as_deref()
+                .is_some_and(|token| group.agents.iter().any(|agent| agent == token))
+        })
+        .collect();
+    let applying = if named.is_empty() {
+        groups
+            .iter()
+            .filter(|group| group.agents.iter().any(|agent| agent == "*"))
+            .collect()
+    } else {
+        named
+    };
+    // The longest matching pattern wins, an allow rule over an equally long disallow rule
+    let mut best: Option<(usize, bool)> = None;
+    for (allow, pattern) in applying.iter().flat_map(|group| &group.rules) {
+        if pattern.is_empty() || !matches(pattern.as_bytes(), path.as_bytes()) {
+            continue;
+        }
+        let len = pattern.len();
+        let better = match best {
+            None => true,
+            Some((best_len, best_allow)) => {
+                len > best_len || (len == best_len && *allow && !best_allow)
+            }
+        };
+        if better {
+            best = Some((len, *allow));
+        }
+    }
+    !matches!(best, Some((_, false)))
+}
+
+/// A group of rules, with the agents of the `User-agent` lines that start it, lowercased
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<(bool, String)>,
+}
+
+fn parse_groups(robots_txt: &str) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    // Consecutive user-agent lines start a single group
+    let mut in_agents = false;
+    for line in robots_txt.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                if !in_agents {
+                    groups.push(Group {
+                        agents: Vec::new(),
+                        rules: Vec::new(),
+                    });
+                    in_agents = true;
+                }
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+            }
+            key @ ("allow" | "disallow") => {
+                in_agents = false;
+                // Rules before the first user-agent line belong to no group
+                if let Some(group) = groups.last_mut() {
+                    group.rules.push((key == "allow", value.to_string()));
+                }
+            }
+            // Other records, such as sitemap, do not end the user-agent lines of a group
+            _ => {}
+        }
+    }
+    groups
+}
+
+/// Returns the product token of the user agent, lowercased: `mybot` for `MyBot/1.0 (+https://…)`
+fn product_token(user_agent: &str) -> String {
+    user_agent
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Returns the path and query of an http or https url, the part the rules are matched against
+fn url_path(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return None;
+    }
+    let rest = rest.split('#').next().unwrap_or_default();
+    let path = match rest.find(['/', '?']) {
+        Some(start) => &rest[start..],
+        None => "",
+    };
+    Some(if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    })
+}
+
+/// Matches the start of the path against the pattern of a rule, where `*` stands for any
+/// characters and a trailing `$` for the end of the path. The rules come from the site, so on a
+/// mismatch only the last `*` is retried, which bounds the steps by the pattern length times the
+/// path length.
+fn matches(pattern: &[u8], path: &[u8]) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix(b"$") {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let (mut p, mut t) = (0, 0);
+    // The pattern position after the last `*` and the path position it was resumed at
+    let mut star: Option<(usize, usize)> = None;
+    loop {
+        if p == pattern.len() {
+            if !anchored || t == path.len() {
+                return true;
+            }
+        } else if pattern[p] == b'*' {
+            p += 1;
+            star = Some((p, t));
+            continue;
+        } else if path.get(t) == Some(&pattern[p]) {
+            p += 1;
+            t += 1;
+            continue;
+        }
+        // Let the last `*` stand for one more character
+        match star {
+            Some((star_p, star_t)) if star_t < path.len() => {
+                p = star_p;
+                t = star_t + 1;
+                star = Some((star_p, t));
+            }
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_allowed_test() {
+        let robots_txt = "# Example\n\
+                          User-agent: *\n\
+                          Disallow: /private/\n\
+                          Allow: /private/public.html\n\
+                          Disallow: /*.pdf$\n\
+                          \n\
+                          User-agent: MyBot\n\
+                          User-agent: OtherBot\n\
+                          Disallow: /\n\
+                          Allow: /open\n\
+                          Sitemap: https://example.com/sitemap.xml\n";
+        let allowed = |agent, url| is_allowed(robots_txt, agent, url);
+
+        assert!(allowed(None, "https://example.com/"));
+        assert!(!allowed(None, "https://example.com/private/a.html"));
+        assert!(allowed(None, "https://example.com/private/public.html"));
+        assert!(!allowed(None, "https://example.com/docs/a.pdf"));
+        assert!(allowed(None, "https://example.com/docs/a.pdf?page=2"));
+        assert!(allowed(Some("AnyBot/2.0"), "https://example.com/docs/"));
+
+        // The named group replaces the * group, whatever the case and the version
+        assert!(!allowed(
+            Some("mybot/1.0 (+https://example.com)"),
+            "http://example.com/docs/"
+        ));
+        assert!(!allowed(Some("OtherBot"), "http://example.com:8080/"));
+        assert!(allowed(Some("MyBot"), "http://example.com/open/a.html"));
+        assert!(allowed(Some("MyBot"), "http://example.com/robots.txt"));
+
+        // Only http and https urls have a robots.txt
+        assert!(allowed(Some("MyBot"), "file:///tmp/a.html"));
+    }
+
+    #[test]
+    fn is_allowed_without_rules_test() {
+        assert!(is_allowed("", None, "https://example.com/a"));
+        assert!(is_allowed(
+            "User-agent: *\nDisallow:\n",
+            None,
+            "https://example.com/a"
+        ));
+        assert!(!is_allowed(
+            "User-agent: *\nDisallow: /\n",
+            None,
+            "https://example.com"
+        ));
+    }
+
+    #[test]
+    fn matches_test() {
+        assert!(matches(b"/a", b"/abc"));
+        assert!(!matches(b"/abc", b"/a"));
+        assert!(matches(b"/*/c", b"/a/b/c"));
+        assert!(matches(b"/a$", b"/a"));
+        assert!(!matches(b"/a$", b"/ab"));
+        assert!(matches(b"*", b""));
+        assert!(matches(b"/a*$", b"/abc"));
+        assert!(matches(b"/*.pdf$", b"/a.pdf.pdf"));
+        assert!(!matches(b"/*.pdf$", b"/a.pdf?x"));
+    }
+
+    #[test]
+    fn matches_many_wildcards_test() {
+        // Tried every way to spread the path over the `*`s before, which never ended
+        let path = format!("/{}", "a".repeat(200));
+        assert!(!matches(b"/*a*a*a*a*a*a*b", path.as_bytes()));
+        assert!(matches(b"/*a*a*a*a*a*a*a", path.as_bytes()));
+
+        let pattern = format!("/{}b$", "*a".repeat(100));
+        let path = format!("/{}b", "a".repeat(1000));
+        assert!(matches(pattern.as_bytes(), path.as_bytes()));
+        assert!(!matches(
+            pattern.as_bytes(),
+            &path.as_bytes()[..path.len() - 1]
+        ));
+    }
+}
@@ -0,0 +1,195 @@
+use strum_macros::{Display, EnumString};
+use unicode_script::{Script as UScript, UnicodeScript};
+
+/// Default minimum number of characters of a foreign script a run must contain to be removed
+pub const DEFAULT_SCRIPT_FILTER_THRESHOLD: usize = 20;
+
+/// Metadata key of the dominant script of the content, see [`Extractor::set_detect_script`]
+///
+/// [`Extractor::set_detect_script`]: crate::Extractor::set_detect_script
+pub(crate) const DOMINANT_SCRIPT_KEY: &str = "extractous:dominant_script";
+
+/// Writing scripts supported by the script filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Hangul,
+    /// Chinese, Japanese and Korean scripts: Han, Hiragana, Katakana, Bopomofo and Hangul
+    Cjk,
+}
+
+impl Script {
+    fn matches(&self, script: UScript) -> bool {
+        match self {
+            Script::Latin => script == UScript::Latin,
+            Script::Cyrillic => script == UScript::Cyrillic,
+            Script::Greek => script == UScript::Greek,
+            Script::Arabic => script == UScript::Arabic,
+            Script::Hebrew => script == UScript::Hebrew,
+            Script::Devanagari => script == UScript::Devanagari,
+            Script::Thai => script == UScript::Thai,
+            Script::Hangul => script == UScript::Hangul,
+            Script::Cjk => matches!(
+                script,
+                UScript::Han
+                    | UScript::Hiragana
+                    | UScript::Katakana
+                    | UScript::Bopomofo
+                    | UScript::Hangul
+            ),
+        }
+    }
+}
+
+/// Removes the runs of text that are not written in the `target` script.
+///
+/// A run starts at a character of another script and extends up to the last such character
+/// before the next character of the target script. Characters that belong to no particular
+/// script (spaces, digits, punctuation, ...) do not break a run. Runs with fewer than
+/// `threshold` foreign characters are kept, so short foreign words and names survive.
+/// Line breaks inside removed runs are preserved to keep the paragraph structure.
+pub(crate) fn filter_script(text: &str, target: Script, threshold: usize) -> String {
+    let mut out = String::with_capacity(text.len());
+    // Start byte offset, end byte offset (after the last foreign char) and foreign char count
+    let mut run: Option<(usize, usize, usize)> = None;
+
+    for (idx, c) in text.char_indices() {
+        let script = c.script();
+        if matches!(
+            script,
+            UScript::Common | UScript::Inherited | UScript::Unknown
+        ) {
+            if run.is_none() {
+                out.push(c);
+            }
+            continue;
+        }
+
+        if target.matches(script) {
+            if let Some((start, end, count)) = run.take() {
+                flush_run(
+                    &mut out,
+                    &text[start..end],
+                    &text[end..idx],
+                    count,
+                    threshold,
+                );
+            }
+            out.push(c);
+        } else {
+            let end = idx + c.len_utf8();
+            run = match run {
+                Some((start, _, count)) => Some((start, end, count + 1)),
+                None => Some((idx, end, 1)),
+            };
+        }
+    }
+
+    if let Some((start, end, count)) = run {
+        flush_run(&mut out, &text[start..end], &text[end..], count, threshold);
+    }
+    out
+}
+
+/// Returns the script most of the letters of the text are written in, or `None` when it has no
+/// letters of the supported scripts. Han, Hiragana, Katakana and Bopomofo count as
+/// [`Script::Cjk`], while Hangul counts as [`Script::Hangul`] since Korean separates its words
+/// with spaces. On a tie the script listed first in [`Script`] wins.
+pub fn dominant_script(text: &str) -> Option<Script> {
+    // Hangul comes before Cjk, which also matches it
+    const SCRIPTS: [Script; 9] = [
+        Script::Latin,
+        Script::Cyrillic,
+        Script::Greek,
+        Script::Arabic,
+        Script::Hebrew,
+        Script::Devanagari,
+        Script::Thai,
+        Script::Hangul,
+        Script::Cjk,
+    ];
+    let mut counts = [0usize; SCRIPTS.len()];
+    for c in text.chars() {
+        let script = c.script();
+        if let Some(i) = SCRIPTS.iter().position(|s| s.matches(script)) {
+            counts[i] += 1;
+        }
+    }
+    let mut dominant = None;
+    let mut max = 0;
+    for (script, count) in SCRIPTS.into_iter().zip(counts) {
+        if count > max {
+            dominant = Some(script);
+            max = count;
+        }
+    }
+    dominant
+}
+
+/// Writes a foreign run followed by the neutral characters that trail it. When the run is
+/// removed only the whitespace of the tail is kept, so its closing punctuation goes with it.
+fn flush_run(out: &mut String, run: &str, tail: &str, count: usize, threshold: usize) {
+    if count < threshold {
+        out.push_str(run);
+        out.push_str(tail);
+    } else {
+        out.extend(run.chars().filter(|&c| c == '\n'));
+        out.extend(tail.chars().filter(|c| c.is_whitespace()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dominant_script, filter_script, Script};
+
+    #[test]
+    fn removes_long_foreign_runs() {
+        let text = "这是中文内容。\nThis is a long English boilerplate paragraph.\n更多中文内容。";
+        let result = filter_script(text, Script::Cjk, 20);
+        assert_eq!(result, "这是中文内容。\n\n更多中文内容。");
+    }
+
+    #[test]
+    fn keeps_short_foreign_runs() {
+        let text = "我们使用 Rust 编写代码";
+        assert_eq!(filter_script(text, Script::Cjk, 20), text);
+    }
+
+    #[test]
+    fn keeps_neutral_characters_around_runs() {
+        let text = "Hello world, 2024! Привет мир и всем остальным людям на планете. Bye";
+        let result = filter_script(text, Script::Latin, 10);
+        assert_eq!(result, "Hello world, 2024!  Bye");
+    }
+
+    #[test]
+    fn removes_trailing_run() {
+        let text = "Just latin text. Долгий русский текст в конце документа";
+        let result = filter_script(text, Script::Latin, 10);
+        assert_eq!(result, "Just latin text. ");
+    }
+
+    #[test]
+    fn detects_dominant_script() {
+        assert_eq!(
+            dominant_script("我们使用 Rust 编写代码，速度很快。"),
+            Some(Script::Cjk)
+        );
+        assert_eq!(dominant_script("日本語のテキストです"), Some(Script::Cjk));
+        assert_eq!(dominant_script("한국어 텍스트입니다"), Some(Script::Hangul));
+        assert_eq!(dominant_script("Hello, Мир!"), Some(Script::Latin));
+        assert_eq!(dominant_script("2024-03-04 12:00"), None);
+    }
+
+    #[test]
+    fn parses_script_names() {
+        assert_eq!("Cjk".parse::<Script>().unwrap(), Script::Cjk);
+        assert_eq!(Script::Latin.to_string(), "Latin");
+    }
+}
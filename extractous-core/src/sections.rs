@@ -0,0 +1,245 @@
+use crate::errors::ExtractResult;
+use crate::paragraphs::is_block_element;
+use crate::{ContentHandler, Extractor};
+
+/// A section of a document: a heading and everything up to the next heading of the same or a
+/// higher level. Returned by [`Extractor::extract_file_sections`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Section {
+    /// Text of the heading, `None` for the content that precedes the first heading
+    pub heading: Option<String>,
+    /// Heading level from 1 (`h1`) to 6 (`h6`), 0 for the content without heading
+    pub level: u8,
+    /// Paragraphs between the heading and its first subsection, separated by new lines
+    pub content: String,
+    /// Sections under lower level headings
+    pub subsections: Vec<Section>,
+}
+
+fn heading_level(name: &str) -> Option<u8> {
+    match name {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+pub(crate) fn extract_file_sections(
+    extractor: &Extractor,
+    file_path: &str,
+) -> ExtractResult<Vec<Section>> {
+    let mut handler = SectionHandler::default();
+    extractor.extract_file_with_handler(file_path, &mut handler)?;
+    Ok(handler.into_sections())
+}
+
+/// Splits the body into a flat list of sections, one per heading
+#[derive(Default)]
+struct SectionHandler {
+    in_body: bool,
+    /// Level of the heading element being read, if any
+    heading: Option<u8>,
+    text: String,
+    /// Content that precedes the first heading
+    preamble: String,
+    sections: Vec<Section>,
+}
+
+impl SectionHandler {
+    fn flush(&mut self) {
+        let paragraph = self.text.trim();
+        if !paragraph.is_empty() {
+            let content = match self.sections.last_mut() {
+                Some(section) => &mut section.content,
+                None => &mut self.preamble,
+            };
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(paragraph);
+        }
+        self.text.clear();
+    }
+
+    /// Nests the sections under the closest preceding heading of a higher level
+    fn into_sections(mut self) -> Vec<Section> {
+        self.flush();
+        let mut roots = Vec::new();
+        if !self.preamble.is_empty() || self.sections.is_empty() {
+            roots.push(Section {
+                content: self.preamble,
+                ..Section::default()
+            });
+        }
+
+        let mut open: Vec<Section> = Vec::new();
+        for section in self.sections {
+            close_sections(&mut open, &mut roots, section.level);
+            open.push(section);
+        }
+        close_sections(&mut open, &mut roots, 0);
+        roots
+    }
+}
+
+/// Closes the open sections with a level greater or equal to `level`, attaching each to its
+/// parent, or to the roots for top-level sections
+fn close_sections(open: &mut Vec<Section>, roots: &mut Vec<Section>, level: u8) {
+    while open.last().is_some_and(|section| section.level >= level) {
+        let section = open.pop().unwrap();
+        match open.last_mut() {
+            Some(parent) => parent.subsections.push(section),
+            None => roots.push(section),
+        }
+    }
+}
+
+impl ContentHandler for SectionHandler {
+    fn start_element(&mut self, name: &str, _attributes: &[(String, String)]) {
+        if name == "body" {
+            self.in_body = true;
+        }
+        if self.heading.is_some() {
+            return;
+        }
+        if let Some(level) = heading_level(name) {
+            self.flush();
+            self.heading = Some(level);
+        } else if is_block_element(name) {
+            self.flush();
+        }
+    }
+
+    fn end_element(&mut self, name: &str) {
+        match self.heading {
+            Some(level) if heading_level(name) == Some(level) => {
+                self.heading = None;
+                let heading = self.text.trim().to_string();
+                self.text.clear();
+                // Empty headings are layout artifacts, their content stays in the current section
+                if !heading.is_empty() {
+                    self.sections.push(Section {
+                        heading: Some(heading),
+                        level,
+                        ..Section::default()
+                    });
+                }
+            }
+            Some(_) => {}
+            None if is_block_element(name) => self.flush(),
+            None => {}
+        }
+        if name == "body" {
+            self.in_body = false;
+        }
+    }
+
+    fn characters(&mut self, text: &str) {
+        if self.in_body {
+            self.text.push_str(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Section, SectionHandler};
+    use crate::ContentHandler;
+
+    fn element(handler: &mut SectionHandler, name: &str, text: &str) {
+        handler.start_element(name, &[]);
+        handler.characters(text);
+        handler.end_element(name);
+    }
+
+    fn section(heading: &str, level: u8, content: &str, subsections: Vec<Section>) -> Section {
+        Section {
+            heading: Some(heading.to_string()),
+            level,
+            content: content.to_string(),
+            subsections,
+        }
+    }
+
+    #[test]
+    fn builds_section_tree() {
+        let mut handler = SectionHandler::default();
+        handler.start_element("body", &[]);
+        element(&mut handler, "p", "Preamble");
+        element(&mut handler, "h1", "Introduction");
+        element(&mut handler, "p", "Intro text");
+        element(&mut handler, "h2", "Background");
+        element(&mut handler, "p", "First");
+        element(&mut handler, "p", "Second");
+        element(&mut handler, "h3", "Details");
+        element(&mut handler, "p", "Detail text");
+        element(&mut handler, "h2", "Goals");
+        element(&mut handler, "h1", "Results");
+        element(&mut handler, "p", "Result text");
+        handler.end_element("body");
+
+        assert_eq!(
+            handler.into_sections(),
+            vec![
+                Section {
+                    content: "Preamble".to_string(),
+                    ..Section::default()
+                },
+                section(
+                    "Introduction",
+                    1,
+                    "Intro text",
+                    vec![
+                        section(
+                            "Background",
+                            2,
+                            "First\nSecond",
+                            vec![section("Details", 3, "Detail text", vec![])]
+                        ),
+                        section("Goals", 2, "", vec![]),
+                    ]
+                ),
+                section("Results", 1, "Result text", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn skipped_levels_nest_under_closest_heading() {
+        let mut handler = SectionHandler::default();
+        handler.start_element("body", &[]);
+        element(&mut handler, "h3", "Deep");
+        element(&mut handler, "h1", "Top");
+        element(&mut handler, "h4", "Nested");
+        handler.end_element("body");
+
+        assert_eq!(
+            handler.into_sections(),
+            vec![
+                section("Deep", 3, "", vec![]),
+                section("Top", 1, "", vec![section("Nested", 4, "", vec![])]),
+            ]
+        );
+    }
+
+    #[test]
+    fn document_without_headings_is_a_single_section() {
+        let mut handler = SectionHandler::default();
+        handler.start_element("body", &[]);
+        element(&mut handler, "p", "One");
+        element(&mut handler, "p", "Two");
+        handler.end_element("body");
+
+        assert_eq!(
+            handler.into_sections(),
+            vec![Section {
+                content: "One\nTwo".to_string(),
+                ..Section::default()
+            }]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A sentence of the extracted text. Returned by [`crate::Extractor::extract_file_sentences`].
+///
+/// `start` and `end` are the byte offsets of the sentence in the text returned by
+/// [`crate::Extractor::extract_file_to_string`] for the same file and extractor, so that
+/// `&content[start..end] == text`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sentence {
+    /// Text of the sentence, without the surrounding whitespace
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Abbreviations that end with a period without ending the sentence, in lowercase. The ones that
+/// commonly end a sentence too, such as `etc.` or `Inc.`, are left out.
+const ABBREVIATIONS: [&str; 22] = [
+    "mr.", "mrs.", "ms.", "dr.", "prof.", "sr.", "jr.", "st.", "mt.", "vs.", "e.g.", "i.e.", "cf.",
+    "al.", "fig.", "figs.", "no.", "nos.", "vol.", "p.", "pp.", "approx.",
+];
+
+/// Splits the text into sentences, with their byte offsets in the text. Sentences are split on
+/// the sentence boundaries of Unicode (UAX #29), adjusted for the layout of extracted text:
+/// - a blank line always ends a sentence
+/// - a single line break ends a sentence unless the next line starts with a lowercase letter or
+///   the line ends with a comma or a hyphen, so that lines wrapped in the middle of a sentence,
+///   as in PDF documents, are joined while headings and list items stay apart
+/// - a period after a common abbreviation (`Dr.`, `e.g.`, ...) or an initial (`J.`) does not
+///   end the sentence
+pub fn split_sentences(text: &str) -> Vec<Sentence> {
+    // Soft line breaks become spaces, which keeps the byte offsets
+    let prepared = join_wrapped_lines(text);
+
+    let mut sentences: Vec<Sentence> = Vec::new();
+    // Whether the previous segment ended with a hard line break, which no abbreviation continues
+    let mut after_line_break = true;
+    for (offset, segment) in prepared.split_sentence_bound_indices() {
+        let trimmed_start = segment.len() - segment.trim_start().len();
+        let trimmed = segment.trim();
+        if trimmed.is_empty() {
+            after_line_break = true;
+            continue;
+        }
+        let start = offset + trimmed_start;
+        let end = start + trimmed.len();
+
+        match sentences.last_mut() {
+            Some(previous) if !after_line_break && ends_with_abbreviation(&previous.text) => {
+                previous.end = end;
+                previous.text = text[previous.start..end].to_string();
+            }
+            _ => sentences.push(Sentence {
+                text: text[start..end].to_string(),
+                start,
+                end,
+            }),
+        }
+        after_line_break = segment
+            .trim_end_matches([' ', '\t'])
+            .ends_with(['\n', '\r']);
+    }
+    sentences
+}
+
+/// Replaces the line breaks that do not end a sentence with spaces
+fn join_wrapped_lines(text: &str) -> String {
+    let mut prepared = text.as_bytes().to_vec();
+    let lines: Vec<(usize, &str)> = text
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+        .collect();
+
+    for pair in lines.windows(2) {
+        let (offset, line) = pair[0];
+        let next = pair[1].1;
+        let content = line.trim_end();
+        let next_content = next.trim_start_matches([' ', '\t']);
+        if content.is_empty() || next_content.trim().is_empty() {
+            // A blank line
+            continue;
+        }
+        let continues = content.ends_with([',', '-'])
+            || next_content.chars().next().is_some_and(char::is_lowercase);
+        if continues {
+            for byte in &mut prepared[offset + content.len()..offset + line.len()] {
+                if matches!(byte, b'\n' | b'\r') {
+                    *byte = b' ';
+                }
+            }
+        }
+    }
+    // Only ASCII bytes were replaced by other ASCII bytes
+    String::from_utf8(prepared).unwrap_or_else(|_| text.to_string())
+}
+
+fn ends_with_abbreviation(sentence: &str) -> bool {
+    let last_word = sentence
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches(['(', '"', '\'']);
+    let lower = last_word.to_lowercase();
+    if ABBREVIATIONS.contains(&lower.as_str()) {
+        return true;
+    }
+    // An initial, such as the J. of J. Smith
+    let mut chars = last_word.chars();
+    matches!(
+        (chars.next(), chars.next(), chars.next()),
+        (Some(initial), Some('.'), None) if initial.is_uppercase()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_sentences;
+
+    fn texts(text: &str) -> Vec<String> {
+        split_sentences(text)
+            .into_iter()
+            .map(|sentence| {
+                assert_eq!(&text[sentence.start..sentence.end], sentence.text);
+                sentence.text
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_sentences() {
+        assert_eq!(
+            texts("  The results are in. Did it work? Yes! Revenue grew 3.5% in Q3.\n"),
+            vec![
+                "The results are in.",
+                "Did it work?",
+                "Yes!",
+                "Revenue grew 3.5% in Q3."
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_abbreviations_and_initials() {
+        assert_eq!(
+            texts(
+                "Dr. Smith met Prof. Jones, e.g. at the lab. J. R. Tolkien wrote it. It ends here."
+            ),
+            vec![
+                "Dr. Smith met Prof. Jones, e.g. at the lab.",
+                "J. R. Tolkien wrote it.",
+                "It ends here."
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_line_breaks() {
+        let text = "Introduction\nThe quarterly report covers\nthe third quarter,\nWhen sales grew. Costs fell.\n\nSummary\n";
+        assert_eq!(
+            texts(text),
+            vec![
+                "Introduction",
+                "The quarterly report covers\nthe third quarter,\nWhen sales grew.",
+                "Costs fell.",
+                "Summary"
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_byte_offsets() {
+        let text = "Café crème. Ça va?";
+        let sentences = split_sentences(text);
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(sentences[1].start, "Café crème. ".len());
+        assert_eq!(sentences[1].end, text.len());
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashSet;
+use unicode_script::{Script as UScript, UnicodeScript};
+
+/// Metadata key of the SimHash of the content, see [`Extractor::set_compute_simhash`]
+///
+/// [`Extractor::set_compute_simhash`]: crate::Extractor::set_compute_simhash
+pub(crate) const SIMHASH_KEY: &str = "X-SimHash";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Returns the 64-bit SimHash fingerprint of the text. Near-duplicate texts, such as reformatted
+/// copies or versions with minor edits, get fingerprints that differ in few bits, so the number
+/// of differing bits, `(a ^ b).count_ones()`, measures how far apart two texts are.
+///
+/// The algorithm is fixed and gives the same fingerprint across versions:
+/// 1. The text is lowercased and split into tokens: the runs of letters and digits, except for
+///    Han, Hiragana and Katakana characters, which are tokens on their own since these scripts
+///    do not separate words.
+/// 2. Each distinct token counts once, however often it occurs.
+/// 3. Each token is hashed with 64-bit FNV-1a over its UTF-8 bytes.
+/// 4. Bit `i` of the fingerprint is set when more token hashes have bit `i` set than unset.
+///
+/// A text without tokens has the fingerprint 0.
+pub fn simhash(text: &str) -> u64 {
+    let mut weights = [0i64; 64];
+    for token in tokens(text) {
+        let hash = fnv1a(token.as_bytes());
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, &weight)| weight > 0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | (1 << bit))
+}
+
+/// Formats a fingerprint the way it is stored in the metadata, as 16 lowercase hex digits
+pub(crate) fn format_simhash(fingerprint: u64) -> String {
+    format!("{:016x}", fingerprint)
+}
+
+fn tokens(text: &str) -> HashSet<String> {
+    let mut tokens = HashSet::new();
+    let mut token = String::new();
+    for c in text.chars() {
+        if !c.is_alphanumeric() {
+            if !token.is_empty() {
+                tokens.insert(std::mem::take(&mut token));
+            }
+        } else if matches!(
+            c.script(),
+            UScript::Han | UScript::Hiragana | UScript::Katakana
+        ) {
+            if !token.is_empty() {
+                tokens.insert(std::mem::take(&mut token));
+            }
+            tokens.insert(c.to_string());
+        } else {
+            token.extend(c.to_lowercase());
+        }
+    }
+    if !token.is_empty() {
+        tokens.insert(token);
+    }
+    tokens
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, simhash};
+
+    #[test]
+    fn is_stable() {
+        // Reference values of the FNV-1a specification
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+
+        assert_eq!(simhash(""), 0);
+        assert_eq!(simhash("a"), 0xaf63dc4c8601ec8c);
+        // Case, punctuation, spacing and repeated words do not matter
+        assert_eq!(
+            simhash("The quick brown fox"),
+            simhash("the QUICK, brown... fox fox")
+        );
+    }
+
+    #[test]
+    fn near_duplicates_are_close() {
+        let original = "Quarterly revenue grew by twelve percent, driven by strong demand for \
+            cloud services in Europe and North America, while operating costs stayed flat.";
+        let edited = "Quarterly revenue grew by eleven percent, driven by strong demand for \
+            cloud services in Europe and North America, while operating costs stayed flat.";
+        let other = "The committee will meet on Tuesday to review the new safety guidelines \
+            for the laboratory and to vote on the proposed budget for next year.";
+
+        let distance = |a: &str, b: &str| (simhash(a) ^ simhash(b)).count_ones();
+        assert!(distance(original, edited) < distance(original, other));
+        assert!(
+            distance(original, edited) <= 10,
+            "{}",
+            distance(original, edited)
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use crate::errors::{Error, ExtractResult};
+
+/// A contact of a vCard (.vcf) address book
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Contact {
+    /// Formatted name (`FN`), or the name assembled from the `N` components when missing
+    pub name: Option<String>,
+    /// Email addresses in the order of the card
+    pub emails: Vec<String>,
+    /// Phone numbers in the order of the card
+    pub phones: Vec<String>,
+}
+
+/// An event of an iCalendar (.ics) calendar
+///
+/// Dates are returned as written in the calendar, e.g. `20240105T090000Z` for a UTC date-time,
+/// `20240105T090000` for a local or `TZID` qualified one, or `20240105` for an all-day event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub summary: Option<String>,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub location: Option<String>,
+}
+
+/// A content line `NAME;PARAM=...:value` of a vCard or iCalendar file
+struct Property<'a> {
+    name: String,
+    value: &'a str,
+}
+
+/// Joins folded lines: a line starting with a space or a tab continues the previous one
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match line.strip_prefix([' ', '\t']) {
+            Some(continuation) if !lines.is_empty() => {
+                lines.last_mut().unwrap().push_str(continuation)
+            }
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property<'_>> {
+    // The value starts at the first colon outside of a quoted parameter value
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(i),
+        _ => None,
+    })?;
+
+    let name = line[..colon].split(';').next().unwrap_or_default();
+    // Drop the group prefix of grouped vCard properties, e.g. `item1.EMAIL`
+    let name = name.rsplit('.').next().unwrap_or(name);
+    Some(Property {
+        name: name.trim().to_ascii_uppercase(),
+        value: &line[colon + 1..],
+    })
+}
+
+/// Resolves the backslash escapes of a text value
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Splits a structured value on the unescaped `;` separators
+fn split_components(value: &str) -> Vec<String> {
+    let mut components = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            ';' if !escaped => {
+                components.push(unescape(&value[start..i]));
+                start = i + 1;
+            }
+            _ => escaped = false,
+        }
+    }
+    components.push(unescape(&value[start..]));
+    components
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Parses the contacts of a vCard file
+pub(crate) fn parse_vcard(text: &str) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+    let mut current: Option<Contact> = None;
+    // Name assembled from the N property, used when the card has no FN
+    let mut structured_name: Option<String> = None;
+
+    for line in unfold(text) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        match property.name.as_str() {
+            "BEGIN" if property.value.eq_ignore_ascii_case("VCARD") => {
+                current = Some(Contact::default());
+                structured_name = None;
+            }
+            "END" if property.value.eq_ignore_ascii_case("VCARD") => {
+                if let Some(mut contact) = current.take() {
+                    if contact.name.is_none() {
+                        contact.name = structured_name.take();
+                    }
+                    contacts.push(contact);
+                }
+            }
+            name => {
+                let Some(contact) = current.as_mut() else {
+                    continue;
+                };
+                match name {
+                    "FN" => contact.name = non_empty(unescape(property.value)),
+                    "N" => {
+                        // family;given;additional;prefixes;suffixes
+                        let components = split_components(property.value);
+                        let order = [3, 1, 2, 0, 4];
+                        let parts: Vec<&str> = order
+                            .iter()
+                            .filter_map(|&i| components.get(i))
+                            .map(|part| part.trim())
+                            .filter(|part| !part.is_empty())
+                            .collect();
+                        structured_name = non_empty(parts.join(" "));
+                    }
+                    "EMAIL" => {
+                        if let Some(email) = non_empty(unescape(property.value)) {
+                            contact.emails.push(email);
+                        }
+                    }
+                    "TEL" => {
+                        let value = property.value.trim();
+                        let value = value.strip_prefix("tel:").unwrap_or(value);
+                        if let Some(phone) = non_empty(unescape(value)) {
+                            contact.phones.push(phone);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    contacts
+}
+
+/// Parses the events of an iCalendar file. Properties of components nested in an event, such
+/// as alarms, are ignored
+pub(crate) fn parse_icalendar(text: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<CalendarEvent> = None;
+    // Depth of the components opened inside the current event
+    let mut nested = 0;
+
+    for line in unfold(text) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        let Some(event) = current.as_mut() else {
+            if property.name == "BEGIN" && property.value.eq_ignore_ascii_case("VEVENT") {
+                current = Some(CalendarEvent::default());
+                nested = 0;
+            }
+            continue;
+        };
+
+        match property.name.as_str() {
+            "BEGIN" => nested += 1,
+            "END" if nested > 0 => nested -= 1,
+            "END" if property.value.eq_ignore_ascii_case("VEVENT") => {
+                events.extend(current.take());
+            }
+            _ if nested > 0 => {}
+            "SUMMARY" => event.summary = non_empty(unescape(property.value)),
+            "LOCATION" => event.location = non_empty(unescape(property.value)),
+            "DTSTART" => event.start = non_empty(property.value.to_string()),
+            "DTEND" => event.end = non_empty(property.value.to_string()),
+            _ => {}
+        }
+    }
+    events
+}
+
+/// Reads a text file, replacing invalid UTF-8 sequences
+pub(crate) fn read_text(file_path: &str) -> ExtractResult<String> {
+    let bytes = std::fs::read(file_path)
+        .map_err(|e| Error::IoError(format!("Could not open file: {}", e)))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_icalendar, parse_vcard, CalendarEvent, Contact};
+
+    #[test]
+    fn parses_vcard_contacts() {
+        let text = "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;John;;Dr.;\r\nEMAIL;TYPE=work:john@\r\n example.com\r\nTEL;TYPE=cell:+1 555 0100\r\nEND:VCARD\r\n\
+                    BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Roe\\, PhD\r\nitem1.EMAIL:jane@example.org\r\nTEL;VALUE=uri:tel:+44-20-7946-0000\r\nEND:VCARD\r\n";
+        assert_eq!(
+            parse_vcard(text),
+            vec![
+                Contact {
+                    name: Some("Dr. John Doe".to_string()),
+                    emails: vec!["john@example.com".to_string()],
+                    phones: vec!["+1 555 0100".to_string()],
+                },
+                Contact {
+                    name: Some("Jane Roe, PhD".to_string()),
+                    emails: vec!["jane@example.org".to_string()],
+                    phones: vec!["+44-20-7946-0000".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_icalendar_events() {
+        let text = "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Team sync\nDTSTART;TZID=Europe/Berlin:20240105T090000\nDTEND;TZID=Europe/Berlin:20240105T093000\nLOCATION:Room 1\\, 2nd floor\nBEGIN:VALARM\nSUMMARY:Reminder\nEND:VALARM\nEND:VEVENT\n\
+                    BEGIN:VEVENT\nSUMMARY:Holiday\nDTSTART;VALUE=DATE:20240101\nEND:VEVENT\nEND:VCALENDAR\n";
+        assert_eq!(
+            parse_icalendar(text),
+            vec![
+                CalendarEvent {
+                    summary: Some("Team sync".to_string()),
+                    start: Some("20240105T090000".to_string()),
+                    end: Some("20240105T093000".to_string()),
+                    location: Some("Room 1, 2nd floor".to_string()),
+                },
+                CalendarEvent {
+                    summary: Some("Holiday".to_string()),
+                    start: Some("20240101".to_string()),
+                    end: None,
+                    location: None,
+                },
+            ]
+        );
+    }
+}
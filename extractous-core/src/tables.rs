@@ -0,0 +1,258 @@
+use crate::errors::ExtractResult;
+use crate::{ContentHandler, Extractor};
+
+/// A table of a document, its header row split from its data rows. Returned by
+/// [`Extractor::extract_file_tables`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    /// Names of the columns, `None` when the table has no recognizable header row
+    pub header: Option<Vec<String>>,
+    /// Text of the cells of each data row, with their whitespace collapsed. Rows without any
+    /// text are left out
+    pub rows: Vec<Vec<String>>,
+}
+
+pub(crate) fn extract_file_tables(
+    extractor: &Extractor,
+    file_path: &str,
+) -> ExtractResult<Vec<Table>> {
+    let mut handler = TableHandler::default();
+    extractor.extract_file_with_handler(file_path, &mut handler)?;
+    Ok(handler.tables)
+}
+
+struct Row {
+    cells: Vec<String>,
+    /// Whether the row is in a `<thead>` or only has `<th>` cells
+    is_header: bool,
+}
+
+/// A table being read, `index` is its position in the tables of the document
+#[derive(Default)]
+struct OpenTable {
+    index: usize,
+    rows: Vec<Row>,
+    in_thead: bool,
+    cells: Vec<String>,
+    all_th: bool,
+    cell: Option<String>,
+}
+
+impl OpenTable {
+    fn end_row(&mut self) {
+        if !self.cells.is_empty() {
+            self.rows.push(Row {
+                cells: std::mem::take(&mut self.cells),
+                is_header: self.in_thead || self.all_th,
+            });
+        }
+        self.all_th = true;
+    }
+
+    fn into_table(mut self) -> Table {
+        self.end_row();
+        let mut rows: Vec<Row> = self
+            .rows
+            .into_iter()
+            .filter(|row| row.cells.iter().any(|cell| !cell.is_empty()))
+            .collect();
+
+        let marked = rows.iter().take_while(|row| row.is_header).count();
+        let header = if marked > 0 {
+            Some(merge_header_rows(rows.drain(..marked).map(|row| row.cells)))
+        } else if rows.len() > 1 && looks_like_header(&rows[0].cells, &rows[1..]) {
+            Some(rows.remove(0).cells)
+        } else {
+            None
+        };
+        Table {
+            header,
+            rows: rows.into_iter().map(|row| row.cells).collect(),
+        }
+    }
+}
+
+/// Joins the names of each column over the header rows, e.g. a group name above the names of
+/// its columns
+fn merge_header_rows(rows: impl Iterator<Item = Vec<String>>) -> Vec<String> {
+    let mut header: Vec<String> = Vec::new();
+    for row in rows {
+        for (i, cell) in row.into_iter().enumerate() {
+            if i == header.len() {
+                header.push(cell);
+            } else if !cell.is_empty() {
+                if !header[i].is_empty() {
+                    header[i].push(' ');
+                }
+                header[i].push_str(&cell);
+            }
+        }
+    }
+    header
+}
+
+/// Tables without `<th>` cells, such as those of spreadsheets and CSV files, have a header when
+/// their first row holds distinct names and no numbers, above a column that holds only numbers
+fn looks_like_header(first: &[String], rows: &[Row]) -> bool {
+    let names: Vec<&String> = first.iter().filter(|cell| !cell.is_empty()).collect();
+    if names.is_empty() || names.iter().any(|cell| is_number(cell)) {
+        return false;
+    }
+    if (1..names.len()).any(|i| names[..i].contains(&names[i])) {
+        return false;
+    }
+    first.iter().enumerate().any(|(column, name)| {
+        let mut values = rows
+            .iter()
+            .filter_map(|row| row.cells.get(column))
+            .filter(|cell| !cell.is_empty())
+            .peekable();
+        !name.is_empty() && values.peek().is_some() && values.all(|cell| is_number(cell))
+    })
+}
+
+/// Numbers as found in tables: `12`, `-3.5`, `1,234`, `$99`, `34.5%`
+fn is_number(cell: &str) -> bool {
+    let value = cell
+        .trim()
+        .trim_start_matches(['$', '€', '£', '¥'])
+        .trim_end_matches('%')
+        .replace(',', "");
+    !value.is_empty() && value.parse::<f64>().is_ok()
+}
+
+/// Collects the tables of the XHTML output, nested tables being tables of their own
+#[derive(Default)]
+struct TableHandler {
+    open: Vec<OpenTable>,
+    tables: Vec<Table>,
+}
+
+impl ContentHandler for TableHandler {
+    fn start_element(&mut self, name: &str, _attributes: &[(String, String)]) {
+        if name == "table" {
+            // The slot keeps the tables in the order they start, outer tables first
+            self.open.push(OpenTable {
+                index: self.tables.len(),
+                all_th: true,
+                ..OpenTable::default()
+            });
+            self.tables.push(Table::default());
+            return;
+        }
+        let Some(table) = self.open.last_mut() else {
+            return;
+        };
+        match name {
+            "thead" => table.in_thead = true,
+            "tr" => table.end_row(),
+            "td" | "th" => {
+                table.all_th &= name == "th";
+                table.cell = Some(String::new());
+            }
+            // Separates the paragraphs and lines of a cell
+            _ => {
+                if let Some(cell) = table.cell.as_mut() {
+                    cell.push(' ');
+                }
+            }
+        }
+    }
+
+    fn end_element(&mut self, name: &str) {
+        if name == "table" {
+            if let Some(table) = self.open.pop() {
+                let index = table.index;
+                self.tables[index] = table.into_table();
+            }
+            return;
+        }
+        let Some(table) = self.open.last_mut() else {
+            return;
+        };
+        match name {
+            "thead" => table.in_thead = false,
+            "tr" => table.end_row(),
+            "td" | "th" => {
+                if let Some(cell) = table.cell.take() {
+                    table
+                        .cells
+                        .push(cell.split_whitespace().collect::<Vec<_>>().join(" "));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn characters(&mut self, text: &str) {
+        if let Some(cell) = self.open.last_mut().and_then(|table| table.cell.as_mut()) {
+            cell.push_str(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Table, TableHandler};
+    use crate::ContentHandler;
+
+    fn row(handler: &mut TableHandler, cell: &str, cells: &[&str]) {
+        handler.start_element("tr", &[]);
+        for text in cells {
+            handler.start_element(cell, &[]);
+            handler.characters(text);
+            handler.end_element(cell);
+        }
+        handler.end_element("tr");
+    }
+
+    fn strings(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn splits_th_header_and_guesses_numeric_ones() {
+        let mut handler = TableHandler::default();
+        handler.start_element("table", &[]);
+        handler.start_element("thead", &[]);
+        row(&mut handler, "th", &["Region", "Sales"]);
+        handler.end_element("thead");
+        row(&mut handler, "td", &["North", " 1,200\n"]);
+        handler.end_element("table");
+
+        // No th, a numeric column under a row of names
+        handler.start_element("table", &[]);
+        row(&mut handler, "td", &["Item", "Price", ""]);
+        row(&mut handler, "td", &["Widget", "$4.50", ""]);
+        row(&mut handler, "td", &["", "", ""]);
+        row(&mut handler, "td", &["Gadget", "12", "new"]);
+        handler.end_element("table");
+
+        // A layout table
+        handler.start_element("table", &[]);
+        row(&mut handler, "td", &["Name", "Alice"]);
+        row(&mut handler, "td", &["City", "Lyon"]);
+        handler.end_element("table");
+
+        assert_eq!(
+            handler.tables,
+            vec![
+                Table {
+                    header: Some(strings(&["Region", "Sales"])),
+                    rows: vec![strings(&["North", "1,200"])],
+                },
+                Table {
+                    header: Some(strings(&["Item", "Price", ""])),
+                    rows: vec![
+                        strings(&["Widget", "$4.50", ""]),
+                        strings(&["Gadget", "12", "new"])
+                    ],
+                },
+                Table {
+                    header: None,
+                    rows: vec![strings(&["Name", "Alice"]), strings(&["City", "Lyon"])],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+/// Bounds the total size of the byte buffers handed over to the VM as direct ByteBuffers at the
+/// same time. Every byte-based extraction takes a permit for the size of its buffer before the
+/// buffer is wrapped, and holds it until the VM is done reading it.
+struct BufferGate {
+    state: Mutex<GateState>,
+    released: Condvar,
+}
+
+struct GateState {
+    max_bytes: usize,
+    outstanding: usize,
+}
+
+static GATE: BufferGate = BufferGate {
+    state: Mutex::new(GateState {
+        max_bytes: usize::MAX,
+        outstanding: 0,
+    }),
+    released: Condvar::new(),
+};
+
+impl BufferGate {
+    // The state is two counters that are always left consistent, a panic elsewhere can not
+    // break it
+    fn lock(&self) -> MutexGuard<'_, GateState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Sets the maximum total size, in bytes, of the buffers of the byte-based extractions, the
+/// `extract_bytes*` methods, `detect_mime_bytes` and `detect_mime_candidates`, that the VM reads
+/// at the same time, across all extractions of the process. The VM reads these buffers in place
+/// through direct ByteBuffers, so this bounds the native memory they pin down. Extractions that
+/// would go over the limit wait until enough of the running ones are done; a buffer larger than
+/// the limit waits until no other buffer is in use and then runs alone. The streams of
+/// [`Extractor::extract_bytes`](crate::Extractor::extract_bytes) count until they are dropped.
+/// Extractions already waiting pick up a new limit immediately.
+/// Default: `usize::MAX`, no limit
+pub fn set_max_direct_buffer_bytes(max_bytes: usize) {
+    GATE.lock().max_bytes = max_bytes;
+    GATE.released.notify_all();
+}
+
+/// The share of the direct buffer limit taken by a buffer, given back when dropped
+#[derive(Debug)]
+pub(crate) struct DirectBufferPermit {
+    len: usize,
+}
+
+/// Waits until a buffer of the given length fits under the limit and takes its share
+pub(crate) fn acquire_direct_buffer(len: usize) -> DirectBufferPermit {
+    let mut state = GATE.lock();
+    while state.outstanding > 0 && state.outstanding.saturating_add(len) > state.max_bytes {
+        state = GATE
+            .released
+            .wait(state)
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+    state.outstanding += len;
+    DirectBufferPermit { len }
+}
+
+impl Drop for DirectBufferPermit {
+    fn drop(&mut self) {
+        GATE.lock().outstanding -= self.len;
+        GATE.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{acquire_direct_buffer, set_max_direct_buffer_bytes};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn waits_for_room_under_the_limit() {
+        set_max_direct_buffer_bytes(100);
+
+        let first = acquire_direct_buffer(60);
+        let (sender, receiver) = mpsc::channel();
+        let waiting = thread::spawn(move || {
+            let _second = acquire_direct_buffer(60);
+            sender.send(()).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        drop(first);
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        waiting.join().unwrap();
+
+        // Larger than the limit, it runs alone
+        drop(acquire_direct_buffer(500));
+
+        set_max_direct_buffer_bytes(usize::MAX);
+    }
+}
@@ -0,0 +1,122 @@
+use std::any::Any;
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use crate::errors::{Error, ExtractResult};
+use crate::tika::jni_utils::jni_jobject_to_string;
+use jni::objects::{JClass, JObject, JString};
+use jni::sys::{jlong, jstring};
+use jni::{JNIEnv, NativeMethod};
+
+const EMBEDDED_FILE_WRITER_CLASS: &str = "ai/yobix/EmbeddedFileWriter";
+
+/// State shared with the `ai.yobix.EmbeddedFileWriter` upcalls. The target creates the file each
+/// embedded document is written to, from its resource name, if any, and the extension of its
+/// detected type. A pointer to the context is
+/// handed to java as a `long`, so it must stay pinned on the stack until the parse call has
+/// returned.
+pub(crate) struct TargetContext<'t> {
+    target: &'t mut dyn FnMut(Option<&str>, &str) -> ExtractResult<PathBuf>,
+    error: Option<Error>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<'t> TargetContext<'t> {
+    pub(crate) fn new(
+        target: &'t mut dyn FnMut(Option<&str>, &str) -> ExtractResult<PathBuf>,
+    ) -> Self {
+        Self {
+            target,
+            error: None,
+            panic: None,
+        }
+    }
+
+    pub(crate) fn as_ptr(&mut self) -> jlong {
+        self as *mut Self as jlong
+    }
+
+    /// Resumes a panic raised by the target during the upcalls, if any, or returns the first
+    /// error it failed with.
+    pub(crate) fn finish(&mut self) -> ExtractResult<()> {
+        if let Some(payload) = self.panic.take() {
+            resume_unwind(payload);
+        }
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Binds the native method of `ai.yobix.EmbeddedFileWriter` to the upcall below.
+/// Registering again simply replaces the previous binding.
+pub(crate) fn register_embedded_target(env: &mut JNIEnv) -> ExtractResult<()> {
+    let methods = [NativeMethod {
+        name: "nativeTarget".into(),
+        sig: "(JLjava/lang/String;Ljava/lang/String;)Ljava/lang/String;".into(),
+        fn_ptr: native_target as *mut c_void,
+    }];
+    env.register_native_methods(EMBEDDED_FILE_WRITER_CLASS, &methods)?;
+    Ok(())
+}
+
+/// Returns the path of the file the document is written to. Once the target has failed or
+/// panicked, the later documents are refused: the SAXException thrown to abort the parse can be
+/// caught and recorded by the parser of an enclosing document, which then goes on with the next
+/// ones.
+extern "system" fn native_target<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+    name: JString<'local>,
+    extension: JString<'local>,
+) -> jstring {
+    // Safety: ptr comes from TargetContext::as_ptr and the context outlives the parse call that
+    // triggers the upcalls, which all happen on the calling thread
+    let ctx = unsafe { &mut *(ptr as *mut TargetContext) };
+    if ctx.panic.is_none() && ctx.error.is_none() {
+        let path = read_args(&mut env, name, extension).and_then(|(name, extension)| {
+            catch_unwind(AssertUnwindSafe(|| {
+                (ctx.target)(name.as_deref(), &extension)
+            }))
+            .map_err(|payload| {
+                ctx.panic = Some(payload);
+                Error::Unknown("The embedded target panicked".to_string())
+            })?
+        });
+        let path = path.and_then(|path| {
+            let path = path.to_str().ok_or_else(|| {
+                Error::IoError(format!("{} is not a valid UTF-8 path", path.display()))
+            })?;
+            Ok(env.new_string(path)?)
+        });
+        match path {
+            Ok(path) => return path.into_raw(),
+            Err(error) => {
+                if ctx.panic.is_none() {
+                    ctx.error = Some(error);
+                }
+            }
+        }
+    }
+    // A pending java exception left by a failed conversion is replaced by this one
+    let _ = env.exception_clear();
+    let _ = env.throw_new("org/xml/sax/SAXException", "Rust embedded target failed");
+    std::ptr::null_mut()
+}
+
+fn read_args<'local>(
+    env: &mut JNIEnv<'local>,
+    name: JString<'local>,
+    extension: JString<'local>,
+) -> ExtractResult<(Option<String>, String)> {
+    let name = if name.is_null() {
+        None
+    } else {
+        Some(jni_jobject_to_string(env, JObject::from(name))?)
+    };
+    let extension = jni_jobject_to_string(env, JObject::from(extension))?;
+    Ok((name, extension))
+}
@@ -0,0 +1,154 @@
+use std::any::Any;
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+use crate::errors::ExtractResult;
+use crate::tika::jni_utils::{jni_jobject_array_to_vec, jni_jobject_to_string};
+use crate::ContentHandler;
+use jni::objects::{JClass, JObjectArray, JString};
+use jni::sys::jlong;
+use jni::{JNIEnv, NativeMethod};
+
+const NATIVE_HANDLER_CLASS: &str = "ai/yobix/NativeContentHandler";
+
+/// State shared with the `ai.yobix.NativeContentHandler` upcalls. A pointer to it is handed to
+/// java as a `long`, so it must stay pinned on the stack until the parse call has returned.
+pub(crate) struct HandlerContext<'h> {
+    handler: &'h mut dyn ContentHandler,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<'h> HandlerContext<'h> {
+    pub(crate) fn new(handler: &'h mut dyn ContentHandler) -> Self {
+        Self {
+            handler,
+            panic: None,
+        }
+    }
+
+    pub(crate) fn as_ptr(&mut self) -> jlong {
+        self as *mut Self as jlong
+    }
+
+    /// Resumes a panic raised by the handler during the upcalls, if any
+    pub(crate) fn resume_panic(&mut self) {
+        if let Some(payload) = self.panic.take() {
+            resume_unwind(payload);
+        }
+    }
+}
+
+/// Binds the native methods of `ai.yobix.NativeContentHandler` to the upcalls below.
+/// Registering again simply replaces the previous binding.
+pub(crate) fn register_native_handler(env: &mut JNIEnv) -> ExtractResult<()> {
+    let methods = [
+        NativeMethod {
+            name: "nativeStartDocument".into(),
+            sig: "(J)V".into(),
+            fn_ptr: native_start_document as *mut c_void,
+        },
+        NativeMethod {
+            name: "nativeEndDocument".into(),
+            sig: "(J)V".into(),
+            fn_ptr: native_end_document as *mut c_void,
+        },
+        NativeMethod {
+            name: "nativeStartElement".into(),
+            sig: "(JLjava/lang/String;[Ljava/lang/String;)V".into(),
+            fn_ptr: native_start_element as *mut c_void,
+        },
+        NativeMethod {
+            name: "nativeEndElement".into(),
+            sig: "(JLjava/lang/String;)V".into(),
+            fn_ptr: native_end_element as *mut c_void,
+        },
+        NativeMethod {
+            name: "nativeCharacters".into(),
+            sig: "(JLjava/lang/String;)V".into(),
+            fn_ptr: native_characters as *mut c_void,
+        },
+    ];
+    env.register_native_methods(NATIVE_HANDLER_CLASS, &methods)?;
+    Ok(())
+}
+
+/// Runs `f` with the handler behind `ptr`. Panics must not unwind into java, so they are caught,
+/// saved for [`HandlerContext::resume_panic`] and turned into a SAXException that aborts the parse.
+fn dispatch<F>(env: &mut JNIEnv, ptr: jlong, f: F)
+where
+    F: FnOnce(&mut dyn ContentHandler),
+{
+    // Safety: ptr comes from HandlerContext::as_ptr and the context outlives the parse call
+    // that triggers the upcalls, which all happen on the calling thread
+    let ctx = unsafe { &mut *(ptr as *mut HandlerContext) };
+    if ctx.panic.is_some() {
+        return;
+    }
+    if let Err(payload) = catch_unwind(AssertUnwindSafe(|| f(&mut *ctx.handler))) {
+        ctx.panic = Some(payload);
+        let _ = env.throw_new("org/xml/sax/SAXException", "Rust content handler panicked");
+    }
+}
+
+extern "system" fn native_start_document<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+) {
+    dispatch(&mut env, ptr, |handler| handler.start_document());
+}
+
+extern "system" fn native_end_document<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+) {
+    dispatch(&mut env, ptr, |handler| handler.end_document());
+}
+
+extern "system" fn native_start_element<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+    name: JString<'local>,
+    attributes: JObjectArray<'local>,
+) {
+    let Ok(name) = jni_jobject_to_string(&mut env, name.into()) else {
+        return;
+    };
+    let Ok(flat) = jni_jobject_array_to_vec(&mut env, attributes.into()) else {
+        return;
+    };
+    // Attributes come interleaved as [name0, value0, name1, value1, ...]
+    let attributes: Vec<(String, String)> = flat
+        .chunks_exact(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect();
+    dispatch(&mut env, ptr, |handler| {
+        handler.start_element(&name, &attributes)
+    });
+}
+
+extern "system" fn native_end_element<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+    name: JString<'local>,
+) {
+    let Ok(name) = jni_jobject_to_string(&mut env, name.into()) else {
+        return;
+    };
+    dispatch(&mut env, ptr, |handler| handler.end_element(&name));
+}
+
+extern "system" fn native_characters<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+    text: JString<'local>,
+) {
+    let Ok(text) = jni_jobject_to_string(&mut env, text.into()) else {
+        return;
+    };
+    dispatch(&mut env, ptr, |handler| handler.characters(&text));
+}
@@ -1,3 +1,4 @@
+use std::ffi::CString;
 use std::os::raw::{c_char, c_void};
 
 use crate::errors::{Error, ExtractResult};
@@ -159,10 +160,19 @@ pub fn jni_check_exception(env: &mut JNIEnv) -> ExtractResult<bool> {
 ///
 /// This function uses the standard JVM invocation API and relies on the jni-sys crate.
 /// No need to specify any libraries because the graalvm native image is already
-/// linked in by the build script.
-pub fn create_vm_isolate() -> JavaVM {
+/// linked in by the build script. `extra_options` are added to the default ones, e.g. `-Xmx512m`.
+pub fn create_vm_isolate(extra_options: &[String]) -> JavaVM {
+    // The option strings must be nul terminated and outlive the call that creates the vm
+    let extra_options: Vec<CString> = extra_options
+        .iter()
+        .map(|option| {
+            CString::new(option.as_str()).unwrap_or_else(|_| {
+                panic!("The vm option {:?} contains a nul byte", option);
+            })
+        })
+        .collect();
     unsafe {
-        let vm_options: Vec<sys::JavaVMOption> = vec![
+        let mut vm_options: Vec<sys::JavaVMOption> = vec![
             // Set java.library.path to be able to load libawt.so, which must be in the same dir as libtika_native.so
             sys::JavaVMOption {
                 optionString: "-Djava.library.path=.".as_ptr() as *mut c_char,
@@ -174,6 +184,10 @@ pub fn create_vm_isolate() -> JavaVM {
                 extraInfo: std::ptr::null_mut(),
             },
         ];
+        vm_options.extend(extra_options.iter().map(|option| sys::JavaVMOption {
+            optionString: option.as_ptr() as *mut c_char,
+            extraInfo: std::ptr::null_mut(),
+        }));
 
         let mut args = sys::JavaVMInitArgs {
             version: sys::JNI_VERSION_1_8,
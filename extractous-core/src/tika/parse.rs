@@ -1,46 +1,232 @@
-use std::sync::OnceLock;
+use std::io::{BufReader, Read};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{PoisonError, RwLock};
 
-use crate::errors::ExtractResult;
+use crate::errors::{Error, ExtractResult};
+use crate::mime_message::MimeParts;
+use crate::tika::embedded_target::{register_embedded_target, TargetContext};
+use crate::tika::handler::{register_native_handler, HandlerContext};
 use crate::tika::jni_utils::*;
+use crate::tika::reader::{into_owned_ptr, register_native_reader, ReaderContext};
+use crate::tika::sink::{register_native_sink, SinkContext};
 use crate::tika::wrappers::*;
+use crate::tika::{acquire_direct_buffer, DirectBufferPermit};
 use crate::{
-    CharSet, Metadata, OfficeParserConfig, PdfParserConfig, RecursiveExtraction, StreamReader, TesseractOcrConfig,
+    ContentHandler, Document, FontInfo, LanguageResult, Metadata, OfficeParserConfig, ParseOptions,
+    PdfConformance, PdfParserConfig, RecursiveConfig, RecursiveExtraction, Revision, SignatureInfo,
+    StreamReader, TesseractOcrConfig, WordBox, DEFAULT_BUF_SIZE,
 };
-use jni::objects::JValue;
-use jni::{AttachGuard, JavaVM};
+use jni::objects::{GlobalRef, JObject, JValue};
+use jni::{AttachGuard, JNIEnv, JavaVM};
+
+/// Controls what happens when the shared VM is used from a child process created by `fork()`
+/// after the VM was initialized. The VM threads do not survive a fork, so the inherited isolate
+/// cannot be used by the child.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ForkBehavior {
+    /// Fail every call in the child with [`Error::VmForked`] until [`reinit_after_fork`] is called
+    #[default]
+    Error,
+    /// Transparently create a new VM isolate in the child on its first call
+    Reinitialize,
+}
+
+/// Options of the VM isolate, set with [`configure_vm`] before the VM is created. GraalVM
+/// isolates cannot be resized once created, so these are fixed for the life of the process.
+/// Options left unset keep the GraalVM defaults, a maximum heap that is a share of the physical
+/// memory, or of the memory limit of the container.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VmOptions {
+    max_heap_mb: Option<u32>,
+    initial_heap_mb: Option<u32>,
+}
+
+impl VmOptions {
+    /// Creates a new instance of VmOptions with the GraalVM defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum size of the java heap in MiB, `-Xmx`. Extractions that need more fail
+    /// with an out of memory error instead of growing the process past it.
+    /// Default: set by GraalVM
+    pub fn set_max_heap_mb(mut self, val: u32) -> Self {
+        self.max_heap_mb = Some(val);
+        self
+    }
+
+    /// Sets the initial size of the java heap in MiB, `-Xms`, reserved when the VM is created.
+    /// Default: set by GraalVM
+    pub fn set_initial_heap_mb(mut self, val: u32) -> Self {
+        self.initial_heap_mb = Some(val);
+        self
+    }
+
+    /// Returns the options in the format of the invocation API
+    pub(crate) fn to_vm_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(max) = self.max_heap_mb {
+            args.push(format!("-Xmx{}m", max));
+        }
+        if let Some(initial) = self.initial_heap_mb {
+            args.push(format!("-Xms{}m", initial));
+        }
+        args
+    }
+}
+
+struct SharedVm {
+    vm: &'static JavaVM,
+    pid: u32,
+}
+
+// The VM handles are leaked on purpose: static items do not call `Drop` on program termination,
+// and an isolate inherited through fork() cannot be torn down by the child
+static GRAAL_VM: RwLock<Option<SharedVm>> = RwLock::new(None);
+static REINIT_ON_FORK: AtomicBool = AtomicBool::new(false);
+static VM_OPTIONS: RwLock<VmOptions> = RwLock::new(VmOptions {
+    max_heap_mb: None,
+    initial_heap_mb: None,
+});
+
+/// Sets the options the shared VM is created with, such as the size of its heap, which is how
+/// the memory of the VM is fitted to the limits of a container. Must be called before the first
+/// extraction of the process: once the VM exists it fails with [`Error::VmAlreadyCreated`], and
+/// with [`Error::InvalidInput`] when a heap size is 0 or the initial heap is larger than the
+/// maximum one. The VM created again in a forked child, see [`ForkBehavior`], uses the same
+/// options.
+pub fn configure_vm(options: VmOptions) -> ExtractResult<()> {
+    if options.max_heap_mb == Some(0) || options.initial_heap_mb == Some(0) {
+        return Err(Error::InvalidInput(
+            "The heap sizes must be at least 1 MiB".to_string(),
+        ));
+    }
+    if let (Some(max), Some(initial)) = (options.max_heap_mb, options.initial_heap_mb) {
+        if initial > max {
+            return Err(Error::InvalidInput(format!(
+                "The initial heap of {} MiB is larger than the maximum heap of {} MiB",
+                initial, max
+            )));
+        }
+    }
+
+    // Holding the vm lock, so that no extraction creates the vm in the meantime
+    let guard = GRAAL_VM.write().unwrap_or_else(PoisonError::into_inner);
+    if guard.is_some() {
+        return Err(Error::VmAlreadyCreated);
+    }
+    *VM_OPTIONS.write().unwrap_or_else(PoisonError::into_inner) = options;
+    Ok(())
+}
+
+fn create_configured_vm() -> &'static JavaVM {
+    let options = VM_OPTIONS.read().unwrap_or_else(PoisonError::into_inner);
+    Box::leak(Box::new(create_vm_isolate(&options.to_vm_args())))
+}
+
+/// Sets the behavior of the shared VM in processes forked after it was initialized.
+/// Default: [`ForkBehavior::Error`]
+pub fn set_fork_behavior(behavior: ForkBehavior) {
+    REINIT_ON_FORK.store(behavior == ForkBehavior::Reinitialize, Ordering::SeqCst);
+}
+
+/// Creates a new VM isolate for the current process if the shared VM was inherited from a parent
+/// process through `fork()`. Meant to be called from a post-fork hook, for example Gunicorn's
+/// `post_fork`. It is a no-op when the VM belongs to the current process or was not created yet.
+pub fn reinit_after_fork() -> ExtractResult<()> {
+    let pid = std::process::id();
+    let mut guard = GRAAL_VM.write().unwrap_or_else(PoisonError::into_inner);
+    if let Some(shared) = guard.as_ref() {
+        if shared.pid != pid {
+            *guard = Some(SharedVm {
+                vm: create_configured_vm(),
+                pid,
+            });
+        }
+    }
+    Ok(())
+}
 
 /// Returns a reference to the shared VM isolate
 /// Instead of creating a new VM for every tika call, we create a single VM that is shared
-/// throughout the application.
-pub(crate) fn vm() -> &'static JavaVM {
-    // static items do not call `Drop` on program termination
-    static GRAAL_VM: OnceLock<JavaVM> = OnceLock::new();
-    GRAAL_VM.get_or_init(create_vm_isolate)
+/// throughout the application. The VM is bound to the process that created it, see
+/// [`ForkBehavior`] for what happens in forked children.
+pub(crate) fn vm() -> ExtractResult<&'static JavaVM> {
+    let pid = std::process::id();
+    {
+        let guard = GRAAL_VM.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(shared) = guard.as_ref() {
+            if shared.pid == pid {
+                return Ok(shared.vm);
+            }
+            if !REINIT_ON_FORK.load(Ordering::SeqCst) {
+                return Err(Error::VmForked(shared.pid));
+            }
+        }
+    }
+
+    let mut guard = GRAAL_VM.write().unwrap_or_else(PoisonError::into_inner);
+    // Another thread might have initialized the vm while we were waiting for the lock
+    match guard.as_ref() {
+        Some(shared) if shared.pid == pid => Ok(shared.vm),
+        _ => {
+            let vm = create_configured_vm();
+            *guard = Some(SharedVm { vm, pid });
+            Ok(vm)
+        }
+    }
 }
 
 fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>> {
     // Attaching a thead that is already attached is a no-op. Good to have this in case this method
     // is called from another thread
-    let env = vm().attach_current_thread()?;
+    let env = vm()?.attach_current_thread()?;
     Ok(env)
 }
 
+/// Attaches the current thread to the vm for the lifetime of the returned guard, and returns its
+/// java `Thread` as well, which other threads can then interrupt with [`interrupt_java_thread`]
+pub(crate) fn attach_java_thread<'local>() -> ExtractResult<(AttachGuard<'local>, GlobalRef)> {
+    let mut env = get_vm_attach_current_thread()?;
+    let thread = jni_call_static_method(
+        &mut env,
+        "java/lang/Thread",
+        "currentThread",
+        "()Ljava/lang/Thread;",
+        &[],
+    )?
+    .l()?;
+    let thread = env.new_global_ref(thread)?;
+    Ok((env, thread))
+}
+
+/// Sets the interrupt flag of a java thread returned by [`attach_java_thread`]
+pub(crate) fn interrupt_java_thread(thread: &GlobalRef) -> ExtractResult<()> {
+    let mut env = get_vm_attach_current_thread()?;
+    jni_call_method(&mut env, thread.as_obj(), "interrupt", "()V", &[])?;
+    Ok(())
+}
+
 fn parse_to_stream(
     mut env: AttachGuard,
     data_source_val: JValue,
-    char_set: &CharSet,
+    charset_name: &str,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
     method_name: &str,
     signature: &str,
+    buffer_permit: Option<DirectBufferPermit>,
 ) -> ExtractResult<(StreamReader, Metadata)> {
-    let charset_name_val = jni_new_string_as_jvalue(&mut env, &char_set.to_string())?;
+    let charset_name_val = jni_new_string_as_jvalue(&mut env, charset_name)?;
     let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
     let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
     let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
 
     // Make the java parse call
     let call_result = jni_call_static_method(
@@ -54,6 +240,7 @@ fn parse_to_stream(
             (&j_pdf_conf.internal).into(),
             (&j_office_conf.internal).into(),
             (&j_ocr_conf.internal).into(),
+            (&j_parse_options.internal).into(),
             JValue::Bool(if as_xml { 1 } else { 0 }),
             JValue::Bool(if as_embedded { 1 } else { 0 }),
         ],
@@ -64,15 +251,25 @@ fn parse_to_stream(
     let result = JReaderResult::new(&mut env, call_result_obj)?;
     let j_reader = JReaderInputStream::new(&mut env, result.java_reader)?;
 
-    Ok((StreamReader { inner: j_reader }, result.metadata))
+    Ok((
+        StreamReader {
+            inner: BufReader::with_capacity(DEFAULT_BUF_SIZE, j_reader),
+            _buffer_permit: buffer_permit,
+            _source: None,
+            metadata: result.metadata.clone(),
+        },
+        result.metadata,
+    ))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn parse_file(
     file_path: &str,
-    char_set: &CharSet,
+    charset_name: &str,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
 ) -> ExtractResult<(StreamReader, Metadata)> {
@@ -82,10 +279,11 @@ pub fn parse_file(
     parse_to_stream(
         env,
         (&file_path_val).into(),
-        char_set,
+        charset_name,
         pdf_conf,
         office_conf,
         ocr_conf,
+        parse_options,
         as_xml,
         as_embedded,
         "parseFile",
@@ -94,20 +292,25 @@ pub fn parse_file(
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
         ZZ\
         )Lai/yobix/ReaderResult;",
+        None,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn parse_bytes(
     buffer: &[u8],
-    char_set: &CharSet,
+    charset_name: &str,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
 ) -> ExtractResult<(StreamReader, Metadata)> {
+    let buffer_permit = acquire_direct_buffer(buffer.len());
     let mut env = get_vm_attach_current_thread()?;
 
     // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
@@ -119,10 +322,11 @@ pub fn parse_bytes(
     parse_to_stream(
         env,
         (&byte_buffer).into(),
-        char_set,
+        charset_name,
         pdf_conf,
         office_conf,
         ocr_conf,
+        parse_options,
         as_xml,
         as_embedded,
         "parseBytes",
@@ -131,17 +335,60 @@ pub fn parse_bytes(
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        ZZ\
+        )Lai/yobix/ReaderResult;",
+        Some(buffer_permit),
+    )
+}
+
+/// Parses the bytes read from the given reader. The VM keeps reading it while the returned
+/// stream is consumed, and drops it once the parse is over.
+#[allow(clippy::too_many_arguments)]
+pub fn parse_reader(
+    reader: impl Read + Send + 'static,
+    charset_name: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_xml: bool,
+    as_embedded: bool,
+) -> ExtractResult<(StreamReader, Metadata)> {
+    let mut env = get_vm_attach_current_thread()?;
+    register_native_reader(&mut env)?;
+
+    parse_to_stream(
+        env,
+        JValue::Long(into_owned_ptr(reader)),
+        charset_name,
+        pdf_conf,
+        office_conf,
+        ocr_conf,
+        parse_options,
+        as_xml,
+        as_embedded,
+        "parseReader",
+        "(J\
+        Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
         ZZ\
         )Lai/yobix/ReaderResult;",
+        None,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn parse_url(
     url: &str,
-    char_set: &CharSet,
+    charset_name: &str,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
 ) -> ExtractResult<(StreamReader, Metadata)> {
@@ -151,10 +398,11 @@ pub fn parse_url(
     parse_to_stream(
         env,
         (&url_val).into(),
-        char_set,
+        charset_name,
         pdf_conf,
         office_conf,
         ocr_conf,
+        parse_options,
         as_xml,
         as_embedded,
         "parseUrl",
@@ -163,8 +411,10 @@ pub fn parse_url(
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
         ZZ\
         )Lai/yobix/ReaderResult;",
+        None,
     )
 }
 
@@ -176,6 +426,7 @@ pub fn parse_to_string(
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
     method_name: &str,
@@ -184,18 +435,56 @@ pub fn parse_to_string(
     let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
     let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
     let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
 
-    let call_result = jni_call_static_method(
+    call_parse_to_string(
         &mut env,
+        data_source_val,
+        max_length,
+        &JParseConfigs {
+            pdf_conf: &j_pdf_conf.internal,
+            office_conf: &j_office_conf.internal,
+            ocr_conf: &j_ocr_conf.internal,
+            parse_options: &j_parse_options.internal,
+        },
+        as_xml,
+        as_embedded,
+        method_name,
+        signature,
+    )
+}
+
+/// The java config objects handed to the parse methods of `ai.yobix.TikaNativeMain`
+struct JParseConfigs<'a> {
+    pdf_conf: &'a JObject<'a>,
+    office_conf: &'a JObject<'a>,
+    ocr_conf: &'a JObject<'a>,
+    parse_options: &'a JObject<'a>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn call_parse_to_string(
+    env: &mut JNIEnv,
+    data_source_val: JValue,
+    max_length: i32,
+    configs: &JParseConfigs,
+    as_xml: bool,
+    as_embedded: bool,
+    method_name: &str,
+    signature: &str,
+) -> ExtractResult<(String, Metadata)> {
+    let call_result = jni_call_static_method(
+        env,
         "ai/yobix/TikaNativeMain",
         method_name,
         signature,
         &[
             data_source_val,
             JValue::Int(max_length),
-            (&j_pdf_conf.internal).into(),
-            (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
+            configs.pdf_conf.into(),
+            configs.office_conf.into(),
+            configs.ocr_conf.into(),
+            configs.parse_options.into(),
             JValue::Bool(if as_xml { 1 } else { 0 }),
             JValue::Bool(if as_embedded { 1 } else { 0 }),
         ],
@@ -203,17 +492,37 @@ pub fn parse_to_string(
     let call_result_obj = call_result?.l()?;
 
     // Create and process the JStringResult
-    let result = JStringResult::new(&mut env, call_result_obj)?;
+    let result = JStringResult::new(env, call_result_obj)?;
     Ok((result.content, result.metadata))
 }
 
+const PARSE_TO_STRING_SIGNATURE: &str = "(Ljava/lang/String;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        ZZ\
+        )Lai/yobix/StringResult;";
+
+const PARSE_BYTES_TO_STRING_SIGNATURE: &str = "(Ljava/nio/ByteBuffer;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        ZZ\
+        )Lai/yobix/StringResult;";
+
 /// Parses a file to a string using the Apache Tika library.
+#[allow(clippy::too_many_arguments)]
 pub fn parse_file_to_string(
     file_path: &str,
     max_length: i32,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
 ) -> ExtractResult<(String, Metadata)> {
@@ -227,29 +536,27 @@ pub fn parse_file_to_string(
         pdf_conf,
         office_conf,
         ocr_conf,
+        parse_options,
         as_xml,
         as_embedded,
         "parseFileToString",
-        "(Ljava/lang/String;\
-        I\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        ZZ\
-        )Lai/yobix/StringResult;",
+        PARSE_TO_STRING_SIGNATURE,
     )
 }
 
 /// Parses bytes to a string using the Apache Tika library.
+#[allow(clippy::too_many_arguments)]
 pub fn parse_bytes_to_string(
     buffer: &[u8],
     max_length: i32,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
 ) -> ExtractResult<(String, Metadata)> {
+    let _buffer_permit = acquire_direct_buffer(buffer.len());
     let mut env = get_vm_attach_current_thread()?;
 
     // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
@@ -265,26 +572,65 @@ pub fn parse_bytes_to_string(
         pdf_conf,
         office_conf,
         ocr_conf,
+        parse_options,
         as_xml,
         as_embedded,
         "parseBytesToString",
-        "(Ljava/nio/ByteBuffer;\
+        PARSE_BYTES_TO_STRING_SIGNATURE,
+    )
+}
+
+/// Parses the bytes read from the given reader to a string. The reader is read on the calling
+/// thread, and a panic it raises is resumed once the parse call has returned.
+#[allow(clippy::too_many_arguments)]
+pub fn parse_reader_to_string(
+    reader: impl Read,
+    max_length: i32,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_xml: bool,
+    as_embedded: bool,
+) -> ExtractResult<(String, Metadata)> {
+    let mut env = get_vm_attach_current_thread()?;
+    register_native_reader(&mut env)?;
+
+    let mut context = ReaderContext::new(reader);
+    let result = parse_to_string(
+        env,
+        JValue::Long(context.as_ptr()),
+        max_length,
+        pdf_conf,
+        office_conf,
+        ocr_conf,
+        parse_options,
+        as_xml,
+        as_embedded,
+        "parseReaderToString",
+        "(J\
         I\
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
         ZZ\
         )Lai/yobix/StringResult;",
-    )
+    );
+    // A panic of the reader takes precedence over the parse error it caused
+    context.finish();
+    result
 }
 
 /// Parses a url to a string using the Apache Tika library.
+#[allow(clippy::too_many_arguments)]
 pub fn parse_url_to_string(
     url: &str,
     max_length: i32,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     as_embedded: bool,
 ) -> ExtractResult<(String, Metadata)> {
@@ -298,19 +644,149 @@ pub fn parse_url_to_string(
         pdf_conf,
         office_conf,
         ocr_conf,
+        parse_options,
         as_xml,
         as_embedded,
         "parseUrlToString",
-        "(Ljava/lang/String;\
-        I\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        ZZ\
-        )Lai/yobix/StringResult;",
+        PARSE_TO_STRING_SIGNATURE,
     )
 }
 
+/// Number of local references reserved for the local frame of each session parse call. The
+/// frame grows past it when needed, this is only a hint.
+const SESSION_LOCAL_FRAME_CAPACITY: i32 = 64;
+
+/// Keeps the current thread attached to the VM together with the java config objects, so that
+/// many parse calls can be made without attaching and building the configs for each of them.
+/// Every call runs in its own JNI local frame, the references it creates are freed on return.
+pub struct ParseSession {
+    env: AttachGuard<'static>,
+    pdf_conf: GlobalRef,
+    office_conf: GlobalRef,
+    ocr_conf: GlobalRef,
+    parse_options: GlobalRef,
+}
+
+impl ParseSession {
+    pub fn new(
+        pdf_conf: &PdfParserConfig,
+        office_conf: &OfficeParserConfig,
+        ocr_conf: &TesseractOcrConfig,
+        parse_options: &ParseOptions,
+    ) -> ExtractResult<Self> {
+        let mut env = vm()?.attach_current_thread()?;
+
+        let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+        let pdf_conf = env.new_global_ref(j_pdf_conf.internal)?;
+        let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+        let office_conf = env.new_global_ref(j_office_conf.internal)?;
+        let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+        let ocr_conf = env.new_global_ref(j_ocr_conf.internal)?;
+        let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
+        let parse_options = env.new_global_ref(j_parse_options.internal)?;
+
+        Ok(Self {
+            env,
+            pdf_conf,
+            office_conf,
+            ocr_conf,
+            parse_options,
+        })
+    }
+
+    pub fn parse_file_to_string(
+        &mut self,
+        file_path: &str,
+        max_length: i32,
+        as_xml: bool,
+        as_embedded: bool,
+    ) -> ExtractResult<(String, Metadata)> {
+        self.parse_to_string(
+            "parseFileToString",
+            PARSE_TO_STRING_SIGNATURE,
+            max_length,
+            as_xml,
+            as_embedded,
+            |env| Ok(jni_new_string_as_jvalue(env, file_path)?.l()?),
+        )
+    }
+
+    pub fn parse_bytes_to_string(
+        &mut self,
+        buffer: &[u8],
+        max_length: i32,
+        as_xml: bool,
+        as_embedded: bool,
+    ) -> ExtractResult<(String, Metadata)> {
+        let _buffer_permit = acquire_direct_buffer(buffer.len());
+        self.parse_to_string(
+            "parseBytesToString",
+            PARSE_BYTES_TO_STRING_SIGNATURE,
+            max_length,
+            as_xml,
+            as_embedded,
+            |env| {
+                // The buffer is only read, see parse_bytes_to_string
+                let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+                Ok(jni_new_direct_buffer(env, mut_ptr, buffer.len())?.into())
+            },
+        )
+    }
+
+    pub fn parse_url_to_string(
+        &mut self,
+        url: &str,
+        max_length: i32,
+        as_xml: bool,
+        as_embedded: bool,
+    ) -> ExtractResult<(String, Metadata)> {
+        self.parse_to_string(
+            "parseUrlToString",
+            PARSE_TO_STRING_SIGNATURE,
+            max_length,
+            as_xml,
+            as_embedded,
+            |env| Ok(jni_new_string_as_jvalue(env, url)?.l()?),
+        )
+    }
+
+    /// Runs a to string parse call in a new local frame, `data_source` creates the java data
+    /// source of the call in that frame
+    fn parse_to_string<F>(
+        &mut self,
+        method_name: &str,
+        signature: &str,
+        max_length: i32,
+        as_xml: bool,
+        as_embedded: bool,
+        data_source: F,
+    ) -> ExtractResult<(String, Metadata)>
+    where
+        F: for<'local> FnOnce(&mut JNIEnv<'local>) -> ExtractResult<JObject<'local>>,
+    {
+        let configs = JParseConfigs {
+            pdf_conf: &self.pdf_conf,
+            office_conf: &self.office_conf,
+            ocr_conf: &self.ocr_conf,
+            parse_options: &self.parse_options,
+        };
+        self.env
+            .with_local_frame(SESSION_LOCAL_FRAME_CAPACITY, |env| {
+                let data_source_obj = data_source(env)?;
+                call_parse_to_string(
+                    env,
+                    (&data_source_obj).into(),
+                    max_length,
+                    &configs,
+                    as_xml,
+                    as_embedded,
+                    method_name,
+                    signature,
+                )
+            })
+    }
+}
+
 /// 内部通用函数：递归解析文档
 fn parse_recursive(
     mut env: AttachGuard,
@@ -319,6 +795,8 @@ fn parse_recursive(
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    recursive_conf: &RecursiveConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
     method_name: &str,
     signature: &str,
@@ -326,6 +804,8 @@ fn parse_recursive(
     let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
     let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
     let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
+    let j_recursive_conf = JRecursiveConfig::new(&mut env, recursive_conf)?;
 
     // 调用 Java 方法
     let call_result = jni_call_static_method(
@@ -339,6 +819,8 @@ fn parse_recursive(
             (&j_pdf_conf.internal).into(),
             (&j_office_conf.internal).into(),
             (&j_ocr_conf.internal).into(),
+            (&j_recursive_conf.internal).into(),
+            (&j_parse_options.internal).into(),
             JValue::Bool(if as_xml { 1 } else { 0 }),
         ],
     );
@@ -350,12 +832,15 @@ fn parse_recursive(
 }
 
 /// 递归解析文件，返回容器文档及所有嵌套文档
+#[allow(clippy::too_many_arguments)]
 pub fn parse_file_recursive(
     file_path: &str,
     max_length: i32,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    recursive_conf: &RecursiveConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
 ) -> ExtractResult<RecursiveExtraction> {
     let mut env = get_vm_attach_current_thread()?;
@@ -368,6 +853,8 @@ pub fn parse_file_recursive(
         pdf_conf,
         office_conf,
         ocr_conf,
+        recursive_conf,
+        parse_options,
         as_xml,
         "parseFileRecursive",
         "(Ljava/lang/String;\
@@ -375,11 +862,601 @@ pub fn parse_file_recursive(
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/RecursiveConfig;\
+        Lai/yobix/ParseOptions;\
+        Z\
+        )Lai/yobix/RecursiveResult;",
+    )
+}
+
+/// 递归解析文件，每个文档解析完成后立即交给 sink，而不是收集到内存中。
+/// 嵌套文档按结束顺序先交付，容器文档最后交付
+#[allow(clippy::too_many_arguments)]
+pub fn parse_file_recursive_to_sink(
+    file_path: &str,
+    max_length: i32,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    recursive_conf: &RecursiveConfig,
+    parse_options: &ParseOptions,
+    as_xml: bool,
+    sink: &mut dyn FnMut(Document) -> ExtractResult<()>,
+) -> ExtractResult<()> {
+    let mut env = get_vm_attach_current_thread()?;
+    register_native_sink(&mut env)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
+    let j_recursive_conf = JRecursiveConfig::new(&mut env, recursive_conf)?;
+
+    let mut context = SinkContext::new(sink);
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseFileRecursiveToSink",
+        "(Ljava/lang/String;\
+        I\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/RecursiveConfig;\
+        Lai/yobix/ParseOptions;\
         Z\
+        J\
         )Lai/yobix/RecursiveResult;",
+        &[
+            (&file_path_val).into(),
+            JValue::Int(max_length),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            (&j_recursive_conf.internal).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Bool(if as_xml { 1 } else { 0 }),
+            JValue::Long(context.as_ptr()),
+        ],
+    );
+    // sink 的失败优先于它导致的解析错误
+    context.finish()?;
+    let call_result_obj = call_result?.l()?;
+
+    JRecursiveResult::new(&mut env, call_result_obj)?;
+    Ok(())
+}
+
+/// Parses a data source for its metadata only, see the `parse*Metadata` methods of
+/// `ai.yobix.TikaNativeMain`
+#[allow(clippy::too_many_arguments)]
+fn parse_to_metadata(
+    mut env: AttachGuard,
+    data_source_val: JValue,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_embedded: bool,
+    method_name: &str,
+    signature: &str,
+) -> ExtractResult<Metadata> {
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        method_name,
+        signature,
+        &[
+            data_source_val,
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Bool(if as_embedded { 1 } else { 0 }),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
+
+/// Parses a file for its metadata only, without collecting its content
+pub fn parse_file_metadata(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_embedded: bool,
+) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    parse_to_metadata(
+        env,
+        (&file_path_val).into(),
+        pdf_conf,
+        office_conf,
+        ocr_conf,
+        parse_options,
+        as_embedded,
+        "parseFileMetadata",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        Z\
+        )Lai/yobix/StringResult;",
+    )
+}
+
+/// Parses a byte buffer for its metadata only, without collecting its content
+pub fn parse_bytes_metadata(
+    buffer: &[u8],
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_embedded: bool,
+) -> ExtractResult<Metadata> {
+    let _buffer_permit = acquire_direct_buffer(buffer.len());
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    parse_to_metadata(
+        env,
+        (&byte_buffer).into(),
+        pdf_conf,
+        office_conf,
+        ocr_conf,
+        parse_options,
+        as_embedded,
+        "parseBytesMetadata",
+        "(Ljava/nio/ByteBuffer;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        Z\
+        )Lai/yobix/StringResult;",
+    )
+}
+
+/// Parses a url for its metadata only, without collecting its content
+pub fn parse_url_metadata(
+    url: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_embedded: bool,
+) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
+    parse_to_metadata(
+        env,
+        (&url_val).into(),
+        pdf_conf,
+        office_conf,
+        ocr_conf,
+        parse_options,
+        as_embedded,
+        "parseUrlMetadata",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        Z\
+        )Lai/yobix/StringResult;",
     )
 }
 
+/// Parses a file and forwards the SAX events to the given handler
+pub fn parse_file_with_handler(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    ocr_conf: &TesseractOcrConfig,
+    parse_options: &ParseOptions,
+    as_embedded: bool,
+    handler: &mut dyn ContentHandler,
+) -> ExtractResult<Metadata> {
+    let mut env = get_vm_attach_current_thread()?;
+    register_native_handler(&mut env)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
+
+    let mut context = HandlerContext::new(handler);
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "parseFileWithHandler",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/ParseOptions;\
+        Z\
+        J\
+        )Lai/yobix/StringResult;",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_ocr_conf.internal).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Bool(if as_embedded { 1 } else { 0 }),
+            JValue::Long(context.as_ptr()),
+        ],
+    );
+    // A handler panic takes precedence over the parse error it caused
+    context.resume_panic();
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.metadata)
+}
+
+/// Returns the digital signatures of the given file
+pub fn get_signature_info(file_path: &str) -> ExtractResult<Vec<SignatureInfo>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "getSignatureInfo",
+        "(Ljava/lang/String;)Lai/yobix/SignatureResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JSignatureResult::new(&mut env, call_result_obj)?;
+    Ok(result.signatures)
+}
+
+/// Returns the fonts referenced by the given PDF file
+pub fn list_fonts(file_path: &str) -> ExtractResult<Vec<FontInfo>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "listFonts",
+        "(Ljava/lang/String;)Lai/yobix/FontResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JFontResult::new(&mut env, call_result_obj)?;
+    Ok(result.fonts)
+}
+
+/// Returns the PDF/A conformance and the tagging of the given PDF file
+pub fn pdf_conformance_info(file_path: &str) -> ExtractResult<PdfConformance> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "pdfConformanceInfo",
+        "(Ljava/lang/String;)Lai/yobix/PdfConformanceResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JPdfConformanceResult::new(&mut env, call_result_obj)?;
+    Ok(result.conformance)
+}
+
+/// Returns the bodies and the attachments of the given email message
+pub(crate) fn split_mime_message(file_path: &str) -> ExtractResult<MimeParts> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "splitMimeMessage",
+        "(Ljava/lang/String;)Lai/yobix/MimeMessageResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JMimeMessageResult::new(&mut env, call_result_obj)?;
+    Ok(result.message)
+}
+
+/// Returns the revision history of the given Office file
+pub fn extract_revisions(file_path: &str) -> ExtractResult<Vec<Revision>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractRevisions",
+        "(Ljava/lang/String;)Lai/yobix/RevisionResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JRevisionResult::new(&mut env, call_result_obj)?;
+    Ok(result.revisions)
+}
+
+/// Writes the raw bytes of the documents embedded in the given file, at any depth, to the files
+/// `target` creates for them
+pub fn extract_file_embedded_to_dir(
+    file_path: &str,
+    pdf_conf: &PdfParserConfig,
+    office_conf: &OfficeParserConfig,
+    parse_options: &ParseOptions,
+    target: &mut dyn FnMut(Option<&str>, &str) -> ExtractResult<PathBuf>,
+) -> ExtractResult<()> {
+    let mut env = get_vm_attach_current_thread()?;
+    register_embedded_target(&mut env)?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
+    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
+    let j_parse_options = JParseOptions::new(&mut env, parse_options)?;
+
+    let mut context = TargetContext::new(target);
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractEmbeddedToDir",
+        "(Ljava/lang/String;\
+        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
+        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
+        Lai/yobix/ParseOptions;\
+        J\
+        )Lai/yobix/StringResult;",
+        &[
+            (&file_path_val).into(),
+            (&j_pdf_conf.internal).into(),
+            (&j_office_conf.internal).into(),
+            (&j_parse_options.internal).into(),
+            JValue::Long(context.as_ptr()),
+        ],
+    );
+    // The failure of the target comes before the parse error it caused
+    context.finish()?;
+    let call_result_obj = call_result?.l()?;
+
+    JStringResult::new(&mut env, call_result_obj)?;
+    Ok(())
+}
+
+/// Returns the names of the streams of the given OLE compound document
+pub fn list_ole_streams(file_path: &str) -> ExtractResult<Vec<String>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "listOleStreams",
+        "(Ljava/lang/String;)Lai/yobix/OleStreamResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JOleStreamResult::new(&mut env, call_result_obj)?;
+    result.names(&mut env)
+}
+
+/// Returns the raw bytes of the named stream of the given OLE compound document
+pub fn get_ole_stream(file_path: &str, name: &str) -> ExtractResult<Vec<u8>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let name_val = jni_new_string_as_jvalue(&mut env, name)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "getOleStream",
+        "(Ljava/lang/String;Ljava/lang/String;)Lai/yobix/OleStreamResult;",
+        &[(&file_path_val).into(), (&name_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JOleStreamResult::new(&mut env, call_result_obj)?;
+    result.data(&mut env)
+}
+
+/// Returns the robots.txt of the site of the url, fetched with the user agent, empty when the
+/// site has none or the url is not http or https
+pub fn fetch_robots_txt(url: &str, user_agent: Option<&str>) -> ExtractResult<String> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
+    let user_agent_val = jni_new_string_as_jvalue(&mut env, user_agent.unwrap_or_default())?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/UrlFetch",
+        "fetchRobotsTxt",
+        "(Ljava/lang/String;Ljava/lang/String;)Lai/yobix/StringResult;",
+        &[(&url_val).into(), (&user_agent_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
+
+/// Returns the media type of the given file, detected from its magic bytes and its extension
+pub fn detect_mime_file(file_path: &str) -> ExtractResult<String> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectMimeFile",
+        "(Ljava/lang/String;)Lai/yobix/StringResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
+
+/// Returns the media type of the given bytes, detected from their magic bytes
+pub fn detect_mime_bytes(buffer: &[u8]) -> ExtractResult<String> {
+    let _buffer_permit = acquire_direct_buffer(buffer.len());
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectMimeBytes",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/StringResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JStringResult::new(&mut env, call_result_obj)?;
+    Ok(result.content)
+}
+
+/// Returns the candidate media types of the given bytes with their confidence
+pub fn detect_mime_candidates(buffer: &[u8]) -> ExtractResult<Vec<(String, f32)>> {
+    let _buffer_permit = acquire_direct_buffer(buffer.len());
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectMimeCandidates",
+        "(Ljava/nio/ByteBuffer;)Lai/yobix/MimeCandidatesResult;",
+        &[(&byte_buffer).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JMimeCandidatesResult::new(&mut env, call_result_obj)?;
+    Ok(result.candidates)
+}
+
+/// Returns the language the given text is written in
+pub fn detect_language(text: &str) -> ExtractResult<LanguageResult> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let text_val = jni_new_string_as_jvalue(&mut env, text)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectLanguage",
+        "(Ljava/lang/String;)Lai/yobix/LanguageResult;",
+        &[(&text_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JLanguageResult::new(&mut env, call_result_obj)?;
+    Ok(result.result)
+}
+
+/// Returns the words of the given PDF file with their bounding boxes
+pub fn get_pdf_word_boxes(file_path: &str) -> ExtractResult<Vec<WordBox>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "extractPdfWordBoxes",
+        "(Ljava/lang/String;)Lai/yobix/WordBoxResult;",
+        &[(&file_path_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JWordBoxResult::new(&mut env, call_result_obj)?;
+    Ok(result.words)
+}
+
+/// Sets the maximum number of Tesseract invocations that can run at the same time across all
+/// extractions of the process. Extractions that need OCR wait for a free slot.
+/// Values lower than 1 are treated as 1. Default: the number of available CPUs
+pub fn set_max_ocr_concurrency(max: usize) -> ExtractResult<()> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let max = i32::try_from(max).unwrap_or(i32::MAX);
+    jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "setMaxOcrConcurrency",
+        "(I)V",
+        &[JValue::Int(max)],
+    )?;
+    Ok(())
+}
+
+/// The memory usage of the VM as reported by its `MemoryMXBean`, see
+/// [`get_jvm_memory_stats`]. The sizes are in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JvmMemoryStats {
+    /// Size of the objects on the heap, garbage not yet collected included
+    pub heap_used: u64,
+    /// Size of the heap reserved from the operating system
+    pub heap_committed: u64,
+    /// Size the heap can grow to, `None` when it is not bounded
+    pub heap_max: Option<u64>,
+    /// Size of the memory used outside of the heap, such as the code and metaspace
+    pub non_heap_used: u64,
+    /// Number of collections run by all the garbage collectors since the VM started
+    pub gc_count: u64,
+}
+
+/// Gets the current memory usage of the VM, e.g. to export it as gauges
+pub fn get_jvm_memory_stats() -> ExtractResult<JvmMemoryStats> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "getMemoryStats",
+        "()Lai/yobix/MemoryStats;",
+        &[],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    let result = JMemoryStats::new(&mut env, call_result_obj)?;
+    Ok(result.stats)
+}
+
 /// Gets current JVM memory usage statistics
 /// Returns a JSON string with memory information
 pub fn get_jvm_memory_usage() -> ExtractResult<String> {
@@ -419,14 +1496,18 @@ pub fn trigger_jvm_gc() -> ExtractResult<String> {
 }
 
 /// 递归解析字节数组，返回容器文档及所有嵌套文档
+#[allow(clippy::too_many_arguments)]
 pub fn parse_bytes_recursive(
     buffer: &[u8],
     max_length: i32,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    recursive_conf: &RecursiveConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
 ) -> ExtractResult<RecursiveExtraction> {
+    let _buffer_permit = acquire_direct_buffer(buffer.len());
     let mut env = get_vm_attach_current_thread()?;
 
     let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
@@ -439,6 +1520,8 @@ pub fn parse_bytes_recursive(
         pdf_conf,
         office_conf,
         ocr_conf,
+        recursive_conf,
+        parse_options,
         as_xml,
         "parseBytesRecursive",
         "(Ljava/nio/ByteBuffer;\
@@ -446,18 +1529,23 @@ pub fn parse_bytes_recursive(
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/RecursiveConfig;\
+        Lai/yobix/ParseOptions;\
         Z\
         )Lai/yobix/RecursiveResult;",
     )
 }
 
 /// 递归解析 URL，返回容器文档及所有嵌套文档
+#[allow(clippy::too_many_arguments)]
 pub fn parse_url_recursive(
     url: &str,
     max_length: i32,
     pdf_conf: &PdfParserConfig,
     office_conf: &OfficeParserConfig,
     ocr_conf: &TesseractOcrConfig,
+    recursive_conf: &RecursiveConfig,
+    parse_options: &ParseOptions,
     as_xml: bool,
 ) -> ExtractResult<RecursiveExtraction> {
     let mut env = get_vm_attach_current_thread()?;
@@ -470,6 +1558,8 @@ pub fn parse_url_recursive(
         pdf_conf,
         office_conf,
         ocr_conf,
+        recursive_conf,
+        parse_options,
         as_xml,
         "parseUrlRecursive",
         "(Ljava/lang/String;\
@@ -477,6 +1567,8 @@ pub fn parse_url_recursive(
         Lorg/apache/tika/parser/pdf/PDFParserConfig;\
         Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
         Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
+        Lai/yobix/RecursiveConfig;\
+        Lai/yobix/ParseOptions;\
         Z\
         )Lai/yobix/RecursiveResult;",
     )
@@ -0,0 +1,121 @@
+use std::any::Any;
+use std::io::{ErrorKind, Read};
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+use crate::errors::ExtractResult;
+use jni::objects::{JByteBuffer, JClass};
+use jni::sys::{jint, jlong};
+use jni::{JNIEnv, NativeMethod};
+
+const NATIVE_INPUT_STREAM_CLASS: &str = "ai/yobix/NativeInputStream";
+
+/// State shared with the `ai.yobix.NativeInputStream` upcalls. A pointer to the context is handed
+/// to java as a `long`. A borrowed context must stay pinned on the stack until the parse call has
+/// returned, while an owned one is moved to the heap and freed by java once the stream is closed.
+pub(crate) struct ReaderContext<'r> {
+    reader: Box<dyn Read + 'r>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<'r> ReaderContext<'r> {
+    pub(crate) fn new(reader: impl Read + 'r) -> Self {
+        Self {
+            reader: Box::new(reader),
+            panic: None,
+        }
+    }
+
+    pub(crate) fn as_ptr(&mut self) -> jlong {
+        self as *mut Self as jlong
+    }
+
+    /// Resumes a panic raised by the reader during the upcalls, if any
+    pub(crate) fn finish(&mut self) {
+        if let Some(payload) = self.panic.take() {
+            resume_unwind(payload);
+        }
+    }
+}
+
+/// Moves the reader to the heap for a parse that goes on after the call has returned. The java
+/// stream takes the context over and frees it from the `nativeClose` upcall. The reader is then
+/// read and dropped on a java thread, hence the `Send` bound.
+pub(crate) fn into_owned_ptr(reader: impl Read + Send + 'static) -> jlong {
+    Box::into_raw(Box::new(ReaderContext::new(reader))) as jlong
+}
+
+/// Binds the native methods of `ai.yobix.NativeInputStream` to the upcalls below.
+/// Registering again simply replaces the previous bindings.
+pub(crate) fn register_native_reader(env: &mut JNIEnv) -> ExtractResult<()> {
+    let methods = [
+        NativeMethod {
+            name: "nativeRead".into(),
+            sig: "(JLjava/nio/ByteBuffer;I)I".into(),
+            fn_ptr: native_read as *mut c_void,
+        },
+        NativeMethod {
+            name: "nativeClose".into(),
+            sig: "(J)V".into(),
+            fn_ptr: native_close as *mut c_void,
+        },
+    ];
+    env.register_native_methods(NATIVE_INPUT_STREAM_CLASS, &methods)?;
+    Ok(())
+}
+
+/// Reads at most `length` bytes from the reader behind `ptr` into the direct buffer. Returns the
+/// number of bytes read, or -1 at the end of the input. A failed or panicked reader throws an
+/// IOException, and so does every later read once the reader has panicked.
+extern "system" fn native_read<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+    buffer: JByteBuffer<'local>,
+    length: jint,
+) -> jint {
+    // Safety: ptr comes from ReaderContext::as_ptr or into_owned_ptr, and java only calls this
+    // method while the stream is open, one call at a time
+    let ctx = unsafe { &mut *(ptr as *mut ReaderContext) };
+    if ctx.panic.is_some() {
+        let _ = env.throw_new("java/io/IOException", "Rust reader panicked");
+        return -1;
+    }
+
+    let address = match env.get_direct_buffer_address(&buffer) {
+        Ok(address) => address,
+        Err(error) => {
+            let _ = env.exception_clear();
+            let _ = env.throw_new("java/io/IOException", error.to_string());
+            return -1;
+        }
+    };
+    // Safety: java allocates the direct buffer with at least length bytes and keeps it alive
+    // during this call
+    let buf = unsafe { std::slice::from_raw_parts_mut(address, length.max(0) as usize) };
+
+    loop {
+        match catch_unwind(AssertUnwindSafe(|| ctx.reader.read(buf))) {
+            Ok(Ok(0)) => return -1,
+            Ok(Ok(read)) => return read as jint,
+            Ok(Err(error)) if error.kind() == ErrorKind::Interrupted => continue,
+            Ok(Err(error)) => {
+                let _ = env.throw_new("java/io/IOException", error.to_string());
+                return -1;
+            }
+            Err(payload) => {
+                ctx.panic = Some(payload);
+                let _ = env.throw_new("java/io/IOException", "Rust reader panicked");
+                return -1;
+            }
+        }
+    }
+}
+
+/// Frees the owned context behind `ptr`. A panic raised while dropping the reader is swallowed,
+/// as there is no rust caller left to resume it on.
+extern "system" fn native_close<'local>(_env: JNIEnv<'local>, _class: JClass<'local>, ptr: jlong) {
+    // Safety: ptr comes from into_owned_ptr, and java only calls this method once
+    let ctx = unsafe { Box::from_raw(ptr as *mut ReaderContext<'static>) };
+    let _ = catch_unwind(AssertUnwindSafe(|| drop(ctx)));
+}
@@ -0,0 +1,90 @@
+use std::any::Any;
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+use crate::errors::{Error, ExtractResult};
+use crate::tika::wrappers::jni_tika_metadata_to_document;
+use crate::Document;
+use jni::objects::{JClass, JObject};
+use jni::sys::jlong;
+use jni::{JNIEnv, NativeMethod};
+
+const NATIVE_SINK_CLASS: &str = "ai/yobix/NativeDocumentSink";
+
+/// State shared with the `ai.yobix.NativeDocumentSink` upcalls. The sink receives the documents
+/// of a recursive parse one at a time. A pointer to the context is handed to java as a `long`,
+/// so it must stay pinned on the stack until the parse call has returned.
+pub(crate) struct SinkContext<'s> {
+    sink: &'s mut dyn FnMut(Document) -> ExtractResult<()>,
+    error: Option<Error>,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<'s> SinkContext<'s> {
+    pub(crate) fn new(sink: &'s mut dyn FnMut(Document) -> ExtractResult<()>) -> Self {
+        Self {
+            sink,
+            error: None,
+            panic: None,
+        }
+    }
+
+    pub(crate) fn as_ptr(&mut self) -> jlong {
+        self as *mut Self as jlong
+    }
+
+    /// Resumes a panic raised by the sink during the upcalls, if any, or returns the first error
+    /// the sink or the conversion of a document failed with.
+    pub(crate) fn finish(&mut self) -> ExtractResult<()> {
+        if let Some(payload) = self.panic.take() {
+            resume_unwind(payload);
+        }
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Binds the native method of `ai.yobix.NativeDocumentSink` to the upcall below.
+/// Registering again simply replaces the previous binding.
+pub(crate) fn register_native_sink(env: &mut JNIEnv) -> ExtractResult<()> {
+    let methods = [NativeMethod {
+        name: "nativeDocument".into(),
+        sig: "(JLorg/apache/tika/metadata/Metadata;)V".into(),
+        fn_ptr: native_document as *mut c_void,
+    }];
+    env.register_native_methods(NATIVE_SINK_CLASS, &methods)?;
+    Ok(())
+}
+
+/// Hands the document to the sink behind `ptr`. Once the sink has failed or panicked, the later
+/// documents are dropped: the SAXException thrown to abort the parse can be caught and recorded
+/// by the parser of an enclosing document, which then goes on with the next ones.
+extern "system" fn native_document<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    ptr: jlong,
+    metadata: JObject<'local>,
+) {
+    // Safety: ptr comes from SinkContext::as_ptr and the context outlives the parse call that
+    // triggers the upcalls, which all happen on the calling thread
+    let ctx = unsafe { &mut *(ptr as *mut SinkContext) };
+    if ctx.panic.is_some() || ctx.error.is_some() {
+        let _ = env.throw_new("org/xml/sax/SAXException", "Rust document sink failed");
+        return;
+    }
+
+    let result = match jni_tika_metadata_to_document(&mut env, metadata) {
+        Ok(document) => catch_unwind(AssertUnwindSafe(|| (ctx.sink)(document))),
+        Err(error) => Ok(Err(error)),
+    };
+    match result {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => ctx.error = Some(error),
+        Err(payload) => ctx.panic = Some(payload),
+    }
+    // A pending java exception left by a failed conversion is replaced by this one
+    let _ = env.exception_clear();
+    let _ = env.throw_new("org/xml/sax/SAXException", "Rust document sink failed");
+}
@@ -1,12 +1,17 @@
 use crate::errors::{Error, ExtractResult};
+use crate::mime_message::{MimePart, MimeParts};
 use crate::tika::jni_utils::{
-    jni_call_method, jni_jobject_to_string, jni_new_string_as_jvalue,
-    jni_tika_metadata_to_rust_metadata,
+    jni_call_method, jni_call_static_method, jni_jobject_array_to_vec, jni_jobject_to_string,
+    jni_new_string_as_jvalue, jni_tika_metadata_to_rust_metadata,
 };
 use crate::tika::vm;
-use crate::{Document, Metadata, OfficeParserConfig, PdfParserConfig, RecursiveExtraction, TesseractOcrConfig, DEFAULT_BUF_SIZE};
+use crate::{
+    Document, FontInfo, JvmMemoryStats, LanguageResult, Metadata, OfficeParserConfig,
+    ParseOptions, PdfConformance, PdfParserConfig, RecursiveConfig, RecursiveExtraction,
+    Revision, SignatureInfo, TesseractOcrConfig, WordBox, DEFAULT_BUF_SIZE,
+};
 use bytemuck::cast_slice_mut;
-use jni::objects::{GlobalRef, JByteArray, JObject, JValue};
+use jni::objects::{GlobalRef, JByteArray, JFloatArray, JIntArray, JObject, JValue};
 use jni::sys::jsize;
 use jni::JNIEnv;
 
@@ -37,7 +42,7 @@ impl JReaderInputStream {
     }
 
     pub(crate) fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        let mut env = vm().attach_current_thread().map_err(Error::JniError)?;
+        let mut env = vm()?.attach_current_thread().map_err(Error::JniError)?;
 
         let length = buf.len() as jsize;
 
@@ -96,9 +101,16 @@ impl JReaderInputStream {
     }
 }
 
+impl std::io::Read for JReaderInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        JReaderInputStream::read(self, buf)
+    }
+}
+
 impl Drop for JReaderInputStream {
     fn drop(&mut self) {
-        if let Ok(mut env) = vm().attach_current_thread() {
+        // Skip the close when the vm is not usable, e.g. in a forked child process
+        if let Ok(mut env) = vm().and_then(|vm| Ok(vm.attach_current_thread()?)) {
             // Call the Java Reader's `close` method
             jni_call_method(&mut env, &self.internal, "close", "()V", &[]).ok();
         }
@@ -125,13 +137,19 @@ impl<'local> JStringResult {
             match status {
                 1 => Err(Error::IoError(msg)),
                 2 => Err(Error::ParseError(msg)),
+                3 => Err(Error::EncryptedDocument(msg)),
                 _ => Err(Error::Unknown(msg)),
             }
         } else {
-            let call_result_obj = env
-                .call_method(&obj, "getContent", "()Ljava/lang/String;", &[])?
-                .l()?;
-            let content = jni_jobject_to_string(env, call_result_obj)?;
+            let is_chunked = jni_call_method(env, &obj, "isChunked", "()Z", &[])?.z()?;
+            let content = if is_chunked {
+                Self::take_chunks(env, &obj)?
+            } else {
+                let call_result_obj = env
+                    .call_method(&obj, "getContent", "()Ljava/lang/String;", &[])?
+                    .l()?;
+                jni_jobject_to_string(env, call_result_obj)?
+            };
             let tika_metadata_obj: JObject = env
                 .call_method(
                     &obj,
@@ -144,6 +162,26 @@ impl<'local> JStringResult {
             Ok(Self { content, metadata })
         }
     }
+
+    /// Appends the UTF-8 chunks of a streamed content one at a time, each chunk is released on
+    /// the java side once copied
+    fn take_chunks(env: &mut JNIEnv<'local>, obj: &JObject<'local>) -> ExtractResult<String> {
+        let count = jni_call_method(env, obj, "getChunkCount", "()I", &[])?.i()?;
+        let mut bytes = Vec::new();
+        for index in 0..count {
+            let chunk_obj =
+                jni_call_method(env, obj, "takeChunk", "(I)[B", &[JValue::from(index)])?.l()?;
+            let chunk = JByteArray::from(chunk_obj);
+            let start = bytes.len();
+            bytes.resize(start + env.get_array_length(&chunk)? as usize, 0);
+            env.get_byte_array_region(&chunk, 0, cast_slice_mut(&mut bytes[start..]))?;
+            // Keeps the local reference table from filling up on documents with many chunks
+            env.delete_local_ref(chunk)?;
+        }
+        // The chunks are split at any byte, only the whole content is valid UTF-8
+        Ok(String::from_utf8(bytes)
+            .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned()))
+    }
 }
 
 /// Wrapper for the Java class  `ai.yobix.ReaderResult`
@@ -208,7 +246,8 @@ impl<'local> JPDFParserConfig<'local> {
     /// keeps reference to the object and method IDs for later use
     pub(crate) fn new(env: &mut JNIEnv<'local>, config: &PdfParserConfig) -> ExtractResult<Self> {
         // Create the java object
-        let class = env.find_class("org/apache/tika/parser/pdf/PDFParserConfig")?;
+        // The subclass carries the password, see ai.yobix.DocumentPasswords
+        let class = env.find_class("ai/yobix/DocumentPasswords$PdfConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -228,6 +267,13 @@ impl<'local> JPDFParserConfig<'local> {
             "(Z)V",
             &[JValue::from(config.extract_unique_inline_images_only)],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractInlineImageMetadata",
+            "(Z)V",
+            &[JValue::from(config.extract_inline_image_metadata)],
+        )?;
         jni_call_method(
             env,
             &obj,
@@ -242,6 +288,20 @@ impl<'local> JPDFParserConfig<'local> {
             "(Z)V",
             &[JValue::from(config.extract_annotation_text)],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractAcroFormContent",
+            "(Z)V",
+            &[JValue::from(config.extract_acro_form_content)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setSortByPosition",
+            "(Z)V",
+            &[JValue::from(config.sort_by_position)],
+        )?;
         // The PdfOcrStrategy enum names must match the Java org.apache.tika.parser.pdf
         // .PDFParserConfig$OCR_STRATEGY enum names
         let ocr_str_val = jni_new_string_as_jvalue(env, &config.ocr_strategy.to_string())?;
@@ -252,6 +312,37 @@ impl<'local> JPDFParserConfig<'local> {
             "(Ljava/lang/String;)V",
             &[(&ocr_str_val).into()],
         )?;
+        if let Some(font_dir) = &config.font_dir {
+            let font_dir_val = jni_new_string_as_jvalue(env, font_dir)?;
+            jni_call_static_method(
+                env,
+                "ai/yobix/PdfFontMapper",
+                "registerFontDir",
+                "(Ljava/lang/String;)V",
+                &[(&font_dir_val).into()],
+            )?;
+        }
+        if let Some(password) = &config.password {
+            let password_val = jni_new_string_as_jvalue(env, password)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setPassword",
+                "(Ljava/lang/String;)V",
+                &[(&password_val).into()],
+            )?;
+        }
+        if let Some((start, end)) = config.page_range {
+            let start = i32::try_from(start).unwrap_or(i32::MAX);
+            let end = i32::try_from(end).unwrap_or(i32::MAX);
+            jni_call_method(
+                env,
+                &obj,
+                "setPageRange",
+                "(II)V",
+                &[JValue::Int(start), JValue::Int(end)],
+            )?;
+        }
 
         Ok(Self { internal: obj })
     }
@@ -270,7 +361,8 @@ impl<'local> JOfficeParserConfig<'local> {
         config: &OfficeParserConfig,
     ) -> ExtractResult<Self> {
         // Create the java object
-        let class = env.find_class("org/apache/tika/parser/microsoft/OfficeParserConfig")?;
+        // The subclass carries the password, see ai.yobix.DocumentPasswords
+        let class = env.find_class("ai/yobix/DocumentPasswords$OfficeConfig")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -346,6 +438,23 @@ impl<'local> JOfficeParserConfig<'local> {
             "(Z)V",
             &[JValue::from(config.extract_all_alternatives_from_msg)],
         )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setIncludeComments",
+            "(Z)V",
+            &[JValue::from(config.include_comments)],
+        )?;
+        if let Some(password) = &config.password {
+            let password_val = jni_new_string_as_jvalue(env, password)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setPassword",
+                "(Ljava/lang/String;)V",
+                &[(&password_val).into()],
+            )?;
+        }
 
         Ok(Self { internal: obj })
     }
@@ -363,7 +472,8 @@ impl<'local> JTesseractOcrConfig<'local> {
         config: &TesseractOcrConfig,
     ) -> ExtractResult<Self> {
         // Create the java object
-        let class = env.find_class("org/apache/tika/parser/ocr/TesseractOCRConfig")?;
+        // The subclass carries the tesseract locations, see ai.yobix.OcrEngines
+        let class = env.find_class("ai/yobix/OcrEngines$Config")?;
         let obj = env.new_object(&class, "()V", &[])?;
 
         // Call the setters
@@ -408,6 +518,269 @@ impl<'local> JTesseractOcrConfig<'local> {
             &[(&lang_string_val).into()],
         )?;
 
+        let psm_string_val = jni_new_string_as_jvalue(env, &config.page_seg_mode.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPageSegMode",
+            "(Ljava/lang/String;)V",
+            &[(&psm_string_val).into()],
+        )?;
+
+        let tesseract_path_val = jni_new_string_as_jvalue(env, &config.tesseract_path)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setTesseractPath",
+            "(Ljava/lang/String;)V",
+            &[(&tesseract_path_val).into()],
+        )?;
+        let tessdata_path_val = jni_new_string_as_jvalue(env, &config.tessdata_path)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setTessdataPath",
+            "(Ljava/lang/String;)V",
+            &[(&tessdata_path_val).into()],
+        )?;
+
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.RecursiveConfig`.
+pub(crate) struct JRecursiveConfig<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JRecursiveConfig<'local> {
+    /// Creates a new object instance of `JRecursiveConfig` in the java world
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>, config: &RecursiveConfig) -> ExtractResult<Self> {
+        // Create the java object
+        let class = env.find_class("ai/yobix/RecursiveConfig")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+
+        // Call the setters
+        // Make sure all of these methods are declared in jni-config.json file, otherwise
+        // java method not found exception will be thrown
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxContainerDepth",
+            "(I)V",
+            &[JValue::from(config.max_container_depth)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExtractLeafResources",
+            "(Z)V",
+            &[JValue::from(config.extract_leaf_resources)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPerDocumentMaxLength",
+            "(I)V",
+            &[JValue::from(config.per_document_max_length)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setRecordContentDigests",
+            "(Z)V",
+            &[JValue::from(config.record_content_digests)],
+        )?;
+        // -1 means no limit on the java side
+        let max_total_output_bytes = config
+            .max_total_output_bytes
+            .map_or(-1, |max| i64::try_from(max).unwrap_or(i64::MAX));
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxTotalOutputBytes",
+            "(J)V",
+            &[JValue::from(max_total_output_bytes)],
+        )?;
+        let parallelism = i32::try_from(config.parallelism).unwrap_or(i32::MAX);
+        jni_call_method(
+            env,
+            &obj,
+            "setParallelism",
+            "(I)V",
+            &[JValue::from(parallelism)],
+        )?;
+        // -1 means no limit on the java side
+        let max_embedded_count = config
+            .max_embedded_count
+            .map_or(-1, |max| i32::try_from(max).unwrap_or(i32::MAX));
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxEmbeddedCount",
+            "(I)V",
+            &[JValue::from(max_embedded_count)],
+        )?;
+        let max_embedded_bytes = config
+            .max_embedded_bytes
+            .map_or(-1, |max| i64::try_from(max).unwrap_or(i64::MAX));
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxEmbeddedBytes",
+            "(J)V",
+            &[JValue::from(max_embedded_bytes)],
+        )?;
+        for mime_type in &config.embedded_mime_filter {
+            let type_val = jni_new_string_as_jvalue(env, mime_type)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addEmbeddedMimeType",
+                "(Ljava/lang/String;)V",
+                &[(&type_val).into()],
+            )?;
+        }
+
+        Ok(Self { internal: obj })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ParseOptions`.
+pub(crate) struct JParseOptions<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JParseOptions<'local> {
+    /// Creates a new object instance of `JParseOptions` in the java world
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>, options: &ParseOptions) -> ExtractResult<Self> {
+        // Create the java object
+        let class = env.find_class("ai/yobix/ParseOptions")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+
+        // Call the setters
+        // Make sure all of these methods are declared in jni-config.json file, otherwise
+        // java method not found exception will be thrown
+        let max_element_depth = i32::try_from(options.max_element_depth).unwrap_or(i32::MAX);
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxElementDepth",
+            "(I)V",
+            &[JValue::from(max_element_depth)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOcrSpillToDisk",
+            "(Z)V",
+            &[JValue::from(options.ocr_spill_to_disk)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setExcludeHiddenText",
+            "(Z)V",
+            &[JValue::from(options.exclude_hidden_text)],
+        )?;
+        // 0 means no budget, a budget below a millisecond is rounded up to one
+        let soft_time_budget_millis = options.soft_time_budget.map_or(0, |budget| {
+            i64::try_from(budget.as_millis().max(1)).unwrap_or(i64::MAX)
+        });
+        jni_call_method(
+            env,
+            &obj,
+            "setSoftTimeBudgetMillis",
+            "(J)V",
+            &[JValue::from(soft_time_budget_millis)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setTrustDeclaredCharset",
+            "(Z)V",
+            &[JValue::from(options.trust_declared_charset)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setEncodingAuto",
+            "(Z)V",
+            &[JValue::from(options.encoding_auto)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setDedupRepeatedLines",
+            "(Z)V",
+            &[JValue::from(options.dedup_repeated_lines)],
+        )?;
+        for force_text_type in &options.force_text_types {
+            let type_val = jni_new_string_as_jvalue(env, force_text_type)?;
+            jni_call_method(
+                env,
+                &obj,
+                "addForceTextType",
+                "(Ljava/lang/String;)V",
+                &[(&type_val).into()],
+            )?;
+        }
+        // 0 means never, a threshold above the size of a java array is as good as never
+        let string_streaming_threshold =
+            options.string_streaming_threshold.map_or(0, |threshold| {
+                i32::try_from(threshold.max(1)).unwrap_or(i32::MAX)
+            });
+        jni_call_method(
+            env,
+            &obj,
+            "setStringStreamingThreshold",
+            "(I)V",
+            &[JValue::from(string_streaming_threshold)],
+        )?;
+        for kind in &options.parser_fallbacks {
+            let kind_val = jni_new_string_as_jvalue(env, &kind.to_string())?;
+            jni_call_method(
+                env,
+                &obj,
+                "addParserFallback",
+                "(Ljava/lang/String;)V",
+                &[(&kind_val).into()],
+            )?;
+        }
+        jni_call_method(
+            env,
+            &obj,
+            "setRecordParserChain",
+            "(Z)V",
+            &[JValue::from(options.record_parser_chain)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setZipRecovery",
+            "(Z)V",
+            &[JValue::from(options.zip_recovery)],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPreserveScripts",
+            "(Z)V",
+            &[JValue::from(options.preserve_scripts)],
+        )?;
+        if let Some(user_agent) = &options.url_fetch.user_agent {
+            let user_agent_val = jni_new_string_as_jvalue(env, user_agent)?;
+            jni_call_method(
+                env,
+                &obj,
+                "setUserAgent",
+                "(Ljava/lang/String;)V",
+                &[(&user_agent_val).into()],
+            )?;
+        }
+
         Ok(Self { internal: obj })
     }
 }
@@ -430,6 +803,7 @@ impl<'local> JRecursiveResult {
             return match status {
                 1 => Err(Error::IoError(msg)),
                 2 => Err(Error::ParseError(msg)),
+                3 => Err(Error::EncryptedDocument(msg)),
                 _ => Err(Error::Unknown(msg)),
             };
         }
@@ -451,29 +825,537 @@ impl<'local> JRecursiveResult {
         let mut documents = Vec::with_capacity(length);
         for i in 0..length {
             let metadata_obj = env.get_object_array_element(&j_array, i as i32)?;
+            documents.push(jni_tika_metadata_to_document(env, metadata_obj)?);
+        }
 
-            // 获取内容字段
-            let content_key = jni_new_string_as_jvalue(env, "X-TIKA:content")?;
-            let content_obj = jni_call_method(
-                env,
-                &metadata_obj,
-                "get",
-                "(Ljava/lang/String;)Ljava/lang/String;",
-                &[(&content_key).into()],
-            )?.l()?;
-
-            let content = if content_obj.is_null() {
-                String::new()
+        Ok(Self {
+            extraction: RecursiveExtraction::new(documents),
+        })
+    }
+}
+
+/// 将递归解析得到的 Java `Metadata` 转换为 Document，内容取自 `X-TIKA:content` 键
+pub(crate) fn jni_tika_metadata_to_document<'local>(
+    env: &mut JNIEnv<'local>,
+    metadata_obj: JObject<'local>,
+) -> ExtractResult<Document> {
+    // 获取内容字段
+    let content_key = jni_new_string_as_jvalue(env, "X-TIKA:content")?;
+    let content_obj = jni_call_method(
+        env,
+        &metadata_obj,
+        "get",
+        "(Ljava/lang/String;)Ljava/lang/String;",
+        &[(&content_key).into()],
+    )?
+    .l()?;
+
+    let content = if content_obj.is_null() {
+        String::new()
+    } else {
+        jni_jobject_to_string(env, content_obj)?
+    };
+
+    let metadata = jni_tika_metadata_to_rust_metadata(env, metadata_obj)?;
+    Ok(Document::new(content, metadata))
+}
+
+/// Wrapper for the `ai.yobix.SignatureResult` java object
+pub struct JSignatureResult {
+    pub signatures: Vec<SignatureInfo>,
+}
+
+impl<'local> JSignatureResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let array_obj = jni_call_method(
+            env,
+            &obj,
+            "getSignatures",
+            "()[Lai/yobix/SignatureInfo;",
+            &[],
+        )?
+        .l()?;
+        let j_array = jni::objects::JObjectArray::from(array_obj);
+        let length = env.get_array_length(&j_array)?;
+
+        let mut signatures = Vec::with_capacity(length as usize);
+        for i in 0..length {
+            let info_obj = env.get_object_array_element(&j_array, i)?;
+
+            let signer_obj =
+                jni_call_method(env, &info_obj, "getSigner", "()Ljava/lang/String;", &[])?.l()?;
+            let signer = if signer_obj.is_null() {
+                None
+            } else {
+                Some(jni_jobject_to_string(env, signer_obj)?)
+            };
+
+            let signed_at_obj =
+                jni_call_method(env, &info_obj, "getSignedAt", "()Ljava/lang/String;", &[])?.l()?;
+            let signed_at = if signed_at_obj.is_null() {
+                None
+            } else {
+                Some(jni_jobject_to_string(env, signed_at_obj)?)
+            };
+
+            let verified = jni_call_method(env, &info_obj, "isVerified", "()Z", &[])?.z()?;
+            let valid = if verified {
+                Some(jni_call_method(env, &info_obj, "isValid", "()Z", &[])?.z()?)
+            } else {
+                None
+            };
+
+            signatures.push(SignatureInfo {
+                signer,
+                signed_at,
+                valid,
+            });
+        }
+
+        Ok(Self { signatures })
+    }
+}
+
+/// Wrapper for the `ai.yobix.FontResult` java object
+pub struct JFontResult {
+    pub fonts: Vec<FontInfo>,
+}
+
+impl<'local> JFontResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let array_obj =
+            jni_call_method(env, &obj, "getFonts", "()[Lai/yobix/FontInfo;", &[])?.l()?;
+        let j_array = jni::objects::JObjectArray::from(array_obj);
+        let length = env.get_array_length(&j_array)?;
+
+        let mut fonts = Vec::with_capacity(length as usize);
+        for i in 0..length {
+            let info_obj = env.get_object_array_element(&j_array, i)?;
+
+            let name_obj =
+                jni_call_method(env, &info_obj, "getName", "()Ljava/lang/String;", &[])?.l()?;
+            let name = if name_obj.is_null() {
+                None
             } else {
-                jni_jobject_to_string(env, content_obj)?
+                Some(jni_jobject_to_string(env, name_obj)?)
             };
 
-            let metadata = jni_tika_metadata_to_rust_metadata(env, metadata_obj)?;
-            documents.push(Document::new(content, metadata));
+            let subtype_obj =
+                jni_call_method(env, &info_obj, "getSubtype", "()Ljava/lang/String;", &[])?.l()?;
+            let subtype = jni_jobject_to_string(env, subtype_obj)?;
+
+            let embedded = jni_call_method(env, &info_obj, "isEmbedded", "()Z", &[])?.z()?;
+
+            fonts.push(FontInfo {
+                name,
+                subtype,
+                embedded,
+            });
         }
 
+        Ok(Self { fonts })
+    }
+}
+
+/// Wrapper for the `ai.yobix.LanguageResult` java object
+pub struct JLanguageResult {
+    pub result: LanguageResult,
+}
+
+impl<'local> JLanguageResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let language_obj =
+            jni_call_method(env, &obj, "getLanguage", "()Ljava/lang/String;", &[])?.l()?;
+        let language = jni_jobject_to_string(env, language_obj)?;
+        let confidence = jni_call_method(env, &obj, "getConfidence", "()F", &[])?.f()?;
+
         Ok(Self {
-            extraction: RecursiveExtraction::new(documents),
+            result: LanguageResult {
+                language,
+                confidence,
+            },
+        })
+    }
+}
+
+/// Wrapper for the `ai.yobix.MemoryStats` java object
+pub struct JMemoryStats {
+    pub stats: JvmMemoryStats,
+}
+
+impl<'local> JMemoryStats {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let mut get = |method: &str| -> ExtractResult<i64> {
+            Ok(jni_call_method(env, &obj, method, "()J", &[])?.j()?)
+        };
+        // The sizes are never negative but the maximum heap, -1 when it is not defined
+        let heap_max = get("getHeapMax")?;
+
+        Ok(Self {
+            stats: JvmMemoryStats {
+                heap_used: get("getHeapUsed")?.max(0) as u64,
+                heap_committed: get("getHeapCommitted")?.max(0) as u64,
+                heap_max: u64::try_from(heap_max).ok(),
+                non_heap_used: get("getNonHeapUsed")?.max(0) as u64,
+                gc_count: get("getGcCount")?.max(0) as u64,
+            },
+        })
+    }
+}
+
+/// Wrapper for the `ai.yobix.PdfConformanceResult` java object
+pub struct JPdfConformanceResult {
+    pub conformance: PdfConformance,
+}
+
+impl<'local> JPdfConformanceResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let info_obj = jni_call_method(
+            env,
+            &obj,
+            "getConformance",
+            "()Lai/yobix/PdfConformance;",
+            &[],
+        )?
+        .l()?;
+
+        let level_obj =
+            jni_call_method(env, &info_obj, "getPdfaLevel", "()Ljava/lang/String;", &[])?.l()?;
+        let pdfa_level = if level_obj.is_null() {
+            None
+        } else {
+            Some(jni_jobject_to_string(env, level_obj)?)
+        };
+        let tagged = jni_call_method(env, &info_obj, "isTagged", "()Z", &[])?.z()?;
+        let has_structure_tree =
+            jni_call_method(env, &info_obj, "hasStructureTree", "()Z", &[])?.z()?;
+
+        Ok(Self {
+            conformance: PdfConformance {
+                pdfa_level,
+                tagged,
+                has_structure_tree,
+            },
+        })
+    }
+}
+
+/// Wrapper for the `ai.yobix.RevisionResult` java object
+pub struct JRevisionResult {
+    pub revisions: Vec<Revision>,
+}
+
+impl<'local> JRevisionResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let array_obj =
+            jni_call_method(env, &obj, "getRevisions", "()[Lai/yobix/Revision;", &[])?.l()?;
+        let j_array = jni::objects::JObjectArray::from(array_obj);
+        let length = env.get_array_length(&j_array)?;
+
+        let mut revisions = Vec::with_capacity(length as usize);
+        for i in 0..length {
+            let revision_obj = env.get_object_array_element(&j_array, i)?;
+            let mut optional_string = |method: &str| -> ExtractResult<Option<String>> {
+                let value_obj =
+                    jni_call_method(env, &revision_obj, method, "()Ljava/lang/String;", &[])?
+                        .l()?;
+                if value_obj.is_null() {
+                    Ok(None)
+                } else {
+                    Ok(Some(jni_jobject_to_string(env, value_obj)?))
+                }
+            };
+
+            revisions.push(Revision {
+                author: optional_string("getAuthor")?,
+                timestamp: optional_string("getTimestamp")?,
+                location: optional_string("getLocation")?,
+            });
+        }
+
+        Ok(Self { revisions })
+    }
+}
+
+/// Wrapper for the `ai.yobix.OleStreamResult` java object, either the names of the streams of a
+/// document or the bytes of one of them
+pub struct JOleStreamResult<'local> {
+    obj: JObject<'local>,
+}
+
+impl<'local> JOleStreamResult<'local> {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        Ok(Self { obj })
+    }
+
+    pub(crate) fn names(&self, env: &mut JNIEnv<'local>) -> ExtractResult<Vec<String>> {
+        let names_obj =
+            jni_call_method(env, &self.obj, "getNames", "()[Ljava/lang/String;", &[])?.l()?;
+        jni_jobject_array_to_vec(env, names_obj)
+    }
+
+    pub(crate) fn data(&self, env: &mut JNIEnv<'local>) -> ExtractResult<Vec<u8>> {
+        let data_obj = jni_call_method(env, &self.obj, "getData", "()[B", &[])?.l()?;
+        Ok(env.convert_byte_array(JByteArray::from(data_obj))?)
+    }
+}
+
+/// Wrapper for the `ai.yobix.MimeMessageResult` java object
+pub struct JMimeMessageResult {
+    pub(crate) message: MimeParts,
+}
+
+impl<'local> JMimeMessageResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let text_plain_obj =
+            jni_call_method(env, &obj, "getTextPlain", "()Ljava/lang/String;", &[])?.l()?;
+        let text_plain = if text_plain_obj.is_null() {
+            None
+        } else {
+            Some(jni_jobject_to_string(env, text_plain_obj)?)
+        };
+        let text_html_obj =
+            jni_call_method(env, &obj, "getTextHtml", "()Ljava/lang/String;", &[])?.l()?;
+        let text_html = if text_html_obj.is_null() {
+            None
+        } else {
+            Some(jni_jobject_to_string(env, text_html_obj)?)
+        };
+
+        let names_obj = jni_call_method(
+            env,
+            &obj,
+            "getAttachmentNames",
+            "()[Ljava/lang/String;",
+            &[],
+        )?
+        .l()?;
+        let names_array = jni::objects::JObjectArray::from(names_obj);
+        let types_obj = jni_call_method(
+            env,
+            &obj,
+            "getAttachmentTypes",
+            "()[Ljava/lang/String;",
+            &[],
+        )?
+        .l()?;
+        let types = jni_jobject_array_to_vec(env, types_obj)?;
+        let data_obj = jni_call_method(env, &obj, "getAttachmentData", "()[[B", &[])?.l()?;
+        let data_array = jni::objects::JObjectArray::from(data_obj);
+
+        let mut attachments = Vec::with_capacity(types.len());
+        for (i, mime_type) in types.into_iter().enumerate() {
+            let name_obj = env.get_object_array_element(&names_array, i as jsize)?;
+            let name = if name_obj.is_null() {
+                None
+            } else {
+                Some(jni_jobject_to_string(env, name_obj)?)
+            };
+            let bytes_obj = env.get_object_array_element(&data_array, i as jsize)?;
+            let data = env.convert_byte_array(JByteArray::from(bytes_obj))?;
+            attachments.push(MimePart {
+                name,
+                mime_type,
+                data,
+            });
+        }
+
+        Ok(Self {
+            message: MimeParts {
+                text_plain,
+                text_html,
+                attachments,
+            },
+        })
+    }
+}
+
+/// Wrapper for the `ai.yobix.WordBoxResult` java object
+pub struct JWordBoxResult {
+    pub words: Vec<WordBox>,
+}
+
+impl<'local> JWordBoxResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        // The words come as parallel arrays, so the pages and the boxes are copied in one call
+        // each and only the texts need a call per word
+        let pages_obj = jni_call_method(env, &obj, "getPages", "()[I", &[])?.l()?;
+        let pages_array = JIntArray::from(pages_obj);
+        let length = env.get_array_length(&pages_array)? as usize;
+        let mut pages = vec![0; length];
+        env.get_int_array_region(&pages_array, 0, &mut pages)?;
+
+        let boxes_obj = jni_call_method(env, &obj, "getBoxes", "()[F", &[])?.l()?;
+        let boxes_array = JFloatArray::from(boxes_obj);
+        let mut boxes = vec![0.0; length * 4];
+        env.get_float_array_region(&boxes_array, 0, &mut boxes)?;
+
+        let texts_obj =
+            jni_call_method(env, &obj, "getTexts", "()[Ljava/lang/String;", &[])?.l()?;
+        let texts_array = jni::objects::JObjectArray::from(texts_obj);
+
+        let mut words = Vec::with_capacity(length);
+        for (i, (page, bounds)) in pages.iter().zip(boxes.chunks_exact(4)).enumerate() {
+            let text_obj = env.get_object_array_element(&texts_array, i as jsize)?;
+            let text = jni_jobject_to_string(env, text_obj)?;
+            words.push(WordBox {
+                text,
+                page: *page as u32,
+                x: bounds[0],
+                y: bounds[1],
+                width: bounds[2],
+                height: bounds[3],
+            });
+        }
+
+        Ok(Self { words })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.MimeCandidatesResult`
+pub struct JMimeCandidatesResult {
+    pub candidates: Vec<(String, f32)>,
+}
+
+impl<'local> JMimeCandidatesResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+            let msg_obj =
+                jni_call_method(env, &obj, "getErrorMessage", "()Ljava/lang/String;", &[])?.l()?;
+            let msg = jni_jobject_to_string(env, msg_obj)?;
+            return match status {
+                1 => Err(Error::IoError(msg)),
+                2 => Err(Error::ParseError(msg)),
+                _ => Err(Error::Unknown(msg)),
+            };
+        }
+
+        let confidences_obj = jni_call_method(env, &obj, "getConfidences", "()[F", &[])?.l()?;
+        let confidences_array = JFloatArray::from(confidences_obj);
+        let length = env.get_array_length(&confidences_array)? as usize;
+        let mut confidences = vec![0.0; length];
+        env.get_float_array_region(&confidences_array, 0, &mut confidences)?;
+
+        let types_obj =
+            jni_call_method(env, &obj, "getTypes", "()[Ljava/lang/String;", &[])?.l()?;
+        let types = jni_jobject_array_to_vec(env, types_obj)?;
+
+        Ok(Self {
+            candidates: types.into_iter().zip(confidences).collect(),
         })
     }
 }
@@ -0,0 +1,152 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::errors::{Error, ExtractResult};
+use crate::tika;
+use jni::objects::GlobalRef;
+
+/// How often the caller of [`run_cancellable`] looks at the token
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// What the caller and the worker of [`run_with_timeout`] know of each other
+#[derive(Default)]
+struct Watch {
+    // The java thread of the worker, once it is attached
+    thread: Option<GlobalRef>,
+    // Set on a timeout or a cancellation, the caller is gone
+    abandoned: bool,
+}
+
+/// A flag to cancel extractions from another thread, see [`run_cancellable`] and the
+/// `*_cancellable` methods of [`Extractor`](crate::Extractor). Clones share the same flag, so
+/// one can be handed to the code that notices that the result is not wanted anymore, such as
+/// the handler of a dropped connection, and another to the extraction. A token stays
+/// cancelled: the extractions started with it afterwards fail right away.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the extractions running with this token or one of its clones
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Runs the extraction on a worker thread attached to the VM, and fails with [`Error::Timeout`]
+/// when it has not finished after `timeout`. The java thread of the worker is then interrupted:
+/// the parsers end at their next content event, or when they wait on something that honors the
+/// interrupt, such as an OCR process. A parser that spins without producing any content keeps
+/// the worker busy until it is done. Either way the result of the worker is dropped.
+///
+/// This is what [`Extractor::set_timeout`](crate::Extractor::set_timeout) uses, and it can bound
+/// several extractions made by the same closure as well.
+pub fn run_with_timeout<T, F>(timeout: Duration, extract: F) -> ExtractResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> ExtractResult<T> + Send + 'static,
+{
+    run_interruptible(Some(timeout), None, extract)
+}
+
+/// Runs the extraction on a worker thread attached to the VM, and fails with
+/// [`Error::Cancelled`] once the token is cancelled. The token is looked at every 50 ms, then
+/// the java thread of the worker is interrupted as on a timeout of [`run_with_timeout`], which
+/// ends the parse and frees what it holds in the VM soon after, and its result is dropped.
+///
+/// This is what the `*_cancellable` methods of [`Extractor`](crate::Extractor) use, and it can
+/// cancel several extractions made by the same closure as well.
+pub fn run_cancellable<T, F>(token: &CancellationToken, extract: F) -> ExtractResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> ExtractResult<T> + Send + 'static,
+{
+    run_interruptible(None, Some(token), extract)
+}
+
+/// Runs the extraction on a worker thread until the timeout elapses or the token is cancelled,
+/// whichever comes first
+pub(crate) fn run_interruptible<T, F>(
+    timeout: Option<Duration>,
+    token: Option<&CancellationToken>,
+    extract: F,
+) -> ExtractResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> ExtractResult<T> + Send + 'static,
+{
+    if token.is_some_and(CancellationToken::is_cancelled) {
+        return Err(Error::Cancelled);
+    }
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let (sender, receiver) = channel();
+    let watch = Arc::new(Mutex::new(Watch::default()));
+    let worker_watch = Arc::clone(&watch);
+    thread::Builder::new()
+        .name("extractous-timeout".to_string())
+        .spawn(move || {
+            // Stays attached while extracting, so that the thread the caller interrupts is the
+            // one the parse runs on
+            let (_env, java_thread) = match tika::attach_java_thread() {
+                Ok(attached) => attached,
+                Err(error) => {
+                    let _ = sender.send(Err(error));
+                    return;
+                }
+            };
+            {
+                let mut watch = worker_watch.lock().unwrap_or_else(PoisonError::into_inner);
+                if watch.abandoned {
+                    return;
+                }
+                watch.thread = Some(java_thread);
+            }
+            // The receiver is gone after a timeout or a cancellation, which drops the result
+            let _ = sender.send(extract());
+        })
+        .map_err(|e| Error::Unknown(format!("Could not start the extraction thread: {}", e)))?;
+
+    let error = loop {
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        let wait = match (remaining, token) {
+            (Some(remaining), Some(_)) => remaining.min(CANCEL_POLL_INTERVAL),
+            (Some(remaining), None) => remaining,
+            (None, _) => CANCEL_POLL_INTERVAL,
+        };
+        match receiver.recv_timeout(wait) {
+            Ok(result) => return result,
+            // The worker panicked and dropped the sender
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(Error::Unknown("The extraction panicked".to_string()))
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if token.is_some_and(CancellationToken::is_cancelled) {
+                    break Error::Cancelled;
+                }
+                if let (Some(timeout), Some(deadline)) = (timeout, deadline) {
+                    if Instant::now() >= deadline {
+                        break Error::Timeout(timeout);
+                    }
+                }
+            }
+        }
+    };
+
+    let mut watch = watch.lock().unwrap_or_else(PoisonError::into_inner);
+    watch.abandoned = true;
+    if let Some(java_thread) = &watch.thread {
+        // The error is reported even if the interrupt cannot be delivered
+        let _ = tika::interrupt_java_thread(java_thread);
+    }
+    Err(error)
+}
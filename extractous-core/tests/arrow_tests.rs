@@ -0,0 +1,44 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::{Array, StringArray, UInt32Array};
+use extractous::{arrow_schema, Extractor};
+
+#[test]
+fn test_extract_files_to_arrow() {
+    let extractor = Extractor::new();
+    let batch = extractor
+        .extract_files_to_arrow(&[
+            "../test_files/documents/2022_Q3_AAPL.pdf",
+            "../test_files/documents/sections.html",
+        ])
+        .unwrap();
+
+    assert_eq!(batch.schema(), arrow_schema());
+    assert_eq!(batch.num_rows(), 2);
+
+    let mime_types = batch
+        .column_by_name("mime_type")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(mime_types.value(0), "application/pdf");
+    assert_eq!(mime_types.value(1), "text/html");
+
+    let pages = batch
+        .column_by_name("page_count")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<UInt32Array>()
+        .unwrap();
+    assert!(pages.value(0) > 0);
+    assert!(pages.is_null(1));
+}
+
+#[test]
+fn test_extract_files_to_arrow_missing_file() {
+    let extractor = Extractor::new();
+    assert!(extractor
+        .extract_files_to_arrow(&["../test_files/documents/missing.pdf"])
+        .is_err());
+}
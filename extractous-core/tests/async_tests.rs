@@ -0,0 +1,64 @@
+#![cfg(feature = "async")]
+
+use extractous::{Error, Extractor};
+use std::fs;
+use std::io::Read;
+use tokio::io::AsyncReadExt;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_extract_file_async() {
+    let file_path = "../test_files/documents/simple.odt";
+    let extractor = Extractor::new();
+    let (mut stream, expected_metadata) = extractor.extract_file(file_path).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+
+    // Spawned, so the futures have to be Send as in a web server
+    let (mut stream, metadata) =
+        tokio::spawn(async move { Extractor::new().extract_file_async(file_path).await })
+            .await
+            .unwrap()
+            .unwrap();
+    let mut extracted = String::new();
+    stream.read_to_string(&mut extracted).await.unwrap();
+    assert_eq!(extracted, expected);
+    assert_eq!(metadata["Content-Type"], expected_metadata["Content-Type"]);
+    assert_eq!(
+        stream.content_type(),
+        Some("application/vnd.oasis.opendocument.text")
+    );
+
+    let result = extractor
+        .extract_file_async("../test_files/documents/missing.odt")
+        .await;
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_extract_bytes_to_string_async() {
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let extractor = Extractor::new();
+    let expected = extractor.extract_bytes_to_string(&bytes).unwrap();
+
+    let (content, metadata) = extractor
+        .extract_bytes_to_string_async(bytes.clone())
+        .await
+        .unwrap();
+    assert_eq!(content, expected.0);
+    assert_eq!(metadata["Content-Type"], expected.1["Content-Type"]);
+
+    // Several extractions at once on the blocking pool
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let extractor = extractor.clone();
+            let bytes = bytes.clone();
+            tokio::spawn(async move { extractor.extract_bytes_async(bytes).await })
+        })
+        .collect();
+    for task in tasks {
+        let (mut stream, _) = task.await.unwrap().unwrap();
+        let mut content = String::new();
+        stream.read_to_string(&mut content).await.unwrap();
+        assert!(content.contains("Apple Inc."));
+    }
+}
@@ -0,0 +1,41 @@
+use extractous::Extractor;
+use std::fs;
+
+#[test]
+fn test_detect_mime_candidates_pdf() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let candidates = extractor.detect_mime_candidates(&bytes).unwrap();
+
+    assert_eq!(candidates[0], ("application/pdf".to_string(), 1.0));
+}
+
+#[test]
+fn test_detect_mime_candidates_lists_supertypes() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/category-level.docx").unwrap();
+    let candidates = extractor.detect_mime_candidates(&bytes).unwrap();
+
+    let (detected, confidence) = &candidates[0];
+    assert_eq!(
+        detected,
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    );
+    assert_eq!(*confidence, 1.0);
+
+    // The zip container matches the magic bytes, with a lower confidence
+    let zip = candidates
+        .iter()
+        .find(|(mime, _)| mime == "application/zip")
+        .expect("application/zip should be a candidate");
+    assert!(zip.1 < 1.0);
+
+    assert!(candidates.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+}
+
+#[test]
+fn test_detect_mime_candidates_empty_input() {
+    let extractor = Extractor::new();
+    let candidates = extractor.detect_mime_candidates(&[]).unwrap();
+    assert_eq!(candidates.len(), 1);
+}
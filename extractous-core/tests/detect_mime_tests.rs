@@ -0,0 +1,48 @@
+use extractous::{Error, Extractor};
+use std::fs;
+
+#[test]
+fn test_detect_mime_file() {
+    let extractor = Extractor::new();
+
+    let mime = extractor
+        .detect_mime_file("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert_eq!(mime, "application/pdf");
+
+    let mime = extractor
+        .detect_mime_file("../test_files/documents/category-level.docx")
+        .unwrap();
+    assert_eq!(
+        mime,
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    );
+
+    let result = extractor.detect_mime_file("../test_files/documents/missing.pdf");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
+
+#[test]
+fn test_detect_mime_bytes_truncated() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+
+    assert_eq!(
+        extractor.detect_mime_bytes(&bytes).unwrap(),
+        "application/pdf"
+    );
+    // The first few kilobytes hold the magic bytes
+    assert_eq!(
+        extractor.detect_mime_bytes(&bytes[..4096]).unwrap(),
+        "application/pdf"
+    );
+}
+
+#[test]
+fn test_detect_mime_bytes_empty_input() {
+    let extractor = Extractor::new();
+    assert_eq!(
+        extractor.detect_mime_bytes(&[]).unwrap(),
+        "application/octet-stream"
+    );
+}
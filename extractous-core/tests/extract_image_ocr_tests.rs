@@ -0,0 +1,31 @@
+use extractous::Extractor;
+
+#[test]
+fn test_extract_image_ocr_scanned_pdf() {
+    // Every page of the scan is an image drawn on that page
+    let extractor = Extractor::new();
+    let images = extractor
+        .extract_image_ocr("../test_files/documents/eng-ocr.pdf")
+        .unwrap();
+
+    assert!(!images.is_empty());
+    for (index, image) in images.iter().enumerate() {
+        assert_eq!(image.image_index, index);
+        assert!(image.page.is_some(), "{:?}", image);
+    }
+    assert_eq!(images[0].page, Some(1));
+    assert!(
+        images[0].text.contains("Inventors Are Everywhere"),
+        "{}",
+        images[0].text
+    );
+}
+
+#[test]
+fn test_extract_image_ocr_without_images() {
+    let extractor = Extractor::new();
+    let images = extractor
+        .extract_image_ocr("../test_files/documents/sections.html")
+        .unwrap();
+    assert!(images.is_empty());
+}
@@ -0,0 +1,46 @@
+use extractous::{Error, Extractor};
+
+#[test]
+fn test_extract_mime_message_alternative_with_attachment() {
+    let extractor = Extractor::new();
+    let message = extractor
+        .extract_mime_message("../test_files/documents/multipart-alternative.eml")
+        .unwrap();
+
+    let text_plain = message.text_plain.unwrap();
+    assert!(text_plain.contains("The quarterly orders are attached."));
+    assert!(!text_plain.contains("<b>"));
+
+    // Decoded from quoted-printable
+    let text_html = message.text_html.unwrap();
+    assert!(
+        text_html.contains("<b>quarterly orders</b>"),
+        "{}",
+        text_html
+    );
+    assert!(text_html.contains("<p>Alice</p>"), "{}", text_html);
+
+    assert_eq!(message.attachments.len(), 1);
+    let attachment = &message.attachments[0];
+    assert_eq!(attachment.metadata["resourceName"], vec!["orders.csv"]);
+    assert!(
+        attachment.content.contains("ORDER-2002"),
+        "{}",
+        attachment.content
+    );
+    assert!(!attachment.content.contains("quarterly"));
+}
+
+#[test]
+fn test_extract_mime_message_not_a_message() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_mime_message("../test_files/documents/simple.odt");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
+
+#[test]
+fn test_extract_mime_message_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_mime_message("../test_files/documents/missing.eml");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
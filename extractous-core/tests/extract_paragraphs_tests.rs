@@ -0,0 +1,48 @@
+use extractous::{Error, Extractor};
+
+#[test]
+fn test_extract_file_paragraphs() {
+    let extractor = Extractor::new();
+    let mut paragraphs = extractor
+        .extract_file_paragraphs("../test_files/documents/category-level.docx")
+        .unwrap();
+
+    let collected: Vec<String> = paragraphs.by_ref().collect();
+    assert!(collected.len() > 1);
+    assert!(collected
+        .iter()
+        .all(|p| !p.is_empty() && p.trim() == p.as_str()));
+
+    // The paragraphs hold the same text as the string extraction
+    let (content, _) = extractor
+        .extract_file_to_string("../test_files/documents/category-level.docx")
+        .unwrap();
+    let first_line = content.lines().find(|l| !l.trim().is_empty()).unwrap();
+    assert!(collected.iter().any(|p| p.contains(first_line.trim())));
+
+    let metadata = paragraphs.finish().unwrap();
+    assert!(metadata.contains_key("Content-Type"));
+}
+
+#[test]
+fn test_extract_file_paragraphs_dropped_early() {
+    let extractor = Extractor::new();
+    let first = extractor
+        .extract_file_paragraphs("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap()
+        .next();
+    assert!(first.is_some());
+
+    // The extractor is still usable while the abandoned parse finishes in the background
+    let (content, _) = extractor
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .unwrap();
+    assert!(!content.is_empty());
+}
+
+#[test]
+fn test_extract_file_paragraphs_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_file_paragraphs("../test_files/documents/missing.docx");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
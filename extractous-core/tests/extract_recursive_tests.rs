@@ -1,4 +1,6 @@
-use extractous::Extractor;
+use extractous::{
+    Document, EntityKind, Extractor, PdfParserConfig, RecursiveConfig, RecursiveExtraction,
+};
 use std::fs;
 
 #[cfg(test)]
@@ -123,3 +125,580 @@ fn test_recursive_embedded_documents_accessor() {
     // 测试 total_count() 方法
     assert_eq!(result.total_count(), result.documents.len());
 }
+
+fn resource_names(result: &RecursiveExtraction) -> Vec<String> {
+    result
+        .embedded_documents()
+        .iter()
+        .filter_map(|doc| doc.metadata.get("resourceName"))
+        .filter_map(|values| values.first().cloned())
+        .collect()
+}
+
+#[test]
+fn test_recursive_parent_hierarchy() {
+    // nested.txt 位于第二层压缩包 inner.zip 中
+    let result = Extractor::new()
+        .extract_file_recursive("../test_files/documents/nested-archive.zip")
+        .unwrap();
+    let index_of = |name: &str| {
+        result
+            .documents
+            .iter()
+            .position(|doc| doc.metadata.get("resourceName") == Some(&vec![name.to_string()]))
+            .unwrap()
+    };
+    let inner = index_of("inner.zip");
+    let nested = index_of("nested.txt");
+
+    assert_eq!(result.documents[0].parent, None);
+    assert_eq!(result.documents[inner].parent, Some(0));
+    assert_eq!(result.documents[nested].parent, Some(inner));
+    assert!(result.children(0).contains(&inner));
+    assert!(result.children(inner).contains(&nested));
+    assert!(!result.children(0).contains(&nested));
+}
+
+#[test]
+fn test_recursive_embedded_names() {
+    // 每个嵌套文档都有文件名、嵌套路径和类型，可以按原文件名写回磁盘
+    for file in ["nested-archive.zip", "science-exploration-1p.pptx"] {
+        let result = Extractor::new()
+            .extract_file_recursive(&format!("../test_files/documents/{}", file))
+            .unwrap();
+        assert_eq!(result.container().unwrap().embedded_path(), None);
+        for doc in result.embedded_documents() {
+            let path = doc.embedded_path().expect("嵌套文档应有嵌套路径");
+            let name = doc.resource_name().expect("嵌套文档应有文件名");
+            assert!(path.ends_with(name), "{} {}", path, name);
+            assert!(doc.metadata.contains_key("Content-Type"), "{}", path);
+        }
+    }
+
+    let result = Extractor::new()
+        .extract_file_recursive("../test_files/documents/nested-archive.zip")
+        .unwrap();
+    let nested = result
+        .embedded_documents()
+        .iter()
+        .find(|doc| doc.resource_name() == Some("nested.txt"))
+        .unwrap();
+    assert_eq!(nested.embedded_path(), Some("/inner.zip/nested.txt"));
+}
+
+#[test]
+fn test_recursive_max_container_depth_keeps_leaf_resources() {
+    // 只展开顶层附件，不进入嵌套压缩包，但仍保留图片
+    let extractor = Extractor::new()
+        .set_recursive_config(RecursiveConfig::new().set_max_container_depth(1));
+    let result = extractor
+        .extract_file_recursive("../test_files/documents/nested-archive.zip")
+        .unwrap();
+
+    let names = resource_names(&result);
+    assert!(names.contains(&"top.txt".to_string()));
+    assert!(names.contains(&"top.png".to_string()));
+    assert!(names.contains(&"inner.zip".to_string()));
+    assert!(names.contains(&"nested.png".to_string()));
+    assert!(!names.contains(&"nested.txt".to_string()), "不应进入第二层容器: {:?}", names);
+}
+
+#[test]
+fn test_recursive_without_leaf_resources() {
+    // 不限制深度，但不输出图片
+    let extractor = Extractor::new()
+        .set_recursive_config(RecursiveConfig::new().set_extract_leaf_resources(false));
+    let result = extractor
+        .extract_file_recursive("../test_files/documents/nested-archive.zip")
+        .unwrap();
+
+    let names = resource_names(&result);
+    assert!(names.contains(&"nested.txt".to_string()));
+    assert!(!names.iter().any(|name| name.ends_with(".png")), "不应包含图片: {:?}", names);
+}
+
+#[test]
+fn test_recursive_embedded_mime_filter() {
+    // 只解析 MIME 类型匹配的嵌套文档，不匹配的压缩包连同其中的文档一起跳过
+    let path = "../test_files/documents/nested-archive.zip";
+    let extractor = Extractor::new().set_embedded_mime_filter(vec!["image/*".to_string()]);
+    let result = extractor.extract_file_recursive(path).unwrap();
+    assert_eq!(resource_names(&result), ["top.png"]);
+    assert!(result.container().is_some());
+
+    let extractor = Extractor::new()
+        .set_embedded_mime_filter(vec!["Application/Zip".to_string(), "image/*".to_string()])
+        .set_recursive_config(RecursiveConfig::new());
+    let mut names = resource_names(&extractor.extract_file_recursive(path).unwrap());
+    names.sort();
+    assert_eq!(names, ["inner.zip", "nested.png", "top.png"]);
+}
+
+#[test]
+fn test_recursive_max_embedded_count() {
+    // 达到嵌套文档数量上限后停止解析，返回已提取的文档并在容器文档中标记
+    let path = "../test_files/documents/nested-archive.zip";
+    let result = Extractor::new()
+        .set_max_embedded_count(2)
+        .extract_file_recursive(path)
+        .unwrap();
+    assert_eq!(result.embedded_documents().len(), 2);
+    assert!(result.is_embedded_truncated());
+
+    let result = Extractor::new()
+        .set_max_embedded_count(100)
+        .extract_file_recursive(path)
+        .unwrap();
+    assert!(resource_names(&result).contains(&"nested.txt".to_string()));
+    assert!(!result.is_embedded_truncated());
+}
+
+#[test]
+fn test_recursive_max_embedded_bytes() {
+    // 解压字节数达到上限后，后续的嵌套文档不再解析
+    let result = Extractor::new()
+        .set_max_embedded_bytes(50)
+        .extract_file_recursive("../test_files/documents/nested-archive.zip")
+        .unwrap();
+    let names = resource_names(&result);
+    assert!(names.contains(&"top.txt".to_string()));
+    assert!(!names.contains(&"inner.zip".to_string()), "{:?}", names);
+    assert!(result.is_embedded_truncated());
+}
+
+#[test]
+fn test_recursive_per_document_max_length() {
+    // 每个文档单独截断，被截断的文档在元数据中标记
+    let extractor = Extractor::new()
+        .set_recursive_config(RecursiveConfig::new().set_per_document_max_length(10));
+    let result = extractor
+        .extract_file_recursive("../test_files/documents/nested-archive.zip")
+        .unwrap();
+
+    let top = result
+        .embedded_documents()
+        .iter()
+        .find(|doc| {
+            doc.metadata
+                .get("resourceName")
+                .is_some_and(|names| names.contains(&"top.txt".to_string()))
+        })
+        .expect("应包含 top.txt");
+    assert!(top.content.chars().count() <= 10, "内容未截断: {:?}", top.content);
+    assert!(top.content.starts_with("This text"));
+    assert_eq!(
+        top.metadata.get("extractous:content_truncated"),
+        Some(&vec!["true".to_string()])
+    );
+
+    // 后面的文档不受前面文档长度的影响
+    let names = resource_names(&result);
+    assert!(names.contains(&"nested.txt".to_string()), "{:?}", names);
+}
+
+#[test]
+fn test_recursive_record_content_digests() {
+    // 每个文档（包括容器）都记录原始字节长度和 SHA-256
+    let path = "../test_files/documents/nested-archive.zip";
+    let extractor = Extractor::new()
+        .set_recursive_config(RecursiveConfig::new().set_record_content_digests(true));
+    let result = extractor.extract_file_recursive(path).unwrap();
+
+    let container = result.container().unwrap();
+    assert_eq!(
+        container.metadata.get("X-Content-Length"),
+        Some(&vec![fs::metadata(path).unwrap().len().to_string()])
+    );
+    for doc in &result.documents {
+        let sha256 = &doc.metadata["X-Content-SHA256"][0];
+        assert_eq!(sha256.len(), 64, "{:?}", doc.metadata);
+        assert!(sha256.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+        assert!(doc.metadata["X-Content-Length"][0].parse::<u64>().unwrap() > 0);
+    }
+
+    // 默认不记录
+    let result = Extractor::new().extract_file_recursive(path).unwrap();
+    assert!(result
+        .documents
+        .iter()
+        .all(|doc| !doc.metadata.contains_key("X-Content-SHA256")));
+}
+
+#[test]
+fn test_recursive_max_total_output_bytes() {
+    // 所有文档的内容合计不超过总输出预算，超出部分被截断或跳过
+    let path = "../test_files/documents/nested-archive.zip";
+    let full = Extractor::new().extract_file_recursive(path).unwrap();
+    assert!(!full.is_output_truncated());
+    let full_len: usize = full.documents.iter().map(|doc| doc.content.len()).sum();
+
+    let max = 40;
+    assert!(full_len > max);
+    let extractor = Extractor::new()
+        .set_recursive_config(RecursiveConfig::new().set_max_total_output_bytes(max as u64));
+    let result = extractor.extract_file_recursive(path).unwrap();
+
+    let total_len: usize = result.documents.iter().map(|doc| doc.content.len()).sum();
+    assert!(total_len <= max, "{} > {}", total_len, max);
+    assert!(result.is_output_truncated());
+    assert!(result.documents.iter().any(|doc| {
+        doc.metadata.get("extractous:content_truncated") == Some(&vec!["true".to_string()])
+    }));
+}
+
+#[test]
+fn test_recursive_parallelism_keeps_sequential_order() {
+    // 并行解析顶层附件，结果顺序、路径和内容应与顺序解析一致
+    let path = "../test_files/documents/nested-archive.zip";
+    let summary = |result: &RecursiveExtraction| -> Vec<(Option<String>, Option<String>, String)> {
+        result
+            .documents
+            .iter()
+            .map(|doc| {
+                let value = |key: &str| {
+                    doc.metadata
+                        .get(key)
+                        .and_then(|values| values.first().cloned())
+                };
+                (
+                    value("X-TIKA:embedded_resource_path"),
+                    value("X-TIKA:embedded_depth"),
+                    doc.content.clone(),
+                )
+            })
+            .collect()
+    };
+
+    let sequential = Extractor::new().extract_file_recursive(path).unwrap();
+    let parallel = Extractor::new()
+        .set_recursive_parallelism(4)
+        .extract_file_recursive(path)
+        .unwrap();
+
+    assert_eq!(summary(&parallel), summary(&sequential));
+    assert!(resource_names(&parallel).contains(&"nested.txt".to_string()));
+
+    // 并行度在更换递归配置后保留，深度限制依然生效
+    let limited = Extractor::new()
+        .set_recursive_parallelism(4)
+        .set_recursive_config(RecursiveConfig::new().set_max_container_depth(1))
+        .extract_file_recursive(path)
+        .unwrap();
+    let names = resource_names(&limited);
+    assert!(names.contains(&"nested.png".to_string()), "{:?}", names);
+    assert!(!names.contains(&"nested.txt".to_string()), "{:?}", names);
+}
+
+fn warc_captures(result: &RecursiveExtraction) -> Vec<(String, String, String)> {
+    // 返回 (URL, 抓取时间, 内容) 列表
+    result
+        .embedded_documents()
+        .iter()
+        .filter_map(|doc| {
+            let url = doc.metadata.get("warc:WARC-Target-URI")?.first()?.clone();
+            let date = doc.metadata.get("warc:WARC-Date")?.first()?.clone();
+            Some((url, date, doc.content.clone()))
+        })
+        .collect()
+}
+
+#[test]
+fn test_recursive_warc_records_as_embedded_documents() {
+    // WARC 中每个 response 记录的载荷作为一个嵌套文档，元数据中包含原始 URL 和抓取时间
+    let extractor = Extractor::new();
+    let result = extractor
+        .extract_file_recursive("../test_files/documents/web-archive.warc")
+        .unwrap();
+
+    let captures = warc_captures(&result);
+    assert_eq!(captures.len(), 2, "应包含两个 HTML 抓取记录: {:?}", captures);
+
+    let (url, date, content) = &captures[0];
+    assert_eq!(url, "https://example.com/");
+    assert_eq!(date, "2024-05-01T10:00:01Z");
+    assert!(content.contains("This domain is for use in illustrative examples."));
+
+    let (url, date, content) = &captures[1];
+    assert_eq!(url, "https://example.org/news");
+    assert_eq!(date, "2024-05-01T10:00:02Z");
+    assert!(content.contains("Web archives preserve captured pages."));
+}
+
+#[test]
+fn test_recursive_wacz_records_as_embedded_documents() {
+    // WACZ 是包含 archive/*.warc.gz 的 zip 包，其中的抓取记录同样作为嵌套文档返回
+    let extractor = Extractor::new();
+    let result = extractor
+        .extract_file_recursive("../test_files/documents/web-archive.wacz")
+        .unwrap();
+
+    let urls: Vec<String> = warc_captures(&result)
+        .into_iter()
+        .map(|(url, _, _)| url)
+        .collect();
+    assert!(urls.contains(&"https://example.com/".to_string()), "{:?}", urls);
+    assert!(urls.contains(&"https://example.org/news".to_string()), "{:?}", urls);
+}
+
+#[test]
+fn test_recursive_mbox_messages_as_embedded_documents() {
+    // mbox 中的每封邮件作为一个嵌套文档返回，并带有邮件元数据
+    let extractor = Extractor::new();
+    let result = extractor
+        .extract_file_recursive("../test_files/documents/mail-archive.mbox")
+        .unwrap();
+
+    let messages: Vec<&Document> = result
+        .embedded_documents()
+        .iter()
+        .filter(|doc| doc.metadata.contains_key("Message-From"))
+        .collect();
+    assert_eq!(messages.len(), 3, "{:?}", result.embedded_documents());
+
+    let subjects: Vec<String> = messages
+        .iter()
+        .filter_map(|doc| doc.metadata.get("dc:title"))
+        .filter_map(|values| values.first().cloned())
+        .collect();
+    assert!(subjects.contains(&"Quarterly report".to_string()), "{:?}", subjects);
+    assert!(subjects.contains(&"Re: Quarterly report".to_string()), "{:?}", subjects);
+    assert!(subjects.contains(&"Team lunch".to_string()), "{:?}", subjects);
+
+    let lunch = messages
+        .iter()
+        .find(|doc| doc.content.contains("Lunch is booked for Friday"));
+    assert!(lunch.is_some(), "邮件正文应作为嵌套文档内容返回");
+}
+
+#[test]
+fn test_recursive_extract_entities() {
+    // 实体偏移量指向返回的文档内容
+    let text = b"Contact Jane at jane.doe@example.com or +1 555 0100.\n\
+        Docs: https://example.com/docs\n";
+
+    let result = Extractor::new().extract_bytes_recursive(text).unwrap();
+    assert!(result.container().unwrap().entities.is_empty());
+
+    let extractor = Extractor::new().set_extract_entities(true);
+    let result = extractor.extract_bytes_recursive(text).unwrap();
+    let container = result.container().unwrap();
+
+    let found: Vec<(EntityKind, &str)> = container
+        .entities
+        .iter()
+        .map(|entity| (entity.kind, &container.content[entity.start..entity.end]))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (EntityKind::Email, "jane.doe@example.com"),
+            (EntityKind::Phone, "+1 555 0100"),
+            (EntityKind::Url, "https://example.com/docs"),
+        ]
+    );
+    assert_eq!(container.entities[1].value, "+15550100");
+}
+
+#[test]
+fn test_recursive_to_ndjson() {
+    // 流式写出的文档与内存中的递归提取结果一致，容器文档最后写出
+    let path = "../test_files/documents/nested-archive.zip";
+    let extractor = Extractor::new();
+    let mut out = Vec::new();
+    let count = extractor
+        .extract_file_recursive_to_ndjson(path, &mut out)
+        .unwrap();
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("每行应为一个 JSON 对象"))
+        .collect();
+    let result = extractor.extract_file_recursive(path).unwrap();
+    assert_eq!(count, lines.len());
+    assert_eq!(count, result.total_count());
+
+    let (container, embedded) = lines.split_last().unwrap();
+    assert!(container["embedded_path"].is_null());
+    assert_eq!(
+        container["metadata"]["Content-Type"][0],
+        result.container().unwrap().metadata["Content-Type"][0]
+    );
+    assert!(embedded.iter().all(|line| line["embedded_path"].is_string()));
+    let nested = embedded
+        .iter()
+        .find(|line| line["metadata"]["resourceName"][0] == "nested.txt")
+        .expect("应包含第二层容器中的 nested.txt");
+    assert!(nested["embedded_path"]
+        .as_str()
+        .unwrap()
+        .ends_with("/inner.zip/nested.txt"));
+}
+
+#[test]
+fn test_recursive_iter() {
+    // 容器文档最先返回，只有解析前已知的元数据，内容完整的容器文档最后返回
+    let path = "../test_files/documents/nested-archive.zip";
+    let extractor = Extractor::new();
+    let documents: Vec<_> = extractor
+        .extract_file_recursive_iter(path)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let result = extractor.extract_file_recursive(path).unwrap();
+    assert_eq!(documents.len(), result.total_count() + 1);
+
+    let (start, rest) = documents.split_first().unwrap();
+    assert!(!start.metadata.contains_key("X-TIKA:embedded_resource_path"));
+    assert_eq!(start.metadata["Content-Type"], vec!["application/zip"]);
+    assert!(start.content.is_empty());
+
+    let (container, embedded) = rest.split_last().unwrap();
+    assert!(!container
+        .metadata
+        .contains_key("X-TIKA:embedded_resource_path"));
+    assert_eq!(
+        container.metadata["Content-Type"],
+        result.documents[0].metadata["Content-Type"]
+    );
+    assert!(embedded
+        .iter()
+        .any(|doc| doc.content.contains("This text is a top level attachment.")));
+
+    // 提前丢弃迭代器不会阻塞
+    let mut documents = extractor.extract_file_recursive_iter(path).unwrap();
+    assert!(documents.next().unwrap().is_ok());
+    drop(documents);
+
+    let missing = extractor.extract_file_recursive_iter("../test_files/documents/missing.zip");
+    assert!(matches!(missing, Err(extractous::Error::IoError(_))));
+}
+
+#[test]
+fn test_extract_file_embedded_to_dir() {
+    // 写出的是嵌套文档的原始字节，第二层容器中的文档也会写出
+    let out_dir =
+        std::env::temp_dir().join(format!("extractous-embedded-to-dir-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&out_dir);
+    let extractor = Extractor::new();
+    let paths = extractor
+        .extract_file_embedded_to_dir("../test_files/documents/nested-archive.zip", &out_dir)
+        .unwrap();
+
+    let mut names: Vec<_> = paths
+        .iter()
+        .map(|path| path.file_name().unwrap().to_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["inner.zip", "nested.png", "nested.txt", "top.png", "top.txt"]);
+    assert!(paths.iter().all(|path| path.starts_with(&out_dir)));
+
+    let top = fs::read_to_string(out_dir.join("top.txt")).unwrap();
+    assert!(top.contains("This text is a top level attachment."));
+    let inner = fs::read(out_dir.join("inner.zip")).unwrap();
+    assert!(inner.starts_with(b"PK"));
+
+    // 再次写出到同一目录时不会覆盖已有文件
+    let again = extractor
+        .extract_file_embedded_to_dir("../test_files/documents/nested-archive.zip", &out_dir)
+        .unwrap();
+    assert!(again.contains(&out_dir.join("top_1.txt")));
+    fs::remove_dir_all(&out_dir).unwrap();
+}
+
+struct FailingWriter;
+
+impl std::io::Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("磁盘已满"))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_recursive_to_ndjson_write_error() {
+    // 写入失败会中止解析并返回 IoError
+    let result = Extractor::new().extract_file_recursive_to_ndjson(
+        "../test_files/documents/nested-archive.zip",
+        FailingWriter,
+    );
+    assert!(
+        matches!(result, Err(extractous::Error::IoError(_))),
+        "{:?}",
+        result
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_recursive_serde_round_trip() {
+    // 递归提取结果序列化为 JSON 后可以原样读回
+    let path = "../test_files/documents/nested-archive.zip";
+    let result = Extractor::new().extract_file_recursive(path).unwrap();
+
+    let json = serde_json::to_string(&result).unwrap();
+    let read_back: RecursiveExtraction = serde_json::from_str(&json).unwrap();
+    assert_eq!(read_back.total_count(), result.total_count());
+    for (doc, expected) in read_back.documents.iter().zip(&result.documents) {
+        assert_eq!(doc.content, expected.content);
+        assert_eq!(doc.metadata, expected.metadata);
+    }
+
+    // 配置中缺少的字段取默认值
+    let config: RecursiveConfig = serde_json::from_str(r#"{"max_container_depth": 1}"#).unwrap();
+    assert_eq!(config, RecursiveConfig::new().set_max_container_depth(1));
+}
+
+#[test]
+fn test_recursive_pdf_inline_image_metadata() {
+    // 同一张图片绘制了两次：第一次在带 Alt 的 Figure 标记内容中，第二次不带
+    let path = "../test_files/documents/tagged-figure.pdf";
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_extract_inline_image_metadata(true));
+    let result = extractor.extract_file_recursive(path).unwrap();
+
+    let container = result.container().unwrap();
+    assert_eq!(
+        container.metadata.get("extractous:pdf_image_count"),
+        Some(&vec!["2".to_string()])
+    );
+    let images = result.embedded_documents();
+    assert_eq!(images.len(), 2);
+    let first = |doc: &Document, key: &str| doc.metadata.get(key).map(|values| values[0].clone());
+    assert_eq!(first(&images[0], "extractous:image_page").unwrap(), "1");
+    assert_eq!(
+        first(&images[0], "extractous:image_box").unwrap(),
+        "72.00,600.00,100.00,50.00"
+    );
+    assert_eq!(first(&images[0], "tiff:ImageWidth").unwrap(), "2");
+    assert_eq!(first(&images[0], "tiff:ImageLength").unwrap(), "2");
+    assert_eq!(
+        first(&images[0], "extractous:image_type").unwrap(),
+        "image/png"
+    );
+    assert_eq!(
+        first(&images[0], "extractous:image_alt").unwrap(),
+        "Company logo"
+    );
+    assert!(images[0].content.trim().is_empty());
+    assert_eq!(
+        first(&images[1], "extractous:image_box").unwrap(),
+        "300.00,600.00,20.00,20.00"
+    );
+    assert_eq!(first(&images[1], "extractous:image_alt"), None);
+
+    // 默认不描述图片
+    let result = Extractor::new().extract_file_recursive(path).unwrap();
+    assert_eq!(result.total_count(), 1);
+    assert_eq!(
+        result
+            .container()
+            .unwrap()
+            .metadata
+            .get("extractous:pdf_image_count"),
+        None
+    );
+}
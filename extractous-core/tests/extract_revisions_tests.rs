@@ -0,0 +1,55 @@
+use extractous::{Error, Extractor, Revision};
+
+#[test]
+fn test_extract_revisions_shared_workbook() {
+    let extractor = Extractor::new();
+    let revisions = extractor
+        .extract_revisions("../test_files/documents/shared-workbook.xlsx")
+        .unwrap();
+    assert_eq!(
+        revisions,
+        vec![
+            Revision {
+                author: Some("Alice Martin".to_string()),
+                timestamp: Some("2024-03-04T09:12:45".to_string()),
+                location: None,
+            },
+            Revision {
+                author: Some("Bob Chen".to_string()),
+                timestamp: Some("2024-03-05T16:40:03".to_string()),
+                location: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_extract_revisions_without_history() {
+    let extractor = Extractor::new();
+    let revisions = extractor
+        .extract_revisions("../test_files/documents/category-level.docx")
+        .unwrap();
+    assert!(revisions.is_empty());
+
+    // The save history of Word 97-2003 documents records no time
+    let revisions = extractor
+        .extract_revisions("../test_files/documents/simple.doc")
+        .unwrap();
+    for revision in &revisions {
+        assert!(revision.timestamp.is_none(), "{:?}", revision);
+    }
+}
+
+#[test]
+fn test_extract_revisions_not_an_office_document() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_revisions("../test_files/documents/2022_Q3_AAPL.pdf");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
+
+#[test]
+fn test_extract_revisions_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_revisions("../test_files/documents/missing.xlsx");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
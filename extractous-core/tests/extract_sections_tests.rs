@@ -0,0 +1,47 @@
+use extractous::Extractor;
+
+#[test]
+fn test_extract_file_sections() {
+    let extractor = Extractor::new();
+    let sections = extractor
+        .extract_file_sections("../test_files/documents/sections.html")
+        .unwrap();
+
+    let headings: Vec<_> = sections.iter().map(|s| s.heading.as_deref()).collect();
+    assert_eq!(headings, vec![None, Some("Overview"), Some("Outlook")]);
+    assert_eq!(sections[0].content, "Prepared by the finance team.");
+
+    let overview = &sections[1];
+    assert_eq!(overview.level, 1);
+    assert_eq!(overview.content, "The year in numbers.");
+    let subheadings: Vec<_> = overview
+        .subsections
+        .iter()
+        .map(|s| s.heading.as_deref())
+        .collect();
+    assert_eq!(subheadings, vec![Some("Revenue"), Some("Costs")]);
+
+    let revenue = &overview.subsections[0];
+    assert_eq!(revenue.level, 2);
+    assert_eq!(revenue.content, "Revenue grew by twelve percent.");
+    assert_eq!(revenue.subsections.len(), 1);
+    assert_eq!(revenue.subsections[0].heading.as_deref(), Some("By region"));
+    assert_eq!(revenue.subsections[0].content, "Europe led the growth.");
+
+    assert_eq!(sections[2].content, "Next year looks promising.");
+    assert!(sections[2].subsections.is_empty());
+}
+
+#[test]
+fn test_extract_file_sections_without_headings() {
+    let extractor = Extractor::new();
+    let sections = extractor
+        .extract_file_sections("../test_files/documents/table-multi-row-column-cells-actual.csv")
+        .unwrap();
+
+    assert_eq!(sections.len(), 1);
+    assert_eq!(sections[0].heading, None);
+    assert_eq!(sections[0].level, 0);
+    assert!(!sections[0].content.is_empty());
+    assert!(sections[0].subsections.is_empty());
+}
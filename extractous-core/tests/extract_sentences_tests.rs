@@ -0,0 +1,24 @@
+use extractous::Extractor;
+
+#[test]
+fn test_extract_file_sentences() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/sections.html";
+    let sentences = extractor.extract_file_sentences(file_path).unwrap();
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    for sentence in &sentences {
+        assert_eq!(&content[sentence.start..sentence.end], sentence.text);
+    }
+    let texts: Vec<_> = sentences.iter().map(|s| s.text.as_str()).collect();
+    // Headings on their own line are not joined with the paragraph that follows them
+    for expected in [
+        "Prepared by the finance team.",
+        "Overview",
+        "The year in numbers.",
+        "Revenue grew by twelve percent.",
+        "Next year looks promising.",
+    ] {
+        assert!(texts.contains(&expected), "{expected:?} not in {texts:?}");
+    }
+}
@@ -0,0 +1,52 @@
+use extractous::Extractor;
+
+#[test]
+fn test_extract_svg_text_and_titles() {
+    let extractor = Extractor::new();
+    let (content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/labeled-chart.svg")
+        .unwrap();
+
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            "Quarterly revenue",
+            "Bar chart of the revenue per quarter in 2024",
+            "Q1: 1.2M",
+            "First quarter",
+            "Second quarter",
+            "Third quarter",
+        ]
+    );
+    // Styles are not text
+    assert!(!content.contains("steelblue"));
+
+    assert_eq!(
+        metadata.get("dc:title"),
+        Some(&vec!["Quarterly revenue".to_string()])
+    );
+    assert_eq!(
+        metadata.get("dc:description"),
+        Some(&vec![
+            "Bar chart of the revenue per quarter in 2024".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_extract_svg_does_not_resolve_external_entities() {
+    let svg = br#"<?xml version="1.0"?>
+<!DOCTYPE svg [<!ENTITY secret SYSTEM "file:///etc/passwd">]>
+<svg xmlns="http://www.w3.org/2000/svg"><text>Label &secret;</text></svg>"#;
+
+    let extractor = Extractor::new();
+    // The parse may fail on the entity, but it must never leak the file content
+    if let Ok((content, _metadata)) = extractor.extract_bytes_to_string(svg) {
+        assert!(!content.contains("root:"));
+    }
+}
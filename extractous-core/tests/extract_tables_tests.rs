@@ -0,0 +1,49 @@
+use extractous::{Extractor, Table};
+
+fn strings(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|cell| cell.to_string()).collect()
+}
+
+#[test]
+fn test_extract_file_tables() {
+    let extractor = Extractor::new();
+    let tables = extractor
+        .extract_file_tables("../test_files/documents/tables.html")
+        .unwrap();
+
+    assert_eq!(
+        tables,
+        vec![
+            Table {
+                header: Some(strings(&["Region", "Q1", "Q2"])),
+                rows: vec![
+                    strings(&["North", "1,200", "1,350"]),
+                    strings(&["South", "980", "1,020"]),
+                ],
+            },
+            Table {
+                header: Some(strings(&["Product", "Units", "Price"])),
+                rows: vec![
+                    strings(&["Widget", "40", "$4.50"]),
+                    strings(&["Gadget", "12", "$19.99"]),
+                ],
+            },
+            Table {
+                header: None,
+                rows: vec![
+                    strings(&["Prepared by", "Finance team"]),
+                    strings(&["Reviewed by", "Audit committee"]),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_extract_file_tables_without_tables() {
+    let extractor = Extractor::new();
+    let tables = extractor
+        .extract_file_tables("../test_files/documents/sections.html")
+        .unwrap();
+    assert!(tables.is_empty());
+}
@@ -1,6 +1,10 @@
-use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use extractous::{
+    CharSet, Error, Extractor, PdfOcrStrategy, PdfParserConfig, SourceInput, TesseractOcrConfig,
+};
+use std::collections::HashMap;
 use std::fs;
-use std::io::Read;
+use std::io::{BufRead, Read};
+use std::path::PathBuf;
 use test_case::test_case;
 use textdistance::nstr::cosine;
 
@@ -86,3 +90,159 @@ fn test_extract_bytes_to_stream_ara_ocr_png() {
     );
     println!("{}: {}", "ara-ocr.png", dist);
 }
+
+#[test]
+fn test_extract_to_stream_with_source() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/simple.odt";
+
+    let (mut stream, _metadata, source) = extractor.extract_file_with_source(file_path).unwrap();
+    let mut from_file = String::new();
+    stream.read_to_string(&mut from_file).unwrap();
+    assert_eq!(source, SourceInput::Path(PathBuf::from(file_path)));
+
+    // The bytes handed back are the ones the stream read
+    let bytes = fs::read(file_path).unwrap();
+    let (mut stream, _metadata, source) =
+        extractor.extract_bytes_with_source(bytes.clone()).unwrap();
+    match source {
+        SourceInput::Bytes(original) => assert_eq!(&original[..], &bytes[..]),
+        SourceInput::Path(_) => panic!("expected the bytes of the input"),
+    }
+    let mut from_bytes = String::new();
+    stream.read_to_string(&mut from_bytes).unwrap();
+    assert_eq!(from_bytes, from_file);
+}
+
+#[test]
+fn test_extract_reader_to_stream() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/simple.odt";
+
+    let (mut stream, expected_metadata) = extractor.extract_file(file_path).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+
+    // The file is read by the VM while the stream is consumed
+    let (mut stream, metadata) = extractor
+        .extract_reader(fs::File::open(file_path).unwrap())
+        .unwrap();
+    let mut extracted = String::new();
+    stream.read_to_string(&mut extracted).unwrap();
+    assert_eq!(extracted, expected);
+    assert_eq!(metadata["Content-Type"], expected_metadata["Content-Type"]);
+}
+
+#[test]
+fn test_extract_file_to_stream_lines() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/simple.odt";
+
+    let (mut stream, _) = extractor.extract_file(file_path).unwrap();
+    let mut expected = String::new();
+    stream.read_to_string(&mut expected).unwrap();
+
+    let (stream, _) = extractor.extract_file(file_path).unwrap();
+    let lines: Vec<String> = stream.lines().map(|line| line.unwrap()).collect();
+    assert_eq!(lines, expected.lines().collect::<Vec<_>>());
+
+    // Reads through the buffer pick up where the lines left off
+    let (mut stream, _) = extractor.extract_file(file_path).unwrap();
+    let mut first = String::new();
+    stream.read_line(&mut first).unwrap();
+    let mut rest = String::new();
+    stream.read_to_string(&mut rest).unwrap();
+    assert_eq!(first + &rest, expected);
+}
+
+#[test]
+fn test_extract_file_to_stream_metadata() {
+    let file_path = "../test_files/documents/simple.odt";
+    let (stream, metadata) = Extractor::new().extract_file(file_path).unwrap();
+    assert_eq!(stream.metadata(), &metadata);
+    assert_eq!(
+        stream.content_type(),
+        Some("application/vnd.oasis.opendocument.text")
+    );
+
+    // The stream keeps the renamed keys too
+    let key_map = HashMap::from([("Content-Type".to_string(), "mime".to_string())]);
+    let (stream, metadata) = Extractor::new()
+        .set_metadata_key_map(key_map)
+        .extract_file(file_path)
+        .unwrap();
+    assert_eq!(stream.metadata(), &metadata);
+    assert!(stream.metadata().contains_key("mime"));
+    assert_eq!(stream.content_type(), None);
+}
+
+#[test]
+fn test_extract_file_to_writer() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/simple.odt";
+
+    let (mut stream, expected_metadata) = extractor.extract_file(file_path).unwrap();
+    let mut expected = Vec::new();
+    stream.read_to_end(&mut expected).unwrap();
+
+    let mut out = Vec::new();
+    let metadata = extractor
+        .extract_file_to_writer(file_path, &mut out)
+        .unwrap();
+    assert_eq!(out, expected);
+    assert_eq!(metadata["Content-Type"], expected_metadata["Content-Type"]);
+
+    let result = extractor.extract_file_to_writer("../test_files/documents/missing.odt", &mut out);
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
+
+#[test_case(CharSet::ISO_8859_1, "Café", b"Caf\xe9"; "ISO-8859-1")]
+#[test_case(CharSet::WINDOWS_1252, "“quoted”", b"\x93quoted\x94"; "windows-1252")]
+#[test_case(CharSet::UTF_16LE, "Café", b"C\0a\0f\0\xe9\0"; "UTF-16LE")]
+#[test_case(CharSet::SHIFT_JIS, "日本語", b"\x93\xfa\x96\x7b\x8c\xea"; "Shift_JIS")]
+fn test_extract_bytes_to_stream_encoding(encoding: CharSet, text: &str, expected: &[u8]) {
+    // The meta charset makes sure the input is decoded as UTF-8, whatever the short text
+    let html = format!(
+        "<html><head><meta charset=\"utf-8\"></head><body><p>{}</p></body></html>",
+        text
+    );
+    let extractor = Extractor::new().set_encoding(encoding);
+    let (mut stream, _metadata) = extractor.extract_bytes(html.as_bytes()).unwrap();
+    let mut extracted = Vec::new();
+    stream.read_to_end(&mut extracted).unwrap();
+
+    assert!(
+        extracted
+            .windows(expected.len())
+            .any(|window| window == expected),
+        "{:?} not found in {:?}",
+        expected,
+        extracted
+    );
+}
+
+#[test]
+fn test_extract_bytes_to_stream_encoding_name() {
+    let html = "<html><head><meta charset=\"utf-8\"></head><body><p>Привет</p></body></html>";
+    let extractor = Extractor::new().set_encoding_name("KOI8-R");
+    let (mut stream, _metadata) = extractor.extract_bytes(html.as_bytes()).unwrap();
+    let mut extracted = Vec::new();
+    stream.read_to_end(&mut extracted).unwrap();
+
+    let expected = b"\xf0\xd2\xc9\xd7\xc5\xd4";
+    assert!(extracted
+        .windows(expected.len())
+        .any(|window| window == expected));
+
+    let extractor = Extractor::new().set_encoding_name("no-such-charset");
+    let result = extractor.extract_bytes(html.as_bytes());
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
+
+#[test]
+fn test_charset_names() {
+    assert_eq!(CharSet::UTF_8.to_string(), "UTF-8");
+    assert_eq!(CharSet::WINDOWS_1252.to_string(), "windows-1252");
+    assert_eq!("SHIFT_JIS".parse::<CharSet>().unwrap(), CharSet::SHIFT_JIS);
+    assert_eq!("Shift_JIS".parse::<CharSet>().unwrap(), CharSet::SHIFT_JIS);
+}
@@ -1,5 +1,14 @@
-use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use extractous::{
+    simhash, CancellationToken, Error, Extractor, MetadataExt, OfficeParserConfig, ParserKind,
+    PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig, UrlFetchConfig,
+};
+use std::collections::HashMap;
 use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
 use test_case::test_case;
 use textdistance::nstr::cosine;
 
@@ -50,6 +59,19 @@ fn test_extract_file_to_string(file_name: &str, target_dist: f64) {
     ));
 }
 
+#[test]
+fn test_extract_file_to_string_text_is_not_ocr() {
+    let extractor = Extractor::new();
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/table-multi-row-column-cells-actual.csv")
+        .unwrap();
+
+    assert_eq!(
+        metadata.get("extractous:ocr_used"),
+        Some(&vec!["false".to_string()])
+    );
+}
+
 #[test]
 fn test_extract_file_to_string_ara_ocr_png() {
     let extractor = Extractor::new()
@@ -74,6 +96,63 @@ fn test_extract_file_to_string_ara_ocr_png() {
     );
 }
 
+#[test]
+fn test_extract_file_to_string_ocr_used_ara_ocr_png() {
+    let extractor = Extractor::new().set_ocr_config(TesseractOcrConfig::new().set_language("ara"));
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+
+    assert_eq!(
+        metadata.get("extractous:ocr_used"),
+        Some(&vec!["true".to_string()])
+    );
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_ocr_config_set_languages() {
+    let config = TesseractOcrConfig::new()
+        .set_languages(vec!["eng".to_string(), "ara".to_string()])
+        .unwrap();
+    assert_eq!(config, TesseractOcrConfig::new().set_language("eng+ara"));
+
+    let missing =
+        TesseractOcrConfig::new().set_languages(vec!["eng".to_string(), "xx_missing".to_string()]);
+    assert!(matches!(
+        missing,
+        Err(Error::OcrLanguageUnavailable(lang)) if lang == "xx_missing"
+    ));
+    assert!(matches!(
+        TesseractOcrConfig::new().set_languages(vec![]),
+        Err(Error::InvalidInput(_))
+    ));
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_ocr_config_set_tesseract_path() {
+    let missing = TesseractOcrConfig::new().set_tesseract_path("../test_files/documents");
+    assert!(matches!(missing, Err(Error::OcrEngineNotFound(_))));
+
+    // The directory of the tesseract on the PATH, given explicitly
+    let dir = std::env::split_paths(&std::env::var_os("PATH").unwrap())
+        .find(|dir| dir.join("tesseract").is_file())
+        .unwrap();
+    let ocr_config = TesseractOcrConfig::new()
+        .set_tesseract_path(dir.to_str().unwrap())
+        .unwrap()
+        .set_language("ara");
+    let (_, metadata) = Extractor::new()
+        .set_ocr_config(ocr_config)
+        .extract_file_to_string("../test_files/documents/ara-ocr.png")
+        .unwrap();
+    assert_eq!(
+        metadata.get("extractous:ocr_used"),
+        Some(&vec!["true".to_string()])
+    );
+}
+
 #[cfg(not(target_os = "macos"))]
 #[test]
 fn test_extract_file_to_string_ocr_only_strategy_deu_ocr_pdf() {
@@ -102,6 +181,79 @@ fn test_extract_file_to_string_ocr_only_strategy_deu_ocr_pdf() {
     );
 }
 
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_extract_file_to_string_ocr_page_count_deu_ocr_pdf() {
+    let extractor = Extractor::new()
+        .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
+        .set_pdf_config(
+            PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_AND_TEXT_EXTRACTION),
+        );
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/deu-ocr.pdf")
+        .unwrap();
+
+    let ocr_page_count: u32 = metadata.get("pdf:ocrPageCount").unwrap()[0]
+        .parse()
+        .unwrap();
+    assert!(ocr_page_count > 0);
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_extract_file_to_string_ocr_density_and_page_seg_mode_deu_ocr_pdf() {
+    let pdf_config = PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY);
+    let ocr_config = TesseractOcrConfig::new()
+        .set_language("deu")
+        .set_density(400)
+        .set_page_seg_mode(3);
+    let (extracted, _) = Extractor::new()
+        .set_ocr_config(ocr_config.clone())
+        .set_pdf_config(pdf_config.clone())
+        .extract_file_to_string("../test_files/documents/deu-ocr.pdf")
+        .unwrap();
+
+    let expected = fs::read_to_string("../test_files/expected_result/deu-ocr.pdf.txt").unwrap();
+    let dist = cosine(&expected, &extracted);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9 for file: deu-ocr.pdf, dist: {}",
+        dist
+    );
+
+    // Tesseract has no page segmentation mode 14, and does not render below 150 dpi
+    for ocr_config in [
+        ocr_config.clone().set_page_seg_mode(14),
+        ocr_config.set_density(72),
+    ] {
+        let result = Extractor::new()
+            .set_ocr_config(ocr_config)
+            .set_pdf_config(pdf_config.clone())
+            .extract_file_to_string("../test_files/documents/deu-ocr.pdf");
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_extract_file_to_string_ocr_timeout_deu_ocr_pdf() {
+    // A page rendered at 600 dpi takes Tesseract far longer than a second
+    let (_, metadata) = Extractor::new()
+        .set_ocr_config(
+            TesseractOcrConfig::new()
+                .set_language("deu")
+                .set_density(600)
+                .set_timeout_seconds(1),
+        )
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY))
+        .extract_file_to_string("../test_files/documents/deu-ocr.pdf")
+        .unwrap();
+    assert_eq!(
+        metadata.get("X-TIKA:ocr_timed_out"),
+        Some(&vec!["true".to_string()])
+    );
+}
+
 #[cfg(not(target_os = "macos"))]
 #[test]
 fn test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf() {
@@ -115,3 +267,1034 @@ fn test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf() {
 
     assert_eq!("", extracted.trim())
 }
+
+fn nested_html(depth: usize) -> Vec<u8> {
+    let mut html = String::from("<html><body>");
+    html.push_str(&"<div>".repeat(depth));
+    html.push_str("deep text");
+    html.push_str(&"</div>".repeat(depth));
+    html.push_str("</body></html>");
+    html.into_bytes()
+}
+
+#[test]
+fn test_extract_bytes_to_string_max_element_depth_exceeded() {
+    let extractor = Extractor::new().set_max_element_depth(100);
+    let result = extractor.extract_bytes_to_string(&nested_html(400));
+    match result {
+        Err(Error::ParseError(msg)) => assert!(msg.contains("nesting"), "{}", msg),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_extract_bytes_to_string_within_max_element_depth() {
+    let extractor = Extractor::new().set_max_element_depth(100);
+    let (content, _metadata) = extractor.extract_bytes_to_string(&nested_html(50)).unwrap();
+    assert!(content.contains("deep text"));
+
+    // 0 disables the limit
+    let extractor = Extractor::new().set_max_element_depth(0);
+    let (content, _metadata) = extractor
+        .extract_bytes_to_string(&nested_html(2_000))
+        .unwrap();
+    assert!(content.contains("deep text"));
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_extract_bytes_to_string_ocr_spill_to_disk_deu_ocr_pdf() {
+    let extractor = Extractor::new()
+        .set_ocr_spill_to_disk(true)
+        .set_ocr_config(TesseractOcrConfig::new().set_language("deu"))
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY));
+    let bytes = fs::read("../test_files/documents/deu-ocr.pdf").unwrap();
+    let (extracted, metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+
+    let expected = fs::read_to_string("../test_files/expected_result/deu-ocr.pdf.txt").unwrap();
+    let dist = cosine(&expected, &extracted);
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9 for file: deu-ocr.pdf, dist: {}",
+        dist
+    );
+    assert_eq!(
+        metadata.get("extractous:ocr_used"),
+        Some(&vec!["true".to_string()])
+    );
+}
+
+#[test]
+fn test_extract_file_to_string_exclude_hidden_text_pdf() {
+    let file_path = "../test_files/documents/hidden-text.pdf";
+
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("White keyword stuffing"));
+    assert!(content.contains("Invisible rendering mode"));
+
+    let extractor = Extractor::new().set_exclude_hidden_text(true);
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Quarterly report for the visible reader."));
+    assert!(content.contains("Closing visible paragraph."));
+    assert!(!content.contains("White keyword stuffing"));
+    assert!(!content.contains("Invisible rendering mode"));
+}
+
+#[test]
+fn test_extract_bytes_to_string_exclude_hidden_text_html() {
+    let html = b"<html><body><p>Shown text</p>\
+        <div style=\"color: red; display: none\">Stuffed keywords</div>\
+        <p hidden>Hidden paragraph</p>\
+        <span style=\"visibility:hidden\">Invisible span</span>\
+        <p>More shown text</p></body></html>";
+
+    let extractor = Extractor::new().set_exclude_hidden_text(true);
+    let (content, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+    assert!(content.contains("Shown text"));
+    assert!(content.contains("More shown text"));
+    assert!(!content.contains("Stuffed keywords"));
+    assert!(!content.contains("Hidden paragraph"));
+    assert!(!content.contains("Invisible span"));
+
+    // The visibility attributes are not part of the xml output
+    let extractor = extractor.set_xml_output(true);
+    let (content, _metadata) = extractor.extract_bytes_to_string(html).unwrap();
+    assert!(content.contains("Shown text"));
+    assert!(!content.contains("style="));
+}
+
+#[test]
+fn test_extract_file_to_string_encrypted_pdf() {
+    // RC4 128 bits, user password "secret"
+    let file_path = "../test_files/documents/encrypted.pdf";
+
+    let result = Extractor::new().extract_file_to_string(file_path);
+    assert!(matches!(result, Err(Error::EncryptedDocument(_))));
+
+    let extractor =
+        Extractor::new().set_pdf_config(PdfParserConfig::default().set_password("wrong"));
+    let result = extractor.extract_file_to_string(file_path);
+    assert!(matches!(result, Err(Error::EncryptedDocument(_))));
+
+    let extractor =
+        Extractor::new().set_pdf_config(PdfParserConfig::default().set_password("secret"));
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Confidential quarterly figures behind a password."));
+}
+
+#[test]
+fn test_config_debug_hides_password() {
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_password("pdf-secret"))
+        .set_office_config(OfficeParserConfig::new().set_password("office-secret"));
+    let debug = format!("{:?}", extractor);
+    assert!(!debug.contains("pdf-secret"));
+    assert!(!debug.contains("office-secret"));
+    assert!(debug.contains("password: Some(\"***\")"));
+}
+
+#[test]
+fn test_extract_file_to_string_pdf_page_range() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extract = |config: PdfParserConfig| {
+        Extractor::new()
+            .set_pdf_config(config)
+            .extract_file_to_string(file_path)
+            .unwrap()
+    };
+
+    let (all_pages, _metadata) = extract(PdfParserConfig::new());
+    let (first_page, metadata) = extract(PdfParserConfig::new().set_page_range(1, 1));
+    assert_eq!(
+        metadata.get("xmpTPg:NPages").unwrap(),
+        &vec!["1".to_string()]
+    );
+    assert!(!first_page.trim().is_empty());
+    assert!(first_page.len() < all_pages.len());
+
+    // Out of range values are clamped to the page count
+    let (clamped, _metadata) = extract(PdfParserConfig::new().set_page_range(0, 1000));
+    assert_eq!(clamped, all_pages);
+    let (past_the_end, _metadata) = extract(PdfParserConfig::new().set_page_range(1000, 1000));
+    assert!(past_the_end.trim().is_empty());
+}
+
+#[test]
+fn test_extract_file_to_string_pdf_annotations() {
+    let file_path = "../test_files/documents/annotated-contract.pdf";
+
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Termination requires ninety days notice."));
+    // A sticky note and a highlight, with their authors
+    assert!(content.contains("Alice Reviewer"));
+    assert!(content.contains("Cap the liability at the contract value."));
+    assert!(content.contains("Bob Counsel"));
+    assert!(content.contains("Check the termination notice period."));
+
+    let extractor =
+        Extractor::new().set_pdf_config(PdfParserConfig::new().set_extract_annotation_text(false));
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Termination requires ninety days notice."));
+    assert!(!content.contains("Alice Reviewer"));
+    assert!(!content.contains("Cap the liability at the contract value."));
+}
+
+#[test]
+fn test_extract_file_to_string_pdf_image_count() {
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_extract_inline_image_metadata(true));
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/tagged-figure.pdf")
+        .unwrap();
+    assert_eq!(
+        metadata.view().first("extractous:pdf_image_count"),
+        Some("2")
+    );
+}
+
+#[test]
+fn test_extract_file_to_string_pdf_form_fields() {
+    let file_path = "../test_files/documents/filled-form.pdf";
+
+    let (content, metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Jane Doe"));
+    let fields = metadata.view().pdf_form_fields();
+    assert_eq!(fields.get("applicant.name").unwrap(), &["Jane Doe"]);
+    assert_eq!(fields.get("subscribed").unwrap(), &["Yes"]);
+    // The radio button state is the index of its export value
+    assert_eq!(fields.get("contact").unwrap(), &["Phone"]);
+
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_extract_acro_form_content(false));
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(!content.contains("Jane Doe"));
+    assert!(metadata.view().pdf_form_fields().is_empty());
+}
+
+#[test]
+fn test_extract_file_to_string_docx_tracked_changes() {
+    let file_path = "../test_files/documents/tracked-changes.docx";
+
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("up to the contract value"));
+    assert!(!content.contains("without any limit"));
+    assert_eq!(content.matches("Notices are sent by mail.").count(), 1);
+
+    let office_config = OfficeParserConfig::new()
+        .set_include_deleted_content(true)
+        .set_include_move_from_content(true);
+    let extractor = Extractor::new().set_office_config(office_config.clone());
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("The supplier is liable without any limit up to the contract value"));
+    assert_eq!(content.matches("Notices are sent by mail.").count(), 2);
+
+    let extractor = Extractor::new()
+        .set_office_config(office_config)
+        .set_xml_output(true);
+    let (xml, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(xml.contains(r#"<del class="deleted">without any limit </del>"#));
+    assert!(xml.contains(r#"<del class="moved">Notices are sent by mail.</del>"#));
+}
+
+#[test]
+fn test_extract_file_to_string_office_comments() {
+    let file_path = "../test_files/documents/commented.docx";
+
+    let (_content, metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(metadata.view().office_comments().is_empty());
+
+    let extractor =
+        Extractor::new().set_office_config(OfficeParserConfig::new().set_include_comments(true));
+    let (_content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    let comments = metadata.view().office_comments();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].author, Some("Alice Reviewer"));
+    assert_eq!(comments[0].date, Some("2024-03-05T10:15:30Z"));
+    assert_eq!(
+        comments[0].text,
+        "Should this be forty five days?\nCheck with finance."
+    );
+    assert_eq!(comments[1].author, Some("Bob Counsel"));
+    assert_eq!(comments[1].date, None);
+    assert_eq!(comments[1].text, "Add a notice period.");
+}
+
+#[test]
+fn test_extract_file_to_string_distrust_declared_charset() {
+    // The page declares iso-8859-1 but is encoded in UTF-8
+    let file_path = "../test_files/documents/wrong-charset.html";
+
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("crÃ¨me"));
+
+    let extractor = Extractor::new().set_trust_declared_charset(false);
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Our café serves crème brûlée"));
+    assert!(content.contains("Die Größe der Portionen"));
+    assert!(
+        metadata
+            .get("Content-Encoding")
+            .is_some_and(|values| values.contains(&"UTF-8".to_string())),
+        "{:?}",
+        metadata.get("Content-Encoding")
+    );
+}
+
+#[test]
+fn test_extract_file_to_string_encoding_auto() {
+    let extractor = Extractor::new().set_encoding_auto(true);
+
+    let file_path = "../test_files/documents/cp1251-report.txt";
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Отчёт о работе склада за третий квартал"));
+    assert_eq!(
+        metadata.get("Content-Encoding"),
+        Some(&vec!["windows-1251".to_string()])
+    );
+
+    // Plain ASCII is read as UTF-8 rather than as one of the single byte charsets
+    let (content, metadata) = extractor
+        .extract_bytes_to_string(b"Shipment 42 received on time.\n")
+        .unwrap();
+    assert!(content.contains("Shipment 42 received on time."));
+    assert_eq!(
+        metadata.get("Content-Encoding"),
+        Some(&vec!["UTF-8".to_string()])
+    );
+}
+
+#[test]
+fn test_extract_file_to_string_dedup_repeated_lines() {
+    // Four pages, each with the same header and a page number footer
+    let file_path = "../test_files/documents/running-footer.pdf";
+
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert_eq!(
+        content.matches("Northwind Traders - Confidential").count(),
+        4
+    );
+    assert!(content.contains("Page 4 of 4"));
+
+    let extractor = Extractor::new().set_dedup_repeated_lines(true);
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(
+        content.matches("Northwind Traders - Confidential").count(),
+        1
+    );
+    assert_eq!(content.matches(" of 4").count(), 1);
+    assert!(content.contains("Page 1 of 4"));
+    assert!(content.contains("Revenue grew in every region this year."));
+    assert!(content.contains("The board approved the dividend."));
+    assert!(!content.contains('\u{c}'));
+}
+
+#[test]
+fn test_extract_file_to_string_force_text_types() {
+    // A text export whose record separators make it look binary
+    let file_path = "../test_files/documents/records.dat";
+
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(!content.contains("ORDER-1001"));
+
+    let extractor = Extractor::new().set_force_text_types(vec![".DAT".to_string()]);
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("ORDER-1001"), "{}", content);
+    assert!(content.contains("Sprocket"));
+    assert!(metadata["Content-Type"][0].starts_with("text/plain"));
+
+    // By detected media type
+    let extractor =
+        Extractor::new().set_force_text_types(vec!["application/octet-stream".to_string()]);
+    let (content, _metadata) = extractor
+        .extract_bytes_to_string(&fs::read(file_path).unwrap())
+        .unwrap();
+    assert!(content.contains("ORDER-1002"), "{}", content);
+}
+
+#[test]
+fn test_extract_file_to_string_parser_fallback_chain() {
+    // Cut off in the middle of an element, which the XML parser fails on
+    let file_path = "../test_files/documents/truncated.xml";
+    let result = Extractor::new().extract_file_to_string(file_path);
+    assert!(matches!(result, Err(Error::ParseError(_))));
+
+    let extractor =
+        Extractor::new().set_parser_fallback_chain(vec![ParserKind::Html, ParserKind::PlainText]);
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("Midnight Rain"), "{}", content);
+    // The lenient HTML parser, first in the chain, makes do with the missing end tags
+    assert_eq!(metadata["extractous:fallback_parser"], vec!["Html"]);
+    assert!(!metadata["X-TIKA:EXCEPTION:warn"].is_empty());
+
+    // A document its parser succeeds on is not retried
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/sections.html")
+        .unwrap();
+    assert!(!metadata.contains_key("extractous:fallback_parser"));
+}
+
+#[test]
+fn test_extract_file_to_string_rejects_directories() {
+    let result = Extractor::new().extract_file_to_string("../test_files/documents");
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+}
+
+#[test]
+fn test_extract_file_to_string_record_parser_chain() {
+    let file_path = "../test_files/documents/table-multi-row-column-cells-actual.csv";
+    let (_content, metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(!metadata.contains_key("extractous:parser_chain"));
+
+    let extractor = Extractor::new().set_record_parser_chain(true);
+    let (_content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(
+        metadata["extractous:parser_chain"],
+        vec![
+            "org.apache.tika.parser.AutoDetectParser",
+            "org.apache.tika.parser.DefaultParser",
+            "org.apache.tika.parser.csv.TextAndCSVParser",
+        ]
+    );
+
+    // Already there before the stream is read
+    let (_reader, stream_metadata) = extractor.extract_file(file_path).unwrap();
+    assert_eq!(
+        stream_metadata["extractous:parser_chain"],
+        metadata["extractous:parser_chain"]
+    );
+
+    // The fallback parser comes after the parser that failed
+    let extractor = extractor.set_parser_fallback_chain(vec![ParserKind::Html]);
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/truncated.xml")
+        .unwrap();
+    let chain = &metadata["extractous:parser_chain"];
+    assert!(
+        chain.iter().any(|parser| parser.ends_with(".XMLParser")),
+        "{:?}",
+        chain
+    );
+    assert_eq!(
+        chain.last().unwrap(),
+        "org.apache.tika.parser.html.JSoupParser"
+    );
+}
+
+#[test]
+fn test_extract_bytes_to_string_zip_recovery() {
+    // Cut off in the middle of the third entry, inner.zip, which leaves no central directory
+    let bytes = fs::read("../test_files/documents/nested-archive.zip").unwrap();
+    let truncated = &bytes[..250];
+    assert!(Extractor::new().extract_bytes_to_string(truncated).is_err());
+
+    let extractor = Extractor::new().set_zip_recovery(true);
+    let (content, metadata) = extractor.extract_bytes_to_string(truncated).unwrap();
+    assert!(
+        content.contains("This text is a top level attachment."),
+        "{}",
+        content
+    );
+    assert_eq!(
+        metadata["extractous:zip_recovered_entries"],
+        vec!["3".to_string()]
+    );
+
+    // Sound archives are parsed as they are
+    let (_content, metadata) = extractor.extract_bytes_to_string(&bytes).unwrap();
+    assert!(!metadata.contains_key("extractous:zip_recovered_entries"));
+}
+
+#[test]
+fn test_extract_file_to_string_streaming_threshold() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let (expected, expected_metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(expected.len() > 1000);
+
+    // Streamed in many chunks, which may end in the middle of a character
+    let extractor = Extractor::new().set_string_streaming_threshold(1000);
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content, expected);
+    assert_eq!(metadata["Content-Type"], expected_metadata["Content-Type"]);
+
+    let (expected, _metadata) = Extractor::new()
+        .set_xml_output(true)
+        .extract_file_to_string(file_path)
+        .unwrap();
+    let (content, _metadata) = extractor
+        .set_xml_output(true)
+        .extract_file_to_string(file_path)
+        .unwrap();
+    assert_eq!(content, expected);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_extract_file_to_json() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/sections.html";
+    let value = extractor.extract_file_to_json(file_path).unwrap();
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    assert_eq!(value["content"], content.as_str());
+    assert!(value["content_type"]
+        .as_str()
+        .is_some_and(|content_type| content_type.starts_with("text/html")));
+    assert_eq!(
+        value["metadata"]["Content-Type"][0],
+        metadata["Content-Type"][0].as_str()
+    );
+}
+
+#[test]
+fn test_session_matches_extractor() {
+    let extractor = Extractor::new().set_extract_string_max_length(10_000);
+    let mut session = extractor.session().unwrap();
+
+    for file_name in ["2022_Q3_AAPL.pdf", "simple.odt", "category-level.docx"] {
+        let file_path = format!("../test_files/documents/{}", file_name);
+        let (expected, _metadata) = extractor.extract_file_to_string(&file_path).unwrap();
+
+        // Run each file twice to make sure the session can be reused
+        for _ in 0..2 {
+            let (content, metadata) = session.extract_file_to_string(&file_path).unwrap();
+            assert_eq!(content, expected, "file: {}", file_name);
+            assert!(metadata.contains_key("Content-Type"));
+        }
+
+        let bytes = fs::read(&file_path).unwrap();
+        let (content, _metadata) = session.extract_bytes_to_string(&bytes).unwrap();
+        assert_eq!(content, expected, "file: {}", file_name);
+    }
+
+    assert!(session
+        .extract_file_to_string("../test_files/documents/missing.pdf")
+        .is_err());
+}
+
+#[test]
+fn test_extract_bytes_to_string_emit_line_numbers() {
+    let log = b"2024-05-01 10:00:00 INFO service started\n\
+        2024-05-01 10:00:05 WARN disk usage at 91%\n\
+        \n\
+        2024-05-01 10:01:00 ERROR request failed\n";
+
+    let extractor = Extractor::new().set_emit_line_numbers(true);
+    let (content, _metadata) = extractor.extract_bytes_to_string(log).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "1\t2024-05-01 10:00:00 INFO service started",
+            "2\t2024-05-01 10:00:05 WARN disk usage at 91%",
+            "3\t",
+            "4\t2024-05-01 10:01:00 ERROR request failed",
+        ]
+    );
+
+    // Formats whose lines do not come from the source are left unchanged
+    let file_path = "../test_files/documents/simple.odt";
+    let (numbered, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    let (plain, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert_eq!(numbered, plain);
+}
+
+#[test]
+fn test_extract_bytes_to_string_detect_script() {
+    let extractor = Extractor::new().set_detect_script(true);
+    let (_content, metadata) = extractor
+        .extract_bytes_to_string("自然语言处理需要先进行分词，然后再建立索引。".as_bytes())
+        .unwrap();
+    assert_eq!(
+        metadata.get("extractous:dominant_script"),
+        Some(&vec!["Cjk".to_string()])
+    );
+
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .unwrap();
+    assert_eq!(
+        metadata.get("extractous:dominant_script"),
+        Some(&vec!["Latin".to_string()])
+    );
+
+    // Off by default
+    let (_content, metadata) = Extractor::new()
+        .extract_bytes_to_string("自然语言处理".as_bytes())
+        .unwrap();
+    assert!(!metadata.contains_key("extractous:dominant_script"));
+}
+
+#[test]
+fn test_detect_language() {
+    let extractor = Extractor::new();
+    let result = extractor
+        .detect_language(
+            "Die Quartalszahlen zeigen ein deutliches Wachstum im Geschäft mit Cloud-Diensten.",
+        )
+        .unwrap();
+    assert_eq!(result.language, "de");
+    assert!(result.confidence > 0.5 && result.confidence <= 1.0);
+
+    let result = extractor.detect_language("").unwrap();
+    assert_eq!(result.language, "");
+}
+
+#[test]
+fn test_extract_bytes_to_string_detect_language() {
+    let extractor = Extractor::new().set_detect_language(true);
+    let text = "Les résultats trimestriels montrent une forte croissance des services en ligne.";
+    let (_content, metadata) = extractor.extract_bytes_to_string(text.as_bytes()).unwrap();
+    assert_eq!(metadata.get("language"), Some(&vec!["fr".to_string()]));
+
+    // Off by default
+    let (_content, metadata) = Extractor::new()
+        .extract_bytes_to_string(text.as_bytes())
+        .unwrap();
+    assert!(!metadata.contains_key("language"));
+}
+
+#[test]
+fn test_extract_file_to_string_compute_simhash() {
+    let file_path = "../test_files/documents/simple.odt";
+    let (_content, metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(!metadata.contains_key("X-SimHash"));
+
+    let extractor = Extractor::new().set_compute_simhash(true);
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(
+        metadata["X-SimHash"],
+        vec![format!("{:016x}", simhash(&content))]
+    );
+
+    // A reformatted copy of the same text gets the same fingerprint
+    let (_content, copy_metadata) = extractor
+        .extract_bytes_to_string(content.to_uppercase().as_bytes())
+        .unwrap();
+    assert_eq!(copy_metadata["X-SimHash"], metadata["X-SimHash"]);
+}
+
+#[test]
+fn test_extract_file_to_string_preserve_scripts() {
+    let file_path = "../test_files/documents/chemistry.html";
+    let (content, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("CH4 + 2O2 → CO2 + 2H2O"), "{}", content);
+
+    let extractor = Extractor::new().set_preserve_scripts(true);
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.contains("CH₄ + 2O₂ → CO₂ + 2H₂O"), "{}", content);
+    assert!(content.contains("x²"), "{}", content);
+    // Without Unicode superscript or subscript characters for all the letters
+    assert!(content.contains("1^{st} study"), "{}", content);
+    assert!(content.contains("K_{eq} was"), "{}", content);
+
+    // The xml output keeps the elements
+    let (content, _metadata) = extractor
+        .extract_file_to_string_opt(file_path, None, Some(true), None)
+        .unwrap();
+    assert!(content.contains("<sub>4</sub>"), "{}", content);
+}
+
+#[test]
+fn test_extract_files_streaming() {
+    let file_paths = [
+        "../test_files/documents/2022_Q3_AAPL.pdf",
+        "../test_files/documents/simple.odt",
+        "../test_files/documents/missing.pdf",
+        "../test_files/documents/category-level.docx",
+    ];
+    let extractor = Extractor::new();
+    let results: HashMap<String, _> = extractor
+        .extract_files_streaming(&file_paths)
+        .into_iter()
+        .collect();
+
+    // One result per file, whatever the order they finished in
+    assert_eq!(results.len(), file_paths.len());
+    for file_path in file_paths {
+        match extractor.extract_file_to_string(file_path) {
+            Ok((content, _metadata)) => {
+                assert_eq!(results[file_path].as_ref().unwrap().0, content)
+            }
+            Err(_) => assert!(matches!(results[file_path], Err(Error::IoError(_)))),
+        }
+    }
+
+    assert_eq!(extractor.extract_files_streaming(&[]).iter().count(), 0);
+
+    // A single worker still gives every file its result
+    let results: HashMap<String, _> = extractor
+        .extract_files_streaming_with_workers(&file_paths, 1)
+        .into_iter()
+        .collect();
+    assert_eq!(results.len(), file_paths.len());
+    assert!(matches!(
+        results["../test_files/documents/missing.pdf"],
+        Err(Error::IoError(_))
+    ));
+}
+
+#[test]
+fn test_extract_files_to_string() {
+    let file_paths = [
+        "../test_files/documents/2022_Q3_AAPL.pdf",
+        "../test_files/documents/simple.odt",
+        "../test_files/documents/missing.pdf",
+        "../test_files/documents/category-level.docx",
+        "../test_files/documents/simple.odt",
+    ];
+    let extractor = Extractor::new();
+    let results = extractor.extract_files_to_string(&file_paths);
+
+    // The results follow the order of the paths, duplicates included
+    assert_eq!(results.len(), file_paths.len());
+    for (file_path, result) in file_paths.iter().zip(&results) {
+        match extractor.extract_file_to_string(file_path) {
+            Ok((content, _metadata)) => assert_eq!(result.as_ref().unwrap().0, content),
+            Err(_) => assert!(matches!(result, Err(Error::IoError(_)))),
+        }
+    }
+
+    assert!(extractor.extract_files_to_string(&[]).is_empty());
+}
+
+#[test]
+fn test_extract_file_to_string_metadata_key_map() {
+    let file_path = "../test_files/documents/simple.odt";
+    let (_content, expected) = Extractor::new().extract_file_to_string(file_path).unwrap();
+
+    let extractor = Extractor::new().set_metadata_key_map(HashMap::from([
+        ("Content-Type".to_string(), "mime".to_string()),
+        ("not-reported".to_string(), "unused".to_string()),
+    ]));
+    let (_content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(metadata["mime"], expected["Content-Type"]);
+    assert!(!metadata.contains_key("Content-Type"));
+    assert!(!metadata.contains_key("unused"));
+    // The other keys are left as they are
+    assert_eq!(metadata.len(), expected.len());
+
+    // The stream methods return the renamed keys too
+    let (_reader, metadata) = extractor.extract_file(file_path).unwrap();
+    assert!(metadata.contains_key("mime"));
+    assert!(!metadata.contains_key("Content-Type"));
+}
+
+#[test]
+fn test_extract_file_to_string_is_deterministic() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    for extractor in [
+        Extractor::new(),
+        Extractor::new().set_pdf_config(PdfParserConfig::new().set_sort_by_position(true)),
+    ] {
+        let (expected, expected_metadata) = extractor.extract_file_to_string(file_path).unwrap();
+        for _ in 0..3 {
+            let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+            assert_eq!(content, expected);
+            assert_eq!(
+                metadata.sorted_entries(),
+                expected_metadata.sorted_entries()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_extract_file_to_string_soft_time_budget() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let (full, metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+    assert!(!metadata.contains_key("extractous:content_partial"));
+
+    // The budget elapses long before the end of the document: the extraction still succeeds,
+    // with the content gathered so far
+    let extractor = Extractor::new().set_soft_time_budget(Duration::from_millis(1));
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert!(content.len() < full.len());
+    assert_eq!(
+        metadata.get("extractous:content_partial"),
+        Some(&vec!["true".to_string()])
+    );
+
+    // A budget the parse fits in changes nothing
+    let extractor = Extractor::new().set_soft_time_budget(Duration::from_secs(600));
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content, full);
+    assert!(!metadata.contains_key("extractous:content_partial"));
+}
+
+#[test]
+fn test_extract_file_to_string_timeout() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let (full, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+
+    // The timeout elapses long before the end of the document: nothing of it is returned
+    let extractor = Extractor::new().set_timeout(Duration::from_millis(1));
+    assert!(matches!(
+        extractor.extract_file_to_string(file_path),
+        Err(Error::Timeout(_))
+    ));
+    assert!(matches!(
+        extractor.extract_file_to_string_opt(file_path, None, None, None),
+        Err(Error::Timeout(_))
+    ));
+    let bytes = std::fs::read(file_path).unwrap();
+    assert!(matches!(
+        extractor.extract_bytes_recursive(&bytes),
+        Err(Error::Timeout(_))
+    ));
+    assert!(matches!(
+        Extractor::new().extract_file_opt_with_timeout(
+            file_path,
+            None,
+            None,
+            None,
+            Duration::from_millis(1)
+        ),
+        Err(Error::Timeout(_))
+    ));
+
+    // A timeout the parse fits in changes nothing, and the interrupted parses leave the VM usable
+    let extractor = Extractor::new().set_timeout(Duration::from_secs(600));
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content, full);
+}
+
+/// The path and user agent of the requests a test server got
+type Requests = Arc<Mutex<Vec<(String, String)>>>;
+
+/// Serves a site whose robots.txt, at `robots_path` where `/robots.txt` redirects to otherwise,
+/// disallows `/private/` to TestBot on a local port, and returns its base url and the path and
+/// user agent of every request it gets
+fn serve_robots_site(robots_path: &'static str) -> (String, Requests) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&requests);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut lines = BufReader::new(&stream).lines().map(Result::unwrap);
+            let request_line = lines.next().unwrap_or_default();
+            let path = request_line
+                .split(' ')
+                .nth(1)
+                .unwrap_or_default()
+                .to_string();
+            let user_agent = lines
+                .take_while(|line| !line.is_empty())
+                .find_map(|line| line.strip_prefix("User-Agent: ").map(str::to_string))
+                .unwrap_or_default();
+            seen.lock().unwrap().push((path.clone(), user_agent));
+            let (status, location, body) = if path == robots_path {
+                ("200 OK", "", "User-agent: TestBot\nDisallow: /private/\n")
+            } else if path == "/robots.txt" {
+                ("301 Moved Permanently", robots_path, "")
+            } else {
+                ("200 OK", "", "A page of the test site")
+            };
+            let location = if location.is_empty() {
+                String::new()
+            } else {
+                format!("Location: {}\r\n", location)
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\n{}Content-Type: text/plain\r\nContent-Length: {}\r\n\
+                 Connection: close\r\n\r\n{}",
+                status,
+                location,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+    (base_url, requests)
+}
+
+#[test]
+fn test_extract_url_to_string_respect_robots() {
+    let (base_url, requests) = serve_robots_site("/robots.txt");
+    let private_url = format!("{}/private/page.txt", base_url);
+    let public_url = format!("{}/public/page.txt", base_url);
+
+    let config = UrlFetchConfig::new().set_user_agent("TestBot/1.0");
+    let extractor = Extractor::new().set_url_fetch_config(config.clone().set_respect_robots(true));
+    let result = extractor.extract_url_to_string(&private_url);
+    assert!(
+        matches!(result, Err(Error::DisallowedByRobots(ref url)) if *url == private_url),
+        "{:?}",
+        result
+    );
+    let (content, _metadata) = extractor.extract_url_to_string(&public_url).unwrap();
+    assert!(content.contains("A page of the test site"));
+
+    // The disallowed page was never fetched, and every request sent the user agent
+    let seen = requests.lock().unwrap().clone();
+    let paths: Vec<_> = seen.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths, ["/robots.txt", "/robots.txt", "/public/page.txt"]);
+    assert!(seen
+        .iter()
+        .all(|(_, user_agent)| user_agent == "TestBot/1.0"));
+
+    // Off by default
+    let extractor = Extractor::new().set_url_fetch_config(config);
+    let (content, _metadata) = extractor.extract_url_to_string(&private_url).unwrap();
+    assert!(content.contains("A page of the test site"));
+}
+
+#[test]
+fn test_extract_url_to_string_respect_robots_redirect() {
+    // Such as the redirect from http to https, which the connection does not follow itself
+    let (base_url, requests) = serve_robots_site("/moved/robots.txt");
+    let private_url = format!("{}/private/page.txt", base_url);
+
+    let config = UrlFetchConfig::new()
+        .set_user_agent("TestBot/1.0")
+        .set_respect_robots(true);
+    let result = Extractor::new()
+        .set_url_fetch_config(config)
+        .extract_url_to_string(&private_url);
+    assert!(
+        matches!(result, Err(Error::DisallowedByRobots(_))),
+        "{:?}",
+        result
+    );
+    let seen = requests.lock().unwrap().clone();
+    let paths: Vec<_> = seen.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(paths, ["/robots.txt", "/moved/robots.txt"]);
+}
+
+#[test]
+fn test_extract_file_to_string_cancellable() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new();
+    let (full, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    // Cancelled from another thread while the document is parsed
+    let token = CancellationToken::new();
+    let canceller = {
+        let token = token.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(1));
+            token.cancel();
+        })
+    };
+    assert!(matches!(
+        extractor.extract_file_to_string_cancellable(file_path, &token),
+        Err(Error::Cancelled)
+    ));
+    canceller.join().unwrap();
+
+    // A cancelled token fails the extractions that follow right away
+    let bytes = fs::read(file_path).unwrap();
+    assert!(matches!(
+        extractor.extract_bytes_to_string_cancellable(&bytes, &token),
+        Err(Error::Cancelled)
+    ));
+
+    // A token that is never cancelled changes nothing, and the cancelled parse left the VM usable
+    let (content, _metadata) = extractor
+        .extract_file_to_string_cancellable(file_path, &CancellationToken::new())
+        .unwrap();
+    assert_eq!(content, full);
+}
+
+#[test]
+fn test_extract_file_to_string_with_font_dir() {
+    // The test files directory holds no font, its files are skipped and the fonts are mapped
+    // as without a font directory
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let (expected, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_font_dir("../test_files/documents"));
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content, expected);
+
+    // A missing directory yields no fonts and is not an error
+    let extractor = Extractor::new()
+        .set_pdf_config(PdfParserConfig::new().set_font_dir("../test_files/missing-fonts"));
+    assert!(extractor.extract_file_to_string(file_path).is_ok());
+}
+
+#[test]
+fn test_extract_reader_to_string() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new();
+    let (expected, expected_metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    // A reader that hands the bytes over in two parts, like a network stream would
+    let bytes = fs::read(file_path).unwrap();
+    let (head, tail) = bytes.split_at(bytes.len() / 3);
+    let (content, metadata) = extractor
+        .extract_reader_to_string(head.chain(tail))
+        .unwrap();
+    assert_eq!(content, expected);
+    assert_eq!(metadata["Content-Type"], expected_metadata["Content-Type"]);
+
+    // The errors of the reader fail the parse
+    struct FailingReader;
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("connection reset"))
+        }
+    }
+    assert!(extractor.extract_reader_to_string(FailingReader).is_err());
+}
+
+#[test]
+fn test_extract_path_to_string() {
+    let extractor = Extractor::new();
+    let path: PathBuf = ["..", "test_files", "documents", "simple.odt"]
+        .iter()
+        .collect();
+    let (expected, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .unwrap();
+
+    let (content, _metadata) = extractor.extract_path_to_string(&path).unwrap();
+    assert_eq!(content, expected);
+    let extraction = extractor.extract_path_recursive(path.as_path()).unwrap();
+    assert!(extraction.container().is_some());
+
+    assert!(matches!(
+        extractor.extract_path(path.with_file_name("missing.odt")),
+        Err(Error::IoError(_))
+    ));
+}
+
+#[test]
+fn test_extract_file_metadata() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new();
+    let (_content, expected) = extractor.extract_file_to_string(file_path).unwrap();
+
+    let metadata = extractor.extract_file_metadata(file_path).unwrap();
+    assert_eq!(metadata["Content-Type"], expected["Content-Type"]);
+    assert_eq!(metadata["xmpTPg:NPages"], expected["xmpTPg:NPages"]);
+
+    let bytes = fs::read(file_path).unwrap();
+    let metadata = extractor.extract_bytes_metadata(&bytes).unwrap();
+    assert_eq!(metadata["xmpTPg:NPages"], expected["xmpTPg:NPages"]);
+
+    assert!(matches!(
+        extractor.extract_file_metadata("../test_files/documents/missing.pdf"),
+        Err(Error::IoError(_))
+    ));
+}
+
+#[test]
+fn test_extract_file_to_string_metadata_view() {
+    let extractor = Extractor::new();
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+
+    let view = metadata.view();
+    assert_eq!(view.content_type(), Some("application/pdf"));
+    assert_eq!(view.page_count(), Some(28));
+    let created = view.created().unwrap();
+    assert_eq!(
+        created.duration_since(UNIX_EPOCH).unwrap(),
+        Duration::from_secs(1_659_089_001)
+    );
+}
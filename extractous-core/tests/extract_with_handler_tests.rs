@@ -0,0 +1,81 @@
+use extractous::{ContentHandler, Extractor};
+
+#[derive(Default)]
+struct RecordingHandler {
+    documents: usize,
+    open_elements: Vec<String>,
+    max_depth: usize,
+    elements: Vec<String>,
+    text: String,
+}
+
+impl ContentHandler for RecordingHandler {
+    fn start_document(&mut self) {
+        self.documents += 1;
+    }
+
+    fn start_element(&mut self, name: &str, _attributes: &[(String, String)]) {
+        self.open_elements.push(name.to_string());
+        self.max_depth = self.max_depth.max(self.open_elements.len());
+        self.elements.push(name.to_string());
+    }
+
+    fn end_element(&mut self, name: &str) {
+        assert_eq!(self.open_elements.pop().as_deref(), Some(name));
+    }
+
+    fn characters(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+}
+
+#[test]
+fn test_extract_file_with_handler_receives_events() {
+    let extractor = Extractor::new();
+    let mut handler = RecordingHandler::default();
+    let metadata = extractor
+        .extract_file_with_handler("../test_files/documents/category-level.docx", &mut handler)
+        .unwrap();
+
+    assert_eq!(handler.documents, 1);
+    assert!(handler.open_elements.is_empty(), "unbalanced elements");
+    assert!(handler.elements.iter().any(|e| e == "body"));
+    assert!(handler.elements.iter().any(|e| e == "p"));
+    assert!(handler.max_depth > 1);
+
+    // The handler sees the same text as the string extraction
+    let (content, _) = extractor
+        .extract_file_to_string("../test_files/documents/category-level.docx")
+        .unwrap();
+    let first_line = content.lines().find(|l| !l.trim().is_empty()).unwrap();
+    assert!(handler.text.contains(first_line.trim()));
+    assert!(metadata.contains_key("Content-Type"));
+}
+
+#[test]
+fn test_extract_file_with_handler_missing_file() {
+    let extractor = Extractor::new();
+    let mut handler = RecordingHandler::default();
+    let result =
+        extractor.extract_file_with_handler("../test_files/documents/missing.docx", &mut handler);
+    assert!(result.is_err());
+    assert_eq!(handler.documents, 0);
+}
+
+struct PanickingHandler;
+
+impl ContentHandler for PanickingHandler {
+    fn characters(&mut self, _text: &str) {
+        panic!("handler failure");
+    }
+}
+
+#[test]
+#[should_panic(expected = "handler failure")]
+fn test_extract_file_with_handler_resumes_panic() {
+    let extractor = Extractor::new();
+    let _ = extractor.extract_file_with_handler(
+        "../test_files/documents/category-level.docx",
+        &mut PanickingHandler,
+    );
+}
@@ -0,0 +1,36 @@
+// Kept in its own test binary: forking while other test threads hold locks is not safe
+#![cfg(unix)]
+
+use extractous::{reinit_after_fork, Error, Extractor};
+
+#[test]
+fn test_shared_vm_after_fork() {
+    let extractor = Extractor::new();
+    // Make sure the vm is initialized in the parent before forking
+    extractor.extract_file_to_string("README.md").unwrap();
+
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0, "fork failed");
+
+    if pid == 0 {
+        // Child: the inherited vm must be rejected with a clear error, then work after reinit
+        let code = match extractor.extract_file_to_string("README.md") {
+            Err(Error::VmForked(_)) => match reinit_after_fork()
+                .and_then(|_| extractor.extract_file_to_string("README.md"))
+            {
+                Ok(_) => 0,
+                Err(_) => 2,
+            },
+            _ => 1,
+        };
+        unsafe { libc::_exit(code) };
+    }
+
+    let mut status = 0;
+    unsafe { libc::waitpid(pid, &mut status, 0) };
+    assert!(libc::WIFEXITED(status));
+    assert_eq!(libc::WEXITSTATUS(status), 0);
+
+    // The parent keeps using its own vm
+    assert!(extractor.extract_file_to_string("README.md").is_ok());
+}
@@ -0,0 +1,20 @@
+use extractous::{get_jvm_memory_stats, trigger_jvm_gc, Extractor};
+
+#[test]
+fn test_jvm_memory_stats() {
+    Extractor::new()
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .unwrap();
+
+    let stats = get_jvm_memory_stats().unwrap();
+    assert!(stats.heap_used > 0);
+    assert!(stats.heap_committed >= stats.heap_used, "{:?}", stats);
+    if let Some(heap_max) = stats.heap_max {
+        assert!(heap_max >= stats.heap_committed, "{:?}", stats);
+    }
+
+    // The collections are counted since the vm started
+    trigger_jvm_gc().unwrap();
+    let after_gc = get_jvm_memory_stats().unwrap();
+    assert!(after_gc.gc_count >= stats.gc_count);
+}
@@ -0,0 +1,48 @@
+use extractous::{Error, Extractor, FontInfo};
+
+#[test]
+fn test_list_fonts_shared_by_pages() {
+    // The four pages use the same standard font, which is not embedded
+    let extractor = Extractor::new();
+    let fonts = extractor
+        .list_fonts("../test_files/documents/running-footer.pdf")
+        .unwrap();
+    assert_eq!(
+        fonts,
+        vec![FontInfo {
+            name: Some("Helvetica".to_string()),
+            subtype: "Type1".to_string(),
+            embedded: false,
+        }]
+    );
+}
+
+#[test]
+fn test_list_fonts() {
+    let extractor = Extractor::new();
+    let fonts = extractor
+        .list_fonts("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert!(!fonts.is_empty());
+    for font in &fonts {
+        assert!(
+            ["Type0", "Type1", "MMType1", "Type3", "TrueType"].contains(&font.subtype.as_str()),
+            "{:?}",
+            font
+        );
+    }
+}
+
+#[test]
+fn test_list_fonts_not_a_pdf() {
+    let extractor = Extractor::new();
+    let result = extractor.list_fonts("../test_files/documents/simple.odt");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
+
+#[test]
+fn test_list_fonts_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.list_fonts("../test_files/documents/missing.pdf");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
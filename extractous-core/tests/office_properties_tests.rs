@@ -0,0 +1,58 @@
+use extractous::{Error, Extractor};
+
+#[test]
+fn test_extract_office_properties_docx() {
+    let extractor = Extractor::new();
+    let properties = extractor
+        .extract_office_properties("../test_files/documents/bug_16.docx")
+        .unwrap();
+
+    assert_eq!(properties.creator.as_deref(), Some("python-docx"));
+    assert_eq!(
+        properties.last_modified_by.as_deref(),
+        Some("NourEldin Osama")
+    );
+    assert_eq!(properties.revision, Some(12));
+    assert_eq!(properties.template.as_deref(), Some("Normal.dotm"));
+    assert_eq!(
+        properties.application.as_deref(),
+        Some("Microsoft Office Word")
+    );
+    assert_eq!(properties.word_count, Some(1));
+    assert_eq!(properties.page_count, Some(1));
+    assert_eq!(properties.created.as_deref(), Some("2013-12-23T23:15:00Z"));
+    // Stored as an empty string
+    assert_eq!(properties.company, None);
+}
+
+#[test]
+fn test_extract_office_properties_pptx() {
+    let extractor = Extractor::new();
+    let properties = extractor
+        .extract_office_properties("../test_files/documents/science-exploration-1p.pptx")
+        .unwrap();
+
+    assert_eq!(properties.company.as_deref(), Some("LM ES&S"));
+    assert_eq!(properties.title.as_deref(), Some("Slide 1"));
+    assert_eq!(properties.revision, Some(122));
+    assert_eq!(properties.word_count, Some(3969));
+}
+
+#[test]
+fn test_extract_office_properties_doc() {
+    let extractor = Extractor::new();
+    let properties = extractor
+        .extract_office_properties("../test_files/documents/simple.doc")
+        .unwrap();
+
+    assert_eq!(properties.creator.as_deref(), Some("python-docx"));
+    assert_eq!(properties.revision, Some(4));
+    assert_eq!(properties.template.as_deref(), Some("Normal.dotm"));
+}
+
+#[test]
+fn test_extract_office_properties_not_office() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_office_properties("../test_files/documents/2022_Q3_AAPL.pdf");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
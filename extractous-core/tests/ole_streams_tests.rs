@@ -0,0 +1,49 @@
+use extractous::{Error, Extractor};
+
+#[test]
+fn test_list_ole_streams() {
+    let extractor = Extractor::new();
+    let mut names = extractor
+        .list_ole_streams("../test_files/documents/simple.doc")
+        .unwrap();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "\u{1}CompObj",
+            "\u{1}Ole",
+            "\u{5}DocumentSummaryInformation",
+            "\u{5}SummaryInformation",
+            "1Table",
+            "WordDocument",
+        ]
+    );
+}
+
+#[test]
+fn test_get_ole_stream() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/simple.doc";
+    let word_document = extractor.get_ole_stream(file_path, "WordDocument").unwrap();
+    assert_eq!(word_document.len(), 3645);
+    // The wIdent of the File Information Block that starts the stream
+    assert_eq!(&word_document[..2], &[0xEC, 0xA5]);
+
+    let summary = extractor
+        .get_ole_stream(file_path, "\u{5}SummaryInformation")
+        .unwrap();
+    assert_eq!(summary.len(), 272);
+
+    let result = extractor.get_ole_stream(file_path, "Missing");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
+
+#[test]
+fn test_ole_streams_not_an_ole_document() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/category-level.docx";
+    let result = extractor.list_ole_streams(file_path);
+    assert!(matches!(result, Err(Error::ParseError(_))));
+    let result = extractor.get_ole_stream(file_path, "WordDocument");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
@@ -0,0 +1,52 @@
+use extractous::{Error, Extractor, PdfConformance};
+
+#[test]
+fn test_pdf_conformance_info_pdfa_tagged() {
+    // Declares PDF/A-2b in its XMP metadata, with the pdfaid properties as attributes
+    let extractor = Extractor::new();
+    let info = extractor
+        .pdf_conformance_info("../test_files/documents/pdfa-tagged.pdf")
+        .unwrap();
+    assert_eq!(
+        info,
+        PdfConformance {
+            pdfa_level: Some("2B".to_string()),
+            tagged: true,
+            has_structure_tree: true,
+        }
+    );
+}
+
+#[test]
+fn test_pdf_conformance_info_tagged_only() {
+    let extractor = Extractor::new();
+    let info = extractor
+        .pdf_conformance_info("../test_files/documents/eng-ocr.pdf")
+        .unwrap();
+    assert_eq!(info.pdfa_level, None);
+    assert!(info.tagged);
+    assert!(info.has_structure_tree);
+}
+
+#[test]
+fn test_pdf_conformance_info_untagged() {
+    let extractor = Extractor::new();
+    let info = extractor
+        .pdf_conformance_info("../test_files/documents/running-footer.pdf")
+        .unwrap();
+    assert_eq!(
+        info,
+        PdfConformance {
+            pdfa_level: None,
+            tagged: false,
+            has_structure_tree: false,
+        }
+    );
+}
+
+#[test]
+fn test_pdf_conformance_info_not_a_pdf() {
+    let extractor = Extractor::new();
+    let result = extractor.pdf_conformance_info("../test_files/documents/simple.odt");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
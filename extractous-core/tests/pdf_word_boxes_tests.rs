@@ -0,0 +1,43 @@
+use extractous::{Error, Extractor};
+
+#[test]
+fn test_extract_pdf_word_boxes() {
+    let extractor = Extractor::new();
+    let words = extractor
+        .extract_pdf_word_boxes("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert!(!words.is_empty());
+
+    let first_page: Vec<_> = words.iter().filter(|word| word.page == 1).collect();
+    assert!(first_page.iter().any(|word| word.text == "Apple"));
+    assert!(first_page.iter().any(|word| word.text == "SECURITIES"));
+    assert!(words.iter().any(|word| word.page > 1));
+
+    for word in &words {
+        assert!(!word.text.trim().is_empty(), "{:?}", word);
+        assert!(word.x >= 0.0 && word.y >= 0.0, "{:?}", word);
+        assert!(word.width > 0.0 && word.height > 0.0, "{:?}", word);
+    }
+
+    // The title comes before the registrant name on the first page
+    let title = first_page
+        .iter()
+        .find(|word| word.text == "SECURITIES")
+        .unwrap();
+    let name = first_page.iter().find(|word| word.text == "Apple").unwrap();
+    assert!(title.y < name.y);
+}
+
+#[test]
+fn test_extract_pdf_word_boxes_not_a_pdf() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_pdf_word_boxes("../test_files/documents/simple.odt");
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}
+
+#[test]
+fn test_extract_pdf_word_boxes_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_pdf_word_boxes("../test_files/documents/missing.pdf");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
@@ -0,0 +1,21 @@
+use extractous::{Error, Extractor};
+use test_case::test_case;
+
+#[test_case("2022_Q3_AAPL.pdf"; "Unsigned pdf")]
+#[test_case("category-level.docx"; "Unsigned docx")]
+#[test_case("vodafone.xlsx"; "Unsigned xlsx")]
+#[test_case("simple.odt"; "Unsupported format")]
+fn test_signature_info_unsigned(file_name: &str) {
+    let extractor = Extractor::new();
+    let signatures = extractor
+        .signature_info(&format!("../test_files/documents/{}", file_name))
+        .unwrap();
+    assert!(signatures.is_empty());
+}
+
+#[test]
+fn test_signature_info_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.signature_info("../test_files/documents/missing.pdf");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
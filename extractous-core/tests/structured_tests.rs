@@ -0,0 +1,107 @@
+use extractous::{ChatFormat, Error, Extractor};
+
+#[test]
+fn test_extract_vcard_contacts() {
+    let extractor = Extractor::new();
+    let contacts = extractor
+        .extract_vcard("../test_files/documents/contacts.vcf")
+        .unwrap();
+    assert_eq!(contacts.len(), 3);
+
+    assert_eq!(contacts[0].name.as_deref(), Some("Alice Martin"));
+    assert_eq!(contacts[0].emails, vec!["alice.martin@example.com"]);
+    assert_eq!(contacts[0].phones, vec!["+1 555 0101"]);
+
+    assert_eq!(contacts[1].name.as_deref(), Some("Bob Chen"));
+    assert_eq!(
+        contacts[1].emails,
+        vec!["bob.chen@example.org", "b.chen@example.com"]
+    );
+    assert_eq!(contacts[1].phones, vec!["+1 555 0102"]);
+
+    // No FN, the name is assembled from the N property
+    assert_eq!(contacts[2].name.as_deref(), Some("Dr. Claire Dubois"));
+    assert_eq!(contacts[2].emails, vec!["claire.dubois@example.fr"]);
+    assert_eq!(contacts[2].phones, vec!["+33-1-5555-0103"]);
+}
+
+#[test]
+fn test_extract_icalendar_events() {
+    let extractor = Extractor::new();
+    let events = extractor
+        .extract_icalendar("../test_files/documents/calendar.ics")
+        .unwrap();
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(events[0].summary.as_deref(), Some("Quarterly planning"));
+    assert_eq!(events[0].start.as_deref(), Some("20240115T090000Z"));
+    assert_eq!(events[0].end.as_deref(), Some("20240115T110000Z"));
+    assert_eq!(events[0].location.as_deref(), Some("Conference room A"));
+
+    assert_eq!(events[1].summary.as_deref(), Some("Company offsite"));
+    assert_eq!(events[1].start.as_deref(), Some("20240301"));
+    assert_eq!(events[1].end.as_deref(), Some("20240303"));
+    assert_eq!(
+        events[1].location.as_deref(),
+        Some("Lakeside Lodge, Building 2")
+    );
+}
+
+#[test]
+fn test_extract_vcard_missing_file() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_vcard("../test_files/documents/missing.vcf");
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
+
+#[test]
+fn test_extract_chat_whatsapp() {
+    let extractor = Extractor::new();
+    let chat = extractor
+        .extract_chat("../test_files/documents/whatsapp-chat.txt")
+        .unwrap();
+    assert_eq!(chat.format, Some(ChatFormat::WhatsApp));
+    assert!(chat.text.is_empty());
+    assert_eq!(chat.messages.len(), 5);
+
+    // System message, without a sender
+    assert_eq!(chat.messages[0].sender, None);
+    assert_eq!(
+        chat.messages[0].timestamp.as_deref(),
+        Some("12/31/23, 9:02\u{202f}PM")
+    );
+
+    let senders: Vec<_> = chat.messages[1..]
+        .iter()
+        .map(|message| message.sender.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        senders,
+        vec!["Alice Martin", "Bob Chen", "Alice Martin", "Bob Chen"]
+    );
+    assert_eq!(
+        chat.messages[2].timestamp.as_deref(),
+        Some("12/31/23, 9:07\u{202f}PM")
+    );
+    // The continuation line belongs to the message above it
+    assert_eq!(
+        chat.messages[2].text,
+        "Yes, 10:30 in room B.\nI will bring the printed contracts."
+    );
+    assert_eq!(
+        chat.messages[3].timestamp.as_deref(),
+        Some("1/1/24, 8:15\u{202f}AM")
+    );
+    assert_eq!(chat.messages[3].text, "Happy new year! See you there.");
+}
+
+#[test]
+fn test_extract_chat_falls_back_to_plain_text() {
+    let extractor = Extractor::new();
+    let chat = extractor
+        .extract_chat("../test_files/documents/contacts.vcf")
+        .unwrap();
+    assert_eq!(chat.format, None);
+    assert!(chat.messages.is_empty());
+    assert!(chat.text.contains("Alice Martin"));
+}
@@ -0,0 +1,30 @@
+// Kept in its own test binary: the vm must not be created by another test before it is configured
+use extractous::{configure_vm, get_jvm_memory_usage, Error, Extractor, VmOptions};
+
+#[test]
+fn test_configure_vm_before_first_extraction() {
+    let invalid = VmOptions::new()
+        .set_max_heap_mb(256)
+        .set_initial_heap_mb(512);
+    assert!(matches!(configure_vm(invalid), Err(Error::InvalidInput(_))));
+    let invalid = VmOptions::new().set_max_heap_mb(0);
+    assert!(matches!(configure_vm(invalid), Err(Error::InvalidInput(_))));
+
+    let options = VmOptions::new()
+        .set_max_heap_mb(512)
+        .set_initial_heap_mb(64);
+    configure_vm(options.clone()).unwrap();
+    Extractor::new()
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .unwrap();
+
+    let usage: serde_json::Value = serde_json::from_str(&get_jvm_memory_usage().unwrap()).unwrap();
+    let max_memory_mb = usage["maxMemoryMB"].as_f64().unwrap();
+    assert!(max_memory_mb <= 512.0, "{}", max_memory_mb);
+
+    // The isolate can not be reconfigured once created
+    assert!(matches!(
+        configure_vm(options),
+        Err(Error::VmAlreadyCreated)
+    ));
+}
@@ -0,0 +1,84 @@
+package ai.yobix;
+
+import java.io.OutputStream;
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * Collects the UTF-8 output of a to-string parse in chunks of bounded size, instead of building a
+ * single java String. The rust side then takes the chunks one at a time and appends them to its
+ * own string, so that the content of a large document is never held twice in full, nor copied
+ * as a whole each time a buffer grows.
+ */
+public class ChunkedOutputStream extends OutputStream {
+
+    private static final int INITIAL_CAPACITY = 8192;
+
+    private final int chunkSize;
+    private final List<byte[]> chunks = new ArrayList<>();
+    private byte[] current;
+    private int count = 0;
+
+    public ChunkedOutputStream(int chunkSize) {
+        this.chunkSize = chunkSize;
+        this.current = new byte[Math.min(chunkSize, INITIAL_CAPACITY)];
+    }
+
+    @Override
+    public void write(int b) {
+        ensureCapacity();
+        current[count++] = (byte) b;
+    }
+
+    @Override
+    public void write(byte[] b, int off, int len) {
+        while (len > 0) {
+            ensureCapacity();
+            final int n = Math.min(len, current.length - count);
+            System.arraycopy(b, off, current, count, n);
+            count += n;
+            off += n;
+            len -= n;
+        }
+    }
+
+    /**
+     * Seals the last chunk, nothing can be written afterwards
+     */
+    @Override
+    public void close() {
+        if (count > 0) {
+            chunks.add(count == current.length ? current : Arrays.copyOf(current, count));
+        }
+        current = new byte[0];
+        count = 0;
+    }
+
+    public int getChunkCount() {
+        return chunks.size();
+    }
+
+    /**
+     * Returns the chunk at the given index and releases it
+     */
+    public byte[] takeChunk(int index) {
+        final byte[] chunk = chunks.get(index);
+        chunks.set(index, null);
+        return chunk;
+    }
+
+    // Grows the current chunk up to the chunk size, then starts a new one
+    private void ensureCapacity() {
+        if (count < current.length) {
+            return;
+        }
+        if (current.length < chunkSize) {
+            current = Arrays.copyOf(current, (int) Math.min(chunkSize, current.length * 2L));
+        } else {
+            chunks.add(current);
+            current = new byte[Math.min(chunkSize, INITIAL_CAPACITY)];
+            count = 0;
+        }
+    }
+}
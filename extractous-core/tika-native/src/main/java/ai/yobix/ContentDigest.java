@@ -0,0 +1,61 @@
+package ai.yobix;
+
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.security.MessageDigest;
+import java.security.NoSuchAlgorithmException;
+
+/**
+ * Records the length and the SHA-256 digest of the raw bytes of a document in its metadata, for
+ * the manifests of recursive extractions. The stream is spooled to a temporary file first, if it
+ * is not a file already, which the parser then reads again.
+ */
+public class ContentDigest {
+
+    /**
+     * Metadata key of the length in bytes of the document, as a decimal number
+     */
+    public static final String CONTENT_LENGTH = "X-Content-Length";
+    /**
+     * Metadata key of the SHA-256 digest of the document, as lowercase hex
+     */
+    public static final String CONTENT_SHA256 = "X-Content-SHA256";
+
+    private static final char[] HEX = "0123456789abcdef".toCharArray();
+
+    private ContentDigest() {
+    }
+
+    static void record(TikaInputStream stream, Metadata metadata) throws IOException {
+        final MessageDigest digest;
+        try {
+            digest = MessageDigest.getInstance("SHA-256");
+        } catch (NoSuchAlgorithmException e) {
+            // Every java platform is required to support SHA-256
+            throw new IllegalStateException(e);
+        }
+
+        long length = 0;
+        final byte[] buffer = new byte[64 * 1024];
+        try (InputStream in = Files.newInputStream(stream.getPath())) {
+            int read;
+            while ((read = in.read(buffer)) != -1) {
+                digest.update(buffer, 0, read);
+                length += read;
+            }
+        }
+
+        final byte[] hash = digest.digest();
+        final char[] hex = new char[hash.length * 2];
+        for (int i = 0; i < hash.length; i++) {
+            hex[i * 2] = HEX[(hash[i] >> 4) & 0xf];
+            hex[i * 2 + 1] = HEX[hash[i] & 0xf];
+        }
+        metadata.set(CONTENT_LENGTH, Long.toString(length));
+        metadata.set(CONTENT_SHA256, new String(hex));
+    }
+}
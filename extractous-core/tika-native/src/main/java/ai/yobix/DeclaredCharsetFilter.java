@@ -0,0 +1,76 @@
+package ai.yobix;
+
+import org.apache.tika.detect.CompositeEncodingDetector;
+import org.apache.tika.detect.EncodingDetector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.html.HtmlEncodingDetector;
+import org.apache.tika.parser.html.charsetdetector.StandardHtmlEncodingDetector;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Ignores the charset a document declares in its content, the {@code <meta charset>} and
+ * {@code <meta http-equiv="Content-Type">} tags of HTML in particular, which some pages get
+ * wrong. The text parsers then decode the document with the charset found by the statistical
+ * detectors, a byte order mark still being honored by them.
+ */
+public class DeclaredCharsetFilter {
+
+    /**
+     * Returns a parser that behaves like the given one, except that the parsers reading their
+     * charset through the parse context use the encoding detectors of the config without the
+     * ones reading the declaration of the document
+     */
+    static Parser wrap(Parser parser, EncodingDetector detector) {
+        final List<EncodingDetector> detectors = new ArrayList<>();
+        addUndeclaredDetectors(detector, detectors);
+        return new FilteredParser(parser, new CompositeEncodingDetector(detectors));
+    }
+
+    private static void addUndeclaredDetectors(EncodingDetector detector, List<EncodingDetector> detectors) {
+        if (detector instanceof CompositeEncodingDetector) {
+            for (EncodingDetector child : ((CompositeEncodingDetector) detector).getDetectors()) {
+                addUndeclaredDetectors(child, detectors);
+            }
+        } else if (!(detector instanceof HtmlEncodingDetector)
+                && !(detector instanceof StandardHtmlEncodingDetector)) {
+            detectors.add(detector);
+        }
+    }
+
+    private static class FilteredParser extends ParserDecorator {
+
+        private final EncodingDetector detector;
+
+        FilteredParser(Parser parser, EncodingDetector detector) {
+            super(parser);
+            this.detector = detector;
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            // The embedded documents share the context, so they get the same detector
+            if (context.get(EncodingDetector.class) == null) {
+                context.set(EncodingDetector.class, detector);
+            }
+            super.parse(stream, handler, metadata, context);
+        }
+    }
+}
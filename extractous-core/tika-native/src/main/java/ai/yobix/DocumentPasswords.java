@@ -0,0 +1,129 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.PasswordProvider;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+
+/**
+ * Hands the passwords of encrypted documents to the parsers. The Tika parser configs have no
+ * password, so the rust side creates the subclasses below, which carry one, and the parse methods
+ * turn them into the PasswordProvider the PDF and Office parsers ask for the password of the
+ * document they open, embedded documents included.
+ * A document that can not be opened fails with an EncryptedDocumentException.
+ */
+public class DocumentPasswords {
+
+    private static final MediaType PDF = MediaType.application("pdf");
+
+    private DocumentPasswords() {
+    }
+
+    /**
+     * Also carries the PDF settings Tika has no place for, the page range, see
+     * {@link PdfPageRange}, and the image descriptions, see {@link PdfImageInfo}
+     */
+    public static class PdfConfig extends PDFParserConfig {
+
+        private String password;
+        private int startPage = 1;
+        private int endPage = -1;
+        private boolean extractInlineImageMetadata = false;
+
+        public PdfConfig() {
+        }
+
+        public String getPassword() {
+            return password;
+        }
+
+        public void setPassword(String password) {
+            this.password = password;
+        }
+
+        public int getStartPage() {
+            return startPage;
+        }
+
+        public int getEndPage() {
+            return endPage;
+        }
+
+        boolean hasPageRange() {
+            return endPage >= 0;
+        }
+
+        /**
+         * Restricts the parse to the pages from startPage to endPage, 1 based and inclusive. The
+         * range is clamped to the pages of each document.
+         * Default: every page
+         */
+        public void setPageRange(int startPage, int endPage) {
+            this.startPage = startPage;
+            this.endPage = endPage;
+        }
+
+        public boolean isExtractInlineImageMetadata() {
+            return extractInlineImageMetadata;
+        }
+
+        /**
+         * Describes the images drawn on the pages, see {@link PdfImageInfo}.
+         * Default: false
+         */
+        public void setExtractInlineImageMetadata(boolean extractInlineImageMetadata) {
+            this.extractInlineImageMetadata = extractInlineImageMetadata;
+        }
+    }
+
+    /**
+     * Also carries whether the comments go to the metadata, see {@link OfficeComments}
+     */
+    public static class OfficeConfig extends OfficeParserConfig {
+
+        private String password;
+        private boolean includeComments = false;
+
+        public OfficeConfig() {
+        }
+
+        public String getPassword() {
+            return password;
+        }
+
+        public void setPassword(String password) {
+            this.password = password;
+        }
+
+        public boolean isIncludeComments() {
+            return includeComments;
+        }
+
+        /**
+         * Adds the comments of OOXML documents to their metadata, see {@link OfficeComments}.
+         * Default: false
+         */
+        public void setIncludeComments(boolean includeComments) {
+            this.includeComments = includeComments;
+        }
+    }
+
+    /**
+     * Sets the PasswordProvider of the context when either config has a password. PDF documents
+     * get the password of the PDF config, the other formats the one of the Office config.
+     */
+    static void applyTo(ParseContext context, PDFParserConfig pdfConfig, OfficeParserConfig officeConfig) {
+        final String pdfPassword = pdfConfig instanceof PdfConfig ? ((PdfConfig) pdfConfig).getPassword() : null;
+        final String officePassword =
+                officeConfig instanceof OfficeConfig ? ((OfficeConfig) officeConfig).getPassword() : null;
+        if (pdfPassword == null && officePassword == null) {
+            return;
+        }
+        context.set(PasswordProvider.class, (Metadata metadata) -> {
+            final MediaType type = MediaType.parse(metadata.get(Metadata.CONTENT_TYPE));
+            return type != null && PDF.equals(type.getBaseType()) ? pdfPassword : officePassword;
+        });
+    }
+}
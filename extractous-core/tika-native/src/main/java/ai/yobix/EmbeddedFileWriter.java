@@ -0,0 +1,103 @@
+package ai.yobix;
+
+import org.apache.commons.io.input.CloseShieldInputStream;
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MimeTypeException;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.nio.file.StandardCopyOption;
+
+/**
+ * Writes the raw bytes of every embedded document to a file instead of parsing its text, the
+ * documents embedded in an embedded document included. The rust side picks the file of each
+ * document from its resource name: the native method is registered from the rust side, and
+ * targetPtr points to the rust state that is only valid for the duration of the parse call that
+ * created this object. A rust side that fails raises a SAXException from the native method,
+ * which aborts the parse.
+ */
+public class EmbeddedFileWriter implements EmbeddedDocumentExtractor {
+
+    // Past this nesting depth the embedded documents are written without looking into them,
+    // which stops the archives that contain themselves
+    private static final int MAX_DEPTH = 32;
+
+    private final long targetPtr;
+    private final TikaConfig config;
+    private final Parser parser;
+    private final ParseContext context;
+    private int depth = 0;
+
+    public EmbeddedFileWriter(long targetPtr, TikaConfig config, Parser parser, ParseContext context) {
+        this.targetPtr = targetPtr;
+        this.config = config;
+        this.parser = parser;
+        this.context = context;
+    }
+
+    @Override
+    public boolean shouldParseEmbedded(Metadata metadata) {
+        return true;
+    }
+
+    @Override
+    public void parseEmbedded(InputStream stream, ContentHandler handler, Metadata metadata, boolean outputHtml)
+            throws SAXException, IOException {
+        final Path path;
+        try (TemporaryResources tmp = new TemporaryResources()) {
+            final TikaInputStream tis = TikaInputStream.get(CloseShieldInputStream.wrap(stream), tmp, metadata);
+            final String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
+            // The name of an anonymous document gets the extension of its detected type
+            final String extension = name == null ? extension(tis, metadata) : "";
+            path = Paths.get(nativeTarget(targetPtr, name, extension));
+            Files.copy(tis, path, StandardCopyOption.REPLACE_EXISTING);
+        }
+
+        if (depth >= MAX_DEPTH) {
+            return;
+        }
+        // The documents embedded in this one, from the file just written
+        final Metadata embeddedMetadata = new Metadata();
+        embeddedMetadata.set(TikaCoreProperties.RESOURCE_NAME_KEY, path.getFileName().toString());
+        depth++;
+        try (TikaInputStream tis = TikaInputStream.get(path, embeddedMetadata)) {
+            parser.parse(tis, new DefaultHandler(), embeddedMetadata, context);
+        } catch (TikaException e) {
+            // The bytes are written all the same, only what it embeds is lost
+        } finally {
+            depth--;
+        }
+    }
+
+    private String extension(TikaInputStream tis, Metadata metadata) throws IOException {
+        final MediaType type = config.getDetector().detect(tis, metadata);
+        try {
+            return config.getMimeRepository().forName(type.toString()).getExtension();
+        } catch (MimeTypeException e) {
+            return "";
+        }
+    }
+
+    /**
+     * Returns the path of the new, empty file the document is written to
+     *
+     * @param name the resource name of the document, null when it has none
+     * @param extension the extension of the detected type when there is no name, such as .png
+     */
+    private static native String nativeTarget(long targetPtr, String name, String extension) throws SAXException;
+}
@@ -0,0 +1,134 @@
+package ai.yobix;
+
+import java.io.FilterInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Bounds the number of embedded documents of a recursive parse and the total number of their
+ * raw, decompressed, bytes, which defends against the ZIP bombs of many entries or of entries
+ * that inflate to huge sizes. Every embedded document takes one from the count before it is
+ * parsed, and its bytes from the byte budget as they are read from the container, through
+ * {@link #wrap}. The bytes of a document embedded in an embedded document are counted again, as
+ * they are decompressed again. Once a limit is reached, the documents that follow are not parsed
+ * at all, and the one that reached the byte limit sees the end of its stream there.
+ * <p>
+ * The limits are shared by the worker threads of {@link ParallelEmbeddedParser}, in which case
+ * which documents are left out depends on the order in which they are parsed.
+ */
+public class EmbeddedLimits {
+
+    /**
+     * Metadata key set to "true" on the container when a limit was reached, so that embedded
+     * documents were cut or left out
+     */
+    public static final String EMBEDDED_TRUNCATED = "extractous:embedded_truncated";
+
+    private final int maxCount;
+    private final long maxBytes;
+    private int count = 0;
+    private long bytes = 0;
+    private volatile boolean countExhausted = false;
+    private volatile boolean bytesExhausted = false;
+
+    /**
+     * @param maxCount the number of embedded documents, negative for no limit
+     * @param maxBytes the total size of the embedded documents in bytes, negative for no limit
+     */
+    EmbeddedLimits(int maxCount, long maxBytes) {
+        this.maxCount = maxCount;
+        this.maxBytes = maxBytes;
+    }
+
+    boolean isExhausted() {
+        return countExhausted || bytesExhausted;
+    }
+
+    /**
+     * Takes one embedded document from the count and returns whether it may be parsed
+     */
+    synchronized boolean admit() {
+        if (isExhausted()) {
+            return false;
+        }
+        if (maxCount >= 0 && count >= maxCount) {
+            countExhausted = true;
+            return false;
+        }
+        count++;
+        return true;
+    }
+
+    /**
+     * Returns the stream of an embedded document that takes the bytes read from the byte budget
+     */
+    InputStream wrap(InputStream stream) {
+        return maxBytes < 0 ? stream : new CountingInputStream(stream);
+    }
+
+    /**
+     * Takes the given number of bytes from the budget and returns how many of them fit
+     */
+    private synchronized int take(int len) {
+        final long remaining = maxBytes - bytes;
+        if (len > remaining) {
+            bytesExhausted = true;
+            bytes = maxBytes;
+            return (int) remaining;
+        }
+        bytes += len;
+        return len;
+    }
+
+    private class CountingInputStream extends FilterInputStream {
+
+        CountingInputStream(InputStream in) {
+            super(in);
+        }
+
+        @Override
+        public int read() throws IOException {
+            if (bytesExhausted) {
+                return -1;
+            }
+            final int b = super.read();
+            if (b != -1 && take(1) == 0) {
+                return -1;
+            }
+            return b;
+        }
+
+        @Override
+        public int read(byte[] b, int off, int len) throws IOException {
+            if (bytesExhausted) {
+                return -1;
+            }
+            final int read = super.read(b, off, len);
+            if (read <= 0) {
+                return read;
+            }
+            final int taken = take(read);
+            return taken == 0 ? -1 : taken;
+        }
+
+        @Override
+        public long skip(long n) throws IOException {
+            // Skipped bytes are decompressed all the same, read them so that they are counted
+            final byte[] buffer = new byte[(int) Math.min(n, 8192)];
+            long skipped = 0;
+            while (skipped < n) {
+                final int read = read(buffer, 0, (int) Math.min(n - skipped, buffer.length));
+                if (read == -1) {
+                    break;
+                }
+                skipped += read;
+            }
+            return skipped;
+        }
+
+        @Override
+        public boolean markSupported() {
+            return false;
+        }
+    }
+}
@@ -0,0 +1,145 @@
+package ai.yobix;
+
+import org.apache.tika.detect.CompositeEncodingDetector;
+import org.apache.tika.detect.EncodingDetector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.html.HtmlEncodingDetector;
+import org.apache.tika.parser.txt.CharsetDetector;
+import org.apache.tika.parser.txt.CharsetMatch;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.ByteBuffer;
+import java.nio.CharBuffer;
+import java.nio.charset.Charset;
+import java.nio.charset.CharsetDecoder;
+import java.nio.charset.CodingErrorAction;
+import java.nio.charset.StandardCharsets;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Replaces the encoding detectors of the text parsers (plain text, CSV, HTML, ...) by the ICU
+ * charset detection, which falls back to UTF-8 rather than to a guess when it is not confident.
+ * A sample that is valid UTF-8 is taken as UTF-8 without asking ICU, which otherwise reports
+ * pure ASCII as ISO-8859-1. The detected charset is recorded in the Content-Encoding metadata.
+ */
+public class EncodingAutoDetect {
+
+    // Same sample size as the Tika detectors
+    private static final int SAMPLE_SIZE = 8000;
+    // ICU confidences go from 0 to 100, the matches below this one are considered ambiguous
+    private static final int MIN_CONFIDENCE = 25;
+
+    private EncodingAutoDetect() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the parsers reading their
+     * charset through the parse context use the ICU detection. The charset declared by HTML
+     * documents comes first when trustDeclared is true.
+     */
+    static Parser wrap(Parser parser, boolean trustDeclared) {
+        final List<EncodingDetector> detectors = new ArrayList<>();
+        if (trustDeclared) {
+            detectors.add(new HtmlEncodingDetector());
+        }
+        detectors.add(new IcuDetector());
+        return new AutoDetectingParser(parser, new CompositeEncodingDetector(detectors));
+    }
+
+    private static class IcuDetector implements EncodingDetector {
+
+        @Override
+        public Charset detect(InputStream input, Metadata metadata) throws IOException {
+            if (input == null) {
+                return null;
+            }
+            final byte[] sample = new byte[SAMPLE_SIZE];
+            int length = 0;
+            input.mark(SAMPLE_SIZE);
+            try {
+                int read;
+                while (length < SAMPLE_SIZE && (read = input.read(sample, length, SAMPLE_SIZE - length)) != -1) {
+                    length += read;
+                }
+            } finally {
+                input.reset();
+            }
+
+            final Charset charset = detect(sample, length);
+            metadata.set(Metadata.CONTENT_ENCODING, charset.name());
+            return charset;
+        }
+
+        private static Charset detect(byte[] sample, int length) {
+            if (isUtf8(sample, length)) {
+                return StandardCharsets.UTF_8;
+            }
+            final CharsetDetector detector = new CharsetDetector();
+            final byte[] text = new byte[length];
+            System.arraycopy(sample, 0, text, 0, length);
+            detector.setText(text);
+            final CharsetMatch match = detector.detect();
+            if (match == null || match.getConfidence() < MIN_CONFIDENCE) {
+                return StandardCharsets.UTF_8;
+            }
+            try {
+                return Charset.forName(match.getName());
+            } catch (IllegalArgumentException e) {
+                // A charset ICU knows but Java does not
+                return StandardCharsets.UTF_8;
+            }
+        }
+
+        /**
+         * Whether the sample decodes as UTF-8. A sample with NUL bytes is not, as it is most
+         * likely UTF-16 or UTF-32 without a byte order mark.
+         */
+        private static boolean isUtf8(byte[] sample, int length) {
+            for (int i = 0; i < length; i++) {
+                if (sample[i] == 0) {
+                    return false;
+                }
+            }
+            final CharsetDecoder decoder = StandardCharsets.UTF_8.newDecoder()
+                    .onMalformedInput(CodingErrorAction.REPORT)
+                    .onUnmappableCharacter(CodingErrorAction.REPORT);
+            // Not the end of the input, so that a character cut by the end of the sample is no error
+            return !decoder.decode(ByteBuffer.wrap(sample, 0, length), CharBuffer.allocate(length), false).isError();
+        }
+    }
+
+    private static class AutoDetectingParser extends ParserDecorator {
+
+        private final EncodingDetector detector;
+
+        AutoDetectingParser(Parser parser, EncodingDetector detector) {
+            super(parser);
+            this.detector = detector;
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            // The embedded documents share the context, so they get the same detector
+            if (context.get(EncodingDetector.class) == null) {
+                context.set(EncodingDetector.class, detector);
+            }
+            super.parse(stream, handler, metadata, context);
+        }
+    }
+}
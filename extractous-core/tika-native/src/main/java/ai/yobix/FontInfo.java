@@ -0,0 +1,43 @@
+package ai.yobix;
+
+/**
+ * A font referenced by a PDF document.
+ */
+public class FontInfo {
+
+    private final String name;
+    private final String subtype;
+    private final boolean embedded;
+
+    public FontInfo(String name, String subtype, boolean embedded) {
+        this.name = name;
+        this.subtype = subtype;
+        this.embedded = embedded;
+    }
+
+    /**
+     * Returns the PostScript name of the font, with the prefix of a subset such as ABCDEF+, or
+     * null if the font has none
+     */
+    public String getName() {
+        return name;
+    }
+
+    /**
+     * Returns the subtype of the font dictionary: Type0, Type1, MMType1, Type3 or TrueType
+     */
+    public String getSubtype() {
+        return subtype;
+    }
+
+    /**
+     * Returns true if the font program is embedded in the document
+     */
+    public boolean isEmbedded() {
+        return embedded;
+    }
+
+    public String toString() {
+        return "name:" + this.name + " subtype: " + this.subtype + " embedded: " + this.embedded;
+    }
+}
@@ -0,0 +1,78 @@
+package ai.yobix;
+
+import org.apache.pdfbox.cos.COSBase;
+import org.apache.pdfbox.cos.COSName;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.PDResources;
+import org.apache.pdfbox.pdmodel.font.PDFont;
+import org.apache.pdfbox.pdmodel.font.PDType3Font;
+import org.apache.pdfbox.pdmodel.graphics.PDXObject;
+import org.apache.pdfbox.pdmodel.graphics.form.PDFormXObject;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAnnotation;
+import org.apache.pdfbox.pdmodel.interactive.annotation.PDAppearanceStream;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.HashSet;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Lists the fonts of a PDF document from the resource dictionaries of its pages, of the form
+ * XObjects and Type3 glyphs they use, and of the appearance streams of their annotations, in
+ * the order they are first found. A font shared by several pages is listed once.
+ */
+class FontLister {
+
+    private final List<FontInfo> fonts = new ArrayList<>();
+    private final Set<COSBase> seenFonts = new HashSet<>();
+    // Resources shared by several pages or forms are walked once, which also stops the forms
+    // that draw themselves
+    private final Set<COSBase> seenResources = new HashSet<>();
+
+    private FontLister() {
+    }
+
+    static List<FontInfo> list(PDDocument document) throws IOException {
+        final FontLister lister = new FontLister();
+        for (PDPage page : document.getPages()) {
+            lister.collect(page.getResources());
+            for (PDAnnotation annotation : page.getAnnotations()) {
+                final PDAppearanceStream appearance = annotation.getNormalAppearanceStream();
+                if (appearance != null) {
+                    lister.collect(appearance.getResources());
+                }
+            }
+        }
+        return lister.fonts;
+    }
+
+    private void collect(PDResources resources) throws IOException {
+        if (resources == null || !seenResources.add(resources.getCOSObject())) {
+            return;
+        }
+        for (COSName name : resources.getFontNames()) {
+            final PDFont font;
+            try {
+                font = resources.getFont(name);
+            } catch (IOException e) {
+                // A damaged font dictionary does not prevent listing the others
+                continue;
+            }
+            if (font == null || !seenFonts.add(font.getCOSObject())) {
+                continue;
+            }
+            fonts.add(new FontInfo(font.getName(), font.getSubType(), font.isEmbedded()));
+            if (font instanceof PDType3Font) {
+                collect(((PDType3Font) font).getResources());
+            }
+        }
+        for (COSName name : resources.getXObjectNames()) {
+            final PDXObject xobject = resources.getXObject(name);
+            if (xobject instanceof PDFormXObject) {
+                collect(((PDFormXObject) xobject).getResources());
+            }
+        }
+    }
+}
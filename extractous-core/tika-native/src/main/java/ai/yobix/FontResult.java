@@ -0,0 +1,60 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class FontResult {
+
+    private final List<FontInfo> fonts;
+    private final byte status;
+    private final String errorMessage;
+
+    public FontResult(List<FontInfo> fonts) {
+        this.fonts = fonts;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public FontResult(byte status, String errorMessage) {
+        this.fonts = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the fonts referenced by the document.
+     * An array is returned to avoid JNI calls into java.util.List from native code
+     * @return FontInfo array or null if there is an error
+     */
+    public FontInfo[] getFonts() {
+        return fonts != null ? fonts.toArray(new FontInfo[0]) : null;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " fonts: " + (fonts != null ? fonts.size() : 0);
+    }
+}
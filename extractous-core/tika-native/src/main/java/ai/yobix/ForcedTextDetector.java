@@ -0,0 +1,79 @@
+package ai.yobix;
+
+import org.apache.tika.detect.Detector;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.Locale;
+import java.util.Set;
+
+/**
+ * Detects the documents of the given extensions or detected media types as text/plain, so that
+ * they are handed to the text parser whatever their content looks like. This is meant for in-house
+ * text formats that the detectors report as application/octet-stream or as another format.
+ */
+public class ForcedTextDetector implements Detector {
+
+    private final Detector detector;
+    private final Set<String> extensions;
+    private final Set<MediaType> types;
+
+    private ForcedTextDetector(Detector detector, Set<String> extensions, Set<MediaType> types) {
+        this.detector = detector;
+        this.extensions = extensions;
+        this.types = types;
+    }
+
+    /**
+     * Returns the given detector when there are no forced text types, so that the default parse
+     * path is left untouched
+     */
+    static Detector wrap(Detector detector, Set<String> extensions, Set<MediaType> types) {
+        if (extensions.isEmpty() && types.isEmpty()) {
+            return detector;
+        }
+        return new ForcedTextDetector(detector, extensions, types);
+    }
+
+    @Override
+    public MediaType detect(InputStream input, Metadata metadata) throws IOException {
+        final String extension = extensionOf(resourceName(input, metadata));
+        if (extension != null && extensions.contains(extension)) {
+            return MediaType.TEXT_PLAIN;
+        }
+        final MediaType type = detector.detect(input, metadata);
+        if (type != null && types.contains(type.getBaseType())) {
+            return MediaType.TEXT_PLAIN;
+        }
+        return type;
+    }
+
+    private static String resourceName(InputStream input, Metadata metadata) {
+        final String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
+        if (name != null) {
+            return name;
+        }
+        // The container of a recursive parse is only known by its file
+        if (input instanceof TikaInputStream && ((TikaInputStream) input).hasFile()) {
+            final Path fileName = ((TikaInputStream) input).getFile().toPath().getFileName();
+            return fileName == null ? null : fileName.toString();
+        }
+        return null;
+    }
+
+    private static String extensionOf(String name) {
+        if (name == null) {
+            return null;
+        }
+        final int dot = name.lastIndexOf('.');
+        if (dot < 0 || dot == name.length() - 1) {
+            return null;
+        }
+        return name.substring(dot + 1).toLowerCase(Locale.ROOT);
+    }
+}
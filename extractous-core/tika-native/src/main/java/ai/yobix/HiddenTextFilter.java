@@ -0,0 +1,369 @@
+package ai.yobix;
+
+import org.apache.pdfbox.Loader;
+import org.apache.pdfbox.contentstream.operator.Operator;
+import org.apache.pdfbox.cos.COSName;
+import org.apache.pdfbox.cos.COSNumber;
+import org.apache.pdfbox.pdfparser.PDFStreamParser;
+import org.apache.pdfbox.pdfwriter.ContentStreamWriter;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.common.PDStream;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.html.DefaultHtmlMapper;
+import org.apache.tika.parser.html.HtmlMapper;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.AttributesImpl;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.nio.file.Path;
+import java.util.ArrayDeque;
+import java.util.ArrayList;
+import java.util.Deque;
+import java.util.List;
+import java.util.Locale;
+import java.util.Set;
+import java.util.regex.Pattern;
+
+/**
+ * Drops the text a reader can not see from PDF and HTML documents. This is best-effort:
+ * <ul>
+ *     <li>PDF: the text shown in the invisible rendering modes or filled in white, which assumes a
+ *     white page. The page content streams are rewritten without it to a temporary file, which
+ *     is then handed to the PDF parser. Form XObjects are left as they are.</li>
+ *     <li>HTML: the elements with a {@code hidden} attribute or an inline {@code display: none} or
+ *     {@code visibility: hidden} style, with all their content. Style sheets are not evaluated.</li>
+ * </ul>
+ */
+public class HiddenTextFilter {
+
+    private static final Set<MediaType> PDF_TYPES = Set.of(MediaType.application("pdf"));
+    private static final Set<MediaType> HTML_TYPES =
+            Set.of(MediaType.text("html"), MediaType.application("xhtml+xml"));
+
+    private static final Pattern HIDDEN_STYLE =
+            Pattern.compile("(?:^|;)\\s*(?:display\\s*:\\s*none|visibility\\s*:\\s*hidden)\\s*(?:!important\\s*)?(?:;|$)");
+
+    private HiddenTextFilter() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the hidden text of PDF and
+     * HTML documents is dropped
+     */
+    static Parser wrap(Parser parser, MediaTypeRegistry registry) {
+        // Later parsers take precedence, so the filtered types are taken over by the decorators
+        return new CompositeParser(registry, parser, new PdfFilter(parser), new HtmlFilter(parser));
+    }
+
+    private static class PdfFilter extends ParserDecorator {
+
+        PdfFilter(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return PDF_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                final Path filtered = removeHiddenText(tis.getPath(), tmp);
+                if (filtered == null) {
+                    super.parse(tis, handler, metadata, context);
+                    return;
+                }
+                try (TikaInputStream filteredStream = TikaInputStream.get(filtered)) {
+                    super.parse(filteredStream, handler, metadata, context);
+                }
+            }
+        }
+    }
+
+    /**
+     * Writes the document without its hidden text to a temporary file. Returns null when there
+     * is no hidden text or the document can not be opened, so it is parsed as it is.
+     */
+    private static Path removeHiddenText(Path path, TemporaryResources tmp) {
+        try (PDDocument document = Loader.loadPDF(path.toFile())) {
+            boolean changed = false;
+            for (PDPage page : document.getPages()) {
+                changed |= filterPage(document, page);
+            }
+            if (!changed) {
+                return null;
+            }
+            document.setAllSecurityToBeRemoved(true);
+            final Path filtered = tmp.createTempFile();
+            document.save(filtered.toFile());
+            return filtered;
+        } catch (IOException e) {
+            // Encrypted or broken documents are left to the PDF parser, which reports the problem
+            return null;
+        }
+    }
+
+    private static boolean filterPage(PDDocument document, PDPage page) throws IOException {
+        final List<Object> tokens = new PDFStreamParser(page).parse();
+        final List<Object> kept = new ArrayList<>(tokens.size());
+        final List<Object> operands = new ArrayList<>();
+        final Deque<TextState> saved = new ArrayDeque<>();
+        TextState state = new TextState();
+        boolean changed = false;
+
+        for (Object token : tokens) {
+            if (!(token instanceof Operator)) {
+                operands.add(token);
+                continue;
+            }
+            final String name = ((Operator) token).getName();
+            switch (name) {
+                case "q":
+                    saved.push(state.copy());
+                    break;
+                case "Q":
+                    if (!saved.isEmpty()) {
+                        state = saved.pop();
+                    }
+                    break;
+                case "Tr":
+                    if (operands.size() == 1 && operands.get(0) instanceof COSNumber) {
+                        state.renderingMode = ((COSNumber) operands.get(0)).intValue();
+                    }
+                    break;
+                case "cs":
+                    // A new color space resets the fill color to its initial value, black
+                    state.whiteFill = false;
+                    break;
+                case "g":
+                case "rg":
+                case "k":
+                case "sc":
+                case "scn":
+                    state.whiteFill = isWhite(operands);
+                    break;
+                case "Tj":
+                case "TJ":
+                case "'":
+                case "\"":
+                    if (state.isHidden()) {
+                        changed = true;
+                        keepLineMove(name, operands, kept);
+                        operands.clear();
+                        continue;
+                    }
+                    break;
+                default:
+                    break;
+            }
+            kept.addAll(operands);
+            kept.add(token);
+            operands.clear();
+        }
+
+        if (changed) {
+            final PDStream contents = new PDStream(document);
+            try (OutputStream out = contents.createOutputStream(COSName.FLATE_DECODE)) {
+                new ContentStreamWriter(out).writeTokens(kept);
+            }
+            page.setContents(contents);
+        }
+        return changed;
+    }
+
+    /**
+     * The ' and " operators move to the next line before showing their text, the move is kept so
+     * that the visible text that follows stays in place
+     */
+    private static void keepLineMove(String name, List<Object> operands, List<Object> kept) {
+        if (name.equals("\"") && operands.size() == 3) {
+            kept.add(operands.get(0));
+            kept.add(Operator.getOperator("Tw"));
+            kept.add(operands.get(1));
+            kept.add(Operator.getOperator("Tc"));
+        }
+        if (name.equals("'") || name.equals("\"")) {
+            kept.add(Operator.getOperator("T*"));
+        }
+    }
+
+    /**
+     * Whether the operands of a fill color operator describe white: 1 in gray, 1 1 1 in RGB and
+     * 0 0 0 0 in CMYK. The color space of sc and scn is guessed from the number of components.
+     */
+    private static boolean isWhite(List<Object> operands) {
+        if (operands.size() != 1 && operands.size() != 3 && operands.size() != 4) {
+            return false;
+        }
+        final float[] components = new float[operands.size()];
+        for (int i = 0; i < components.length; i++) {
+            if (!(operands.get(i) instanceof COSNumber)) {
+                // Patterns are never considered white
+                return false;
+            }
+            components[i] = ((COSNumber) operands.get(i)).floatValue();
+        }
+        final float white = components.length == 4 ? 0f : 1f;
+        for (float component : components) {
+            if (Math.abs(component - white) > 0.001f) {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    private static class TextState {
+        int renderingMode = 0;
+        boolean whiteFill = false;
+
+        TextState copy() {
+            final TextState copy = new TextState();
+            copy.renderingMode = renderingMode;
+            copy.whiteFill = whiteFill;
+            return copy;
+        }
+
+        boolean isHidden() {
+            // 3 is invisible and 7 only adds the text to the clipping path. 0 and 4 only fill
+            // the glyphs, the other modes also stroke them, which keeps them visible
+            return renderingMode == 3 || renderingMode == 7
+                    || (whiteFill && (renderingMode == 0 || renderingMode == 4));
+        }
+    }
+
+    private static class HtmlFilter extends ParserDecorator {
+
+        HtmlFilter(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return HTML_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final HtmlMapper previous = context.get(HtmlMapper.class);
+            context.set(HtmlMapper.class, new VisibilityAttributesMapper(
+                    previous != null ? previous : DefaultHtmlMapper.INSTANCE));
+            try {
+                super.parse(stream, new HiddenElementHandler(handler), metadata, context);
+            } finally {
+                context.set(HtmlMapper.class, previous);
+            }
+        }
+    }
+
+    /**
+     * Keeps the attributes that tell whether an element is hidden, which the default mapper
+     * drops, so that {@link HiddenElementHandler} can see them
+     */
+    private static class VisibilityAttributesMapper implements HtmlMapper {
+
+        private final HtmlMapper mapper;
+
+        VisibilityAttributesMapper(HtmlMapper mapper) {
+            this.mapper = mapper;
+        }
+
+        @Override
+        public String mapSafeElement(String name) {
+            return mapper.mapSafeElement(name);
+        }
+
+        @Override
+        public boolean isDiscardElement(String name) {
+            return mapper.isDiscardElement(name);
+        }
+
+        @Override
+        public String mapSafeAttribute(String elementName, String attributeName) {
+            final String lower = attributeName.toLowerCase(Locale.ROOT);
+            if (lower.equals("hidden") || lower.equals("style")) {
+                return lower;
+            }
+            return mapper.mapSafeAttribute(elementName, attributeName);
+        }
+    }
+
+    /**
+     * Drops the hidden elements together with their content, and removes the visibility
+     * attributes from the other elements so that the output matches the one of a plain parse
+     */
+    private static class HiddenElementHandler extends ContentHandlerDecorator {
+
+        // Depth inside the outermost hidden element, 0 when outside of any
+        private int hiddenDepth = 0;
+
+        HiddenElementHandler(ContentHandler handler) {
+            super(handler);
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            if (hiddenDepth > 0 || isHidden(atts)) {
+                hiddenDepth++;
+                return;
+            }
+            final AttributesImpl visible = new AttributesImpl(atts);
+            for (int i = visible.getLength() - 1; i >= 0; i--) {
+                final String attribute = visible.getLocalName(i);
+                if (attribute.equals("hidden") || attribute.equals("style")) {
+                    visible.removeAttribute(i);
+                }
+            }
+            super.startElement(uri, localName, name, visible);
+        }
+
+        @Override
+        public void endElement(String uri, String localName, String name) throws SAXException {
+            if (hiddenDepth > 0) {
+                hiddenDepth--;
+                return;
+            }
+            super.endElement(uri, localName, name);
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            if (hiddenDepth == 0) {
+                super.characters(ch, start, length);
+            }
+        }
+
+        @Override
+        public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+            if (hiddenDepth == 0) {
+                super.ignorableWhitespace(ch, start, length);
+            }
+        }
+
+        private static boolean isHidden(Attributes atts) {
+            if (atts.getIndex("", "hidden") >= 0) {
+                return true;
+            }
+            final String style = atts.getValue("", "style");
+            return style != null && HIDDEN_STYLE.matcher(style.toLowerCase(Locale.ROOT)).find();
+        }
+    }
+}
@@ -0,0 +1,27 @@
+package ai.yobix;
+
+import org.apache.tika.langdetect.optimaize.OptimaizeLangDetector;
+import org.apache.tika.language.detect.LanguageDetector;
+
+import java.io.IOException;
+
+/**
+ * Detects the language of a text with the Optimaize detector bundled with Tika. The language
+ * profiles take a while to load and a fair amount of memory, so they are loaded once, on the
+ * first detection. The detector keeps the text it is given, so the detections run one at a time.
+ */
+public class LanguageDetection {
+
+    private static LanguageDetector detector;
+
+    private LanguageDetection() {
+    }
+
+    static synchronized LanguageResult detect(String text) throws IOException {
+        if (detector == null) {
+            detector = new OptimaizeLangDetector().loadModels();
+        }
+        final org.apache.tika.language.detect.LanguageResult result = detector.detect(text);
+        return new LanguageResult(result.getLanguage(), result.getRawScore());
+    }
+}
@@ -0,0 +1,69 @@
+package ai.yobix;
+
+/**
+ * The language detected in a text, see {@link LanguageDetection}
+ */
+public class LanguageResult {
+
+    private final String language;
+    private final float confidence;
+    private final byte status;
+    private final String errorMessage;
+
+    public LanguageResult(String language, float confidence) {
+        this.language = language;
+        this.confidence = confidence;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public LanguageResult(byte status, String errorMessage) {
+        this.language = null;
+        this.confidence = 0;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the ISO 639-1 code of the language, with a region for the variants of Chinese,
+     * or an empty string when no language could be detected
+     */
+    public String getLanguage() {
+        return language;
+    }
+
+    /**
+     * Returns the probability of the language, between 0 and 1
+     */
+    public float getConfidence() {
+        return confidence;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage + " language: " + this.language +
+                " confidence: " + this.confidence;
+    }
+}
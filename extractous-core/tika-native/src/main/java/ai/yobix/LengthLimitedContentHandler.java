@@ -0,0 +1,83 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+/**
+ * Content handler that keeps at most maxLength characters of a single document, and no more than
+ * the {@link OutputBudget} shared by the documents of the parse has left. Unlike
+ * {@link org.apache.tika.sax.WriteOutContentHandler} it does not throw once a limit is reached,
+ * the rest of the document is silently dropped so that the parse goes on with the next embedded
+ * document.
+ */
+public class LengthLimitedContentHandler extends ContentHandlerDecorator {
+
+    /**
+     * Metadata key set to "true" on the documents whose content was truncated
+     */
+    public static final String CONTENT_TRUNCATED = "extractous:content_truncated";
+
+    private final int maxLength;
+    private final OutputBudget budget;
+    private int length = 0;
+    private boolean truncated = false;
+
+    public LengthLimitedContentHandler(ContentHandler handler, int maxLength) {
+        this(handler, maxLength, null);
+    }
+
+    /**
+     * @param maxLength the maximum length of the document, negative for no limit
+     * @param budget    the budget of all the documents of the parse, null for none
+     */
+    public LengthLimitedContentHandler(ContentHandler handler, int maxLength, OutputBudget budget) {
+        super(handler);
+        this.maxLength = maxLength;
+        this.budget = budget;
+    }
+
+    public boolean isTruncated() {
+        return truncated;
+    }
+
+    /**
+     * Flags the metadata of the document written to the given handler if its content was truncated
+     */
+    public static void flagTruncated(ContentHandler handler, Metadata metadata) {
+        if (handler instanceof LengthLimitedContentHandler
+                && ((LengthLimitedContentHandler) handler).isTruncated()) {
+            metadata.set(CONTENT_TRUNCATED, "true");
+        }
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int len) throws SAXException {
+        super.characters(ch, start, allowed(ch, start, len));
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int len) throws SAXException {
+        super.ignorableWhitespace(ch, start, allowed(ch, start, len));
+    }
+
+    private int allowed(char[] ch, int start, int len) {
+        if (maxLength >= 0) {
+            final int remaining = maxLength - length;
+            if (len > remaining) {
+                truncated = true;
+                len = Math.max(remaining, 0);
+            }
+        }
+        if (budget != null) {
+            final int fitting = budget.take(ch, start, len);
+            if (fitting < len) {
+                truncated = true;
+                len = fitting;
+            }
+        }
+        length += len;
+        return len;
+    }
+}
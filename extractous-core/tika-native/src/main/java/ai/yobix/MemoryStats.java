@@ -0,0 +1,71 @@
+package ai.yobix;
+
+import java.lang.management.GarbageCollectorMXBean;
+import java.lang.management.ManagementFactory;
+import java.lang.management.MemoryMXBean;
+import java.lang.management.MemoryUsage;
+
+/**
+ * A snapshot of the memory of the VM, read from the MemoryMXBean, in bytes. The getters are
+ * called from the rust side, so make sure they are declared in the reachability-metadata.json
+ * jni section.
+ */
+public class MemoryStats {
+
+    private final long heapUsed;
+    private final long heapCommitted;
+    private final long heapMax;
+    private final long nonHeapUsed;
+    private final long gcCount;
+
+    private MemoryStats(long heapUsed, long heapCommitted, long heapMax, long nonHeapUsed, long gcCount) {
+        this.heapUsed = heapUsed;
+        this.heapCommitted = heapCommitted;
+        this.heapMax = heapMax;
+        this.nonHeapUsed = nonHeapUsed;
+        this.gcCount = gcCount;
+    }
+
+    static MemoryStats current() {
+        final MemoryMXBean memory = ManagementFactory.getMemoryMXBean();
+        final MemoryUsage heap = memory.getHeapMemoryUsage();
+        final MemoryUsage nonHeap = memory.getNonHeapMemoryUsage();
+        long gcCount = 0;
+        for (GarbageCollectorMXBean collector : ManagementFactory.getGarbageCollectorMXBeans()) {
+            // -1 when the collector does not count its collections
+            gcCount += Math.max(collector.getCollectionCount(), 0);
+        }
+        return new MemoryStats(heap.getUsed(), heap.getCommitted(), heap.getMax(), nonHeap.getUsed(), gcCount);
+    }
+
+    public long getHeapUsed() {
+        return heapUsed;
+    }
+
+    public long getHeapCommitted() {
+        return heapCommitted;
+    }
+
+    /**
+     * Returns the maximum size of the heap, -1 when it is not defined
+     */
+    public long getHeapMax() {
+        return heapMax;
+    }
+
+    public long getNonHeapUsed() {
+        return nonHeapUsed;
+    }
+
+    /**
+     * Returns the number of collections run by all the garbage collectors since the VM started
+     */
+    public long getGcCount() {
+        return gcCount;
+    }
+
+    public String toString() {
+        return "heapUsed:" + this.heapUsed + " heapCommitted: " + this.heapCommitted + " heapMax: " + this.heapMax +
+                " nonHeapUsed: " + this.nonHeapUsed + " gcCount: " + this.gcCount;
+    }
+}
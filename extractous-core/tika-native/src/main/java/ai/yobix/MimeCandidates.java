@@ -0,0 +1,92 @@
+package ai.yobix;
+
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.mime.MimeType;
+import org.apache.tika.mime.MimeTypeException;
+import org.apache.tika.mime.MimeTypes;
+
+import java.io.IOException;
+import java.util.ArrayList;
+import java.util.Comparator;
+import java.util.LinkedHashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Ranks the media types a document could be. Tika detectors only report their best guess, so
+ * the alternatives are the other types whose magic bytes match the head of the document.
+ * <p>
+ * The type chosen by the full detector (magic bytes, container inspection, ...) comes first with
+ * a confidence of 1.0, lowered to {@link #AMBIGUOUS} when unrelated types match the magic bytes
+ * too. Generic supertypes of the detected type, such as application/zip for an OOXML document,
+ * are listed with {@link #SUPERTYPE} and the unrelated matches with {@link #ALTERNATIVE}.
+ */
+public final class MimeCandidates {
+
+    static final float AMBIGUOUS = 0.7f;
+    static final float SUPERTYPE = 0.5f;
+    static final float ALTERNATIVE = 0.4f;
+
+    private MimeCandidates() {
+    }
+
+    public static MimeCandidatesResult detect(TikaInputStream stream) throws IOException {
+        final TikaConfig config = TikaConfig.getDefaultConfig();
+        final MimeTypes mimeTypes = config.getMimeRepository();
+        final MediaTypeRegistry registry = config.getMediaTypeRegistry();
+
+        final MediaType detected = config.getDetector().detect(stream, new Metadata());
+        final byte[] header = readHeader(stream, mimeTypes.getMinLength());
+
+        final Map<String, Float> others = new LinkedHashMap<>();
+        boolean ambiguous = false;
+        for (MediaType type : registry.getTypes()) {
+            if (type.equals(detected) || !matchesMagic(mimeTypes, type, header)) {
+                continue;
+            }
+            if (registry.isSpecializationOf(detected, type)) {
+                others.put(type.toString(), SUPERTYPE);
+            } else if (!registry.isSpecializationOf(type, detected)) {
+                others.put(type.toString(), ALTERNATIVE);
+                ambiguous = true;
+            }
+        }
+
+        final List<Map.Entry<String, Float>> candidates = new ArrayList<>();
+        candidates.add(Map.entry(detected.toString(), ambiguous ? AMBIGUOUS : 1.0f));
+        final List<Map.Entry<String, Float>> sorted = new ArrayList<>(others.entrySet());
+        sorted.sort(Map.Entry.<String, Float>comparingByValue(Comparator.reverseOrder())
+                .thenComparing(Map.Entry.comparingByKey()));
+        candidates.addAll(sorted);
+
+        final String[] types = new String[candidates.size()];
+        final float[] confidences = new float[candidates.size()];
+        for (int i = 0; i < candidates.size(); i++) {
+            types[i] = candidates.get(i).getKey();
+            confidences[i] = candidates.get(i).getValue();
+        }
+        return new MimeCandidatesResult(types, confidences);
+    }
+
+    private static boolean matchesMagic(MimeTypes mimeTypes, MediaType type, byte[] header) {
+        try {
+            final MimeType mimeType = mimeTypes.forName(type.toString());
+            return mimeType.hasMagic() && mimeType.matchesMagic(header);
+        } catch (MimeTypeException e) {
+            return false;
+        }
+    }
+
+    private static byte[] readHeader(TikaInputStream stream, int length) throws IOException {
+        stream.mark(length);
+        try {
+            return stream.readNBytes(length);
+        } finally {
+            stream.reset();
+        }
+    }
+}
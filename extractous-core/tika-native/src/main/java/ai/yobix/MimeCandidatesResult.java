@@ -0,0 +1,69 @@
+package ai.yobix;
+
+/**
+ * Candidate media types of a document ordered by decreasing confidence, see
+ * {@link MimeCandidates}. Exposed as parallel arrays like {@link WordBoxResult}.
+ */
+public class MimeCandidatesResult {
+
+    private final String[] types;
+    private final float[] confidences;
+    private final byte status;
+    private final String errorMessage;
+
+    public MimeCandidatesResult(String[] types, float[] confidences) {
+        this.types = types;
+        this.confidences = confidences;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public MimeCandidatesResult(byte status, String errorMessage) {
+        this.types = null;
+        this.confidences = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the candidate media types
+     */
+    public String[] getTypes() {
+        return types;
+    }
+
+    /**
+     * Returns the confidence of each candidate, between 0 and 1
+     */
+    public float[] getConfidences() {
+        return confidences;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " candidates: " + (types != null ? types.length : 0);
+    }
+}
@@ -0,0 +1,143 @@
+package ai.yobix;
+
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * Body parts of an email message, see {@link MimeMessageSplitter}. The attachments are exposed
+ * as parallel arrays like {@link WordBoxResult}.
+ */
+public class MimeMessageResult {
+
+    private final String textPlain;
+    private final String textHtml;
+    private final String[] attachmentNames;
+    private final String[] attachmentTypes;
+    private final byte[][] attachmentData;
+    private final byte status;
+    private final String errorMessage;
+
+    private MimeMessageResult(String textPlain, String textHtml, String[] attachmentNames,
+                              String[] attachmentTypes, byte[][] attachmentData) {
+        this.textPlain = textPlain;
+        this.textHtml = textHtml;
+        this.attachmentNames = attachmentNames;
+        this.attachmentTypes = attachmentTypes;
+        this.attachmentData = attachmentData;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public MimeMessageResult(byte status, String errorMessage) {
+        this.textPlain = null;
+        this.textHtml = null;
+        this.attachmentNames = null;
+        this.attachmentTypes = null;
+        this.attachmentData = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the text/plain body or null if the message has none
+     */
+    public String getTextPlain() {
+        return textPlain;
+    }
+
+    /**
+     * Returns the text/html body or null if the message has none
+     */
+    public String getTextHtml() {
+        return textHtml;
+    }
+
+    /**
+     * Returns the file name of each attachment, null for the ones without
+     */
+    public String[] getAttachmentNames() {
+        return attachmentNames;
+    }
+
+    /**
+     * Returns the declared media type of each attachment
+     */
+    public String[] getAttachmentTypes() {
+        return attachmentTypes;
+    }
+
+    /**
+     * Returns the decoded bytes of each attachment
+     */
+    public byte[][] getAttachmentData() {
+        return attachmentData;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " attachments: " + (attachmentNames != null ? attachmentNames.length : 0);
+    }
+
+    static class Builder {
+        private String textPlain;
+        private String textHtml;
+        private final List<String> names = new ArrayList<>();
+        private final List<String> types = new ArrayList<>();
+        private final List<byte[]> data = new ArrayList<>();
+
+        boolean hasTextPlain() {
+            return textPlain != null;
+        }
+
+        boolean hasTextHtml() {
+            return textHtml != null;
+        }
+
+        void setTextPlain(String textPlain) {
+            this.textPlain = textPlain;
+        }
+
+        void setTextHtml(String textHtml) {
+            this.textHtml = textHtml;
+        }
+
+        void addAttachment(String name, String type, byte[] bytes) {
+            names.add(name);
+            types.add(type);
+            data.add(bytes);
+        }
+
+        MimeMessageResult build() {
+            return new MimeMessageResult(
+                    textPlain,
+                    textHtml,
+                    names.toArray(new String[0]),
+                    types.toArray(new String[0]),
+                    data.toArray(new byte[0][]));
+        }
+    }
+}
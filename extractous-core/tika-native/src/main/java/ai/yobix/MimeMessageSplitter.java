@@ -0,0 +1,81 @@
+package ai.yobix;
+
+import org.apache.james.mime4j.MimeException;
+import org.apache.james.mime4j.dom.Body;
+import org.apache.james.mime4j.dom.Entity;
+import org.apache.james.mime4j.dom.Message;
+import org.apache.james.mime4j.dom.Multipart;
+import org.apache.james.mime4j.dom.TextBody;
+import org.apache.james.mime4j.message.DefaultMessageBuilder;
+import org.apache.james.mime4j.message.DefaultMessageWriter;
+import org.apache.james.mime4j.stream.MimeConfig;
+
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.Reader;
+import java.io.StringWriter;
+
+/**
+ * Splits an RFC822 email message into its text/plain body, its text/html body and its
+ * attachments, following the nested multipart bodies. The first inline text/plain and text/html
+ * parts, wherever they are in the tree, are the bodies: with a multipart/alternative these are
+ * the two renditions of the same text. Every other leaf part is an attachment, the parts with
+ * an attachment disposition or a file name, the inline images of the HTML body, the forwarded
+ * messages and the extra text parts alike.
+ */
+class MimeMessageSplitter {
+
+    private MimeMessageSplitter() {
+    }
+
+    static MimeMessageResult split(InputStream stream) throws IOException, MimeException {
+        final DefaultMessageBuilder messageBuilder = new DefaultMessageBuilder();
+        messageBuilder.setMimeEntityConfig(MimeConfig.PERMISSIVE);
+        final Message message = messageBuilder.parseMessage(stream);
+        try {
+            final MimeMessageResult.Builder result = new MimeMessageResult.Builder();
+            collect(message, result);
+            return result.build();
+        } finally {
+            message.dispose();
+        }
+    }
+
+    private static void collect(Entity entity, MimeMessageResult.Builder result) throws IOException {
+        final Body body = entity.getBody();
+        if (body instanceof Multipart) {
+            for (Entity part : ((Multipart) body).getBodyParts()) {
+                collect(part, result);
+            }
+            return;
+        }
+
+        final String mimeType = entity.getMimeType();
+        final boolean inline = !"attachment".equalsIgnoreCase(entity.getDispositionType())
+                && entity.getFilename() == null;
+        if (inline && body instanceof TextBody) {
+            if ("text/plain".equalsIgnoreCase(mimeType) && !result.hasTextPlain()) {
+                result.setTextPlain(read((TextBody) body));
+                return;
+            }
+            if ("text/html".equalsIgnoreCase(mimeType) && !result.hasTextHtml()) {
+                result.setTextHtml(read((TextBody) body));
+                return;
+            }
+        }
+
+        // The decoded bytes, or the whole forwarded message for message/rfc822 parts
+        final ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+        new DefaultMessageWriter().writeBody(body, bytes);
+        result.addAttachment(entity.getFilename(), mimeType, bytes.toByteArray());
+    }
+
+    private static String read(TextBody body) throws IOException {
+        final StringWriter writer = new StringWriter();
+        try (Reader reader = body.getReader()) {
+            reader.transferTo(writer);
+        }
+        return writer.toString();
+    }
+}
@@ -0,0 +1,74 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+/**
+ * SAX content handler that forwards the parse events to a rust handler. The native methods
+ * are registered from the rust side, and handlerPtr points to the rust handler that is only
+ * valid for the duration of the parse call that created this object.
+ * A rust handler that fails raises a SAXException from the native method, which aborts the parse.
+ */
+public class NativeContentHandler extends DefaultHandler {
+
+    private final long handlerPtr;
+
+    public NativeContentHandler(long handlerPtr) {
+        this.handlerPtr = handlerPtr;
+    }
+
+    @Override
+    public void startDocument() throws SAXException {
+        nativeStartDocument(handlerPtr);
+    }
+
+    @Override
+    public void endDocument() throws SAXException {
+        nativeEndDocument(handlerPtr);
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts)
+            throws SAXException {
+        // Attribute names and values are interleaved to keep the upcall to a single array
+        final String[] attributes = new String[atts.getLength() * 2];
+        for (int i = 0; i < atts.getLength(); i++) {
+            attributes[2 * i] = elementName(atts.getLocalName(i), atts.getQName(i));
+            attributes[2 * i + 1] = atts.getValue(i);
+        }
+        nativeStartElement(handlerPtr, elementName(localName, qName), attributes);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) throws SAXException {
+        nativeEndElement(handlerPtr, elementName(localName, qName));
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (length > 0) {
+            nativeCharacters(handlerPtr, new String(ch, start, length));
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        characters(ch, start, length);
+    }
+
+    private static String elementName(String localName, String qName) {
+        return localName == null || localName.isEmpty() ? qName : localName;
+    }
+
+    private static native void nativeStartDocument(long handlerPtr) throws SAXException;
+
+    private static native void nativeEndDocument(long handlerPtr) throws SAXException;
+
+    private static native void nativeStartElement(long handlerPtr, String name, String[] attributes)
+            throws SAXException;
+
+    private static native void nativeEndElement(long handlerPtr, String name) throws SAXException;
+
+    private static native void nativeCharacters(long handlerPtr, String text) throws SAXException;
+}
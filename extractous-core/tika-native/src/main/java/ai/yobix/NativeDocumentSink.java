@@ -0,0 +1,29 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+import org.xml.sax.SAXException;
+
+/**
+ * Hands the documents of a recursive parse to a rust sink as soon as each one is complete,
+ * instead of collecting them into a {@link RecursiveResult}. The native method is registered
+ * from the rust side, and sinkPtr points to the rust sink that is only valid for the duration of
+ * the parse call that created this object.
+ * A rust sink that fails raises a SAXException from the native method, which aborts the parse.
+ */
+public class NativeDocumentSink {
+
+    private final long sinkPtr;
+
+    public NativeDocumentSink(long sinkPtr) {
+        this.sinkPtr = sinkPtr;
+    }
+
+    /**
+     * Passes a complete document, its content under the X-TIKA:content key of its metadata
+     */
+    public void accept(Metadata metadata) throws SAXException {
+        nativeDocument(sinkPtr, metadata);
+    }
+
+    private static native void nativeDocument(long sinkPtr, Metadata metadata) throws SAXException;
+}
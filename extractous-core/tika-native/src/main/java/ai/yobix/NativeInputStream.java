@@ -0,0 +1,77 @@
+package ai.yobix;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.ByteBuffer;
+import java.util.Objects;
+
+/**
+ * Reads the bytes of a document from a rust reader. The native methods are registered from the
+ * rust side, and readerPtr points to the rust reader.
+ * When owned, the stream frees the rust reader once closed, because the parse goes on in the
+ * background after the call that created this object has returned. Otherwise the rust reader is
+ * only valid for the duration of that call, and closing the stream leaves it alone.
+ * A rust reader that fails or panics raises an IOException from the native read method.
+ */
+public class NativeInputStream extends InputStream {
+
+    private static final int BUFFER_SIZE = 64 * 1024;
+
+    private final long readerPtr;
+    private final boolean owned;
+    // Allocated once, so that the rust side can write into it without copying
+    private final ByteBuffer buffer = ByteBuffer.allocateDirect(BUFFER_SIZE);
+    private boolean eof = false;
+    private boolean closed = false;
+
+    public NativeInputStream(long readerPtr, boolean owned) {
+        this.readerPtr = readerPtr;
+        this.owned = owned;
+    }
+
+    @Override
+    public int read() throws IOException {
+        final byte[] one = new byte[1];
+        final int read = read(one, 0, 1);
+        return read == -1 ? -1 : one[0] & 0xff;
+    }
+
+    @Override
+    public synchronized int read(byte[] b, int off, int len) throws IOException {
+        Objects.checkFromIndexSize(off, len, b.length);
+        if (closed) {
+            throw new IOException("Stream closed");
+        }
+        if (len == 0) {
+            return 0;
+        }
+        if (eof) {
+            return -1;
+        }
+
+        final int read = nativeRead(readerPtr, buffer, Math.min(len, BUFFER_SIZE));
+        if (read < 0) {
+            eof = true;
+            return -1;
+        }
+        buffer.clear();
+        buffer.get(b, off, read);
+        buffer.clear();
+        return read;
+    }
+
+    @Override
+    public synchronized void close() {
+        if (closed) {
+            return;
+        }
+        closed = true;
+        if (owned) {
+            nativeClose(readerPtr);
+        }
+    }
+
+    private static native int nativeRead(long readerPtr, ByteBuffer buffer, int length) throws IOException;
+
+    private static native void nativeClose(long readerPtr);
+}
@@ -0,0 +1,82 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaConfigException;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
+
+import java.util.Collections;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+/**
+ * Runs OCR with a Tesseract installed outside of the PATH. Tika only takes the location of the
+ * tesseract executable and of its language data from its configuration file, so the rust side
+ * creates the {@link Config} subclass below, which carries them, and {@link OcrLimiter} hands
+ * the OCR of the parse to a Tesseract parser of these locations instead of the default one.
+ * The parsers are created once for each location, creating one runs the executable.
+ */
+public class OcrEngines {
+
+    private static final Map<List<String>, TesseractOCRParser> parsers = new HashMap<>();
+
+    private OcrEngines() {
+    }
+
+    public static class Config extends TesseractOCRConfig {
+
+        private String tesseractPath = "";
+        private String tessdataPath = "";
+
+        public Config() {
+        }
+
+        public String getTesseractPath() {
+            return tesseractPath;
+        }
+
+        /**
+         * Sets the directory of the tesseract executable, empty to look it up in the PATH
+         */
+        public void setTesseractPath(String tesseractPath) {
+            this.tesseractPath = tesseractPath;
+        }
+
+        public String getTessdataPath() {
+            return tessdataPath;
+        }
+
+        /**
+         * Sets the directory of the language data, empty for the one tesseract defaults to
+         */
+        public void setTessdataPath(String tessdataPath) {
+            this.tessdataPath = tessdataPath;
+        }
+
+        boolean hasCustomPaths() {
+            return !tesseractPath.isEmpty() || !tessdataPath.isEmpty();
+        }
+    }
+
+    /**
+     * Returns the Tesseract parser of the locations of the given config, or null when it uses
+     * the default locations
+     */
+    static synchronized TesseractOCRParser get(TesseractOCRConfig config) {
+        if (!(config instanceof Config) || !((Config) config).hasCustomPaths()) {
+            return null;
+        }
+        final Config paths = (Config) config;
+        return parsers.computeIfAbsent(List.of(paths.tesseractPath, paths.tessdataPath), key -> {
+            final TesseractOCRParser parser = new TesseractOCRParser();
+            parser.setTesseractPath(key.get(0));
+            parser.setTessdataPath(key.get(1));
+            try {
+                parser.initialize(Collections.emptyMap());
+            } catch (TikaConfigException e) {
+                // Left without Tesseract, the parser supports no type
+            }
+            return parser;
+        });
+    }
+}
@@ -0,0 +1,162 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.ocr.TesseractOCRParser;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.HashSet;
+import java.util.Map;
+import java.util.Set;
+import java.util.concurrent.TimeUnit;
+
+/**
+ * Caps the number of concurrent Tesseract invocations across all extractions of the process.
+ * Every media type the default parser hands to {@link TesseractOCRParser} (images and the
+ * image/ocr-* types used for PDF OCR) is routed through a decorator that waits for a free slot.
+ * <p>
+ * The decorator also keeps a Tesseract run that exceeds the timeout of the
+ * {@link TesseractOCRConfig} from failing the whole extraction: the image or page it was
+ * recognizing yields no text, the rest of the document is extracted as usual and the
+ * {@link OcrTracker} flags the document as timed out.
+ */
+public class OcrLimiter {
+
+    private static final Object lock = new Object();
+    private static int maxConcurrency = Runtime.getRuntime().availableProcessors();
+    private static int running = 0;
+
+    private OcrLimiter() {
+    }
+
+    /**
+     * Sets the maximum number of concurrent OCR invocations. Values lower than 1 are treated as 1.
+     * Extractions already waiting for a slot pick up the new limit immediately.
+     * Default: the number of available processors
+     */
+    public static void setMaxConcurrency(int max) {
+        synchronized (lock) {
+            maxConcurrency = Math.max(1, max);
+            lock.notifyAll();
+        }
+    }
+
+    public static int getMaxConcurrency() {
+        synchronized (lock) {
+            return maxConcurrency;
+        }
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that OCR goes through the limiter,
+     * and is done by the Tesseract of the locations of the given config, see {@link OcrEngines}
+     */
+    static Parser wrap(Parser parser, TesseractOCRConfig ocrConfig) {
+        if (!(parser instanceof CompositeParser)) {
+            return parser;
+        }
+        final CompositeParser composite = (CompositeParser) parser;
+
+        TesseractOCRParser tesseract = null;
+        Set<MediaType> ocrTypes = new HashSet<>();
+        for (Map.Entry<MediaType, Parser> entry : composite.getParsers(new ParseContext()).entrySet()) {
+            if (entry.getValue() instanceof TesseractOCRParser) {
+                tesseract = (TesseractOCRParser) entry.getValue();
+                ocrTypes.add(entry.getKey());
+            }
+        }
+        final TesseractOCRParser configured = OcrEngines.get(ocrConfig);
+        if (configured != null) {
+            // Also found when the default locations have no Tesseract at all
+            tesseract = configured;
+            ocrTypes = configured.getSupportedTypes(new ParseContext());
+        }
+        if (tesseract == null) {
+            // Tesseract is not installed, nothing to limit
+            return parser;
+        }
+        // Later parsers take precedence, so the OCR types are taken over by the throttled parser
+        return new CompositeParser(composite.getMediaTypeRegistry(), parser, new ThrottledParser(tesseract, ocrTypes));
+    }
+
+    private static void acquire() throws TikaException {
+        synchronized (lock) {
+            try {
+                while (running >= maxConcurrency) {
+                    lock.wait();
+                }
+            } catch (InterruptedException e) {
+                Thread.currentThread().interrupt();
+                throw new TikaException("Interrupted while waiting for an OCR slot", e);
+            }
+            running++;
+        }
+    }
+
+    private static void release() {
+        synchronized (lock) {
+            running--;
+            lock.notifyAll();
+        }
+    }
+
+    private static class ThrottledParser extends ParserDecorator {
+
+        private final Set<MediaType> types;
+
+        ThrottledParser(Parser parser, Set<MediaType> types) {
+            super(parser);
+            this.types = types;
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return types;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final OcrTracker tracker = context.get(OcrTracker.class);
+            if (tracker != null) {
+                tracker.markUsed();
+            }
+            acquire();
+            final long start = System.nanoTime();
+            try {
+                super.parse(stream, handler, metadata, context);
+            } catch (TikaException e) {
+                if (!timedOut(context, start)) {
+                    throw e;
+                }
+                if (tracker != null) {
+                    tracker.markTimedOut();
+                }
+            } finally {
+                release();
+            }
+        }
+
+        /**
+         * Returns whether a failed run took the whole timeout, Tesseract reports a timeout with a
+         * plain TikaException. An interrupted thread is the timeout of the extraction instead
+         */
+        private static boolean timedOut(ParseContext context, long start) {
+            final TesseractOCRConfig config = context.get(TesseractOCRConfig.class);
+            if (config == null || Thread.currentThread().isInterrupted()) {
+                return false;
+            }
+            final long elapsed = System.nanoTime() - start;
+            return elapsed >= TimeUnit.SECONDS.toNanos(config.getTimeoutSeconds());
+        }
+    }
+}
@@ -0,0 +1,70 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+
+import java.util.ArrayDeque;
+import java.util.Deque;
+
+/**
+ * Records whether OCR ran while parsing a document, and whether it timed out. An instance is put
+ * in the ParseContext and notified by the OCR parser installed by {@link OcrLimiter}. Embedded
+ * documents of a recursive parse get their own frame, so OCR done on an embedded image is not
+ * attributed to its container.
+ */
+public class OcrTracker {
+
+    /**
+     * Metadata key set to "true" or "false" depending on whether the content came from OCR
+     */
+    public static final String OCR_USED = "extractous:ocr_used";
+
+    /**
+     * Metadata key set to "true" when Tesseract ran out of time on an image or a page, whose
+     * text is then missing from the content
+     */
+    public static final String OCR_TIMED_OUT = "X-TIKA:ocr_timed_out";
+
+    // Number of OCR invocations and of OCR timeouts of each document being parsed, the top frame
+    // is the innermost document. The bottom frame is the document passed to the parser
+    private final Deque<int[]> frames = new ArrayDeque<>();
+
+    public OcrTracker() {
+        frames.push(new int[2]);
+    }
+
+    void markUsed() {
+        frames.peek()[0]++;
+    }
+
+    void markTimedOut() {
+        frames.peek()[1]++;
+    }
+
+    /**
+     * Starts a frame for an embedded document
+     */
+    public void startEmbeddedDocument() {
+        frames.push(new int[2]);
+    }
+
+    /**
+     * Ends the frame of the current embedded document and flags its metadata
+     */
+    public void endEmbeddedDocument(Metadata metadata) {
+        flag(metadata, frames.pop());
+    }
+
+    /**
+     * Flags the metadata of the document passed to the parser
+     */
+    public void endDocument(Metadata metadata) {
+        flag(metadata, frames.peekLast());
+    }
+
+    private static void flag(Metadata metadata, int[] frame) {
+        metadata.set(OCR_USED, Boolean.toString(frame[0] > 0));
+        if (frame[1] > 0) {
+            metadata.set(OCR_TIMED_OUT, "true");
+        }
+    }
+}
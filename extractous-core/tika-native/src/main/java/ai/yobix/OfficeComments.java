@@ -0,0 +1,242 @@
+package ai.yobix;
+
+import org.apache.poi.openxml4j.exceptions.InvalidFormatException;
+import org.apache.poi.openxml4j.opc.OPCPackage;
+import org.apache.poi.openxml4j.opc.PackageAccess;
+import org.apache.poi.openxml4j.opc.PackagePart;
+import org.apache.poi.util.XMLHelper;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.w3c.dom.Element;
+import org.w3c.dom.NamedNodeMap;
+import org.w3c.dom.Node;
+import org.w3c.dom.NodeList;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.Set;
+
+/**
+ * Adds the reviewer comments of OOXML documents to their metadata, with their author and
+ * time, when the Office config of the parse context asks for it, see
+ * {@link DocumentPasswords.OfficeConfig#setIncludeComments}. The comments are read from the
+ * package after the parse:
+ * <ul>
+ * <li>Word: the comments, with their author and time</li>
+ * <li>Excel: the cell notes, with their author. The threaded comments of recent versions also
+ * keep a note, which is what is read</li>
+ * <li>PowerPoint: the classic and the modern comments, with their author and time</li>
+ * </ul>
+ * Each comment adds one value to each of the three keys, an empty one for an author or a time
+ * the document does not record, so that the values of the same index belong together. The
+ * binary Office formats and the encrypted documents yield no comments.
+ */
+public class OfficeComments {
+
+    /**
+     * Metadata key of the author of each comment
+     */
+    public static final String COMMENT_AUTHOR = "extractous:comment_author";
+    /**
+     * Metadata key of the time of each comment, as written in the document, usually ISO-8601
+     */
+    public static final String COMMENT_DATE = "extractous:comment_date";
+    /**
+     * Metadata key of the text of each comment, its paragraphs separated by new lines
+     */
+    public static final String COMMENT_TEXT = "extractous:comment_text";
+
+    private static final Set<MediaType> OOXML_TYPES = Set.of(
+            MediaType.application("vnd.openxmlformats-officedocument.wordprocessingml.document"),
+            MediaType.application("vnd.ms-word.document.macroenabled.12"),
+            MediaType.application("vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+            MediaType.application("vnd.ms-excel.sheet.macroenabled.12"),
+            MediaType.application("vnd.openxmlformats-officedocument.presentationml.presentation"),
+            MediaType.application("vnd.ms-powerpoint.presentation.macroenabled.12"));
+
+    private static final String WORD_COMMENTS =
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.comments+xml";
+    private static final String EXCEL_COMMENTS =
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml";
+    private static final String POWERPOINT_COMMENTS =
+            "application/vnd.openxmlformats-officedocument.presentationml.comments+xml";
+    private static final String POWERPOINT_COMMENT_AUTHORS =
+            "application/vnd.openxmlformats-officedocument.presentationml.commentAuthors+xml";
+    private static final String POWERPOINT_MODERN_COMMENTS = "application/vnd.ms-powerpoint.comments+xml";
+    private static final String POWERPOINT_AUTHORS = "application/vnd.ms-powerpoint.authors+xml";
+
+    private OfficeComments() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the comments of OOXML
+     * documents are added to their metadata when the Office config asks for it
+     */
+    static Parser wrap(Parser parser, MediaTypeRegistry registry) {
+        // Later parsers take precedence, so the OOXML documents are taken over by the decorator
+        return new CompositeParser(registry, parser, new CommentParser(parser));
+    }
+
+    private static class CommentParser extends ParserDecorator {
+
+        CommentParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return OOXML_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final OfficeParserConfig config = context.get(OfficeParserConfig.class);
+            if (!(config instanceof DocumentPasswords.OfficeConfig)
+                    || !((DocumentPasswords.OfficeConfig) config).isIncludeComments()) {
+                super.parse(stream, handler, metadata, context);
+                return;
+            }
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                // The stream is consumed by the parse, the comments are read from the file after it
+                final Path path = tis.getPath();
+                super.parse(tis, handler, metadata, context);
+                try {
+                    addComments(path, metadata);
+                } catch (IOException | InvalidFormatException | SAXException e) {
+                    // The document was parsed already, only the comments are missing from the metadata
+                }
+            }
+        }
+    }
+
+    private static void addComments(Path path, Metadata metadata)
+            throws IOException, InvalidFormatException, SAXException {
+        final OPCPackage pkg = OPCPackage.open(path.toFile(), PackageAccess.READ);
+        try {
+            for (PackagePart part : pkg.getPartsByContentType(WORD_COMMENTS)) {
+                for (Element comment : elements(part, "comment")) {
+                    add(metadata, attribute(comment, "author"), attribute(comment, "date"), text(comment, "p"));
+                }
+            }
+
+            for (PackagePart part : pkg.getPartsByContentType(EXCEL_COMMENTS)) {
+                final Element root = parse(part);
+                final NodeList authors = root.getElementsByTagNameNS("*", "author");
+                final NodeList comments = root.getElementsByTagNameNS("*", "comment");
+                for (int i = 0; i < comments.getLength(); i++) {
+                    final Element comment = (Element) comments.item(i);
+                    String author = "";
+                    try {
+                        final int authorId = Integer.parseInt(comment.getAttribute("authorId"));
+                        if (authorId >= 0 && authorId < authors.getLength()) {
+                            author = authors.item(authorId).getTextContent().trim();
+                        }
+                    } catch (NumberFormatException e) {
+                        // A note without author
+                    }
+                    add(metadata, author, "", text(comment, "text"));
+                }
+            }
+
+            final Map<String, String> authors = new HashMap<>();
+            for (PackagePart part : pkg.getPartsByContentType(POWERPOINT_COMMENT_AUTHORS)) {
+                for (Element author : elements(part, "cmAuthor")) {
+                    authors.put(author.getAttribute("id"), author.getAttribute("name"));
+                }
+            }
+            for (PackagePart part : pkg.getPartsByContentType(POWERPOINT_AUTHORS)) {
+                for (Element author : elements(part, "author")) {
+                    authors.put(author.getAttribute("id"), author.getAttribute("name"));
+                }
+            }
+            for (PackagePart part : pkg.getPartsByContentType(POWERPOINT_COMMENTS)) {
+                for (Element comment : elements(part, "cm")) {
+                    add(metadata, authors.getOrDefault(comment.getAttribute("authorId"), ""),
+                            attribute(comment, "dt"), text(comment, "text"));
+                }
+            }
+            for (PackagePart part : pkg.getPartsByContentType(POWERPOINT_MODERN_COMMENTS)) {
+                for (Element comment : elements(part, "cm")) {
+                    add(metadata, authors.getOrDefault(comment.getAttribute("authorId"), ""),
+                            attribute(comment, "created"), text(comment, "p"));
+                }
+            }
+        } finally {
+            // Read only packages are closed without saving
+            pkg.revert();
+        }
+    }
+
+    private static void add(Metadata metadata, String author, String date, String text) {
+        metadata.add(COMMENT_AUTHOR, author.trim());
+        metadata.add(COMMENT_DATE, date.trim());
+        metadata.add(COMMENT_TEXT, text);
+    }
+
+    private static Element parse(PackagePart part) throws IOException, SAXException {
+        try (InputStream stream = part.getInputStream()) {
+            return XMLHelper.newDocumentBuilder().parse(stream).getDocumentElement();
+        }
+    }
+
+    private static List<Element> elements(PackagePart part, String localName)
+            throws IOException, SAXException {
+        final NodeList nodes = parse(part).getElementsByTagNameNS("*", localName);
+        final List<Element> elements = new ArrayList<>(nodes.getLength());
+        for (int i = 0; i < nodes.getLength(); i++) {
+            elements.add((Element) nodes.item(i));
+        }
+        return elements;
+    }
+
+    /**
+     * Returns the value of the attribute whatever its namespace, empty when it has none
+     */
+    private static String attribute(Element element, String localName) {
+        final NamedNodeMap attributes = element.getAttributes();
+        for (int i = 0; i < attributes.getLength(); i++) {
+            final Node attribute = attributes.item(i);
+            if (localName.equals(attribute.getLocalName())) {
+                return attribute.getNodeValue();
+            }
+        }
+        return "";
+    }
+
+    /**
+     * Returns the text of the comment, one line per paragraph, or the whole text when it has no
+     * element of the given name
+     */
+    private static String text(Element comment, String paragraphName) {
+        final NodeList paragraphs = comment.getElementsByTagNameNS("*", paragraphName);
+        if (paragraphs.getLength() == 0) {
+            return comment.getTextContent().trim();
+        }
+        final StringBuilder text = new StringBuilder();
+        for (int i = 0; i < paragraphs.getLength(); i++) {
+            if (text.length() > 0) {
+                text.append('\n');
+            }
+            text.append(paragraphs.item(i).getTextContent().trim());
+        }
+        return text.toString();
+    }
+}
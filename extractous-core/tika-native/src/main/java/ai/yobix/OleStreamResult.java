@@ -0,0 +1,79 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class OleStreamResult {
+
+    private final List<String> names;
+    private final byte[] data;
+    private final byte status;
+    private final String errorMessage;
+
+    public OleStreamResult(List<String> names) {
+        this.names = names;
+        this.data = null;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public OleStreamResult(byte[] data) {
+        this.names = null;
+        this.data = data;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public OleStreamResult(byte status, String errorMessage) {
+        this.names = null;
+        this.data = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the names of the streams of the document, for a listing.
+     * An array is returned to avoid JNI calls into java.util.List from native code
+     * @return String array or null if there is an error or the result is a stream
+     */
+    public String[] getNames() {
+        return names != null ? names.toArray(new String[0]) : null;
+    }
+
+    /**
+     * Returns the raw bytes of the stream that was read
+     * @return byte array or null if there is an error or the result is a listing
+     */
+    public byte[] getData() {
+        return data;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " names: " + (names != null ? names.size() : 0) +
+                " data: " + (data != null ? data.length : 0);
+    }
+}
@@ -0,0 +1,78 @@
+package ai.yobix;
+
+import org.apache.poi.poifs.filesystem.DirectoryEntry;
+import org.apache.poi.poifs.filesystem.DocumentEntry;
+import org.apache.poi.poifs.filesystem.DocumentInputStream;
+import org.apache.poi.poifs.filesystem.Entry;
+import org.apache.poi.poifs.filesystem.FileMagic;
+import org.apache.poi.poifs.filesystem.POIFSFileSystem;
+
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.nio.file.Path;
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * Lists and reads the streams of OLE compound documents, such as the Word, Excel and PowerPoint
+ * 97-2003 files and the Outlook messages, with the POIFS file system of POI. The streams nested
+ * in storages are named by their path from the root, the names of the storages and of the stream
+ * joined with '/', a character OLE names can not contain.
+ */
+class OleStreams {
+
+    private OleStreams() {
+    }
+
+    static boolean isOleDocument(Path path) throws IOException {
+        return FileMagic.valueOf(path.toFile()) == FileMagic.OLE2;
+    }
+
+    /**
+     * Returns the names of all the streams of the document, storage by storage in the order of
+     * the directory
+     */
+    static List<String> list(Path path) throws IOException {
+        try (POIFSFileSystem fs = new POIFSFileSystem(path.toFile(), true)) {
+            final List<String> names = new ArrayList<>();
+            collect(fs.getRoot(), "", names);
+            return names;
+        }
+    }
+
+    private static void collect(DirectoryEntry directory, String prefix, List<String> names) {
+        for (Entry entry : directory) {
+            if (entry instanceof DirectoryEntry) {
+                collect((DirectoryEntry) entry, prefix + entry.getName() + "/", names);
+            } else if (entry instanceof DocumentEntry) {
+                names.add(prefix + entry.getName());
+            }
+        }
+    }
+
+    /**
+     * Returns the raw bytes of the stream with the given name, as listed by {@link #list}
+     *
+     * @throws FileNotFoundException when the document has no such stream
+     */
+    static byte[] read(Path path, String name) throws IOException {
+        try (POIFSFileSystem fs = new POIFSFileSystem(path.toFile(), true)) {
+            DirectoryEntry directory = fs.getRoot();
+            final String[] parts = name.split("/", -1);
+            for (int i = 0; i < parts.length - 1; i++) {
+                final Entry entry = directory.getEntry(parts[i]);
+                if (!(entry instanceof DirectoryEntry)) {
+                    throw new FileNotFoundException("no storage named " + parts[i]);
+                }
+                directory = (DirectoryEntry) entry;
+            }
+            final Entry entry = directory.getEntry(parts[parts.length - 1]);
+            if (!(entry instanceof DocumentEntry)) {
+                throw new FileNotFoundException(parts[parts.length - 1] + " is a storage");
+            }
+            try (DocumentInputStream stream = new DocumentInputStream((DocumentEntry) entry)) {
+                return stream.readAllBytes();
+            }
+        }
+    }
+}
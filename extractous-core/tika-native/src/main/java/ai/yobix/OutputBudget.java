@@ -0,0 +1,75 @@
+package ai.yobix;
+
+/**
+ * Bounds the total size of the content of all the documents of a recursive parse, as UTF-8
+ * bytes of text, the markup of the xml output aside. The documents take their content from the
+ * budget as it is produced, through {@link LengthLimitedContentHandler}, and the document that
+ * exhausts it is cut there. Once exhausted, the documents that follow are not parsed at all.
+ * <p>
+ * The budget is shared by the worker threads of {@link ParallelEmbeddedParser}, in which case
+ * which documents get cut depends on the order in which they are parsed.
+ */
+public class OutputBudget {
+
+    /**
+     * Metadata key set to "true" on the container when the budget ran out, so that documents
+     * were cut or left out
+     */
+    public static final String OUTPUT_TRUNCATED = "extractous:output_truncated";
+
+    private final boolean limited;
+    private long remaining;
+    private volatile boolean exhausted = false;
+
+    /**
+     * @param maxBytes the total size of the content in UTF-8 bytes, negative for no limit
+     */
+    OutputBudget(long maxBytes) {
+        this.limited = maxBytes >= 0;
+        this.remaining = maxBytes;
+    }
+
+    boolean isLimited() {
+        return limited;
+    }
+
+    boolean isExhausted() {
+        return exhausted;
+    }
+
+    /**
+     * Takes the given characters from the budget and returns how many of them fit, all of them
+     * unless the budget runs out. A surrogate pair is never split.
+     */
+    synchronized int take(char[] ch, int start, int len) {
+        if (!limited) {
+            return len;
+        }
+        int taken = 0;
+        while (taken < len) {
+            final int bytes = utf8Length(ch[start + taken]);
+            if (bytes > remaining) {
+                exhausted = true;
+                remaining = 0;
+                if (taken > 0 && Character.isHighSurrogate(ch[start + taken - 1])) {
+                    taken--;
+                }
+                break;
+            }
+            remaining -= bytes;
+            taken++;
+        }
+        return taken;
+    }
+
+    // A surrogate pair takes 4 bytes, 2 for each half
+    private static int utf8Length(char c) {
+        if (c < 0x80) {
+            return 1;
+        }
+        if (c < 0x800 || Character.isSurrogate(c)) {
+            return 2;
+        }
+        return 3;
+    }
+}
@@ -0,0 +1,50 @@
+package ai.yobix;
+
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.util.ArrayDeque;
+import java.util.Deque;
+
+/**
+ * Writes a form feed at the end of every page of the XHTML output, the {@code <div class="page">}
+ * elements of PDF documents and the {@code <div class="slide-content">} elements of presentations.
+ * The rust side splits the text output on them to find the lines repeated across pages, such as
+ * running headers and footers, and then removes them.
+ */
+public class PageBreakMarker extends ContentHandlerDecorator {
+
+    private static final char[] PAGE_BREAK = {'\f'};
+
+    // Whether each of the open elements is a page
+    private final Deque<Boolean> pages = new ArrayDeque<>();
+
+    public PageBreakMarker(ContentHandler handler) {
+        super(handler);
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+        pages.push(isPage(localName, atts));
+        super.startElement(uri, localName, name, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String name) throws SAXException {
+        // Written inside the page element, so that it is kept by the body content handler
+        if (!pages.isEmpty() && pages.pop()) {
+            super.characters(PAGE_BREAK, 0, PAGE_BREAK.length);
+        }
+        super.endElement(uri, localName, name);
+    }
+
+    private static boolean isPage(String localName, Attributes atts) {
+        if (!"div".equals(localName)) {
+            return false;
+        }
+        final String cssClass = atts.getValue("class");
+        return "page".equals(cssClass) || "slide-content".equals(cssClass);
+    }
+}
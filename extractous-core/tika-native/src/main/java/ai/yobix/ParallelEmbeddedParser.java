@@ -0,0 +1,232 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.StandardCopyOption;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+import java.util.Set;
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.ExecutorService;
+import java.util.concurrent.Executors;
+import java.util.concurrent.Future;
+
+/**
+ * Parses the documents directly embedded in the container of a recursive parse concurrently.
+ * It wraps the parser of the RecursiveParserWrapper, so the wrapper still walks every embedded
+ * document of the container and records its path, id and depth. When told to defer the next
+ * document, it copies it to a temporary file and parses it with its own embedded documents on a
+ * worker thread instead, leaving an empty placeholder in the metadata list of the wrapper.
+ * Once the container is parsed, the placeholders are filled in, in the order of the container,
+ * and the documents embedded in them are inserted before them, as a sequential parse lists them.
+ */
+public class ParallelEmbeddedParser extends ParserDecorator {
+
+    /**
+     * Recursively parses a document on a worker thread, the document first in the returned list
+     */
+    interface SubtreeParser {
+        List<Metadata> parse(TikaInputStream stream, Metadata metadata) throws IOException, TikaException, SAXException;
+    }
+
+    private static class Deferred {
+        final Metadata placeholder;
+        final Future<List<Metadata>> subtree;
+
+        Deferred(Metadata placeholder, Future<List<Metadata>> subtree) {
+            this.placeholder = placeholder;
+            this.subtree = subtree;
+        }
+    }
+
+    private final int parallelism;
+    private final SubtreeParser subtreeParser;
+    private final List<Deferred> deferred = new ArrayList<>();
+    private ExecutorService executor;
+    // Set by the embedded document extractor right before the document to defer is parsed. The
+    // container is parsed on a single thread, so a plain flag is enough
+    private boolean deferNext = false;
+
+    public ParallelEmbeddedParser(Parser parser, int parallelism, SubtreeParser subtreeParser) {
+        super(parser);
+        this.parallelism = parallelism;
+        this.subtreeParser = subtreeParser;
+    }
+
+    @Override
+    public Set<MediaType> getSupportedTypes(ParseContext context) {
+        return getWrappedParser().getSupportedTypes(context);
+    }
+
+    void setDeferNext(boolean deferNext) {
+        this.deferNext = deferNext;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (!deferNext) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+        deferNext = false;
+
+        final Path file = Files.createTempFile("extractous-embedded-", ".tmp");
+        try {
+            Files.copy(stream, file, StandardCopyOption.REPLACE_EXISTING);
+        } catch (IOException e) {
+            Files.deleteIfExists(file);
+            throw e;
+        }
+        final Metadata copy = copyOf(metadata);
+        final Future<List<Metadata>> subtree = executor().submit(() -> {
+            try (TikaInputStream tis = TikaInputStream.get(file)) {
+                return subtreeParser.parse(tis, copy);
+            } finally {
+                Files.deleteIfExists(file);
+            }
+        });
+        deferred.add(new Deferred(metadata, subtree));
+    }
+
+    /**
+     * Waits for the deferred documents and merges them into the metadata list of the container
+     */
+    void mergeInto(List<Metadata> metadataList) throws SAXException {
+        int nextId = 1;
+        for (Metadata metadata : metadataList) {
+            final Integer id = metadata.getInt(TikaCoreProperties.EMBEDDED_ID);
+            if (id != null) {
+                nextId = Math.max(nextId, id + 1);
+            }
+        }
+
+        for (Deferred document : deferred) {
+            final List<Metadata> subtree;
+            try {
+                subtree = document.subtree.get();
+            } catch (InterruptedException e) {
+                Thread.currentThread().interrupt();
+                throw new SAXException("Interrupted while waiting for an embedded document", e);
+            } catch (ExecutionException e) {
+                // Same as a failed embedded document of a sequential parse
+                document.placeholder.add(TikaCoreProperties.EMBEDDED_EXCEPTION, e.getCause().toString());
+                continue;
+            }
+
+            final Metadata placeholder = document.placeholder;
+            int index = indexOf(metadataList, placeholder);
+            if (index < 0 || subtree.isEmpty()) {
+                continue;
+            }
+            final Metadata parsed = subtree.get(0);
+            // The embedded document was the container of its own parse
+            for (String value : parsed.getValues(TikaCoreProperties.CONTAINER_EXCEPTION)) {
+                parsed.add(TikaCoreProperties.EMBEDDED_EXCEPTION, value);
+            }
+            parsed.remove(TikaCoreProperties.CONTAINER_EXCEPTION.getName());
+            for (String name : parsed.names()) {
+                placeholder.remove(name);
+                for (String value : parsed.getValues(name)) {
+                    placeholder.add(name, value);
+                }
+            }
+
+            final String path = placeholder.get(TikaCoreProperties.EMBEDDED_RESOURCE_PATH);
+            final String idPath = placeholder.get(TikaCoreProperties.EMBEDDED_ID_PATH);
+            final Map<String, String> ids = new HashMap<>();
+            for (Metadata descendant : subtree.subList(1, subtree.size())) {
+                final String oldId = descendant.get(TikaCoreProperties.EMBEDDED_ID);
+                if (oldId != null) {
+                    final int newId = nextId++;
+                    ids.put(oldId, String.valueOf(newId));
+                    descendant.set(TikaCoreProperties.EMBEDDED_ID, newId);
+                }
+            }
+            for (Metadata descendant : subtree.subList(1, subtree.size())) {
+                relocate(descendant, path, idPath, ids);
+                metadataList.add(index++, descendant);
+            }
+        }
+        deferred.clear();
+    }
+
+    /**
+     * Stops the worker threads, the pending parses are interrupted
+     */
+    void shutdown() {
+        if (executor != null) {
+            executor.shutdownNow();
+        }
+    }
+
+    private ExecutorService executor() {
+        if (executor == null) {
+            executor = Executors.newFixedThreadPool(parallelism, runnable -> {
+                final Thread thread = new Thread(runnable, "extractous-embedded");
+                thread.setDaemon(true);
+                return thread;
+            });
+        }
+        return executor;
+    }
+
+    /**
+     * Moves a document embedded in a deferred one under the path, id path and depth of the
+     * deferred document in the container
+     */
+    private static void relocate(Metadata descendant, String path, String idPath, Map<String, String> ids) {
+        final String ownPath = descendant.get(TikaCoreProperties.EMBEDDED_RESOURCE_PATH);
+        if (path != null && ownPath != null) {
+            descendant.set(TikaCoreProperties.EMBEDDED_RESOURCE_PATH, path + ownPath);
+        }
+        final String ownIdPath = descendant.get(TikaCoreProperties.EMBEDDED_ID_PATH);
+        if (idPath != null && ownIdPath != null) {
+            final StringBuilder relocated = new StringBuilder(idPath);
+            for (String id : ownIdPath.split("/")) {
+                if (!id.isEmpty()) {
+                    relocated.append('/').append(ids.getOrDefault(id, id));
+                }
+            }
+            descendant.set(TikaCoreProperties.EMBEDDED_ID_PATH, relocated.toString());
+        }
+        final Integer depth = descendant.getInt(TikaCoreProperties.EMBEDDED_DEPTH);
+        if (depth != null) {
+            descendant.set(TikaCoreProperties.EMBEDDED_DEPTH, depth + 1);
+        }
+    }
+
+    private static int indexOf(List<Metadata> metadataList, Metadata metadata) {
+        for (int i = 0; i < metadataList.size(); i++) {
+            if (metadataList.get(i) == metadata) {
+                return i;
+            }
+        }
+        return -1;
+    }
+
+    private static Metadata copyOf(Metadata metadata) {
+        final Metadata copy = new Metadata();
+        for (String name : metadata.names()) {
+            for (String value : metadata.getValues(name)) {
+                copy.add(name, value);
+            }
+        }
+        return copy;
+    }
+}
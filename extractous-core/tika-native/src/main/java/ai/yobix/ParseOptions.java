@@ -0,0 +1,266 @@
+package ai.yobix;
+
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+
+import java.util.ArrayList;
+import java.util.HashSet;
+import java.util.List;
+import java.util.Locale;
+import java.util.Set;
+
+/**
+ * Extractor wide settings that apply to every parse method, whatever the parsed format.
+ * The setters are called from the rust side, so make sure they are declared in the
+ * reachability-metadata.json jni section.
+ */
+public class ParseOptions {
+
+    private int maxElementDepth = 100;
+    private boolean ocrSpillToDisk = false;
+    private boolean excludeHiddenText = false;
+    private long softTimeBudgetMillis = 0;
+    private boolean trustDeclaredCharset = true;
+    private boolean encodingAuto = false;
+    private boolean dedupRepeatedLines = false;
+    private int stringStreamingThreshold = 0;
+    private boolean recordParserChain = false;
+    private boolean zipRecovery = false;
+    private boolean preserveScripts = false;
+    private String userAgent = null;
+    private final Set<String> forceTextExtensions = new HashSet<>();
+    private final Set<MediaType> forceTextTypes = new HashSet<>();
+    private final List<String> parserFallbacks = new ArrayList<>();
+
+    public ParseOptions() {
+    }
+
+    public int getMaxElementDepth() {
+        return maxElementDepth;
+    }
+
+    /**
+     * Sets the maximum nesting depth of the XHTML elements produced by the parser. It is handed to
+     * the SecureContentHandler of the AutoDetectParser, which fails the parse once an element is
+     * nested deeper.
+     * Default: 100, the Tika default. 0 means no limit
+     */
+    public void setMaxElementDepth(int maxElementDepth) {
+        this.maxElementDepth = maxElementDepth;
+    }
+
+    public boolean isOcrSpillToDisk() {
+        return ocrSpillToDisk;
+    }
+
+    /**
+     * Sets whether the buffers of large jobs are kept in temporary files instead of memory: the
+     * PDF documents loaded by PDFBox, whose pages are rendered for OCR, and the input streams
+     * that are not files already.
+     * Default: false
+     */
+    public void setOcrSpillToDisk(boolean ocrSpillToDisk) {
+        this.ocrSpillToDisk = ocrSpillToDisk;
+    }
+
+    public boolean isExcludeHiddenText() {
+        return excludeHiddenText;
+    }
+
+    /**
+     * Sets whether the text a reader can not see is dropped from PDF and HTML documents, see
+     * {@link HiddenTextFilter}.
+     * Default: false
+     */
+    public void setExcludeHiddenText(boolean excludeHiddenText) {
+        this.excludeHiddenText = excludeHiddenText;
+    }
+
+    public long getSoftTimeBudgetMillis() {
+        return softTimeBudgetMillis;
+    }
+
+    /**
+     * Sets the time after which the parse stops and returns the content gathered so far, see
+     * {@link TimeBudget}.
+     * Default: 0, no budget
+     */
+    public void setSoftTimeBudgetMillis(long softTimeBudgetMillis) {
+        this.softTimeBudgetMillis = softTimeBudgetMillis;
+    }
+
+    public boolean isTrustDeclaredCharset() {
+        return trustDeclaredCharset;
+    }
+
+    /**
+     * Sets whether the charset a document declares in its content, such as the meta charset of
+     * HTML, is used to decode it. When false it is ignored, see {@link DeclaredCharsetFilter}.
+     * Default: true, the Tika behavior
+     */
+    public void setTrustDeclaredCharset(boolean trustDeclaredCharset) {
+        this.trustDeclaredCharset = trustDeclaredCharset;
+    }
+
+    public boolean isEncodingAuto() {
+        return encodingAuto;
+    }
+
+    /**
+     * Sets whether the charset of the text documents is detected with ICU, falling back to UTF-8
+     * when ambiguous, see {@link EncodingAutoDetect}.
+     * Default: false, the Tika encoding detectors
+     */
+    public void setEncodingAuto(boolean encodingAuto) {
+        this.encodingAuto = encodingAuto;
+    }
+
+    public boolean isDedupRepeatedLines() {
+        return dedupRepeatedLines;
+    }
+
+    /**
+     * Sets whether the end of every page is marked in the text output, see
+     * {@link PageBreakMarker}. The repeated lines are removed on the rust side.
+     * Default: false
+     */
+    public void setDedupRepeatedLines(boolean dedupRepeatedLines) {
+        this.dedupRepeatedLines = dedupRepeatedLines;
+    }
+
+    public int getStringStreamingThreshold() {
+        return stringStreamingThreshold;
+    }
+
+    /**
+     * Sets the size in UTF-8 bytes above which the content of the to-string parse methods is
+     * handed over in chunks of this size instead of a single String, see
+     * {@link ChunkedOutputStream}.
+     * Default: 0, never
+     */
+    public void setStringStreamingThreshold(int stringStreamingThreshold) {
+        this.stringStreamingThreshold = stringStreamingThreshold;
+    }
+
+    public boolean isRecordParserChain() {
+        return recordParserChain;
+    }
+
+    /**
+     * Sets whether the parsers each document went through are recorded in its metadata, see
+     * {@link ParserChain}.
+     * Default: false
+     */
+    public void setRecordParserChain(boolean recordParserChain) {
+        this.recordParserChain = recordParserChain;
+    }
+
+    public boolean isZipRecovery() {
+        return zipRecovery;
+    }
+
+    /**
+     * Sets whether the ZIP based documents whose central directory is corrupt or missing are
+     * parsed from the entries that can still be read, see {@link ZipRecovery}.
+     * Default: false
+     */
+    public void setZipRecovery(boolean zipRecovery) {
+        this.zipRecovery = zipRecovery;
+    }
+
+    public boolean isPreserveScripts() {
+        return preserveScripts;
+    }
+
+    /**
+     * Sets whether the superscripts and subscripts are kept in the text output, see
+     * {@link ScriptMarker}.
+     * Default: false
+     */
+    public void setPreserveScripts(boolean preserveScripts) {
+        this.preserveScripts = preserveScripts;
+    }
+
+    public String getUserAgent() {
+        return userAgent;
+    }
+
+    /**
+     * Sets the User-Agent header of the http and https requests of the url parse methods, see
+     * {@link UrlFetch}.
+     * Default: null, the default agent of the VM
+     */
+    public void setUserAgent(String userAgent) {
+        this.userAgent = userAgent;
+    }
+
+    public Set<String> getForceTextExtensions() {
+        return forceTextExtensions;
+    }
+
+    public Set<MediaType> getForceTextTypes() {
+        return forceTextTypes;
+    }
+
+    /**
+     * Adds a media type, such as application/x-foo, or a file extension, such as .dat or dat,
+     * whose documents are always parsed as plain text, see {@link ForcedTextDetector}. Media
+     * types are compared without their parameters and extensions ignoring the case.
+     * Default: none
+     */
+    public void addForceTextType(String type) {
+        final String trimmed = type.trim();
+        if (trimmed.contains("/")) {
+            final MediaType mediaType = MediaType.parse(trimmed);
+            if (mediaType != null) {
+                forceTextTypes.add(mediaType.getBaseType());
+            }
+        } else if (!trimmed.isEmpty()) {
+            final String extension = trimmed.startsWith(".") ? trimmed.substring(1) : trimmed;
+            forceTextExtensions.add(extension.toLowerCase(Locale.ROOT));
+        }
+    }
+
+    public List<String> getParserFallbacks() {
+        return parserFallbacks;
+    }
+
+    /**
+     * Adds a parser to try, after the previously added ones, on the documents the detected parser
+     * fails on, see {@link ParserFallbackChain}. The kinds are the names of the ParserKind rust
+     * enum.
+     * Default: none
+     */
+    public void addParserFallback(String kind) {
+        parserFallbacks.add(kind);
+    }
+
+    /**
+     * Applies the options that are PDF parser settings to the given config and returns it
+     */
+    public PDFParserConfig applyTo(PDFParserConfig pdfConfig) {
+        if (ocrSpillToDisk) {
+            // PDFBox keeps no more than this many bytes of the document in memory, 0 means
+            // the whole document goes to a temporary file
+            pdfConfig.setMaxMainMemoryBytes(0);
+        }
+        return pdfConfig;
+    }
+
+    public String toString() {
+        return "maxElementDepth:" + this.maxElementDepth +
+                " ocrSpillToDisk: " + this.ocrSpillToDisk +
+                " excludeHiddenText: " + this.excludeHiddenText +
+                " softTimeBudgetMillis: " + this.softTimeBudgetMillis +
+                " trustDeclaredCharset: " + this.trustDeclaredCharset +
+                " dedupRepeatedLines: " + this.dedupRepeatedLines +
+                " stringStreamingThreshold: " + this.stringStreamingThreshold +
+                " recordParserChain: " + this.recordParserChain +
+                " zipRecovery: " + this.zipRecovery +
+                " preserveScripts: " + this.preserveScripts +
+                " userAgent: " + this.userAgent +
+                " forceTextExtensions: " + this.forceTextExtensions +
+                " forceTextTypes: " + this.forceTextTypes +
+                " parserFallbacks: " + this.parserFallbacks;
+    }
+}
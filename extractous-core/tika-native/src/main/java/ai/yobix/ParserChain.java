@@ -0,0 +1,102 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Set;
+
+/**
+ * Records the chain of parsers a document went through, from the AutoDetectParser down to the
+ * parser of its format, in the {@link #PARSER_CHAIN} metadata key. It is built from the
+ * X-TIKA:Parsed-By entries the composite parsers add as they pick a parser, without the wrappers
+ * the extractor adds around them, and with the fallback parser of {@link ParserFallbackChain}
+ * when one was used.
+ * <p>
+ * The chain is recorded as soon as the document starts producing content, the parsers are picked
+ * by then, so that the metadata returned by the stream methods before the end of the parse holds
+ * it too. It is recorded again once the parse is over, for the documents that produced no
+ * content or failed.
+ */
+public class ParserChain {
+
+    /**
+     * Metadata key of the class names of the parsers the document went through, outermost first
+     */
+    public static final String PARSER_CHAIN = "extractous:parser_chain";
+
+    private ParserChain() {
+    }
+
+    /**
+     * Returns a parser that records the parser chain of the documents it parses, embedded
+     * documents included, or the given parser when record is false
+     */
+    static Parser wrap(Parser parser, boolean record) {
+        if (!record) {
+            return parser;
+        }
+        return new RecordingParser(parser);
+    }
+
+    /**
+     * Whether the parser recorded by a composite parser is one the extractor wraps the Tika
+     * parsers in: the decorators nested in the ai.yobix classes, and the plain CompositeParser
+     * that hands SVG images to the {@link SvgParser}
+     */
+    private static boolean isWrapper(String className) {
+        return (className.startsWith("ai.yobix.") && className.contains("$"))
+                || className.equals(CompositeParser.class.getName());
+    }
+
+    private static void record(Parser parser, Metadata metadata) {
+        metadata.remove(PARSER_CHAIN);
+        metadata.add(PARSER_CHAIN, parser.getClass().getName());
+        final String[] parsedBy = metadata.getValues(TikaCoreProperties.TIKA_PARSED_BY);
+        for (String className : parsedBy) {
+            if (!isWrapper(className)) {
+                metadata.add(PARSER_CHAIN, className);
+            }
+        }
+    }
+
+    private static class RecordingParser extends ParserDecorator {
+
+        RecordingParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final Parser parser = getWrappedParser();
+            final ContentHandler recordingHandler = new ContentHandlerDecorator(handler) {
+                @Override
+                public void startDocument() throws SAXException {
+                    record(parser, metadata);
+                    super.startDocument();
+                }
+            };
+            try {
+                super.parse(stream, recordingHandler, metadata, context);
+            } finally {
+                record(parser, metadata);
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+package ai.yobix;
+
+import org.apache.tika.exception.EncryptedDocumentException;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.exception.ZeroByteFileException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.html.JSoupParser;
+import org.apache.tika.parser.txt.TXTParser;
+import org.apache.tika.parser.xml.XMLParser;
+import org.apache.tika.utils.ParserUtils;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Retries the documents the detected parser fails on with a chain of more lenient parsers, and
+ * keeps the output of the first one that succeeds. The output of each attempt is recorded and
+ * only handed to the content handler once the attempt succeeded, so that a failed parse leaves
+ * no partial content behind. The document is spooled to a temporary file, if it is not a file
+ * already, to be read again.
+ * <p>
+ * Only the failures of the parser trigger a fallback: encrypted and empty documents, and the
+ * documents parsed successfully without any content, are left as they are.
+ */
+public class ParserFallbackChain {
+
+    /**
+     * Metadata key of the fallback parser that produced the content, absent when the detected
+     * parser succeeded
+     */
+    public static final String FALLBACK_PARSER = "extractous:fallback_parser";
+
+    private ParserFallbackChain() {
+    }
+
+    /**
+     * Returns a parser that falls back on the parsers of the given kinds, in order, or the given
+     * parser when there are none
+     */
+    static Parser wrap(Parser parser, List<String> fallbacks) {
+        if (fallbacks.isEmpty()) {
+            return parser;
+        }
+        return new FallbackParser(parser, fallbacks);
+    }
+
+    /**
+     * Creates the parser of the given kind, the names of the ParserKind rust enum
+     */
+    static Parser newParser(String kind) {
+        switch (kind) {
+            case "PlainText":
+                return new TXTParser();
+            case "Html":
+                return new JSoupParser();
+            case "Xml":
+                return new XMLParser();
+            default:
+                throw new IllegalArgumentException("Unknown parser kind: " + kind);
+        }
+    }
+
+    private static boolean isRecoverable(Exception e) {
+        return !(e instanceof EncryptedDocumentException) && !(e instanceof ZeroByteFileException);
+    }
+
+    private static class FallbackParser extends ParserDecorator {
+
+        private final List<String> fallbacks;
+
+        FallbackParser(Parser parser, List<String> fallbacks) {
+            super(parser);
+            this.fallbacks = fallbacks;
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                final Path path = tis.getPath();
+
+                final Exception failure;
+                SaxRecorder recorder = new SaxRecorder();
+                try {
+                    super.parse(tis, recorder, metadata, context);
+                    recorder.replay(handler);
+                    return;
+                } catch (IOException | SAXException | TikaException e) {
+                    if (!isRecoverable(e)) {
+                        throw e;
+                    }
+                    failure = e;
+                }
+
+                for (String kind : fallbacks) {
+                    recorder = new SaxRecorder();
+                    final Parser fallback = newParser(kind);
+                    try (TikaInputStream retry = TikaInputStream.get(path)) {
+                        fallback.parse(retry, recorder, metadata, context);
+                    } catch (IOException | SAXException | TikaException e) {
+                        failure.addSuppressed(e);
+                        continue;
+                    }
+                    metadata.set(FALLBACK_PARSER, kind);
+                    // Listed after the detected parser that failed, as the composite parsers do
+                    ParserUtils.recordParserDetails(fallback, metadata);
+                    metadata.add(TikaCoreProperties.TIKA_META_EXCEPTION_WARNING, failure.toString());
+                    recorder.replay(handler);
+                    return;
+                }
+
+                if (failure instanceof IOException) {
+                    throw (IOException) failure;
+                }
+                if (failure instanceof SAXException) {
+                    throw (SAXException) failure;
+                }
+                throw (TikaException) failure;
+            }
+        }
+    }
+}
@@ -21,16 +21,19 @@ public class ParsingReader extends Reader {
     private final Metadata metadata;
     private final ParseContext context;
     private final boolean outputXml;
+    private final boolean preserveScripts;
     private final String encoding;
     private transient Throwable throwable;
 
     public ParsingReader(Parser parser, InputStream stream, Metadata metadata,
-                            ParseContext context, boolean outputXml, String encoding) throws IOException {
+                            ParseContext context, boolean outputXml, boolean preserveScripts,
+                            String encoding) throws IOException {
         this.parser = parser;
         this.stream = stream;
         this.metadata = metadata;
         this.context = context;
         this.outputXml = outputXml;
+        this.preserveScripts = preserveScripts;
         this.encoding = encoding;
 
         PipedInputStream pipedInputStream = new PipedInputStream();
@@ -84,6 +87,9 @@ public class ParsingReader extends Reader {
                     // BodyContentHandler in Tika 3.x requires Writer, not OutputStream
                     Writer writer = new OutputStreamWriter(pipedOutputStream, encoding);
                     handler = new BodyContentHandler(writer);
+                    if (preserveScripts) {
+                        handler = new ScriptMarker(handler);
+                    }
                 }
                 parser.parse(stream, handler, metadata, context);
             } catch (Throwable t) {
@@ -0,0 +1,111 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDDocumentCatalog;
+import org.apache.pdfbox.pdmodel.common.PDMetadata;
+import org.apache.pdfbox.pdmodel.documentinterchange.logicalstructure.PDMarkInfo;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.utils.XMLReaderUtils;
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.NodeList;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * The PDF/A conformance and the accessibility tagging of a PDF document, as declared in its
+ * document catalog and in the XMP metadata the catalog points to. Nothing is validated: a
+ * document declaring PDF/A conformance may well not conform.
+ */
+public class PdfConformance {
+
+    private static final String PDFAID_NS = "http://www.aiim.org/pdfa/ns/id/";
+
+    private final String pdfaLevel;
+    private final boolean tagged;
+    private final boolean structureTree;
+
+    public PdfConformance(String pdfaLevel, boolean tagged, boolean structureTree) {
+        this.pdfaLevel = pdfaLevel;
+        this.tagged = tagged;
+        this.structureTree = structureTree;
+    }
+
+    static PdfConformance read(PDDocument document) {
+        final PDDocumentCatalog catalog = document.getDocumentCatalog();
+        final PDMarkInfo markInfo = catalog.getMarkInfo();
+        return new PdfConformance(
+                pdfaLevel(catalog.getMetadata()),
+                markInfo != null && markInfo.isMarked(),
+                catalog.getStructureTreeRoot() != null);
+    }
+
+    /**
+     * Returns the part of the PDF/A standard followed by the conformance level, as the pdfaid
+     * properties of the XMP metadata record them, e.g. 1B, 2U or 4, or null if the metadata
+     * declares no PDF/A part or can not be read
+     */
+    private static String pdfaLevel(PDMetadata metadata) {
+        if (metadata == null) {
+            return null;
+        }
+        final Document xmp;
+        try (InputStream stream = metadata.exportXMPMetadata()) {
+            xmp = XMLReaderUtils.buildDOM(stream);
+        } catch (IOException | SAXException | TikaException e) {
+            return null;
+        }
+        final String part = pdfaidProperty(xmp, "part");
+        if (part == null) {
+            return null;
+        }
+        final String conformance = pdfaidProperty(xmp, "conformance");
+        return conformance != null ? part + conformance.toUpperCase() : part;
+    }
+
+    // XMP properties are written either as elements or as attributes of rdf:Description
+    private static String pdfaidProperty(Document xmp, String name) {
+        final NodeList elements = xmp.getElementsByTagNameNS(PDFAID_NS, name);
+        if (elements.getLength() > 0) {
+            final String value = elements.item(0).getTextContent().trim();
+            return value.isEmpty() ? null : value;
+        }
+        final NodeList all = xmp.getElementsByTagNameNS("*", "*");
+        for (int i = 0; i < all.getLength(); i++) {
+            final String value = ((Element) all.item(i)).getAttributeNS(PDFAID_NS, name).trim();
+            if (!value.isEmpty()) {
+                return value;
+            }
+        }
+        return null;
+    }
+
+    /**
+     * Returns the declared PDF/A part and conformance level, e.g. 2B, or null if the document
+     * declares none
+     */
+    public String getPdfaLevel() {
+        return pdfaLevel;
+    }
+
+    /**
+     * Returns true if the MarkInfo dictionary of the catalog marks the document as tagged
+     */
+    public boolean isTagged() {
+        return tagged;
+    }
+
+    /**
+     * Returns true if the catalog has a structure tree root
+     */
+    public boolean hasStructureTree() {
+        return structureTree;
+    }
+
+    public String toString() {
+        return "pdfaLevel:" + this.pdfaLevel + " tagged: " + this.tagged +
+                " structureTree: " + this.structureTree;
+    }
+}
@@ -0,0 +1,57 @@
+package ai.yobix;
+
+public class PdfConformanceResult {
+
+    private final PdfConformance conformance;
+    private final byte status;
+    private final String errorMessage;
+
+    public PdfConformanceResult(PdfConformance conformance) {
+        this.conformance = conformance;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public PdfConformanceResult(byte status, String errorMessage) {
+        this.conformance = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the conformance information of the document
+     * @return PdfConformance or null if there is an error
+     */
+    public PdfConformance getConformance() {
+        return conformance;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " conformance: " + this.conformance;
+    }
+}
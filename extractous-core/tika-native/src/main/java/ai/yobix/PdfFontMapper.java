@@ -0,0 +1,228 @@
+package ai.yobix;
+
+import org.apache.fontbox.FontBoxFont;
+import org.apache.fontbox.ttf.CmapLookup;
+import org.apache.fontbox.ttf.NamingTable;
+import org.apache.fontbox.ttf.OTFParser;
+import org.apache.fontbox.ttf.OpenTypeFont;
+import org.apache.fontbox.ttf.TTFParser;
+import org.apache.fontbox.ttf.TrueTypeCollection;
+import org.apache.fontbox.ttf.TrueTypeFont;
+import org.apache.pdfbox.io.RandomAccessReadBufferedFile;
+import org.apache.pdfbox.pdmodel.font.CIDFontMapping;
+import org.apache.pdfbox.pdmodel.font.FontMapper;
+import org.apache.pdfbox.pdmodel.font.FontMapping;
+import org.apache.pdfbox.pdmodel.font.FontMappers;
+import org.apache.pdfbox.pdmodel.font.PDCIDSystemInfo;
+import org.apache.pdfbox.pdmodel.font.PDFontDescriptor;
+
+import java.io.IOException;
+import java.io.UncheckedIOException;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.util.ArrayList;
+import java.util.HashSet;
+import java.util.List;
+import java.util.Locale;
+import java.util.Map;
+import java.util.Set;
+import java.util.stream.Stream;
+
+/**
+ * Font mapper that gives PDFBox the fonts of user supplied directories for the fonts a PDF
+ * document uses without embedding them. A font of the directories is used when its PostScript
+ * or family name matches the requested one. For CID fonts, the CJK fonts in particular, whose
+ * name often does not match any installed font, a font of the directories covering the script of
+ * the requested character collection is preferred over the last resort fallback of PDFBox.
+ * Other fonts are mapped by the default PDFBox mapper.
+ * PDFBox has a single, process wide, font mapper: once registered, the fonts of a directory are
+ * used by every parse.
+ */
+public class PdfFontMapper implements FontMapper {
+
+    // A character of each CID character collection, to find the fonts covering its script
+    private static final Map<String, Integer> ORDERING_SAMPLES = Map.of(
+            "GB1", 0x4EEC,     // 们
+            "CNS1", 0x5011,    // 們
+            "Japan1", 0x3042,  // あ
+            "Korea1", 0xD55C   // 한
+    );
+
+    // Font files are looked for this deep in the registered directories
+    private static final int MAX_DIR_DEPTH = 8;
+
+    private static final Set<Path> registeredDirs = new HashSet<>();
+    private static volatile List<DirectoryFont> fonts = List.of();
+
+    private final FontMapper defaultMapper;
+
+    private PdfFontMapper(FontMapper defaultMapper) {
+        this.defaultMapper = defaultMapper;
+    }
+
+    /**
+     * Loads the TrueType and OpenType fonts (.ttf, .otf, .ttc, .otc) of the directory and its
+     * subdirectories, and installs the mapper on the first call. A directory is loaded once,
+     * later calls with the same directory do nothing. Files that can not be read or are not
+     * valid fonts are skipped, a directory that can not be read yields no fonts.
+     */
+    public static synchronized void registerFontDir(String dir) {
+        final Path path = Paths.get(dir).toAbsolutePath().normalize();
+        if (!registeredDirs.add(path)) {
+            return;
+        }
+
+        final List<DirectoryFont> loaded = new ArrayList<>(fonts);
+        try (Stream<Path> files = Files.walk(path, MAX_DIR_DEPTH)) {
+            files.filter(Files::isRegularFile).forEach(file -> loadFonts(file, loaded));
+        } catch (IOException | UncheckedIOException e) {
+            // Keep the fonts loaded before the unreadable entry
+        }
+        fonts = List.copyOf(loaded);
+
+        final FontMapper current = FontMappers.instance();
+        if (!(current instanceof PdfFontMapper)) {
+            FontMappers.set(new PdfFontMapper(current));
+        }
+    }
+
+    private static void loadFonts(Path file, List<DirectoryFont> loaded) {
+        final String name = file.getFileName().toString().toLowerCase(Locale.ROOT);
+        try {
+            if (name.endsWith(".ttc") || name.endsWith(".otc")) {
+                // The collection stays open, its fonts read their tables from it on demand
+                final TrueTypeCollection collection = new TrueTypeCollection(file.toFile());
+                collection.processAllFonts(font -> addFont(font, loaded));
+            } else if (name.endsWith(".otf")) {
+                addFont(new OTFParser().parse(new RandomAccessReadBufferedFile(file.toFile())), loaded);
+            } else if (name.endsWith(".ttf")) {
+                addFont(new TTFParser().parse(new RandomAccessReadBufferedFile(file.toFile())), loaded);
+            }
+        } catch (IOException | RuntimeException e) {
+            // Not a font PDFBox can read
+        }
+    }
+
+    private static void addFont(TrueTypeFont font, List<DirectoryFont> loaded) throws IOException {
+        final Set<String> names = new HashSet<>();
+        addName(names, font.getName());
+        final NamingTable naming = font.getNaming();
+        if (naming != null) {
+            addName(names, naming.getPostScriptName());
+            addName(names, naming.getFontFamily());
+        }
+
+        final Set<String> orderings = new HashSet<>();
+        final CmapLookup cmap = font.getUnicodeCmapLookup(false);
+        if (cmap != null) {
+            for (Map.Entry<String, Integer> sample : ORDERING_SAMPLES.entrySet()) {
+                if (cmap.getGlyphId(sample.getValue()) > 0) {
+                    orderings.add(sample.getKey());
+                }
+            }
+        }
+        loaded.add(new DirectoryFont(font, names, orderings));
+    }
+
+    private static void addName(Set<String> names, String name) {
+        if (name != null && !name.isBlank()) {
+            names.add(normalize(name));
+        }
+    }
+
+    /**
+     * Lowercases the name and drops the separators, which PDF writers and font files do not use
+     * consistently: "Noto Sans SC", "NotoSansSC" and "Noto-Sans-SC" are the same name
+     */
+    private static String normalize(String name) {
+        return name.toLowerCase(Locale.ROOT).replaceAll("[\\s,_-]", "");
+    }
+
+    /**
+     * Returns the font of the directories named like the base font, with its style suffix
+     * ("SimSun,Bold", "Arial-BoldMT") or without it
+     */
+    private static TrueTypeFont findByName(String baseFont, boolean allowPostScript) {
+        if (baseFont == null) {
+            return null;
+        }
+        // Subset fonts are prefixed with a tag such as "ABCDEF+"
+        final int plus = baseFont.indexOf('+');
+        final String name = plus == 6 ? baseFont.substring(plus + 1) : baseFont;
+        final String[] candidates = {name, name.split("[,-]", 2)[0]};
+        for (String candidate : candidates) {
+            final String normalized = normalize(candidate);
+            for (DirectoryFont font : fonts) {
+                if (font.names.contains(normalized) && (allowPostScript || !font.isPostScript())) {
+                    return font.font;
+                }
+            }
+        }
+        return null;
+    }
+
+    private static TrueTypeFont findByOrdering(String ordering) {
+        for (DirectoryFont font : fonts) {
+            if (font.orderings.contains(ordering)) {
+                return font.font;
+            }
+        }
+        return null;
+    }
+
+    private static CIDFontMapping cidMapping(TrueTypeFont font, boolean isFallback) {
+        if (font instanceof OpenTypeFont && ((OpenTypeFont) font).isPostScript()) {
+            return new CIDFontMapping((OpenTypeFont) font, null, isFallback);
+        }
+        return new CIDFontMapping(null, font, isFallback);
+    }
+
+    @Override
+    public FontMapping<TrueTypeFont> getTrueTypeFont(String baseFont, PDFontDescriptor fontDescriptor) {
+        final TrueTypeFont font = findByName(baseFont, false);
+        if (font != null) {
+            return new FontMapping<>(font, false);
+        }
+        return defaultMapper.getTrueTypeFont(baseFont, fontDescriptor);
+    }
+
+    @Override
+    public FontMapping<FontBoxFont> getFontBoxFont(String baseFont, PDFontDescriptor fontDescriptor) {
+        final TrueTypeFont font = findByName(baseFont, true);
+        if (font != null) {
+            return new FontMapping<>(font, false);
+        }
+        return defaultMapper.getFontBoxFont(baseFont, fontDescriptor);
+    }
+
+    @Override
+    public CIDFontMapping getCIDFont(String baseFont, PDFontDescriptor fontDescriptor, PDCIDSystemInfo cidSystemInfo) {
+        final TrueTypeFont font = findByName(baseFont, true);
+        if (font != null) {
+            return cidMapping(font, false);
+        }
+        final CIDFontMapping mapping = defaultMapper.getCIDFont(baseFont, fontDescriptor, cidSystemInfo);
+        if (!mapping.isFallback() || cidSystemInfo == null) {
+            return mapping;
+        }
+        final TrueTypeFont scriptFont = findByOrdering(cidSystemInfo.getOrdering());
+        return scriptFont != null ? cidMapping(scriptFont, true) : mapping;
+    }
+
+    private static class DirectoryFont {
+        final TrueTypeFont font;
+        final Set<String> names;
+        final Set<String> orderings;
+
+        DirectoryFont(TrueTypeFont font, Set<String> names, Set<String> orderings) {
+            this.font = font;
+            this.names = names;
+            this.orderings = orderings;
+        }
+
+        boolean isPostScript() {
+            return font instanceof OpenTypeFont && ((OpenTypeFont) font).isPostScript();
+        }
+    }
+}
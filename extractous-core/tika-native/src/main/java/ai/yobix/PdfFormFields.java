@@ -0,0 +1,141 @@
+package ai.yobix;
+
+import org.apache.pdfbox.Loader;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.interactive.form.PDAcroForm;
+import org.apache.pdfbox.pdmodel.interactive.form.PDButton;
+import org.apache.pdfbox.pdmodel.interactive.form.PDChoice;
+import org.apache.pdfbox.pdmodel.interactive.form.PDField;
+import org.apache.pdfbox.pdmodel.interactive.form.PDSignatureField;
+import org.apache.pdfbox.pdmodel.interactive.form.PDTerminalField;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.PDF;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.List;
+import java.util.Set;
+
+/**
+ * Adds the values of the AcroForm fields of PDF documents to their metadata, keyed by the fully
+ * qualified name of each field, when the PDF config of the parse context extracts the AcroForm
+ * content. The PDF parser only writes the fields to the content, as name and value text. The
+ * document is opened a second time for it, and only when the PDF parser reports form fields.
+ * Check boxes and radio buttons come with their export values, "Off" when they are not set,
+ * and the list boxes with one value per selected option. Signature fields and push buttons,
+ * which have no value, are left out.
+ */
+public class PdfFormFields {
+
+    /**
+     * Prefix of the metadata keys of the form fields, followed by the fully qualified name of
+     * the field, such as "extractous:form_field:applicant.name"
+     */
+    public static final String FORM_FIELD_PREFIX = "extractous:form_field:";
+
+    private static final Set<MediaType> PDF_TYPES = Set.of(MediaType.application("pdf"));
+
+    private PdfFormFields() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the form fields of PDF
+     * documents are added to their metadata
+     */
+    static Parser wrap(Parser parser, MediaTypeRegistry registry) {
+        // Later parsers take precedence, so the PDF documents are taken over by the decorator
+        return new CompositeParser(registry, parser, new FormFieldParser(parser));
+    }
+
+    private static class FormFieldParser extends ParserDecorator {
+
+        FormFieldParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return PDF_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final PDFParserConfig config = context.get(PDFParserConfig.class);
+            if (config != null && !config.getExtractAcroFormContent()) {
+                super.parse(stream, handler, metadata, context);
+                return;
+            }
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                // The stream is consumed by the parse, the fields are read from the file after it
+                final Path path = tis.getPath();
+                super.parse(tis, handler, metadata, context);
+                if (Boolean.TRUE.equals(metadata.getBoolean(PDF.HAS_ACROFORM_FIELDS))) {
+                    final String password = config instanceof DocumentPasswords.PdfConfig
+                            ? ((DocumentPasswords.PdfConfig) config).getPassword()
+                            : null;
+                    addFields(path, password, metadata);
+                }
+            }
+        }
+    }
+
+    private static void addFields(Path path, String password, Metadata metadata) {
+        try (PDDocument document = password == null
+                ? Loader.loadPDF(path.toFile())
+                : Loader.loadPDF(path.toFile(), password)) {
+            final PDAcroForm acroForm = document.getDocumentCatalog().getAcroForm();
+            if (acroForm == null) {
+                return;
+            }
+            for (PDField field : acroForm.getFieldTree()) {
+                final String name = field.getFullyQualifiedName();
+                if (!(field instanceof PDTerminalField) || field instanceof PDSignatureField || name == null) {
+                    continue;
+                }
+                for (String value : values(field)) {
+                    metadata.add(FORM_FIELD_PREFIX + name, value);
+                }
+            }
+        } catch (IOException e) {
+            // The document was parsed already, only the form fields are missing from the metadata
+        }
+    }
+
+    private static List<String> values(PDField field) {
+        if (field instanceof PDChoice) {
+            return ((PDChoice) field).getValue();
+        }
+        if (field instanceof PDButton) {
+            // The state of a check box or radio button is the name of its "on" appearance, which
+            // is the index of the export value when the field lists them
+            String value = ((PDButton) field).getValue();
+            final List<String> exportValues = ((PDButton) field).getExportValues();
+            try {
+                final int index = Integer.parseInt(value);
+                if (index >= 0 && index < exportValues.size()) {
+                    value = exportValues.get(index);
+                }
+            } catch (NumberFormatException e) {
+                // The state is the export value itself
+            }
+            return value.isEmpty() ? List.of() : List.of(value);
+        }
+        final String value = field.getValueAsString();
+        return value == null || value.isEmpty() ? List.of() : List.of(value);
+    }
+}
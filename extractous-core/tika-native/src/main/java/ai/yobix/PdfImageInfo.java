@@ -0,0 +1,371 @@
+package ai.yobix;
+
+import org.apache.pdfbox.Loader;
+import org.apache.pdfbox.contentstream.PDFGraphicsStreamEngine;
+import org.apache.pdfbox.contentstream.operator.markedcontent.BeginMarkedContentSequence;
+import org.apache.pdfbox.contentstream.operator.markedcontent.BeginMarkedContentSequenceWithProperties;
+import org.apache.pdfbox.contentstream.operator.markedcontent.EndMarkedContentSequence;
+import org.apache.pdfbox.cos.COSBase;
+import org.apache.pdfbox.cos.COSDictionary;
+import org.apache.pdfbox.cos.COSInteger;
+import org.apache.pdfbox.cos.COSName;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.pdmodel.documentinterchange.logicalstructure.PDMarkedContentReference;
+import org.apache.pdfbox.pdmodel.documentinterchange.logicalstructure.PDStructureElement;
+import org.apache.pdfbox.pdmodel.documentinterchange.logicalstructure.PDStructureNode;
+import org.apache.pdfbox.pdmodel.documentinterchange.logicalstructure.PDStructureTreeRoot;
+import org.apache.pdfbox.pdmodel.graphics.image.PDImage;
+import org.apache.pdfbox.util.Matrix;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.awt.geom.Point2D;
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.ArrayDeque;
+import java.util.ArrayList;
+import java.util.Deque;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Locale;
+import java.util.Map;
+import java.util.Set;
+
+/**
+ * Describes the images drawn on the pages of PDF documents, when the PDF config of the parse
+ * context asks for it, see {@link DocumentPasswords.PdfConfig#setExtractInlineImageMetadata}.
+ * The document is opened a second time after the parse and its page content streams are run
+ * without rendering. The number of images is set on the metadata of the document, and in a
+ * recursive parse every image also becomes an embedded document without content, whose
+ * metadata holds its page, its position, its size in pixels, its type and its alternate
+ * description. The alternate description is the Alt, or failing that the ActualText, of the
+ * structure element, usually a Figure, that the marked content of the image belongs to.
+ */
+public class PdfImageInfo {
+
+    /**
+     * Metadata key of the document set to the number of images drawn on its pages, an image
+     * drawn several times is counted each time
+     */
+    public static final String IMAGE_COUNT = "extractous:pdf_image_count";
+    /**
+     * Metadata key of an image set to the page it is drawn on, 1 based
+     */
+    public static final String IMAGE_PAGE = "extractous:image_page";
+    /**
+     * Metadata key of an image set to the box it is drawn in on the page, as "x,y,width,height"
+     * in points from the bottom left corner of the page
+     */
+    public static final String IMAGE_BOX = "extractous:image_box";
+    /**
+     * Metadata key of an image set to its type, such as image/jpeg, the type of the data the
+     * image is stored as in the document
+     */
+    public static final String IMAGE_TYPE = "extractous:image_type";
+    /**
+     * Metadata key of an image set to its alternate description, when it has one
+     */
+    public static final String IMAGE_ALT = "extractous:image_alt";
+
+    private static final Set<MediaType> PDF_TYPES = Set.of(MediaType.application("pdf"));
+
+    private PdfImageInfo() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the images of PDF documents
+     * are described when the PDF config asks for it
+     */
+    static Parser wrap(Parser parser, MediaTypeRegistry registry) {
+        // Later parsers take precedence, so the PDF documents are taken over by the decorator
+        return new CompositeParser(registry, parser, new ImageInfoParser(parser));
+    }
+
+    private static class ImageInfoParser extends ParserDecorator {
+
+        ImageInfoParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return PDF_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final PDFParserConfig config = context.get(PDFParserConfig.class);
+            if (!(config instanceof DocumentPasswords.PdfConfig)
+                    || !((DocumentPasswords.PdfConfig) config).isExtractInlineImageMetadata()) {
+                super.parse(stream, handler, metadata, context);
+                return;
+            }
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                // The stream is consumed by the parse, the images are read from the file after it
+                final Path path = tis.getPath();
+                super.parse(tis, handler, metadata, context);
+
+                final String password = ((DocumentPasswords.PdfConfig) config).getPassword();
+                final List<Metadata> images = describeImages(path, password);
+                if (images == null) {
+                    return;
+                }
+                metadata.set(IMAGE_COUNT, Integer.toString(images.size()));
+                // Only the recursive parse keeps the embedded documents
+                final EmbeddedDocumentExtractor extractor = context.get(EmbeddedDocumentExtractor.class);
+                if (extractor instanceof RecursiveEmbeddedDocumentExtractor) {
+                    for (Metadata image : images) {
+                        if (!extractor.shouldParseEmbedded(image)) {
+                            continue;
+                        }
+                        // Without content nor name the image is detected as raw bytes, which no
+                        // parser reads
+                        try (TikaInputStream empty = TikaInputStream.get(new byte[0])) {
+                            extractor.parseEmbedded(empty, new DefaultHandler(), image, false);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /**
+     * Returns the metadata of every image drawn on the pages of the document, in page and
+     * drawing order, or null when the document can not be opened
+     */
+    private static List<Metadata> describeImages(Path path, String password) {
+        try (PDDocument document = password == null
+                ? Loader.loadPDF(path.toFile())
+                : Loader.loadPDF(path.toFile(), password)) {
+            final Map<MarkedContentKey, String> altTexts = new HashMap<>();
+            final PDStructureTreeRoot root = document.getDocumentCatalog().getStructureTreeRoot();
+            if (root != null) {
+                collectAltTexts(root, null, null, altTexts);
+            }
+            final List<Metadata> images = new ArrayList<>();
+            int pageNumber = 0;
+            for (PDPage page : document.getPages()) {
+                pageNumber++;
+                new ImageLocator(page, pageNumber, altTexts, images).processPage(page);
+            }
+            return images;
+        } catch (IOException e) {
+            // Encrypted or broken documents are reported by the PDF parser already
+            return null;
+        }
+    }
+
+    /**
+     * Maps the marked content of the structure elements with an alternate description to that
+     * description, the one of the innermost element that has one
+     */
+    private static void collectAltTexts(
+            PDStructureNode node, PDPage page, String altText, Map<MarkedContentKey, String> altTexts) {
+        for (Object kid : node.getKids()) {
+            if (kid instanceof PDStructureElement) {
+                final PDStructureElement element = (PDStructureElement) kid;
+                final PDPage elementPage = element.getPage() != null ? element.getPage() : page;
+                collectAltTexts(element, elementPage, altText(element, altText), altTexts);
+            } else if (altText == null) {
+                continue;
+            } else if (kid instanceof Integer && page != null) {
+                altTexts.put(new MarkedContentKey(page.getCOSObject(), (Integer) kid), altText);
+            } else if (kid instanceof PDMarkedContentReference) {
+                final PDMarkedContentReference reference = (PDMarkedContentReference) kid;
+                final PDPage referencePage = reference.getPage() != null ? reference.getPage() : page;
+                if (referencePage != null) {
+                    altTexts.put(new MarkedContentKey(referencePage.getCOSObject(), reference.getMCID()), altText);
+                }
+            }
+        }
+    }
+
+    private static String altText(PDStructureElement element, String inherited) {
+        final String alt = element.getAlternateDescription();
+        if (alt != null && !alt.isBlank()) {
+            return alt;
+        }
+        final String actualText = element.getActualText();
+        if (actualText != null && !actualText.isBlank()) {
+            return actualText;
+        }
+        return inherited;
+    }
+
+    private static String mimeType(PDImage image) {
+        final String suffix = image.getSuffix();
+        if (suffix == null) {
+            return "image/png";
+        }
+        switch (suffix.toLowerCase(Locale.ROOT)) {
+            case "jpg":
+                return "image/jpeg";
+            case "jpx":
+                return "image/jp2";
+            case "tiff":
+                return "image/tiff";
+            case "jb2":
+                return "image/x-jbig2";
+            default:
+                return "image/png";
+        }
+    }
+
+    private static final class MarkedContentKey {
+
+        private final COSDictionary page;
+        private final int mcid;
+
+        MarkedContentKey(COSDictionary page, int mcid) {
+            this.page = page;
+            this.mcid = mcid;
+        }
+
+        @Override
+        public boolean equals(Object other) {
+            return other instanceof MarkedContentKey
+                    && ((MarkedContentKey) other).page == page
+                    && ((MarkedContentKey) other).mcid == mcid;
+        }
+
+        @Override
+        public int hashCode() {
+            return System.identityHashCode(page) * 31 + mcid;
+        }
+    }
+
+    /**
+     * Runs the content stream of a page and records the images it draws, with the MCID of the
+     * marked content they are drawn in. Nothing is rendered, the paths are ignored.
+     */
+    private static class ImageLocator extends PDFGraphicsStreamEngine {
+
+        private final PDPage page;
+        private final int pageNumber;
+        private final Map<MarkedContentKey, String> altTexts;
+        private final List<Metadata> images;
+        // MCID of each open marked content sequence, -1 for the ones without
+        private final Deque<Integer> markedContent = new ArrayDeque<>();
+
+        ImageLocator(PDPage page, int pageNumber, Map<MarkedContentKey, String> altTexts, List<Metadata> images) {
+            super(page);
+            this.page = page;
+            this.pageNumber = pageNumber;
+            this.altTexts = altTexts;
+            this.images = images;
+            addOperator(new BeginMarkedContentSequence(this));
+            addOperator(new BeginMarkedContentSequenceWithProperties(this));
+            addOperator(new EndMarkedContentSequence(this));
+        }
+
+        @Override
+        public void beginMarkedContentSequence(COSName tag, COSDictionary properties) {
+            final COSBase mcid = properties == null ? null : properties.getDictionaryObject(COSName.MCID);
+            markedContent.push(mcid instanceof COSInteger ? ((COSInteger) mcid).intValue() : -1);
+        }
+
+        @Override
+        public void endMarkedContentSequence() {
+            markedContent.poll();
+        }
+
+        @Override
+        public void drawImage(PDImage image) {
+            final Matrix ctm = getGraphicsState().getCurrentTransformationMatrix();
+            final Metadata metadata = new Metadata();
+            metadata.set(TikaCoreProperties.EMBEDDED_RESOURCE_TYPE,
+                    TikaCoreProperties.EmbeddedResourceType.INLINE.toString());
+            metadata.set(IMAGE_PAGE, Integer.toString(pageNumber));
+            metadata.set(IMAGE_BOX, String.format(Locale.ROOT, "%.2f,%.2f,%.2f,%.2f",
+                    ctm.getTranslateX(), ctm.getTranslateY(), ctm.getScalingFactorX(), ctm.getScalingFactorY()));
+            metadata.set(Metadata.IMAGE_WIDTH, image.getWidth());
+            metadata.set(Metadata.IMAGE_LENGTH, image.getHeight());
+            metadata.set(IMAGE_TYPE, mimeType(image));
+            final String altText = altText();
+            if (altText != null) {
+                metadata.set(IMAGE_ALT, altText);
+            }
+            images.add(metadata);
+        }
+
+        /**
+         * Returns the alternate description of the innermost marked content with one
+         */
+        private String altText() {
+            for (int mcid : markedContent) {
+                final String altText = altTexts.get(new MarkedContentKey(page.getCOSObject(), mcid));
+                if (altText != null) {
+                    return altText;
+                }
+            }
+            return null;
+        }
+
+        @Override
+        public void appendRectangle(Point2D p0, Point2D p1, Point2D p2, Point2D p3) {
+        }
+
+        @Override
+        public void clip(int windingRule) {
+        }
+
+        @Override
+        public void moveTo(float x, float y) {
+        }
+
+        @Override
+        public void lineTo(float x, float y) {
+        }
+
+        @Override
+        public void curveTo(float x1, float y1, float x2, float y2, float x3, float y3) {
+        }
+
+        @Override
+        public Point2D getCurrentPoint() {
+            return new Point2D.Float();
+        }
+
+        @Override
+        public void closePath() {
+        }
+
+        @Override
+        public void endPath() {
+        }
+
+        @Override
+        public void strokePath() {
+        }
+
+        @Override
+        public void fillPath(int windingRule) {
+        }
+
+        @Override
+        public void fillAndStrokePath(int windingRule) {
+        }
+
+        @Override
+        public void shadingFill(COSName shadingName) {
+        }
+    }
+}
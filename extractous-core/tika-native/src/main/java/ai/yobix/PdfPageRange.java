@@ -0,0 +1,114 @@
+package ai.yobix;
+
+import org.apache.pdfbox.Loader;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.Set;
+
+/**
+ * Restricts the PDF parser to the page range of the PDF config of the parse context, see
+ * {@link DocumentPasswords.PdfConfig#setPageRange}. The Tika PDF parser has no such setting, so
+ * the pages outside of the range are removed and the document is written to a temporary file,
+ * which is then handed to the PDF parser. Only the page tree is read to do so, the content of
+ * the removed pages is never parsed.
+ */
+public class PdfPageRange {
+
+    private static final Set<MediaType> PDF_TYPES = Set.of(MediaType.application("pdf"));
+
+    private PdfPageRange() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the PDF documents are cut to
+     * the page range of the PDF config, when it has one
+     */
+    static Parser wrap(Parser parser, MediaTypeRegistry registry) {
+        // Later parsers take precedence, so the PDF documents are taken over by the decorator
+        return new CompositeParser(registry, parser, new PageRangeParser(parser));
+    }
+
+    private static class PageRangeParser extends ParserDecorator {
+
+        PageRangeParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return PDF_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final PDFParserConfig config = context.get(PDFParserConfig.class);
+            if (!(config instanceof DocumentPasswords.PdfConfig)
+                    || !((DocumentPasswords.PdfConfig) config).hasPageRange()) {
+                super.parse(stream, handler, metadata, context);
+                return;
+            }
+            final DocumentPasswords.PdfConfig pdfConfig = (DocumentPasswords.PdfConfig) config;
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                final Path cut = keepPages(tis.getPath(), pdfConfig, tmp);
+                if (cut == null) {
+                    super.parse(tis, handler, metadata, context);
+                    return;
+                }
+                try (TikaInputStream cutStream = TikaInputStream.get(cut)) {
+                    super.parse(cutStream, handler, metadata, context);
+                }
+            }
+        }
+    }
+
+    /**
+     * Writes the document with only the pages of the range to a temporary file, the range
+     * clamped to the pages of the document. Returns null when the range covers every page or the
+     * document can not be opened, so it is parsed as it is.
+     */
+    private static Path keepPages(Path path, DocumentPasswords.PdfConfig config, TemporaryResources tmp) {
+        final String password = config.getPassword();
+        try (PDDocument document = password == null
+                ? Loader.loadPDF(path.toFile())
+                : Loader.loadPDF(path.toFile(), password)) {
+            final int pageCount = document.getNumberOfPages();
+            // 0 based, inclusive
+            final int first = Math.max(config.getStartPage(), 1) - 1;
+            final int last = Math.min(config.getEndPage(), pageCount) - 1;
+            if (first == 0 && last == pageCount - 1) {
+                return null;
+            }
+            // From the end, so that the indexes of the pages left to remove do not move
+            for (int i = pageCount - 1; i >= 0; i--) {
+                if (i < first || i > last) {
+                    document.removePage(i);
+                }
+            }
+            document.setAllSecurityToBeRemoved(true);
+            final Path cut = tmp.createTempFile();
+            document.save(cut.toFile());
+            return cut;
+        } catch (IOException e) {
+            // Encrypted or broken documents are left to the PDF parser, which reports the problem
+            return null;
+        }
+    }
+}
@@ -0,0 +1,206 @@
+package ai.yobix;
+
+import org.apache.tika.mime.MediaType;
+
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Locale;
+
+/**
+ * Settings that control how embedded documents are walked during recursive parsing.
+ * The setters are called from the rust side, so make sure they are declared in the
+ * reachability-metadata.json jni section.
+ */
+public class RecursiveConfig {
+
+    private int maxContainerDepth = -1;
+    private boolean extractLeafResources = true;
+    private int perDocumentMaxLength = -1;
+    private int parallelism = 1;
+    private boolean recordContentDigests = false;
+    private long maxTotalOutputBytes = -1;
+    private int maxEmbeddedCount = -1;
+    private long maxEmbeddedBytes = -1;
+    // Lowercase base types, such as application/pdf, or wildcards, such as image/*
+    private final List<String> embeddedMimeFilter = new ArrayList<>();
+
+    public RecursiveConfig() {
+    }
+
+    /**
+     * Returns the maximum depth of embedded documents that are descended into.
+     * -1 means no limit
+     */
+    public int getMaxContainerDepth() {
+        return maxContainerDepth;
+    }
+
+    /**
+     * Sets the maximum depth of embedded documents that are descended into. Depth 1 are the
+     * documents directly embedded in the container, depth 2 the documents embedded in those, etc.
+     * Leaf resources (images) one level below this limit are still surfaced when
+     * extractLeafResources is true.
+     * Default: -1 (no limit)
+     */
+    public void setMaxContainerDepth(int maxContainerDepth) {
+        this.maxContainerDepth = maxContainerDepth;
+    }
+
+    public boolean isExtractLeafResources() {
+        return extractLeafResources;
+    }
+
+    /**
+     * Sets whether leaf resources such as embedded images are returned as embedded documents.
+     * Default: true
+     */
+    public void setExtractLeafResources(boolean extractLeafResources) {
+        this.extractLeafResources = extractLeafResources;
+    }
+
+    public int getPerDocumentMaxLength() {
+        return perDocumentMaxLength;
+    }
+
+    /**
+     * Sets the maximum length of the content kept for each document, the container included.
+     * Documents cut at this length are flagged with {@link LengthLimitedContentHandler#CONTENT_TRUNCATED}.
+     * Default: -1 (no limit)
+     */
+    public void setPerDocumentMaxLength(int perDocumentMaxLength) {
+        this.perDocumentMaxLength = perDocumentMaxLength;
+    }
+
+    public int getParallelism() {
+        return parallelism;
+    }
+
+    /**
+     * Sets how many of the documents directly embedded in the container are parsed at the same
+     * time, each with its own embedded documents, see {@link ParallelEmbeddedParser}.
+     * Default: 1 (sequential)
+     */
+    public void setParallelism(int parallelism) {
+        this.parallelism = parallelism;
+    }
+
+    public boolean isRecordContentDigests() {
+        return recordContentDigests;
+    }
+
+    /**
+     * Sets whether the length and SHA-256 digest of the raw bytes of every document are
+     * recorded in its metadata, see {@link ContentDigest}.
+     * Default: false
+     */
+    public void setRecordContentDigests(boolean recordContentDigests) {
+        this.recordContentDigests = recordContentDigests;
+    }
+
+    public long getMaxTotalOutputBytes() {
+        return maxTotalOutputBytes;
+    }
+
+    /**
+     * Sets the maximum total size of the content of all the documents, in UTF-8 bytes, see
+     * {@link OutputBudget}.
+     * Default: -1 (no limit)
+     */
+    public void setMaxTotalOutputBytes(long maxTotalOutputBytes) {
+        this.maxTotalOutputBytes = maxTotalOutputBytes;
+    }
+
+    public int getMaxEmbeddedCount() {
+        return maxEmbeddedCount;
+    }
+
+    /**
+     * Sets the maximum number of embedded documents that are parsed, see {@link EmbeddedLimits}.
+     * Default: -1 (no limit)
+     */
+    public void setMaxEmbeddedCount(int maxEmbeddedCount) {
+        this.maxEmbeddedCount = maxEmbeddedCount;
+    }
+
+    public long getMaxEmbeddedBytes() {
+        return maxEmbeddedBytes;
+    }
+
+    /**
+     * Sets the maximum total size of the raw bytes of the embedded documents, see
+     * {@link EmbeddedLimits}.
+     * Default: -1 (no limit)
+     */
+    public void setMaxEmbeddedBytes(long maxEmbeddedBytes) {
+        this.maxEmbeddedBytes = maxEmbeddedBytes;
+    }
+
+    public List<String> getEmbeddedMimeFilter() {
+        return embeddedMimeFilter;
+    }
+
+    /**
+     * Adds a media type, such as application/pdf, or a wildcard, such as image/*, to the types of
+     * the embedded documents that are parsed. Once a type is added, the embedded documents whose
+     * detected type matches none are skipped, together with the documents embedded in them.
+     * Types are compared without their parameters and ignoring the case.
+     * Default: none, every embedded document is parsed
+     */
+    public void addEmbeddedMimeType(String type) {
+        final int parameters = type.indexOf(';');
+        final String baseType = (parameters < 0 ? type : type.substring(0, parameters)).trim();
+        if (!baseType.isEmpty()) {
+            embeddedMimeFilter.add(baseType.toLowerCase(Locale.ROOT));
+        }
+    }
+
+    /**
+     * Returns whether an embedded document of the given detected type passes the MIME filter
+     */
+    boolean acceptsEmbeddedType(MediaType type) {
+        if (embeddedMimeFilter.isEmpty()) {
+            return true;
+        }
+        if (type == null) {
+            return false;
+        }
+        final String baseType = type.getBaseType().toString().toLowerCase(Locale.ROOT);
+        final String wildcard = type.getType().toLowerCase(Locale.ROOT) + "/*";
+        for (String accepted : embeddedMimeFilter) {
+            if (accepted.equals(baseType) || accepted.equals(wildcard) || accepted.equals("*/*")) {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    /**
+     * Returns the config to parse a document directly embedded in the container with its own
+     * embedded documents, as if it was the container: the depth limit is one level lower and
+     * its embedded documents are parsed sequentially
+     */
+    RecursiveConfig forEmbeddedSubtree() {
+        final RecursiveConfig subtree = new RecursiveConfig();
+        subtree.setMaxContainerDepth(maxContainerDepth < 0 ? -1 : maxContainerDepth - 1);
+        subtree.setExtractLeafResources(extractLeafResources);
+        subtree.setPerDocumentMaxLength(perDocumentMaxLength);
+        subtree.setRecordContentDigests(recordContentDigests);
+        subtree.setMaxTotalOutputBytes(maxTotalOutputBytes);
+        subtree.setMaxEmbeddedCount(maxEmbeddedCount);
+        subtree.setMaxEmbeddedBytes(maxEmbeddedBytes);
+        subtree.embeddedMimeFilter.addAll(embeddedMimeFilter);
+        return subtree;
+    }
+
+    public String toString() {
+        return "maxContainerDepth:" + this.maxContainerDepth +
+                " extractLeafResources: " + this.extractLeafResources +
+                " perDocumentMaxLength: " + this.perDocumentMaxLength +
+                " parallelism: " + this.parallelism +
+                " recordContentDigests: " + this.recordContentDigests +
+                " maxTotalOutputBytes: " + this.maxTotalOutputBytes +
+                " maxEmbeddedCount: " + this.maxEmbeddedCount +
+                " maxEmbeddedBytes: " + this.maxEmbeddedBytes +
+                " embeddedMimeFilter: " + this.embeddedMimeFilter;
+    }
+}
@@ -0,0 +1,108 @@
+package ai.yobix;
+
+import org.apache.tika.detect.Detector;
+import org.apache.tika.extractor.ParsingEmbeddedDocumentExtractor;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.commons.io.input.CloseShieldInputStream;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * Embedded document extractor used by the recursive parse. It keeps track of the current
+ * embedded depth and applies the {@link RecursiveConfig} rules before handing the embedded
+ * stream to the default parsing extractor.
+ */
+public class RecursiveEmbeddedDocumentExtractor extends ParsingEmbeddedDocumentExtractor {
+
+    private final RecursiveConfig config;
+    private final Detector detector;
+    // Parses the documents directly embedded in the container concurrently, null when sequential
+    private final ParallelEmbeddedParser parallelParser;
+    // Once the output budget ran out, the remaining embedded documents are skipped
+    private final OutputBudget outputBudget;
+    // Once the count or byte limit is reached, the remaining embedded documents are skipped
+    private final EmbeddedLimits embeddedLimits;
+
+    // Depth of the document whose embedded resources are currently being handled.
+    // 0 is the container itself. Parsing is single threaded, so a plain counter is enough
+    private int depth = 0;
+
+    public RecursiveEmbeddedDocumentExtractor(
+            ParseContext context, RecursiveConfig config, Detector detector, ParallelEmbeddedParser parallelParser,
+            OutputBudget outputBudget, EmbeddedLimits embeddedLimits) {
+        super(context);
+        this.config = config;
+        this.detector = detector;
+        this.parallelParser = parallelParser;
+        this.outputBudget = outputBudget;
+        this.embeddedLimits = embeddedLimits;
+    }
+
+    @Override
+    public void parseEmbedded(
+            InputStream stream, ContentHandler handler, Metadata metadata, boolean outputHtml)
+            throws SAXException, IOException {
+        if (outputBudget.isExhausted() || embeddedLimits.isExhausted()) {
+            return;
+        }
+        final int embeddedDepth = depth + 1;
+
+        try (TemporaryResources tmp = new TemporaryResources()) {
+            final TikaInputStream tis = TikaInputStream.get(
+                    embeddedLimits.wrap(CloseShieldInputStream.wrap(stream)), tmp, metadata);
+            final MediaType type = detector.detect(tis, metadata);
+
+            if (!shouldDescend(embeddedDepth, type) || !embeddedLimits.admit()) {
+                return;
+            }
+            if (config.isRecordContentDigests()) {
+                ContentDigest.record(tis, metadata);
+            }
+
+            // With a depth limit of 0 only the leaf resources are parsed, there is nothing below
+            // them to parse concurrently
+            final boolean defer = parallelParser != null && embeddedDepth == 1
+                    && config.getMaxContainerDepth() != 0;
+            depth = embeddedDepth;
+            try {
+                if (defer) {
+                    parallelParser.setDeferNext(true);
+                }
+                super.parseEmbedded(tis, handler, metadata, outputHtml);
+            } finally {
+                if (defer) {
+                    parallelParser.setDeferNext(false);
+                }
+                depth = embeddedDepth - 1;
+            }
+        }
+    }
+
+    private boolean shouldDescend(int embeddedDepth, MediaType type) {
+        if (!config.acceptsEmbeddedType(type)) {
+            return false;
+        }
+        final boolean isLeaf = isLeafResource(type);
+        if (isLeaf && !config.isExtractLeafResources()) {
+            return false;
+        }
+
+        final int maxDepth = config.getMaxContainerDepth();
+        if (maxDepth < 0 || embeddedDepth <= maxDepth) {
+            return true;
+        }
+        // Past the container limit only leaf resources of the last allowed level are surfaced
+        return isLeaf && embeddedDepth == maxDepth + 1;
+    }
+
+    private static boolean isLeafResource(MediaType type) {
+        return type != null && "image".equals(type.getType());
+    }
+}
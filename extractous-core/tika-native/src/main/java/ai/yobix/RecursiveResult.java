@@ -41,6 +41,7 @@ public class RecursiveResult {
      * 0: OK
      * 1: IOException
      * 2: TikaException
+     * 3: EncryptedDocumentException
      */
     public byte getStatus() {
         return status;
@@ -0,0 +1,42 @@
+package ai.yobix;
+
+/**
+ * A revision recorded in the history of an Office document.
+ */
+public class Revision {
+
+    private final String author;
+    private final String timestamp;
+    private final String location;
+
+    public Revision(String author, String timestamp, String location) {
+        this.author = author;
+        this.timestamp = timestamp;
+        this.location = location;
+    }
+
+    /**
+     * Returns the name of the user who made the revision, or null if it is not recorded
+     */
+    public String getAuthor() {
+        return author;
+    }
+
+    /**
+     * Returns the time of the revision as an ISO-8601 string, or null if it is not recorded
+     */
+    public String getTimestamp() {
+        return timestamp;
+    }
+
+    /**
+     * Returns the path the document was saved to, or null if it is not recorded
+     */
+    public String getLocation() {
+        return location;
+    }
+
+    public String toString() {
+        return "author:" + this.author + " timestamp: " + this.timestamp + " location: " + this.location;
+    }
+}
@@ -0,0 +1,108 @@
+package ai.yobix;
+
+import org.apache.poi.hwpf.HWPFDocument;
+import org.apache.poi.hwpf.model.SavedByEntry;
+import org.apache.poi.hwpf.model.SavedByTable;
+import org.apache.poi.openxml4j.exceptions.InvalidFormatException;
+import org.apache.poi.openxml4j.opc.OPCPackage;
+import org.apache.poi.openxml4j.opc.PackageAccess;
+import org.apache.poi.openxml4j.opc.PackagePart;
+import org.apache.poi.poifs.filesystem.FileMagic;
+import org.apache.poi.util.XMLHelper;
+import org.w3c.dom.Element;
+import org.w3c.dom.NodeList;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.ArrayList;
+import java.util.Collections;
+import java.util.List;
+
+/**
+ * Reads the revision history Office documents keep apart from their content:
+ * <ul>
+ * <li>the save history of Word 97-2003 documents, the user and the path of each save</li>
+ * <li>the revision headers of the shared workbooks of Excel, the user and the time of each
+ * revision</li>
+ * </ul>
+ * The other Office formats keep no such history and yield no revisions. Neither format records
+ * a comment with its revisions.
+ */
+class RevisionReader {
+
+    private static final String REVISION_HEADERS_CONTENT_TYPE =
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.revisionHeaders+xml";
+
+    private RevisionReader() {
+    }
+
+    /**
+     * Whether the media type is one of the Office formats read by POI, the binary and OOXML ones
+     */
+    static boolean isOfficeDocument(String mimeType) {
+        return "application/msword".equals(mimeType)
+                || mimeType.startsWith("application/vnd.ms-")
+                || mimeType.startsWith("application/vnd.openxmlformats-officedocument.");
+    }
+
+    static List<Revision> read(Path path, String mimeType)
+            throws IOException, InvalidFormatException, SAXException {
+        if (FileMagic.valueOf(path.toFile()) == FileMagic.OOXML) {
+            return readRevisionHeaders(path);
+        }
+        if ("application/msword".equals(mimeType)) {
+            return readSavedBy(path);
+        }
+        return Collections.emptyList();
+    }
+
+    private static List<Revision> readSavedBy(Path path) throws IOException {
+        final HWPFDocument document;
+        try (InputStream stream = Files.newInputStream(path)) {
+            document = new HWPFDocument(stream);
+        }
+        final SavedByTable table = document.getSavedByTable();
+        if (table == null) {
+            return Collections.emptyList();
+        }
+        final List<Revision> revisions = new ArrayList<>();
+        for (SavedByEntry entry : table.getEntries()) {
+            revisions.add(new Revision(
+                    emptyToNull(entry.getUserName()), null, emptyToNull(entry.getSaveLocation())));
+        }
+        return revisions;
+    }
+
+    private static List<Revision> readRevisionHeaders(Path path)
+            throws IOException, InvalidFormatException, SAXException {
+        final OPCPackage pkg = OPCPackage.open(path.toFile(), PackageAccess.READ);
+        try {
+            final List<Revision> revisions = new ArrayList<>();
+            for (PackagePart part : pkg.getPartsByContentType(REVISION_HEADERS_CONTENT_TYPE)) {
+                final NodeList headers;
+                try (InputStream stream = part.getInputStream()) {
+                    headers = XMLHelper.newDocumentBuilder().parse(stream)
+                            .getElementsByTagNameNS("*", "header");
+                }
+                for (int i = 0; i < headers.getLength(); i++) {
+                    final Element header = (Element) headers.item(i);
+                    revisions.add(new Revision(
+                            emptyToNull(header.getAttribute("userName")),
+                            emptyToNull(header.getAttribute("dateTime")),
+                            null));
+                }
+            }
+            return revisions;
+        } finally {
+            // Read only packages are closed without saving
+            pkg.revert();
+        }
+    }
+
+    private static String emptyToNull(String value) {
+        return value == null || value.trim().isEmpty() ? null : value.trim();
+    }
+}
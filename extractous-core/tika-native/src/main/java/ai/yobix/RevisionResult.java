@@ -0,0 +1,60 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class RevisionResult {
+
+    private final List<Revision> revisions;
+    private final byte status;
+    private final String errorMessage;
+
+    public RevisionResult(List<Revision> revisions) {
+        this.revisions = revisions;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public RevisionResult(byte status, String errorMessage) {
+        this.revisions = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the revisions of the document, in the order it records them.
+     * An array is returned to avoid JNI calls into java.util.List from native code
+     * @return Revision array or null if there is an error
+     */
+    public Revision[] getRevisions() {
+        return revisions != null ? revisions.toArray(new Revision[0]) : null;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " revisions: " + (revisions != null ? revisions.size() : 0);
+    }
+}
@@ -0,0 +1,88 @@
+package ai.yobix;
+
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.Locator;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.AttributesImpl;
+
+import java.util.ArrayList;
+import java.util.List;
+
+/**
+ * Records the SAX events of a parse to replay them later, so that the output of a parse that
+ * may fail is only handed to the content handler once it succeeded
+ */
+class SaxRecorder implements ContentHandler {
+
+    private interface Event {
+        void replay(ContentHandler handler) throws SAXException;
+    }
+
+    private final List<Event> events = new ArrayList<>();
+
+    void replay(ContentHandler handler) throws SAXException {
+        for (Event event : events) {
+            event.replay(handler);
+        }
+    }
+
+    @Override
+    public void setDocumentLocator(Locator locator) {
+    }
+
+    @Override
+    public void startDocument() {
+        events.add(ContentHandler::startDocument);
+    }
+
+    @Override
+    public void endDocument() {
+        events.add(ContentHandler::endDocument);
+    }
+
+    @Override
+    public void startPrefixMapping(String prefix, String uri) {
+        events.add(handler -> handler.startPrefixMapping(prefix, uri));
+    }
+
+    @Override
+    public void endPrefixMapping(String prefix) {
+        events.add(handler -> handler.endPrefixMapping(prefix));
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String qName, Attributes atts) {
+        final Attributes copy = new AttributesImpl(atts);
+        events.add(handler -> handler.startElement(uri, localName, qName, copy));
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String qName) {
+        events.add(handler -> handler.endElement(uri, localName, qName));
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) {
+        final char[] copy = new char[length];
+        System.arraycopy(ch, start, copy, 0, length);
+        events.add(handler -> handler.characters(copy, 0, copy.length));
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) {
+        final char[] copy = new char[length];
+        System.arraycopy(ch, start, copy, 0, length);
+        events.add(handler -> handler.ignorableWhitespace(copy, 0, copy.length));
+    }
+
+    @Override
+    public void processingInstruction(String target, String data) {
+        events.add(handler -> handler.processingInstruction(target, data));
+    }
+
+    @Override
+    public void skippedEntity(String name) {
+        events.add(handler -> handler.skippedEntity(name));
+    }
+}
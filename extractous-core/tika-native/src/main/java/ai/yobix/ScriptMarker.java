@@ -0,0 +1,153 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.html.DefaultHtmlMapper;
+import org.apache.tika.parser.html.HtmlMapper;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Locale;
+import java.util.Set;
+
+/**
+ * Keeps the superscripts and subscripts of the text output, the {@code <sup>} and {@code <sub>}
+ * elements of the XHTML output, which would otherwise flatten into inline text, H2O for H₂O. The
+ * text of such an element is written with the Unicode superscript or subscript characters when
+ * all of its characters have one, such as the digits, and between markers otherwise, ^{st} for a
+ * superscript and _{eq} for a subscript. The elements nested in a superscript or subscript are
+ * part of it.
+ * <p>
+ * The HTML parser drops the sup and sub elements of the documents by default, {@link #wrap}
+ * returns a parser that keeps them.
+ */
+public class ScriptMarker extends ContentHandlerDecorator {
+
+    private static final String SUPERSCRIPT_FROM = "0123456789+-−=()ni";
+    private static final String SUPERSCRIPT_TO = "⁰¹²³⁴⁵⁶⁷⁸⁹⁺⁻⁻⁼⁽⁾ⁿⁱ";
+    private static final String SUBSCRIPT_FROM = "0123456789+-−=()aehijklmnoprstuvx";
+    private static final String SUBSCRIPT_TO = "₀₁₂₃₄₅₆₇₈₉₊₋₋₌₍₎ₐₑₕᵢⱼₖₗₘₙₒₚᵣₛₜᵤᵥₓ";
+
+    // Keeps the sup and sub elements the default mapper drops
+    private static final HtmlMapper HTML_MAPPER = new DefaultHtmlMapper() {
+        @Override
+        public String mapSafeElement(String name) {
+            if ("sup".equalsIgnoreCase(name) || "sub".equalsIgnoreCase(name)) {
+                return name.toLowerCase(Locale.ROOT);
+            }
+            return super.mapSafeElement(name);
+        }
+    };
+
+    // The outermost superscript or subscript element, null outside of one
+    private String script = null;
+    // Depth of the elements open in it, itself included
+    private int depth = 0;
+    private final StringBuilder text = new StringBuilder();
+
+    public ScriptMarker(ContentHandler handler) {
+        super(handler);
+    }
+
+    /**
+     * Returns a parser that keeps the sup and sub elements of HTML documents, or the given parser
+     * when preserve is false
+     */
+    static Parser wrap(Parser parser, boolean preserve) {
+        if (!preserve) {
+            return parser;
+        }
+        return new HtmlScriptParser(parser);
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+        if (script != null) {
+            depth++;
+        } else if ("sup".equals(localName) || "sub".equals(localName)) {
+            script = localName;
+            depth = 1;
+        }
+        super.startElement(uri, localName, name, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String name) throws SAXException {
+        if (script != null && --depth == 0) {
+            final char[] mapped = map(text, "sup".equals(script)).toCharArray();
+            script = null;
+            text.setLength(0);
+            super.characters(mapped, 0, mapped.length);
+        }
+        super.endElement(uri, localName, name);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        if (script != null) {
+            text.append(ch, start, length);
+        } else {
+            super.characters(ch, start, length);
+        }
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        if (script != null) {
+            text.append(ch, start, length);
+        } else {
+            super.ignorableWhitespace(ch, start, length);
+        }
+    }
+
+    /**
+     * Returns the text with the Unicode superscript or subscript characters, or between markers
+     * when one of its characters, whitespace aside, has none
+     */
+    static String map(CharSequence text, boolean superscript) {
+        final String from = superscript ? SUPERSCRIPT_FROM : SUBSCRIPT_FROM;
+        final String to = superscript ? SUPERSCRIPT_TO : SUBSCRIPT_TO;
+        final StringBuilder mapped = new StringBuilder(text.length());
+        for (int i = 0; i < text.length(); i++) {
+            final char c = text.charAt(i);
+            final int index = from.indexOf(c);
+            if (index >= 0) {
+                mapped.append(to.charAt(index));
+            } else if (Character.isWhitespace(c)) {
+                mapped.append(c);
+            } else {
+                return (superscript ? "^{" : "_{") + text + "}";
+            }
+        }
+        return mapped.toString();
+    }
+
+    private static class HtmlScriptParser extends ParserDecorator {
+
+        HtmlScriptParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            if (context.get(HtmlMapper.class) == null) {
+                context.set(HtmlMapper.class, HTML_MAPPER);
+            }
+            super.parse(stream, handler, metadata, context);
+        }
+    }
+}
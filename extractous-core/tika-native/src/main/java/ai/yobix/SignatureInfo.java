@@ -0,0 +1,53 @@
+package ai.yobix;
+
+/**
+ * A single digital signature found in a document.
+ */
+public class SignatureInfo {
+
+    private final String signer;
+    private final String signedAt;
+    private final boolean verified;
+    private final boolean valid;
+
+    public SignatureInfo(String signer, String signedAt, boolean verified, boolean valid) {
+        this.signer = signer;
+        this.signedAt = signedAt;
+        this.verified = verified;
+        this.valid = valid;
+    }
+
+    /**
+     * Returns the name of the signer or null if it could not be determined
+     */
+    public String getSigner() {
+        return signer;
+    }
+
+    /**
+     * Returns the signing time as an ISO-8601 string or null if the signature has none
+     */
+    public String getSignedAt() {
+        return signedAt;
+    }
+
+    /**
+     * Returns true if the signature bytes were cryptographically checked against the
+     * signed content. The certificate chain is never verified
+     */
+    public boolean isVerified() {
+        return verified;
+    }
+
+    /**
+     * Returns the outcome of the check, only meaningful when isVerified() is true
+     */
+    public boolean isValid() {
+        return valid;
+    }
+
+    public String toString() {
+        return "signer:" + this.signer + " signedAt: " + this.signedAt +
+                " verified: " + this.verified + " valid: " + this.valid;
+    }
+}
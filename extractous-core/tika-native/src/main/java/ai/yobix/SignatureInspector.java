@@ -0,0 +1,166 @@
+package ai.yobix;
+
+import org.apache.pdfbox.Loader;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.interactive.digitalsignature.PDSignature;
+import org.apache.poi.openxml4j.exceptions.InvalidFormatException;
+import org.apache.poi.openxml4j.opc.OPCPackage;
+import org.apache.poi.openxml4j.opc.PackageAccess;
+import org.apache.poi.openxml4j.opc.PackagePart;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.utils.XMLReaderUtils;
+import org.bouncycastle.asn1.x500.RDN;
+import org.bouncycastle.asn1.x500.style.BCStyle;
+import org.bouncycastle.asn1.x500.style.IETFUtils;
+import org.bouncycastle.cert.X509CertificateHolder;
+import org.bouncycastle.cms.CMSProcessableByteArray;
+import org.bouncycastle.cms.CMSSignedData;
+import org.bouncycastle.cms.SignerInformation;
+import org.bouncycastle.cms.jcajce.JcaSimpleSignerInfoVerifierBuilder;
+import org.w3c.dom.Document;
+import org.w3c.dom.Element;
+import org.w3c.dom.NodeList;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.ArrayList;
+import java.util.Base64;
+import java.util.Calendar;
+import java.util.Collection;
+import java.util.List;
+
+/**
+ * Reads the digital signatures of PDF (PDFBox) and OOXML (POI OPC package) documents.
+ * PDF signatures are checked against the signed byte ranges, OOXML signatures are only
+ * reported. Certificate chains are never verified.
+ */
+class SignatureInspector {
+
+    private static final String OOXML_SIGNATURE_CONTENT_TYPE =
+            "application/vnd.openxmlformats-package.digital-signature-xmlsignature+xml";
+    private static final String XML_DSIG_NS = "http://www.w3.org/2000/09/xmldsig#";
+    private static final String OOXML_DSIG_NS = "http://schemas.openxmlformats.org/package/2006/digital-signature";
+
+    private SignatureInspector() {
+    }
+
+    /**
+     * Returns the signatures of the given file. Formats without signature support
+     * yield an empty list
+     *
+     * @param path     the path of the file
+     * @param mimeType the detected mime type of the file
+     */
+    static List<SignatureInfo> inspect(Path path, String mimeType) throws IOException, TikaException {
+        if ("application/pdf".equals(mimeType)) {
+            return inspectPdf(path);
+        }
+        if (mimeType.startsWith("application/vnd.openxmlformats-officedocument.")
+                || mimeType.endsWith(".macroenabled.12")) {
+            return inspectOoxml(path);
+        }
+        return new ArrayList<>();
+    }
+
+    private static List<SignatureInfo> inspectPdf(Path path) throws IOException {
+        final List<SignatureInfo> result = new ArrayList<>();
+        try (PDDocument document = Loader.loadPDF(path.toFile())) {
+            final List<PDSignature> signatures = document.getSignatureDictionaries();
+            if (signatures.isEmpty()) {
+                return result;
+            }
+
+            final byte[] fileBytes = Files.readAllBytes(path);
+            for (PDSignature signature : signatures) {
+                String signer = signature.getName();
+                final Calendar signDate = signature.getSignDate();
+                final String signedAt = signDate != null ? signDate.toInstant().toString() : null;
+
+                boolean verified = false;
+                boolean valid = false;
+                try {
+                    final byte[] contents = signature.getContents(fileBytes);
+                    final byte[] signedContent = signature.getSignedContent(fileBytes);
+                    final CMSSignedData cms = new CMSSignedData(new CMSProcessableByteArray(signedContent), contents);
+                    final SignerInformation signerInfo = cms.getSignerInfos().getSigners().iterator().next();
+                    @SuppressWarnings("unchecked")
+                    final Collection<X509CertificateHolder> certs = cms.getCertificates().getMatches(signerInfo.getSID());
+                    if (!certs.isEmpty()) {
+                        final X509CertificateHolder cert = certs.iterator().next();
+                        if (signer == null) {
+                            signer = commonName(cert);
+                        }
+                        // Only detached signatures cover the signed byte ranges directly
+                        if (isDetached(signature.getSubFilter())) {
+                            valid = signerInfo.verify(new JcaSimpleSignerInfoVerifierBuilder().build(cert));
+                            verified = true;
+                        }
+                    }
+                } catch (Exception e) {
+                    // Malformed or unsupported CMS content, report the signature as unverified
+                }
+
+                result.add(new SignatureInfo(signer, signedAt, verified, valid));
+            }
+        }
+        return result;
+    }
+
+    private static List<SignatureInfo> inspectOoxml(Path path) throws IOException, TikaException {
+        final List<SignatureInfo> result = new ArrayList<>();
+        try (OPCPackage pkg = OPCPackage.open(path.toFile(), PackageAccess.READ)) {
+            for (PackagePart part : pkg.getPartsByContentType(OOXML_SIGNATURE_CONTENT_TYPE)) {
+                final Document doc;
+                try (InputStream stream = part.getInputStream()) {
+                    doc = XMLReaderUtils.buildDOM(stream);
+                } catch (SAXException e) {
+                    throw new TikaException("Invalid signature part " + part.getPartName(), e);
+                }
+
+                String signer = null;
+                final String certText = firstText(doc.getElementsByTagNameNS(XML_DSIG_NS, "X509Certificate"));
+                if (certText != null) {
+                    try {
+                        signer = commonName(new X509CertificateHolder(Base64.getMimeDecoder().decode(certText)));
+                    } catch (IOException | IllegalArgumentException e) {
+                        // Unreadable certificate, keep the signer unknown
+                    }
+                }
+
+                String signedAt = null;
+                final NodeList times = doc.getElementsByTagNameNS(OOXML_DSIG_NS, "SignatureTime");
+                if (times.getLength() > 0) {
+                    signedAt = firstText(((Element) times.item(0)).getElementsByTagNameNS(OOXML_DSIG_NS, "Value"));
+                }
+
+                result.add(new SignatureInfo(signer, signedAt, false, false));
+            }
+        } catch (InvalidFormatException e) {
+            throw new TikaException("Invalid OOXML package: " + e.getMessage(), e);
+        }
+        return result;
+    }
+
+    private static boolean isDetached(String subFilter) {
+        return "adbe.pkcs7.detached".equals(subFilter) || "ETSI.CAdES.detached".equals(subFilter);
+    }
+
+    private static String commonName(X509CertificateHolder cert) {
+        final RDN[] cn = cert.getSubject().getRDNs(BCStyle.CN);
+        if (cn.length > 0) {
+            return IETFUtils.valueToString(cn[0].getFirst().getValue());
+        }
+        return cert.getSubject().toString();
+    }
+
+    private static String firstText(NodeList nodes) {
+        if (nodes.getLength() == 0) {
+            return null;
+        }
+        final String text = nodes.item(0).getTextContent();
+        return text != null ? text.trim() : null;
+    }
+}
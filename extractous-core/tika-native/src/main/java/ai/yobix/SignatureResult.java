@@ -0,0 +1,60 @@
+package ai.yobix;
+
+import java.util.List;
+
+public class SignatureResult {
+
+    private final List<SignatureInfo> signatures;
+    private final byte status;
+    private final String errorMessage;
+
+    public SignatureResult(List<SignatureInfo> signatures) {
+        this.signatures = signatures;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public SignatureResult(byte status, String errorMessage) {
+        this.signatures = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the signatures found in the document, empty if the document is not signed.
+     * An array is returned to avoid JNI calls into java.util.List from native code
+     * @return SignatureInfo array or null if there is an error
+     */
+    public SignatureInfo[] getSignatures() {
+        return signatures != null ? signatures.toArray(new SignatureInfo[0]) : null;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " signatures: " + (signatures != null ? signatures.size() : 0);
+    }
+}
@@ -5,12 +5,14 @@ import org.apache.tika.metadata.Metadata;
 public class StringResult {
 
     private final String content;
+    private final ChunkedOutputStream chunks;
     private final byte status;
     private final String errorMessage;
     private final Metadata metadata;
 
     public StringResult(String content) {
         this.content = content;
+        this.chunks = null;
         this.status = 0;
         this.errorMessage = null;
         this.metadata = null;
@@ -18,6 +20,18 @@ public class StringResult {
 
     public StringResult(String content, Metadata metadata) {
         this.content = content;
+        this.chunks = null;
+        this.status = 0;
+        this.errorMessage = null;
+        this.metadata = metadata;
+    }
+
+    /**
+     * A result whose content is handed over in UTF-8 chunks, see {@link ChunkedOutputStream}
+     */
+    public StringResult(ChunkedOutputStream chunks, Metadata metadata) {
+        this.content = null;
+        this.chunks = chunks;
         this.status = 0;
         this.errorMessage = null;
         this.metadata = metadata;
@@ -25,6 +39,7 @@ public class StringResult {
 
     public StringResult(byte status, String errorMessage) {
         this.content = null;
+        this.chunks = null;
         this.status = status;
         this.errorMessage = errorMessage;
         this.metadata = null;
@@ -38,6 +53,24 @@ public class StringResult {
         return content;
     }
 
+    /**
+     * Returns whether the content is in chunks rather than a String
+     */
+    public boolean isChunked() {
+        return chunks != null;
+    }
+
+    public int getChunkCount() {
+        return chunks.getChunkCount();
+    }
+
+    /**
+     * Returns the UTF-8 bytes of the chunk at the given index and releases it
+     */
+    public byte[] takeChunk(int index) {
+        return chunks.takeChunk(index);
+    }
+
     public boolean isError() {
         return status != 0;
     }
@@ -56,6 +89,7 @@ public class StringResult {
      * 0: OK
      * 1: IOException
      * 2: TikaException
+     * 3: EncryptedDocumentException
      */
     public byte getStatus() {
         return status;
@@ -0,0 +1,115 @@
+package ai.yobix;
+
+import org.apache.commons.io.input.CloseShieldInputStream;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.AbstractParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.sax.OfflineContentHandler;
+import org.apache.tika.sax.XHTMLContentHandler;
+import org.apache.tika.utils.XMLReaderUtils;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Set;
+
+/**
+ * Extracts the text of SVG images: the content of the {@code <text>} elements, with their
+ * {@code <tspan>} and {@code <textPath>} children, and the {@code <title>} and {@code <desc>}
+ * accessibility elements, in document order, one paragraph each. Styles, scripts and the other
+ * character data are left out. The title and description of the root element are also set as
+ * the dc:title and dc:description metadata.
+ * The document goes through the hardened SAX parser of {@link XMLReaderUtils}, which neither
+ * loads external entities nor expands entities without limit.
+ */
+public class SvgParser extends AbstractParser {
+
+    private static final Set<MediaType> SUPPORTED_TYPES = Set.of(MediaType.image("svg+xml"));
+
+    @Override
+    public Set<MediaType> getSupportedTypes(ParseContext context) {
+        return SUPPORTED_TYPES;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        final XHTMLContentHandler xhtml = new XHTMLContentHandler(handler, metadata);
+        xhtml.startDocument();
+        XMLReaderUtils.parseSAX(
+                CloseShieldInputStream.wrap(stream),
+                new OfflineContentHandler(new SvgHandler(xhtml, metadata)),
+                context);
+        xhtml.endDocument();
+    }
+
+    private static class SvgHandler extends DefaultHandler {
+
+        private final XHTMLContentHandler xhtml;
+        private final Metadata metadata;
+
+        // Depth of the current element, the root svg element is at depth 1
+        private int depth = 0;
+        // Depth of the text, title or desc element being collected, 0 when outside of any
+        private int collectDepth = 0;
+        private String collected;
+        private final StringBuilder buffer = new StringBuilder();
+
+        SvgHandler(XHTMLContentHandler xhtml, Metadata metadata) {
+            this.xhtml = xhtml;
+            this.metadata = metadata;
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String qName, Attributes atts) {
+            final String name = localName.isEmpty() ? qName : localName;
+            depth++;
+            if (collectDepth > 0) {
+                // Keep the chunks of consecutive tspan elements apart
+                if (name.equals("tspan") && buffer.length() > 0
+                        && !Character.isWhitespace(buffer.charAt(buffer.length() - 1))) {
+                    buffer.append(' ');
+                }
+                return;
+            }
+            if (name.equals("text") || name.equals("title") || name.equals("desc")) {
+                collectDepth = depth;
+                collected = name;
+                buffer.setLength(0);
+            }
+        }
+
+        @Override
+        public void endElement(String uri, String localName, String qName) throws SAXException {
+            if (depth == collectDepth) {
+                final String text = buffer.toString().replaceAll("\\s+", " ").trim();
+                if (!text.isEmpty()) {
+                    // Only the title and description of the whole image describe the document
+                    if (depth == 2 && collected.equals("title") && metadata.get(TikaCoreProperties.TITLE) == null) {
+                        metadata.set(TikaCoreProperties.TITLE, text);
+                    } else if (depth == 2 && collected.equals("desc")
+                            && metadata.get(TikaCoreProperties.DESCRIPTION) == null) {
+                        metadata.set(TikaCoreProperties.DESCRIPTION, text);
+                    }
+                    xhtml.element("p", text);
+                }
+                collectDepth = 0;
+                collected = null;
+            }
+            depth--;
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) {
+            if (collectDepth > 0) {
+                buffer.append(ch, start, length);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Set;
+
+/**
+ * Ends the parse of a thread that was interrupted, which the rust side does once the timeout of
+ * an extraction has elapsed. Most parsers never check the interrupt flag, so the flag is checked
+ * on each SAX event the parser produces and the parse fails with a SAXException once it is set.
+ * The flag is left set, so that the waits the parser might still do are interrupted as well.
+ */
+public class ThreadInterrupt {
+
+    private ThreadInterrupt() {
+    }
+
+    static Parser wrap(Parser parser) {
+        return new InterruptibleParser(parser);
+    }
+
+    private static class InterruptibleParser extends ParserDecorator {
+
+        InterruptibleParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            super.parse(stream, new InterruptibleContentHandler(handler), metadata, context);
+        }
+    }
+
+    private static class InterruptibleContentHandler extends ContentHandlerDecorator {
+
+        InterruptibleContentHandler(ContentHandler handler) {
+            super(handler);
+        }
+
+        private void checkInterrupted() throws SAXException {
+            if (Thread.currentThread().isInterrupted()) {
+                throw new SAXException("The parse was interrupted");
+            }
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            checkInterrupted();
+            super.startElement(uri, localName, name, atts);
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            checkInterrupted();
+            super.characters(ch, start, length);
+        }
+    }
+}
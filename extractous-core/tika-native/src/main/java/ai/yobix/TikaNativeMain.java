@@ -1,14 +1,25 @@
 package ai.yobix;
 
 import org.apache.commons.io.input.ReaderInputStream;
+import org.apache.james.mime4j.MimeException;
+import org.apache.pdfbox.Loader;
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.poi.openxml4j.exceptions.InvalidFormatException;
 import org.apache.tika.Tika;
 import org.apache.tika.config.TikaConfig;
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.EncryptedDocumentException;
 import org.apache.tika.exception.TikaException;
 import org.apache.tika.exception.WriteLimitReachedException;
+import org.apache.tika.extractor.EmbeddedDocumentExtractor;
 import org.apache.tika.io.TemporaryResources;
 import org.apache.tika.io.TikaInputStream;
 import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
 import org.apache.tika.parser.AutoDetectParser;
+import org.apache.tika.parser.AutoDetectParserConfig;
+import org.apache.tika.parser.CompositeParser;
 import org.apache.tika.parser.EmptyParser;
 import org.apache.tika.parser.ParseContext;
 import org.apache.tika.parser.Parser;
@@ -17,12 +28,15 @@ import org.apache.tika.parser.microsoft.OfficeParserConfig;
 import org.apache.tika.parser.ocr.TesseractOCRConfig;
 import org.apache.tika.parser.pdf.PDFParserConfig;
 import org.apache.tika.sax.BodyContentHandler;
+import org.apache.tika.sax.ContentHandlerDecorator;
 import org.apache.tika.sax.RecursiveParserWrapperHandler;
+import org.apache.tika.sax.ToTextContentHandler;
 import org.apache.tika.sax.ToXMLContentHandler;
 import org.apache.tika.sax.WriteOutContentHandler;
 import org.apache.tika.sax.BasicContentHandlerFactory;
 import org.xml.sax.ContentHandler;
 import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
 
 import java.io.IOException;
 import java.io.InputStream;
@@ -33,7 +47,7 @@ import java.net.URISyntaxException;
 import java.net.URL;
 import java.nio.ByteBuffer;
 import java.nio.charset.Charset;
-import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
 import java.nio.file.Path;
 import java.nio.file.Paths;
 import java.util.List;
@@ -76,6 +90,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
             // maybe replace with a single config class
@@ -85,12 +100,14 @@ public class TikaNativeMain {
             final Metadata metadata = new Metadata();
             final InputStream stream = TikaInputStream.get(path, metadata);
 
-            String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML, asEmbedded);
             // No need to close the stream because parseToString does so
-            return new StringResult(result, metadata);
+            return parseToStringWithConfig(
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                    asEmbedded);
         } catch (java.io.IOException e) {
             return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
         } catch (TikaException e) {
             return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
         }
@@ -108,18 +125,19 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) {
         try {
             final URL url = new URI(urlString).toURL();
             final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = UrlFetch.open(url, metadata, parseOptions);
 
-            String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML, asEmbedded);
             // No need to close the stream because parseToString does so
-            return new StringResult(result, metadata);
+            return parseToStringWithConfig(
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                    asEmbedded);
 
         } catch (MalformedURLException e) {
             return new StringResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
@@ -127,6 +145,8 @@ public class TikaNativeMain {
             return new StringResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
         } catch (java.io.IOException e) {
             return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
         } catch (TikaException e) {
             return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
         }
@@ -144,6 +164,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) {
@@ -152,46 +173,285 @@ public class TikaNativeMain {
         final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
         try {
-            String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML, asEmbedded);
             // No need to close the stream because parseToString does so
-            return new StringResult(result, metadata);
+            return parseToStringWithConfig(
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                    asEmbedded);
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the bytes read from a rust reader to a string. The rust reader is only valid for the
+     * duration of this call, which reads it synchronously on the calling thread.
+     *
+     * @param readerPtr pointer to the rust reader, see {@link NativeInputStream}
+     */
+    public static StringResult parseReaderToString(
+            long readerPtr,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asXML,
+            boolean asEmbedded
+    ) {
+        final Metadata metadata = new Metadata();
+        final NativeInputStream inStream = new NativeInputStream(readerPtr, false);
+        final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
+
+        try {
+            // No need to close the stream because parseToString does so
+            return parseToStringWithConfig(
+                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                    asEmbedded);
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given file for its metadata only. The content events are dropped as they come,
+     * so no text is ever collected, and OCR is skipped since its output would be dropped too.
+     *
+     * @param filePath the path of the file
+     * @return StringResult with an empty content and the document metadata
+     */
+    public static StringResult parseFileMetadata(
+            String filePath,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asEmbedded
+    ) {
+        try {
+            final Path path = Paths.get(filePath);
+            final Metadata metadata = new Metadata();
+            final InputStream stream = TikaInputStream.get(path, metadata);
+
+            return parseMetadataWithConfig(
+                    stream, metadata, pdfConfig, officeConfig, tesseractConfig, parseOptions, asEmbedded);
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given Url for its metadata only, see {@link #parseFileMetadata}
+     */
+    public static StringResult parseUrlMetadata(
+            String urlString,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asEmbedded
+    ) {
+        try {
+            final URL url = new URI(urlString).toURL();
+            final Metadata metadata = new Metadata();
+            final TikaInputStream stream = UrlFetch.open(url, metadata, parseOptions);
+
+            return parseMetadataWithConfig(
+                    stream, metadata, pdfConfig, officeConfig, tesseractConfig, parseOptions, asEmbedded);
+        } catch (MalformedURLException e) {
+            return new StringResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
+        } catch (URISyntaxException e) {
+            return new StringResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given array of bytes for its metadata only, see {@link #parseFileMetadata}
+     */
+    public static StringResult parseBytesMetadata(
+            ByteBuffer data,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asEmbedded
+    ) {
+        final Metadata metadata = new Metadata();
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+        final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
+
+        try {
+            return parseMetadataWithConfig(
+                    stream, metadata, pdfConfig, officeConfig, tesseractConfig, parseOptions, asEmbedded);
         } catch (java.io.IOException e) {
             return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
         } catch (TikaException e) {
             return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
         }
     }
 
-    private static String parseToStringWithConfig(
+    private static StringResult parseMetadataWithConfig(
+            InputStream stream,
+            Metadata metadata,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asEmbedded
+    ) throws IOException, TikaException {
+        tesseractConfig.setSkipOcr(true);
+        try (stream) {
+            final TikaConfig config = TikaConfig.getDefaultConfig();
+            final ParseContext parsecontext = new ParseContext();
+            final Parser parser = newAutoDetectParser(config, parseOptions, tesseractConfig);
+
+            parsecontext.set(Parser.class, parser);
+            parsecontext.set(PDFParserConfig.class, parseOptions.applyTo(pdfConfig));
+            parsecontext.set(OfficeParserConfig.class, officeConfig);
+            DocumentPasswords.applyTo(parsecontext, pdfConfig, officeConfig);
+            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+
+            // Disable embedded document parsing if asEmbedded is false
+            if (!asEmbedded) {
+                parsecontext.set(Parser.class, EmptyParser.INSTANCE);
+            }
+
+            parser.parse(stream, new DefaultHandler(), metadata, parsecontext);
+        } catch (SAXException e) {
+            // The ignoring handler never throws, so this comes from the parser itself
+            throw new TikaException("Unexpected SAX processing failure", e);
+        }
+        return new StringResult("", metadata);
+    }
+
+    /**
+     * Creates the parser used by all the parse methods. OCR invocations made by this parser are
+     * subject to the process wide limit of {@link OcrLimiter}, the ZIP based documents it fails
+     * on are recovered by {@link ZipRecovery}, the documents it still fails on are retried by
+     * {@link ParserFallbackChain}, the parse is subject to the soft time budget of
+     * {@link TimeBudget}, and it ends once its thread is interrupted, see {@link ThreadInterrupt}
+     */
+    private static Parser newAutoDetectParser(
+            TikaConfig config, ParseOptions parseOptions, TesseractOCRConfig ocrConfig) {
+        // The SVG parser takes image/svg+xml over from the generic XML parser
+        Parser delegate = new CompositeParser(
+                config.getMediaTypeRegistry(), OcrLimiter.wrap(config.getParser(), ocrConfig), new SvgParser());
+        delegate = ZipRecovery.wrap(delegate, parseOptions.isZipRecovery());
+        delegate = ParserFallbackChain.wrap(delegate, parseOptions.getParserFallbacks());
+        delegate = ScriptMarker.wrap(delegate, parseOptions.isPreserveScripts());
+        if (parseOptions.isExcludeHiddenText()) {
+            delegate = HiddenTextFilter.wrap(delegate, config.getMediaTypeRegistry());
+        }
+        delegate = TrackedChanges.wrap(delegate, config.getMediaTypeRegistry());
+        delegate = OfficeComments.wrap(delegate, config.getMediaTypeRegistry());
+        delegate = PdfFormFields.wrap(delegate, config.getMediaTypeRegistry());
+        delegate = PdfImageInfo.wrap(delegate, config.getMediaTypeRegistry());
+        // Cut first, so that the hidden text is only looked for in the pages that are kept
+        delegate = PdfPageRange.wrap(delegate, config.getMediaTypeRegistry());
+        if (parseOptions.isEncodingAuto()) {
+            delegate = EncodingAutoDetect.wrap(delegate, parseOptions.isTrustDeclaredCharset());
+        } else if (!parseOptions.isTrustDeclaredCharset()) {
+            delegate = DeclaredCharsetFilter.wrap(delegate, config.getEncodingDetector());
+        }
+        final Detector detector = ForcedTextDetector.wrap(
+                config.getDetector(), parseOptions.getForceTextExtensions(), parseOptions.getForceTextTypes());
+        final AutoDetectParser parser = new AutoDetectParser(detector, delegate);
+
+        // The SecureContentHandler installed by the parser fails the parse with a TikaException
+        // once the XHTML elements are nested deeper than this
+        final AutoDetectParserConfig autoDetectConfig = new AutoDetectParserConfig();
+        final int maxElementDepth = parseOptions.getMaxElementDepth();
+        autoDetectConfig.setMaximumDepth(maxElementDepth > 0 ? maxElementDepth : Integer.MAX_VALUE);
+        if (parseOptions.isOcrSpillToDisk()) {
+            // Spool the input streams that are not files yet to a temporary file before parsing
+            autoDetectConfig.setSpoolToDisk(0L);
+        }
+        parser.setAutoDetectParserConfig(autoDetectConfig);
+        return ThreadInterrupt.wrap(TimeBudget.wrap(
+                ParserChain.wrap(parser, parseOptions.isRecordParserChain()),
+                parseOptions.getSoftTimeBudgetMillis()));
+    }
+
+    private static StringResult parseToStringWithConfig(
             InputStream stream,
             Metadata metadata,
             int maxLength,
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) throws IOException, TikaException {
+        // Above the threshold the content is written in UTF-8 chunks instead of a String
+        final int streamingThreshold = parseOptions.getStringStreamingThreshold();
+        final ChunkedOutputStream chunks =
+                streamingThreshold > 0 ? new ChunkedOutputStream(streamingThreshold) : null;
+        ContentHandler chunksHandler = null;
+
         ContentHandler handler;
         ContentHandler handlerForParser;
         if (asXML) {
-            handler = new WriteOutContentHandler(new ToXMLContentHandler(), maxLength);
+            if (chunks != null) {
+                chunksHandler = new ToXMLContentHandler(chunks, "UTF-8");
+                // Given an encoding, the handler starts with an XML declaration, which the String
+                // output does not have
+                handler = new WriteOutContentHandler(new ContentHandlerDecorator(chunksHandler) {
+                    @Override
+                    public void startDocument() {
+                    }
+                }, maxLength);
+            } else {
+                handler = new WriteOutContentHandler(new ToXMLContentHandler(), maxLength);
+            }
             handlerForParser = handler;
         } else {
-            handler = new WriteOutContentHandler(maxLength);
+            if (chunks != null) {
+                chunksHandler = new ToTextContentHandler(chunks, "UTF-8");
+                handler = new WriteOutContentHandler(chunksHandler, maxLength);
+            } else {
+                handler = new WriteOutContentHandler(maxLength);
+            }
             handlerForParser = new BodyContentHandler(handler);
+            if (parseOptions.isDedupRepeatedLines()) {
+                handlerForParser = new PageBreakMarker(handlerForParser);
+            }
+            if (parseOptions.isPreserveScripts()) {
+                handlerForParser = new ScriptMarker(handlerForParser);
+            }
         }
 
+        final OcrTracker ocrTracker = new OcrTracker();
         try (stream) {
             final TikaConfig config = TikaConfig.getDefaultConfig();
             final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
+            final Parser parser = newAutoDetectParser(config, parseOptions, tesseractConfig);
 
             parsecontext.set(Parser.class, parser);
-            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            parsecontext.set(PDFParserConfig.class, parseOptions.applyTo(pdfConfig));
             parsecontext.set(OfficeParserConfig.class, officeConfig);
+            DocumentPasswords.applyTo(parsecontext, pdfConfig, officeConfig);
             parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+            parsecontext.set(OcrTracker.class, ocrTracker);
 
             // Disable embedded document parsing if asEmbedded is false
             if (!asEmbedded) {
@@ -205,7 +465,18 @@ public class TikaNativeMain {
                 throw new TikaException("Unexpected SAX processing failure", e);
             }
         }
-        return handler.toString();
+        ocrTracker.endDocument(metadata);
+        if (chunks != null) {
+            // Flushes the encoder, the end of the document is not reached when the limit is
+            try {
+                chunksHandler.endDocument();
+            } catch (SAXException e) {
+                throw new IOException("Could not write the content", e);
+            }
+            chunks.close();
+            return new StringResult(chunks, metadata);
+        }
+        return new StringResult(handler.toString(), metadata);
     }
 
 
@@ -218,6 +489,7 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
+     * @param parseOptions extractor wide parse options
      * @param asXML whether to output as XML
      * @param asEmbedded whether to parse embedded documents (default: false)
      * @return ReaderResult
@@ -228,6 +500,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) {
@@ -244,7 +517,8 @@ public class TikaNativeMain {
             final Metadata metadata = new Metadata();
             final TikaInputStream stream = TikaInputStream.get(path, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML, asEmbedded);
+            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                    asEmbedded);
 
         } catch (java.io.IOException e) {
             return new ReaderResult((byte) 1, "Could not open file: " + e.getMessage());
@@ -260,6 +534,7 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
+     * @param parseOptions extractor wide parse options
      * @param asXML whether to output as XML
      * @param asEmbedded whether to parse embedded documents (default: false)
      * @return ReaderResult
@@ -270,15 +545,17 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) {
         try {
             final URL url = new URI(urlString).toURL();
             final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = UrlFetch.open(url, metadata, parseOptions);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML, asEmbedded);
+            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                    asEmbedded);
 
         } catch (MalformedURLException e) {
             return new ReaderResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
@@ -298,6 +575,7 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
+     * @param parseOptions extractor wide parse options
      * @param asXML whether to output as XML
      * @param asEmbedded whether to parse embedded documents (default: false)
      * @return ReaderResult
@@ -308,6 +586,7 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) {
@@ -317,7 +596,40 @@ public class TikaNativeMain {
         final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
         final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
-        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML, asEmbedded);
+        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, parseOptions, asXML,
+                asEmbedded);
+    }
+
+    /**
+     * Parses the bytes read from a rust reader. The parse goes on in the background once this
+     * method has returned, so the stream takes over the rust reader and frees it when closed.
+     *
+     * @param readerPtr pointer to the rust reader, see {@link NativeInputStream}
+     */
+    public static ReaderResult parseReader(
+            long readerPtr,
+            String charsetName,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asXML,
+            boolean asEmbedded
+    ) {
+        final Metadata metadata = new Metadata();
+        final NativeInputStream inStream = new NativeInputStream(readerPtr, true);
+        final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
+
+        final ReaderResult result = parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig,
+                parseOptions, asXML, asEmbedded);
+        if (result.isError()) {
+            // No background parse took the stream over, so free the rust reader now
+            try {
+                stream.close();
+            } catch (java.io.IOException ignored) {
+            }
+        }
+        return result;
     }
 
     private static ReaderResult parse(
@@ -327,24 +639,34 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
             boolean asXML,
             boolean asEmbedded
     ) {
+        // An unknown charset fails the parse rather than falling back to another encoding
+        final Charset charset;
+        try {
+            if (!Charset.isSupported(charsetName)) {
+                closeQuietly(inputStream);
+                return new ReaderResult((byte) 2, "Unsupported charset: " + charsetName);
+            }
+            charset = Charset.forName(charsetName);
+        } catch (IllegalArgumentException e) {
+            // IllegalCharsetNameException, or a null name
+            closeQuietly(inputStream);
+            return new ReaderResult((byte) 2, "Illegal charset name: " + charsetName);
+        }
+
         try {
 
             final TikaConfig config = TikaConfig.getDefaultConfig();
             final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
-            Charset charset;
-            try {
-                charset = Charset.forName(charsetName);
-            } catch (Exception e) {
-                charset = StandardCharsets.UTF_8;
-            }
+            final Parser parser = newAutoDetectParser(config, parseOptions, tesseractConfig);
 
             parsecontext.set(Parser.class, parser);
-            parsecontext.set(PDFParserConfig.class, pdfConfig);
+            parsecontext.set(PDFParserConfig.class, parseOptions.applyTo(pdfConfig));
             parsecontext.set(OfficeParserConfig.class, officeConfig);
+            DocumentPasswords.applyTo(parsecontext, pdfConfig, officeConfig);
             parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
 
             // Disable embedded document parsing if asEmbedded is false
@@ -353,7 +675,9 @@ public class TikaNativeMain {
             }
 
             //final Reader reader = new org.apache.tika.parser.ParsingReader(parser, inputStream, metadata, parsecontext);
-            final Reader reader = new ParsingReader(parser, inputStream, metadata, parsecontext, asXML, charset.name());
+            final Reader reader = new ParsingReader(
+                    parser, inputStream, metadata, parsecontext, asXML, parseOptions.isPreserveScripts(),
+                    charset.name());
 
             // Convert Reader which works with chars to ReaderInputStream which works with bytes
             ReaderInputStream readerInputStream = ReaderInputStream.builder()
@@ -369,6 +693,73 @@ public class TikaNativeMain {
 
     }
 
+    private static void closeQuietly(TikaInputStream stream) {
+        try {
+            stream.close();
+        } catch (java.io.IOException ignored) {
+        }
+    }
+
+    /**
+     * Parses the given file and forwards the XHTML SAX events to the native handler pointed by
+     * handlerPtr. The events are delivered synchronously on the calling thread.
+     *
+     * @param filePath the path of the file
+     * @param pdfConfig PDF parser configuration
+     * @param officeConfig Office parser configuration
+     * @param tesseractConfig OCR configuration
+     * @param parseOptions extractor wide parse options
+     * @param asEmbedded whether to parse embedded documents
+     * @param handlerPtr pointer to the rust handler, see {@link NativeContentHandler}
+     * @return StringResult with an empty content and the document metadata
+     */
+    public static StringResult parseFileWithHandler(
+            String filePath,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            ParseOptions parseOptions,
+            boolean asEmbedded,
+            long handlerPtr
+    ) {
+        try {
+            final Path path = Paths.get(filePath);
+            final Metadata metadata = new Metadata();
+
+            try (TikaInputStream stream = TikaInputStream.get(path, metadata)) {
+                final TikaConfig config = TikaConfig.getDefaultConfig();
+                final ParseContext parsecontext = new ParseContext();
+                final Parser parser = newAutoDetectParser(config, parseOptions, tesseractConfig);
+
+                parsecontext.set(Parser.class, parser);
+                parsecontext.set(PDFParserConfig.class, parseOptions.applyTo(pdfConfig));
+                parsecontext.set(OfficeParserConfig.class, officeConfig);
+                DocumentPasswords.applyTo(parsecontext, pdfConfig, officeConfig);
+                parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
+                final OcrTracker ocrTracker = new OcrTracker();
+                parsecontext.set(OcrTracker.class, ocrTracker);
+
+                // Disable embedded document parsing if asEmbedded is false
+                if (!asEmbedded) {
+                    parsecontext.set(Parser.class, EmptyParser.INSTANCE);
+                }
+
+                parser.parse(stream, new NativeContentHandler(handlerPtr), metadata, parsecontext);
+                ocrTracker.endDocument(metadata);
+            }
+            return new StringResult("", metadata);
+
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        } catch (SAXException e) {
+            return new StringResult((byte) 2, "Content handler error occurred : " + e.getMessage());
+        }
+    }
+
     /**
      * Parses the given file recursively, including all embedded documents.
      * Returns a list of metadata for the container document and all embedded documents.
@@ -378,6 +769,8 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
+     * @param recursiveConfig embedded documents walking configuration
+     * @param parseOptions extractor wide parse options
      * @return RecursiveResult containing list of Metadata for all documents
      */
     public static RecursiveResult parseFileRecursive(
@@ -386,6 +779,8 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            RecursiveConfig recursiveConfig,
+            ParseOptions parseOptions,
             boolean asXml
     ) {
         try {
@@ -393,10 +788,60 @@ public class TikaNativeMain {
             final Metadata metadata = new Metadata();
             final TikaInputStream stream = TikaInputStream.get(path, metadata);
 
-            return parseRecursive(stream, maxLength, pdfConfig, officeConfig, tesseractConfig, asXml);
+            return parseRecursive(
+                    stream, maxLength, pdfConfig, officeConfig, tesseractConfig, recursiveConfig, parseOptions, asXml,
+                    null);
+
+        } catch (java.io.IOException e) {
+            return new RecursiveResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new RecursiveResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new RecursiveResult((byte) 2, "Parse error occurred: " + e.getMessage());
+        } catch (SAXException e) {
+            return new RecursiveResult((byte) 2, "SAX error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Parses the given file recursively, including all embedded documents, and hands each
+     * document to the native sink as soon as it is complete instead of collecting them. Embedded
+     * documents come first, in the order they end, and the container document last.
+     *
+     * @param filePath the path of the file to be parsed
+     * @param maxLength maximum length of content for each document
+     * @param pdfConfig PDF parser configuration
+     * @param officeConfig Office parser configuration
+     * @param tesseractConfig OCR configuration
+     * @param recursiveConfig embedded documents walking configuration
+     * @param parseOptions extractor wide parse options
+     * @param sinkPtr pointer to the rust sink, see {@link NativeDocumentSink}
+     * @return RecursiveResult without documents
+     */
+    public static RecursiveResult parseFileRecursiveToSink(
+            String filePath,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            RecursiveConfig recursiveConfig,
+            ParseOptions parseOptions,
+            boolean asXml,
+            long sinkPtr
+    ) {
+        try {
+            final Path path = Paths.get(filePath);
+            final Metadata metadata = new Metadata();
+            final TikaInputStream stream = TikaInputStream.get(path, metadata);
+
+            return parseRecursive(
+                    stream, maxLength, pdfConfig, officeConfig, tesseractConfig, recursiveConfig, parseOptions, asXml,
+                    new NativeDocumentSink(sinkPtr));
 
         } catch (java.io.IOException e) {
             return new RecursiveResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new RecursiveResult((byte) 3, "Encrypted document: " + e.getMessage());
         } catch (TikaException e) {
             return new RecursiveResult((byte) 2, "Parse error occurred: " + e.getMessage());
         } catch (SAXException e) {
@@ -413,6 +858,8 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
+     * @param recursiveConfig embedded documents walking configuration
+     * @param parseOptions extractor wide parse options
      * @return RecursiveResult containing list of Metadata for all documents
      */
     public static RecursiveResult parseUrlRecursive(
@@ -421,14 +868,18 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            RecursiveConfig recursiveConfig,
+            ParseOptions parseOptions,
             boolean asXml
     ) {
         try {
             final URL url = new URI(urlString).toURL();
             final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = UrlFetch.open(url, metadata, parseOptions);
 
-            return parseRecursive(stream, maxLength, pdfConfig, officeConfig, tesseractConfig, asXml);
+            return parseRecursive(
+                    stream, maxLength, pdfConfig, officeConfig, tesseractConfig, recursiveConfig, parseOptions, asXml,
+                    null);
 
         } catch (MalformedURLException e) {
             return new RecursiveResult((byte) 2, "Malformed URL error occurred: " + e.getMessage());
@@ -436,6 +887,8 @@ public class TikaNativeMain {
             return new RecursiveResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
         } catch (java.io.IOException e) {
             return new RecursiveResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new RecursiveResult((byte) 3, "Encrypted document: " + e.getMessage());
         } catch (TikaException e) {
             return new RecursiveResult((byte) 2, "Parse error occurred: " + e.getMessage());
         } catch (SAXException e) {
@@ -452,6 +905,8 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
+     * @param recursiveConfig embedded documents walking configuration
+     * @param parseOptions extractor wide parse options
      * @return RecursiveResult containing list of Metadata for all documents
      */
     public static RecursiveResult parseBytesRecursive(
@@ -460,6 +915,8 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
+            RecursiveConfig recursiveConfig,
+            ParseOptions parseOptions,
             boolean asXml
     ) {
         try {
@@ -467,10 +924,14 @@ public class TikaNativeMain {
             final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
             final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
-            return parseRecursive(stream, maxLength, pdfConfig, officeConfig, tesseractConfig, asXml);
+            return parseRecursive(
+                    stream, maxLength, pdfConfig, officeConfig, tesseractConfig, recursiveConfig, parseOptions, asXml,
+                    null);
 
         } catch (java.io.IOException e) {
             return new RecursiveResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new RecursiveResult((byte) 3, "Encrypted document: " + e.getMessage());
         } catch (TikaException e) {
             return new RecursiveResult((byte) 2, "Parse error occurred: " + e.getMessage());
         } catch (SAXException e) {
@@ -492,7 +953,10 @@ public class TikaNativeMain {
      * @param pdfConfig PDF parser configuration
      * @param officeConfig Office parser configuration
      * @param tesseractConfig OCR configuration
-     * @return RecursiveResult containing list of Metadata for all documents
+     * @param recursiveConfig embedded documents walking configuration
+     * @param parseOptions extractor wide parse options
+     * @param sink receives each document as soon as it is complete, null to collect them all
+     * @return RecursiveResult containing list of Metadata for all documents, empty with a sink
      */
     private static RecursiveResult parseRecursive(
             TikaInputStream stream,
@@ -500,41 +964,479 @@ public class TikaNativeMain {
             PDFParserConfig pdfConfig,
             OfficeParserConfig officeConfig,
             TesseractOCRConfig tesseractConfig,
-            boolean asXml
+            RecursiveConfig recursiveConfig,
+            ParseOptions parseOptions,
+            boolean asXml,
+            NativeDocumentSink sink
+    ) throws IOException, TikaException, SAXException {
+        final Metadata metadata = new Metadata();
+        if (recursiveConfig.isRecordContentDigests()) {
+            ContentDigest.record(stream, metadata);
+        }
+        final OutputBudget outputBudget = new OutputBudget(recursiveConfig.getMaxTotalOutputBytes());
+        final EmbeddedLimits embeddedLimits =
+                new EmbeddedLimits(recursiveConfig.getMaxEmbeddedCount(), recursiveConfig.getMaxEmbeddedBytes());
+        return new RecursiveResult(parseRecursiveToList(
+                stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, recursiveConfig,
+                parseOptions, asXml, outputBudget, embeddedLimits, sink, false));
+    }
+
+    /**
+     * Parses the document and its embedded documents, see parseRecursive. With failSoft, an
+     * exception of the document itself is only recorded in its metadata, as for the embedded
+     * documents, and the documents parsed so far are returned. The output budget and the embedded
+     * limits are shared with the subtrees parsed concurrently.
+     */
+    private static List<Metadata> parseRecursiveToList(
+            TikaInputStream stream,
+            Metadata metadata,
+            int maxLength,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            TesseractOCRConfig tesseractConfig,
+            RecursiveConfig recursiveConfig,
+            ParseOptions parseOptions,
+            boolean asXml,
+            OutputBudget outputBudget,
+            EmbeddedLimits embeddedLimits,
+            NativeDocumentSink sink,
+            boolean failSoft
     ) throws IOException, TikaException, SAXException {
         try (stream) {
             final TikaConfig config = TikaConfig.getDefaultConfig();
             final ParseContext parseContext = new ParseContext();
-            final AutoDetectParser autoParser = new AutoDetectParser(config);
+            final Parser autoParser = newAutoDetectParser(config, parseOptions, tesseractConfig);
+
+            // The documents embedded in the container are parsed by worker threads, each with its
+            // own embedded documents. Streamed documents are handed over as they are parsed instead
+            final ParallelEmbeddedParser parallelParser = recursiveConfig.getParallelism() > 1 && sink == null
+                    ? new ParallelEmbeddedParser(autoParser, recursiveConfig.getParallelism(),
+                            (subStream, subMetadata) -> parseRecursiveToList(
+                                    subStream, subMetadata, maxLength, pdfConfig, officeConfig, tesseractConfig,
+                                    recursiveConfig.forEmbeddedSubtree(), parseOptions, asXml, outputBudget,
+                                    embeddedLimits, null, true))
+                    : null;
 
             // Use default constructor: catchEmbeddedExceptions = true
             // This ensures embedded document errors don't fail the entire parse
-            final RecursiveParserWrapper wrapper = new RecursiveParserWrapper(autoParser);
+            final RecursiveParserWrapper wrapper =
+                    new RecursiveParserWrapper(parallelParser != null ? parallelParser : autoParser);
 
             // Configure parse context
             parseContext.set(Parser.class, autoParser);
-            parseContext.set(PDFParserConfig.class, pdfConfig);
+            parseContext.set(PDFParserConfig.class, parseOptions.applyTo(pdfConfig));
             parseContext.set(OfficeParserConfig.class, officeConfig);
+            DocumentPasswords.applyTo(parseContext, pdfConfig, officeConfig);
             parseContext.set(TesseractOCRConfig.class, tesseractConfig);
+            final OcrTracker ocrTracker = new OcrTracker();
+            parseContext.set(OcrTracker.class, ocrTracker);
+
+            // Applies the depth and leaf resources rules to every embedded document
+            parseContext.set(EmbeddedDocumentExtractor.class,
+                    new RecursiveEmbeddedDocumentExtractor(
+                            parseContext, recursiveConfig, config.getDetector(), parallelParser, outputBudget,
+                            embeddedLimits));
 
             // Create handler for recursive parsing
             BasicContentHandlerFactory.HANDLER_TYPE handlerType = asXml
                     ? BasicContentHandlerFactory.HANDLER_TYPE.XML
                     : BasicContentHandlerFactory.HANDLER_TYPE.TEXT;
-            BasicContentHandlerFactory factory = new BasicContentHandlerFactory(handlerType, maxLength);
-            RecursiveParserWrapperHandler handler = new RecursiveParserWrapperHandler(factory);
+            final int perDocumentMaxLength = recursiveConfig.getPerDocumentMaxLength();
+            BasicContentHandlerFactory factory = new BasicContentHandlerFactory(handlerType, maxLength) {
+                @Override
+                public ContentHandler getNewContentHandler() {
+                    ContentHandler handler = super.getNewContentHandler();
+                    if (!asXml && parseOptions.isDedupRepeatedLines()) {
+                        handler = new PageBreakMarker(handler);
+                    }
+                    if (!asXml && parseOptions.isPreserveScripts()) {
+                        handler = new ScriptMarker(handler);
+                    }
+                    return perDocumentMaxLength < 0 && !outputBudget.isLimited()
+                            ? handler
+                            : new LengthLimitedContentHandler(
+                                    handler, perDocumentMaxLength, outputBudget.isLimited() ? outputBudget : null);
+                }
+            };
+            RecursiveParserWrapperHandler handler = new RecursiveParserWrapperHandler(factory) {
+                @Override
+                public void startEmbeddedDocument(ContentHandler contentHandler, Metadata metadata)
+                        throws SAXException {
+                    ocrTracker.startEmbeddedDocument();
+                    super.startEmbeddedDocument(contentHandler, metadata);
+                }
+
+                @Override
+                public void endEmbeddedDocument(ContentHandler contentHandler, Metadata metadata)
+                        throws SAXException {
+                    LengthLimitedContentHandler.flagTruncated(contentHandler, metadata);
+                    ocrTracker.endEmbeddedDocument(metadata);
+                    fillEmbeddedNames(metadata);
+                    final int count = getMetadataList().size();
+                    super.endEmbeddedDocument(contentHandler, metadata);
+                    // The document was appended to the list
+                    if (sink != null && getMetadataList().size() > count) {
+                        sink.accept(getMetadataList().remove(count));
+                    }
+                }
+
+                @Override
+                public void endDocument(ContentHandler contentHandler, Metadata metadata)
+                        throws SAXException {
+                    LengthLimitedContentHandler.flagTruncated(contentHandler, metadata);
+                    if (outputBudget.isExhausted()) {
+                        metadata.set(OutputBudget.OUTPUT_TRUNCATED, "true");
+                    }
+                    ocrTracker.endDocument(metadata);
+                    if (parallelParser != null) {
+                        parallelParser.mergeInto(getMetadataList());
+                    }
+                    // After the merge, which waits for the subtrees parsed concurrently
+                    if (embeddedLimits.isExhausted()) {
+                        metadata.set(EmbeddedLimits.EMBEDDED_TRUNCATED, "true");
+                    }
+                    final int count = getMetadataList().size();
+                    super.endDocument(contentHandler, metadata);
+                    // The container was inserted at the head of the list
+                    if (sink != null && getMetadataList().size() > count) {
+                        sink.accept(getMetadataList().remove(0));
+                    }
+                }
+            };
 
             // Parse the document
-            wrapper.parse(stream, handler, new Metadata(), parseContext);
+            try {
+                wrapper.parse(stream, handler, metadata, parseContext);
+            } catch (IOException | TikaException | SAXException e) {
+                if (!failSoft) {
+                    throw e;
+                }
+            } finally {
+                if (parallelParser != null) {
+                    parallelParser.shutdown();
+                }
+            }
 
             // Get the list of all metadata (container + embedded documents)
-            List<Metadata> metadataList = handler.getMetadataList();
+            return handler.getMetadataList();
+        }
+    }
+
+    /**
+     * Writes the raw bytes of the documents embedded in the given file to the files the rust side
+     * picks, see {@link EmbeddedFileWriter}. The text of the documents is not extracted.
+     *
+     * @param filePath the path of the file
+     * @param pdfConfig PDF parser configuration
+     * @param officeConfig Office parser configuration
+     * @param parseOptions extractor wide parse options
+     * @param targetPtr pointer to the rust state naming the files
+     * @return StringResult with an empty content
+     */
+    public static StringResult extractEmbeddedToDir(
+            String filePath,
+            PDFParserConfig pdfConfig,
+            OfficeParserConfig officeConfig,
+            ParseOptions parseOptions,
+            long targetPtr
+    ) {
+        try {
+            final Path path = Paths.get(filePath);
+            final Metadata metadata = new Metadata();
+            try (TikaInputStream stream = TikaInputStream.get(path, metadata)) {
+                final TikaConfig config = TikaConfig.getDefaultConfig();
+                final ParseContext parseContext = new ParseContext();
+                final Parser parser = newAutoDetectParser(config, parseOptions, null);
+                parseContext.set(Parser.class, parser);
+                parseContext.set(PDFParserConfig.class, parseOptions.applyTo(pdfConfig));
+                parseContext.set(OfficeParserConfig.class, officeConfig);
+                DocumentPasswords.applyTo(parseContext, pdfConfig, officeConfig);
+                parseContext.set(EmbeddedDocumentExtractor.class,
+                        new EmbeddedFileWriter(targetPtr, config, parser, parseContext));
+
+                parser.parse(stream, new DefaultHandler(), metadata, parseContext);
+            }
+            return new StringResult("", metadata);
+
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (EncryptedDocumentException e) {
+            return new StringResult((byte) 3, "Encrypted document: " + e.getMessage());
+        } catch (TikaException e) {
+            return new StringResult((byte) 2, "Parse error occurred: " + e.getMessage());
+        } catch (SAXException e) {
+            return new StringResult((byte) 2, "SAX error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Makes sure an embedded document has a resource name and a content type, so that it can be
+     * written back out. The parsers leave the name out for the anonymous resources, such as the
+     * images of an Office document, which then get the last segment of their embedded path, and
+     * the type out when the document could not be read at all.
+     */
+    private static void fillEmbeddedNames(Metadata metadata) {
+        if (metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY) == null) {
+            final String path = metadata.get(TikaCoreProperties.EMBEDDED_RESOURCE_PATH);
+            if (path != null) {
+                metadata.set(TikaCoreProperties.RESOURCE_NAME_KEY, path.substring(path.lastIndexOf('/') + 1));
+            }
+        }
+        if (metadata.get(Metadata.CONTENT_TYPE) == null) {
+            metadata.set(Metadata.CONTENT_TYPE, MediaType.OCTET_STREAM.toString());
+        }
+    }
+
+    /**
+     * Returns the digital signatures of the given file. PDF and OOXML documents are supported,
+     * other formats return an empty list
+     *
+     * @param filePath the path of the file
+     * @return SignatureResult
+     */
+    public static SignatureResult getSignatureInfo(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            final String mimeType = tika.detect(path);
+            return new SignatureResult(SignatureInspector.inspect(path, mimeType));
+
+        } catch (java.io.IOException e) {
+            return new SignatureResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (TikaException e) {
+            return new SignatureResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the words of the given PDF file with their bounding boxes, see {@link WordBoxStripper}
+     * for the coordinate system
+     *
+     * @param filePath the path of the PDF file
+     * @return WordBoxResult
+     */
+    public static WordBoxResult extractPdfWordBoxes(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            final String mimeType = tika.detect(path);
+            if (!"application/pdf".equals(mimeType)) {
+                return new WordBoxResult((byte) 2, "Not a PDF document: " + mimeType);
+            }
+            try (PDDocument document = Loader.loadPDF(path.toFile())) {
+                return WordBoxStripper.extract(document);
+            }
 
-            return new RecursiveResult(metadataList);
+        } catch (java.io.IOException e) {
+            return new WordBoxResult((byte) 1, "Could not open file: " + e.getMessage());
+        }
+    }
 
+    /**
+     * Returns the fonts referenced by the given PDF file, see {@link FontLister}
+     *
+     * @param filePath the path of the PDF file
+     * @return FontResult
+     */
+    public static FontResult listFonts(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            final String mimeType = tika.detect(path);
+            if (!"application/pdf".equals(mimeType)) {
+                return new FontResult((byte) 2, "Not a PDF document: " + mimeType);
+            }
+            try (PDDocument document = Loader.loadPDF(path.toFile())) {
+                return new FontResult(FontLister.list(document));
+            }
+
+        } catch (java.io.IOException e) {
+            return new FontResult((byte) 1, "Could not open file: " + e.getMessage());
         }
     }
 
+    /**
+     * Returns the PDF/A conformance and the tagging of the given PDF file, see
+     * {@link PdfConformance}
+     *
+     * @param filePath the path of the PDF file
+     * @return PdfConformanceResult
+     */
+    public static PdfConformanceResult pdfConformanceInfo(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            final String mimeType = tika.detect(path);
+            if (!"application/pdf".equals(mimeType)) {
+                return new PdfConformanceResult((byte) 2, "Not a PDF document: " + mimeType);
+            }
+            try (PDDocument document = Loader.loadPDF(path.toFile())) {
+                return new PdfConformanceResult(PdfConformance.read(document));
+            }
+
+        } catch (java.io.IOException e) {
+            return new PdfConformanceResult((byte) 1, "Could not open file: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the revision history of the given Office file, see {@link RevisionReader}
+     *
+     * @param filePath the path of the Office file
+     * @return RevisionResult
+     */
+    public static RevisionResult extractRevisions(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            final String mimeType = tika.detect(path);
+            if (!RevisionReader.isOfficeDocument(mimeType)) {
+                return new RevisionResult((byte) 2, "Not an Office document: " + mimeType);
+            }
+            return new RevisionResult(RevisionReader.read(path, mimeType));
+
+        } catch (java.io.IOException e) {
+            return new RevisionResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (InvalidFormatException | SAXException e) {
+            return new RevisionResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the names of the streams of the given OLE compound document, see
+     * {@link OleStreams}
+     *
+     * @param filePath the path of the OLE compound document
+     * @return OleStreamResult
+     */
+    public static OleStreamResult listOleStreams(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            if (!OleStreams.isOleDocument(path)) {
+                return new OleStreamResult((byte) 2, "Not an OLE compound document: " + tika.detect(path));
+            }
+            return new OleStreamResult(OleStreams.list(path));
+
+        } catch (java.io.IOException e) {
+            return new OleStreamResult((byte) 1, "Could not open file: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the raw bytes of the named stream of the given OLE compound document, see
+     * {@link OleStreams}
+     *
+     * @param filePath the path of the OLE compound document
+     * @param name     the name of the stream, the path of its storages included
+     * @return OleStreamResult
+     */
+    public static OleStreamResult getOleStream(String filePath, String name) {
+        try {
+            final Path path = Paths.get(filePath);
+            if (!OleStreams.isOleDocument(path)) {
+                return new OleStreamResult((byte) 2, "Not an OLE compound document: " + tika.detect(path));
+            }
+            try {
+                return new OleStreamResult(OleStreams.read(path, name));
+            } catch (java.io.FileNotFoundException e) {
+                return new OleStreamResult((byte) 2, "No OLE stream named " + name + ": " + e.getMessage());
+            }
+
+        } catch (java.io.IOException e) {
+            return new OleStreamResult((byte) 1, "Could not open file: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the bodies and the attachments of the given email message, see
+     * {@link MimeMessageSplitter}
+     *
+     * @param filePath the path of the RFC822 message
+     * @return MimeMessageResult
+     */
+    public static MimeMessageResult splitMimeMessage(String filePath) {
+        try {
+            final Path path = Paths.get(filePath);
+            final String mimeType = tika.detect(path);
+            if (!"message/rfc822".equals(mimeType)) {
+                return new MimeMessageResult((byte) 2, "Not an email message: " + mimeType);
+            }
+            try (InputStream stream = Files.newInputStream(path)) {
+                return MimeMessageSplitter.split(stream);
+            }
+
+        } catch (java.io.IOException e) {
+            return new MimeMessageResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (MimeException e) {
+            return new MimeMessageResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        }
+    }
+
+    /**
+     * Detects the media type of the given file from its magic bytes and its extension, without
+     * parsing it
+     *
+     * @param filePath the path of the file
+     * @return StringResult holding the media type, such as application/pdf
+     */
+    public static StringResult detectMimeFile(String filePath) {
+        try {
+            return new StringResult(tika.detect(Paths.get(filePath)), new Metadata());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Detects the media type of the given bytes from their magic bytes, without parsing them.
+     * The data may be truncated, the detectors read it no further than they need.
+     *
+     * @param data an array of bytes
+     * @return StringResult holding the media type, such as application/pdf
+     */
+    public static StringResult detectMimeBytes(ByteBuffer data) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+        try (TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), new Metadata())) {
+            return new StringResult(tika.detect(stream), new Metadata());
+        } catch (java.io.IOException e) {
+            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the media types the given bytes could be, ordered by decreasing confidence
+     *
+     * @param data an array of bytes
+     * @return MimeCandidatesResult
+     */
+    public static MimeCandidatesResult detectMimeCandidates(ByteBuffer data) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+        try (TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), new Metadata())) {
+            return MimeCandidates.detect(stream);
+        } catch (java.io.IOException e) {
+            return new MimeCandidatesResult((byte) 1, "IO error occurred: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Detects the language the given text is written in
+     *
+     * @param text the text, the detector looks at its first few thousand characters only
+     * @return LanguageResult
+     */
+    public static LanguageResult detectLanguage(String text) {
+        try {
+            return LanguageDetection.detect(text);
+        } catch (java.io.IOException e) {
+            return new LanguageResult((byte) 1, "Could not load the language profiles: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Sets the maximum number of Tesseract invocations that can run at the same time across all
+     * extractions. Extractions that need OCR wait for a free slot.
+     *
+     * @param maxConcurrency the maximum number of concurrent OCR invocations, at least 1
+     */
+    public static void setMaxOcrConcurrency(int maxConcurrency) {
+        OcrLimiter.setMaxConcurrency(maxConcurrency);
+    }
+
     /**
      * Gets current JVM memory usage statistics.
      * Returns a StringResult containing JSON-formatted memory info:
@@ -577,6 +1479,13 @@ public class TikaNativeMain {
         }
     }
 
+    /**
+     * Returns the memory usage of the VM, see {@link MemoryStats}
+     */
+    public static MemoryStats getMemoryStats() {
+        return MemoryStats.current();
+    }
+
     /**
      * Triggers Java garbage collection manually.
      * NOTE: This is a suggestion to the JVM, not a guarantee that GC will run immediately.
@@ -0,0 +1,135 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Set;
+
+/**
+ * Soft limit on the time spent parsing a document, its embedded documents included. Once the
+ * budget has elapsed, the next SAX event of the parser ends the parse, which then completes
+ * normally with the content gathered so far, and the metadata of the documents that were cut is
+ * flagged with {@link #CONTENT_PARTIAL}. As the budget is only checked when the parser produces
+ * content, a parser busy without producing any, e.g. running OCR on a page, is not interrupted.
+ */
+public class TimeBudget {
+
+    /**
+     * Metadata key set to "true" on the documents whose parse was ended by the budget
+     */
+    public static final String CONTENT_PARTIAL = "extractous:content_partial";
+
+    private final long deadline;
+
+    private TimeBudget(long budgetMillis) {
+        this.deadline = System.nanoTime() + budgetMillis * 1_000_000L;
+    }
+
+    private boolean isExpired() {
+        return System.nanoTime() - deadline >= 0;
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that it stops once budgetMillis
+     * have elapsed since the start of the outermost parse. 0 or less means no budget.
+     */
+    static Parser wrap(Parser parser, long budgetMillis) {
+        if (budgetMillis <= 0) {
+            return parser;
+        }
+        return new BudgetedParser(parser, budgetMillis);
+    }
+
+    private static class BudgetedParser extends ParserDecorator {
+
+        private final long budgetMillis;
+
+        BudgetedParser(Parser parser, long budgetMillis) {
+            super(parser);
+            this.budgetMillis = budgetMillis;
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            // Embedded documents are parsed by this same parser, they share the budget that
+            // the outermost parse started
+            TimeBudget budget = context.get(TimeBudget.class);
+            if (budget == null) {
+                budget = new TimeBudget(budgetMillis);
+                context.set(TimeBudget.class, budget);
+            }
+            try {
+                super.parse(stream, new BudgetedContentHandler(handler, budget), metadata, context);
+            } catch (SAXException | TikaException | IOException e) {
+                if (!isBudgetExpired(e)) {
+                    throw e;
+                }
+                metadata.set(CONTENT_PARTIAL, "true");
+            }
+        }
+    }
+
+    /**
+     * Whether the exception, or one of its causes, is the one thrown once the budget elapsed.
+     * Parsers often wrap the exceptions of the handler into their own.
+     */
+    private static boolean isBudgetExpired(Throwable t) {
+        while (t != null) {
+            if (t instanceof BudgetExpiredException) {
+                return true;
+            }
+            t = t.getCause();
+        }
+        return false;
+    }
+
+    private static class BudgetExpiredException extends SAXException {
+        BudgetExpiredException() {
+            super("Soft time budget elapsed");
+        }
+    }
+
+    private static class BudgetedContentHandler extends ContentHandlerDecorator {
+
+        private final TimeBudget budget;
+
+        BudgetedContentHandler(ContentHandler handler, TimeBudget budget) {
+            super(handler);
+            this.budget = budget;
+        }
+
+        private void checkBudget() throws SAXException {
+            if (budget.isExpired()) {
+                throw new BudgetExpiredException();
+            }
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            checkBudget();
+            super.startElement(uri, localName, name, atts);
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            checkBudget();
+            super.characters(ch, start, length);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MediaTypeRegistry;
+import org.apache.tika.parser.CompositeParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.apache.tika.sax.XHTMLContentHandler;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.AttributesImpl;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.io.OutputStream;
+import java.nio.charset.StandardCharsets;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.Set;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+import java.util.zip.ZipEntry;
+import java.util.zip.ZipInputStream;
+import java.util.zip.ZipOutputStream;
+
+/**
+ * Keeps the deleted and moved-from text of the tracked changes of DOCX documents, when the
+ * Office config of the parse context includes it, and tags it in the XHTML output. The parts of
+ * the document are rewritten to a temporary file, in which the deleted runs are turned into
+ * plain runs between two marker characters, and the markers become {@code <del>} elements, of
+ * class "deleted" or "moved", once the document is parsed. The text output has the text without
+ * the markers. Deletions that span several paragraphs are tagged in each of them.
+ */
+public class TrackedChanges {
+
+    private static final Set<MediaType> DOCX_TYPES = Set.of(
+            MediaType.application("vnd.openxmlformats-officedocument.wordprocessingml.document"),
+            MediaType.application("vnd.ms-word.document.macroenabled.12"));
+
+    // Private use characters, which no document is expected to contain
+    private static final char DELETED_START = '\uE000';
+    private static final char DELETED_END = '\uE001';
+    private static final char MOVED_START = '\uE002';
+    private static final char MOVED_END = '\uE003';
+
+    private static final Pattern WORD_PART = Pattern.compile("word/[^/]+\\.xml");
+    // The start tags of the run containers, the empty ones, which only flag a paragraph mark or
+    // a table row, are left as they are
+    private static final Pattern DEL = Pattern.compile("<w:del\\b[^>]*?(/?)>(</w:del>)?");
+    private static final Pattern MOVE_FROM = Pattern.compile("<w:moveFrom\\b[^>]*?(/?)>(</w:moveFrom>)?");
+
+    private TrackedChanges() {
+    }
+
+    /**
+     * Returns a parser that behaves like the given one, except that the tracked changes of DOCX
+     * documents are kept and tagged as the Office config asks
+     */
+    static Parser wrap(Parser parser, MediaTypeRegistry registry) {
+        // Later parsers take precedence, so the DOCX documents are taken over by the decorator
+        return new CompositeParser(registry, parser, new TrackedChangesParser(parser));
+    }
+
+    private static class TrackedChangesParser extends ParserDecorator {
+
+        TrackedChangesParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return DOCX_TYPES;
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            final OfficeParserConfig config = context.get(OfficeParserConfig.class);
+            final boolean deleted = config != null && config.getIncludeDeletedContent();
+            final boolean moved = config != null && config.getIncludeMoveFromContent();
+            if (!deleted && !moved) {
+                super.parse(stream, handler, metadata, context);
+                return;
+            }
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                final Path marked = tmp.createTempFile();
+                try {
+                    markChanges(tis.getPath(), marked, deleted, moved);
+                } catch (IOException e) {
+                    // Broken packages are left to the parser, which reports the problem
+                    super.parse(tis, handler, metadata, context);
+                    return;
+                }
+                try (TikaInputStream markedStream = TikaInputStream.get(marked)) {
+                    super.parse(markedStream, new MarkerHandler(handler), metadata, context);
+                }
+            }
+        }
+    }
+
+    /**
+     * Copies the package, with the tracked changes of its Word parts marked
+     */
+    private static void markChanges(Path source, Path target, boolean deleted, boolean moved) throws IOException {
+        try (ZipInputStream in = new ZipInputStream(Files.newInputStream(source));
+             ZipOutputStream out = new ZipOutputStream(Files.newOutputStream(target))) {
+            ZipEntry entry;
+            while ((entry = in.getNextEntry()) != null) {
+                out.putNextEntry(new ZipEntry(entry.getName()));
+                if (WORD_PART.matcher(entry.getName()).matches()) {
+                    String xml = new String(in.readAllBytes(), StandardCharsets.UTF_8);
+                    if (deleted) {
+                        xml = markRuns(xml, DEL, "w:del", DELETED_START, DELETED_END)
+                                .replace("<w:delText", "<w:t")
+                                .replace("</w:delText>", "</w:t>");
+                    }
+                    if (moved) {
+                        xml = markRuns(xml, MOVE_FROM, "w:moveFrom", MOVED_START, MOVED_END);
+                    }
+                    out.write(xml.getBytes(StandardCharsets.UTF_8));
+                } else {
+                    in.transferTo(out);
+                }
+                out.closeEntry();
+            }
+        }
+    }
+
+    /**
+     * Replaces the start and end tags of the run containers by runs of the marker characters,
+     * so that their runs become plain runs of the paragraph
+     */
+    private static String markRuns(String xml, Pattern startTag, String name, char start, char end) {
+        final Matcher matcher = startTag.matcher(xml);
+        final StringBuilder marked = new StringBuilder(xml.length());
+        while (matcher.find()) {
+            final boolean empty = !matcher.group(1).isEmpty() || matcher.group(2) != null;
+            matcher.appendReplacement(marked, empty ? "$0" : markerRun(start));
+        }
+        matcher.appendTail(marked);
+        return marked.toString().replace("</" + name + ">", markerRun(end));
+    }
+
+    private static String markerRun(char marker) {
+        return "<w:r><w:t>" + marker + "</w:t></w:r>";
+    }
+
+    /**
+     * Turns the marker characters into {@code <del>} elements
+     */
+    private static class MarkerHandler extends ContentHandlerDecorator {
+
+        // Whether a del element is open, and the depth of the elements it was opened in
+        private boolean open = false;
+        private int openDepth = 0;
+        private int depth = 0;
+
+        MarkerHandler(ContentHandler handler) {
+            super(handler);
+        }
+
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            int from = start;
+            for (int i = start; i < start + length; i++) {
+                final char c = ch[i];
+                if (c != DELETED_START && c != DELETED_END && c != MOVED_START && c != MOVED_END) {
+                    continue;
+                }
+                if (i > from) {
+                    super.characters(ch, from, i - from);
+                }
+                from = i + 1;
+                if (c == DELETED_START || c == MOVED_START) {
+                    closeDel();
+                    final AttributesImpl attributes = new AttributesImpl();
+                    attributes.addAttribute("", "class", "class", "CDATA", c == DELETED_START ? "deleted" : "moved");
+                    super.startElement(XHTMLContentHandler.XHTML, "del", "del", attributes);
+                    open = true;
+                    openDepth = depth;
+                } else {
+                    closeDel();
+                }
+            }
+            if (start + length > from) {
+                super.characters(ch, from, start + length - from);
+            }
+        }
+
+        @Override
+        public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+            depth++;
+            super.startElement(uri, localName, name, atts);
+        }
+
+        @Override
+        public void endElement(String uri, String localName, String name) throws SAXException {
+            // The element is closed before the one it was opened in, such as its paragraph,
+            // which also covers the end markers the parser dropped
+            if (open && depth == openDepth) {
+                closeDel();
+            }
+            depth--;
+            super.endElement(uri, localName, name);
+        }
+
+        private void closeDel() throws SAXException {
+            if (open) {
+                open = false;
+                super.endElement(XHTMLContentHandler.XHTML, "del", "del");
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+package ai.yobix;
+
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+
+import java.io.BufferedInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.net.HttpURLConnection;
+import java.net.MalformedURLException;
+import java.net.URI;
+import java.net.URISyntaxException;
+import java.net.URL;
+import java.net.URLConnection;
+import java.nio.charset.StandardCharsets;
+
+/**
+ * Fetches the documents of the url parse methods with the user agent of the
+ * {@link ParseOptions}, and the robots.txt of their site, whose rules the rust side evaluates.
+ */
+public class UrlFetch {
+
+    // RFC 9309 asks crawlers to read at least the first 500 KiB of a robots.txt
+    private static final int MAX_ROBOTS_TXT_BYTES = 512 * 1024;
+    private static final int ROBOTS_TXT_TIMEOUT_MILLIS = 30_000;
+    // RFC 9309 asks crawlers to follow at least five consecutive redirects
+    private static final int MAX_ROBOTS_TXT_REDIRECTS = 5;
+    private static final String DISALLOW_ALL = "User-agent: *\nDisallow: /\n";
+
+    private UrlFetch() {
+    }
+
+    /**
+     * Opens the url as {@link TikaInputStream#get(URL, Metadata)} does, with the user agent of
+     * the options as the User-Agent header of the http and https requests
+     */
+    public static TikaInputStream open(URL url, Metadata metadata, ParseOptions parseOptions)
+            throws IOException {
+        final String userAgent = parseOptions.getUserAgent();
+        if (userAgent == null || !isHttp(url)) {
+            return TikaInputStream.get(url, metadata);
+        }
+        final URLConnection connection = url.openConnection();
+        connection.setRequestProperty("User-Agent", userAgent);
+
+        final String path = url.getPath();
+        final int slash = path.lastIndexOf('/');
+        if (slash + 1 < path.length()) {
+            metadata.set(TikaCoreProperties.RESOURCE_NAME_KEY, path.substring(slash + 1));
+        }
+        final String type = connection.getContentType();
+        if (type != null) {
+            metadata.set(Metadata.CONTENT_TYPE, type);
+        }
+        final String encoding = connection.getContentEncoding();
+        if (encoding != null) {
+            metadata.set(Metadata.CONTENT_ENCODING, encoding);
+        }
+        final int length = connection.getContentLength();
+        if (length >= 0) {
+            metadata.set(Metadata.CONTENT_LENGTH, Integer.toString(length));
+        }
+        return TikaInputStream.get(
+                new BufferedInputStream(connection.getInputStream()), new TemporaryResources(), metadata);
+    }
+
+    /**
+     * Fetches the robots.txt of the site of the url, sending the user agent when not empty.
+     * Following RFC 9309, a missing robots.txt, a 4xx status, allows everything and is returned
+     * as no rules, while an unreachable one, a 5xx status, disallows everything and is returned
+     * as rules that say so. Up to five redirects on the same host are followed, such as from http
+     * to https, a robots.txt behind more of them or on another host counts as missing. Urls that
+     * are not http or https have no robots.txt and no rules.
+     *
+     * @param urlString the url about to be fetched
+     * @param userAgent the User-Agent header, empty for the default one
+     * @return StringResult holding the robots.txt
+     */
+    public static StringResult fetchRobotsTxt(String urlString, String userAgent) {
+        try {
+            final URL url = new URI(urlString).toURL();
+            if (!isHttp(url)) {
+                return new StringResult("", new Metadata());
+            }
+            URL robotsUrl = new URI(
+                    url.getProtocol(), null, url.getHost(), url.getPort(), "/robots.txt", null, null).toURL();
+            for (int redirects = 0; ; redirects++) {
+                final HttpURLConnection connection = (HttpURLConnection) robotsUrl.openConnection();
+                // Followed below, also from http to https which the connection does not follow
+                connection.setInstanceFollowRedirects(false);
+                connection.setConnectTimeout(ROBOTS_TXT_TIMEOUT_MILLIS);
+                connection.setReadTimeout(ROBOTS_TXT_TIMEOUT_MILLIS);
+                if (!userAgent.isEmpty()) {
+                    connection.setRequestProperty("User-Agent", userAgent);
+                }
+                try {
+                    final int status = connection.getResponseCode();
+                    if (status >= 500) {
+                        return new StringResult(DISALLOW_ALL, new Metadata());
+                    }
+                    if (status >= 300 && status < 400) {
+                        final URL target = redirectTarget(robotsUrl, connection.getHeaderField("Location"));
+                        if (target != null && redirects < MAX_ROBOTS_TXT_REDIRECTS) {
+                            robotsUrl = target;
+                            continue;
+                        }
+                    }
+                    // A missing robots.txt, or one behind too many redirects or on another host
+                    if (status >= 300) {
+                        return new StringResult("", new Metadata());
+                    }
+                    try (InputStream in = connection.getInputStream()) {
+                        final byte[] bytes = in.readNBytes(MAX_ROBOTS_TXT_BYTES);
+                        return new StringResult(new String(bytes, StandardCharsets.UTF_8), new Metadata());
+                    }
+                } finally {
+                    connection.disconnect();
+                }
+            }
+        } catch (MalformedURLException | IllegalArgumentException e) {
+            return new StringResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
+        } catch (URISyntaxException e) {
+            return new StringResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
+        } catch (IOException e) {
+            return new StringResult((byte) 1, "Could not fetch robots.txt: " + e.getMessage());
+        }
+    }
+
+    /**
+     * Returns the url a redirect of the robots.txt points to, or null when it has no location or
+     * leaves the host, whose robots.txt would not apply
+     */
+    private static URL redirectTarget(URL robotsUrl, String location)
+            throws URISyntaxException, MalformedURLException {
+        if (location == null) {
+            return null;
+        }
+        final URL target = robotsUrl.toURI().resolve(location).toURL();
+        if (!isHttp(target) || !target.getHost().equalsIgnoreCase(robotsUrl.getHost())) {
+            return null;
+        }
+        return target;
+    }
+
+    private static boolean isHttp(URL url) {
+        final String protocol = url.getProtocol();
+        return "http".equalsIgnoreCase(protocol) || "https".equalsIgnoreCase(protocol);
+    }
+}
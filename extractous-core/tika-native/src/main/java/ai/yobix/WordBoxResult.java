@@ -0,0 +1,113 @@
+package ai.yobix;
+
+import java.util.ArrayList;
+import java.util.Arrays;
+import java.util.List;
+
+/**
+ * Words of a PDF document with their bounding boxes. A document easily holds tens of thousands of
+ * words, so they are exposed as parallel arrays that the native side copies in a few JNI calls
+ * instead of one object per word.
+ */
+public class WordBoxResult {
+
+    private final String[] texts;
+    private final int[] pages;
+    private final float[] boxes;
+    private final byte status;
+    private final String errorMessage;
+
+    private WordBoxResult(String[] texts, int[] pages, float[] boxes) {
+        this.texts = texts;
+        this.pages = pages;
+        this.boxes = boxes;
+        this.status = 0;
+        this.errorMessage = null;
+    }
+
+    public WordBoxResult(byte status, String errorMessage) {
+        this.texts = null;
+        this.pages = null;
+        this.boxes = null;
+        this.status = status;
+        this.errorMessage = errorMessage;
+    }
+
+    /**
+     * Returns the text of each word
+     */
+    public String[] getTexts() {
+        return texts;
+    }
+
+    /**
+     * Returns the 1-based page number of each word
+     */
+    public int[] getPages() {
+        return pages;
+    }
+
+    /**
+     * Returns the boxes of the words as consecutive x, y, width, height quadruples
+     */
+    public float[] getBoxes() {
+        return boxes;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException
+     * 2: TikaException
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage +
+                " words: " + (texts != null ? texts.length : 0);
+    }
+
+    static class Builder {
+        private final List<String> texts = new ArrayList<>();
+        private int[] pages = new int[256];
+        private float[] boxes = new float[256 * 4];
+
+        void add(String text, int page, float x, float y, float width, float height) {
+            final int i = texts.size();
+            if (i == pages.length) {
+                pages = Arrays.copyOf(pages, i * 2);
+                boxes = Arrays.copyOf(boxes, i * 2 * 4);
+            }
+            texts.add(text);
+            pages[i] = page;
+            boxes[i * 4] = x;
+            boxes[i * 4 + 1] = y;
+            boxes[i * 4 + 2] = width;
+            boxes[i * 4 + 3] = height;
+        }
+
+        WordBoxResult build() {
+            final int count = texts.size();
+            return new WordBoxResult(
+                    texts.toArray(new String[0]),
+                    Arrays.copyOf(pages, count),
+                    Arrays.copyOf(boxes, count * 4));
+        }
+    }
+}
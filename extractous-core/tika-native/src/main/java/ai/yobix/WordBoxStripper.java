@@ -0,0 +1,84 @@
+package ai.yobix;
+
+import org.apache.pdfbox.pdmodel.PDDocument;
+import org.apache.pdfbox.pdmodel.PDPage;
+import org.apache.pdfbox.text.PDFTextStripper;
+import org.apache.pdfbox.text.TextPosition;
+
+import java.io.IOException;
+import java.io.Writer;
+import java.util.List;
+
+/**
+ * Collects the words of a PDF document together with their bounding boxes. Words are the runs of
+ * non blank glyphs of each text chunk reported by PDFBox.
+ * Boxes are in PDF points (1/72 inch) with the origin at the top-left corner of the page, after
+ * the page rotation has been applied, x growing to the right and y growing downwards.
+ */
+class WordBoxStripper extends PDFTextStripper {
+
+    private final WordBoxResult.Builder words = new WordBoxResult.Builder();
+
+    private final StringBuilder text = new StringBuilder();
+    private float minX;
+    private float minY;
+    private float maxX;
+    private float maxY;
+
+    WordBoxStripper() {
+        super();
+        setSortByPosition(true);
+    }
+
+    /**
+     * Returns the words of all pages of the given document
+     */
+    static WordBoxResult extract(PDDocument document) throws IOException {
+        final WordBoxStripper stripper = new WordBoxStripper();
+        stripper.writeText(document, Writer.nullWriter());
+        return stripper.words.build();
+    }
+
+    @Override
+    protected void writeString(String string, List<TextPosition> textPositions) throws IOException {
+        for (TextPosition position : textPositions) {
+            final String unicode = position.getUnicode();
+            if (unicode == null || unicode.isBlank()) {
+                flushWord();
+                continue;
+            }
+
+            final float x = position.getXDirAdj();
+            final float y = position.getYDirAdj() - position.getHeightDir();
+            final float right = x + position.getWidthDirAdj();
+            final float bottom = position.getYDirAdj();
+            if (text.length() == 0) {
+                minX = x;
+                minY = y;
+                maxX = right;
+                maxY = bottom;
+            } else {
+                minX = Math.min(minX, x);
+                minY = Math.min(minY, y);
+                maxX = Math.max(maxX, right);
+                maxY = Math.max(maxY, bottom);
+            }
+            text.append(unicode);
+        }
+        flushWord();
+    }
+
+    @Override
+    protected void endPage(PDPage page) throws IOException {
+        flushWord();
+        super.endPage(page);
+    }
+
+    private void flushWord() {
+        if (text.length() == 0) {
+            return;
+        }
+        words.add(text.toString(), getCurrentPageNo(), minX, minY, maxX - minX, maxY - minY);
+        text.setLength(0);
+    }
+}
@@ -0,0 +1,188 @@
+package ai.yobix;
+
+import org.apache.commons.compress.archivers.zip.ZipArchiveEntry;
+import org.apache.commons.compress.archivers.zip.ZipArchiveInputStream;
+import org.apache.commons.compress.archivers.zip.ZipArchiveOutputStream;
+import org.apache.tika.exception.EncryptedDocumentException;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.Arrays;
+import java.util.Set;
+
+/**
+ * Recovers what it can of the ZIP based documents, archives, OOXML documents, JARs and the like,
+ * whose central directory is corrupt or missing, for instance because the file was truncated.
+ * When the parse of such a document fails, its entries are read again one after the other from
+ * their local file headers, up to the first one that can not be read, and copied to a new archive
+ * with a proper central directory, which is parsed instead. The entry cut short by a truncation
+ * is kept with the data read up to the cut.
+ * <p>
+ * As in {@link ParserFallbackChain}, the output of the failed parse is dropped. Only the
+ * documents starting with a ZIP local file header are spooled to a temporary file to be read
+ * again, and encrypted documents are left as they are.
+ */
+public class ZipRecovery {
+
+    /**
+     * Metadata key set to the number of entries recovered when the document was parsed from its
+     * recovered entries, absent otherwise
+     */
+    public static final String ZIP_RECOVERED_ENTRIES = "extractous:zip_recovered_entries";
+
+    private static final byte[] LOCAL_FILE_HEADER = {'P', 'K', 3, 4};
+
+    private ZipRecovery() {
+    }
+
+    /**
+     * Returns a parser that recovers the ZIP based documents it fails on, or the given parser
+     * when recover is false
+     */
+    static Parser wrap(Parser parser, boolean recover) {
+        if (!recover) {
+            return parser;
+        }
+        return new RecoveringParser(parser);
+    }
+
+    private static boolean startsWithLocalFileHeader(TikaInputStream tis) throws IOException {
+        final byte[] head = new byte[LOCAL_FILE_HEADER.length];
+        tis.mark(head.length);
+        try {
+            return tis.read(head) == head.length && Arrays.equals(head, LOCAL_FILE_HEADER);
+        } finally {
+            tis.reset();
+        }
+    }
+
+    /**
+     * Copies the entries of the archive that can be read from their local file headers to a new
+     * archive, and returns how many were copied
+     */
+    private static int salvage(InputStream brokenZip, Path salvagedZip) throws IOException {
+        int count = 0;
+        try (ZipArchiveInputStream zis = new ZipArchiveInputStream(brokenZip, "UTF-8", true, true);
+             ZipArchiveOutputStream zos = new ZipArchiveOutputStream(salvagedZip.toFile())) {
+            final byte[] buffer = new byte[8192];
+            while (true) {
+                final ZipArchiveEntry entry;
+                try {
+                    entry = zis.getNextZipEntry();
+                } catch (IOException e) {
+                    break;
+                }
+                if (entry == null) {
+                    break;
+                }
+                if (!zis.canReadEntryData(entry)) {
+                    continue;
+                }
+                final ZipArchiveEntry copy = new ZipArchiveEntry(entry.getName());
+                copy.setTime(entry.getTime());
+                zos.putArchiveEntry(copy);
+                count++;
+                boolean truncated = false;
+                try {
+                    int n;
+                    while ((n = zis.read(buffer)) != -1) {
+                        zos.write(buffer, 0, n);
+                    }
+                } catch (IOException e) {
+                    truncated = true;
+                }
+                zos.closeArchiveEntry();
+                if (truncated) {
+                    break;
+                }
+            }
+            zos.finish();
+        }
+        return count;
+    }
+
+    private static void rethrow(Exception e) throws IOException, SAXException, TikaException {
+        if (e instanceof IOException) {
+            throw (IOException) e;
+        }
+        if (e instanceof SAXException) {
+            throw (SAXException) e;
+        }
+        throw (TikaException) e;
+    }
+
+    private static class RecoveringParser extends ParserDecorator {
+
+        RecoveringParser(Parser parser) {
+            super(parser);
+        }
+
+        @Override
+        public Set<MediaType> getSupportedTypes(ParseContext context) {
+            return getWrappedParser().getSupportedTypes(context);
+        }
+
+        @Override
+        public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+                throws IOException, SAXException, TikaException {
+            try (TemporaryResources tmp = new TemporaryResources()) {
+                final TikaInputStream tis = TikaInputStream.get(stream, tmp, metadata);
+                if (!startsWithLocalFileHeader(tis)) {
+                    super.parse(tis, handler, metadata, context);
+                    return;
+                }
+                final Path path = tis.getPath();
+
+                final Exception failure;
+                SaxRecorder recorder = new SaxRecorder();
+                try {
+                    super.parse(tis, recorder, metadata, context);
+                    recorder.replay(handler);
+                    return;
+                } catch (IOException | SAXException | TikaException e) {
+                    if (e instanceof EncryptedDocumentException) {
+                        throw e;
+                    }
+                    failure = e;
+                }
+
+                final Path salvaged = tmp.createTempFile();
+                final int entries;
+                try (InputStream broken = Files.newInputStream(path)) {
+                    entries = salvage(broken, salvaged);
+                } catch (IOException e) {
+                    failure.addSuppressed(e);
+                    rethrow(failure);
+                    return;
+                }
+                if (entries == 0) {
+                    rethrow(failure);
+                }
+
+                recorder = new SaxRecorder();
+                try (TikaInputStream retry = TikaInputStream.get(salvaged)) {
+                    super.parse(retry, recorder, metadata, context);
+                } catch (IOException | SAXException | TikaException e) {
+                    failure.addSuppressed(e);
+                    rethrow(failure);
+                }
+                metadata.set(ZIP_RECOVERED_ENTRIES, Integer.toString(entries));
+                metadata.add(TikaCoreProperties.TIKA_META_EXCEPTION_WARNING, failure.toString());
+                recorder.replay(handler);
+            }
+        }
+    }
+}
@@ -295,6 +295,8 @@ public class DebugTest {
                     pdfConfig,
                     officeConfig,
                     tesseractConfig,
+                    new RecursiveConfig(),
+                    new ParseOptions(),
                     false
             );
 
@@ -351,6 +353,7 @@ public class DebugTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,          // asXML
                 false           // asEmbedded - disables embedded document extraction
         );
@@ -386,6 +389,8 @@ public class DebugTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new RecursiveConfig(),
+                new ParseOptions(),
                 false
         );
 
@@ -444,6 +449,8 @@ public class DebugTest {
                     pdfConfig,
                     officeConfig,
                     tesseractConfig,
+                    new RecursiveConfig(),
+                    new ParseOptions(),
                     false
             );
 
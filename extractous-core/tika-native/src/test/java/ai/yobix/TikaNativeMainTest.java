@@ -9,12 +9,17 @@ import org.junit.jupiter.api.Test;
 import org.junit.jupiter.api.io.TempDir;
 
 import java.io.BufferedReader;
+import java.io.ByteArrayOutputStream;
 import java.io.IOException;
 import java.io.InputStreamReader;
 import java.nio.ByteBuffer;
+import java.nio.charset.StandardCharsets;
 import java.nio.file.Files;
 import java.nio.file.Path;
+import java.util.ArrayList;
 import java.util.List;
+import java.util.zip.ZipEntry;
+import java.util.zip.ZipOutputStream;
 
 import com.fasterxml.jackson.databind.JsonNode;
 import com.fasterxml.jackson.databind.ObjectMapper;
@@ -51,6 +56,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 false
         );
@@ -76,6 +82,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 false
         );
@@ -102,6 +109,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 false
         );
@@ -136,6 +144,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 false
         );
@@ -161,6 +170,8 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new RecursiveConfig(),
+                new ParseOptions(),
                 false
         );
 
@@ -180,6 +191,33 @@ class TikaNativeMainTest {
         assertTrue(content.contains(testContent));
     }
 
+    @Test
+    void testParseFileRecursiveWarc() {
+        RecursiveResult result = TikaNativeMain.parseFileRecursive(
+                "../../test_files/documents/web-archive.warc",
+                10000,
+                new PDFParserConfig(),
+                new OfficeParserConfig(),
+                new TesseractOCRConfig(),
+                new RecursiveConfig(),
+                new ParseOptions(),
+                false
+        );
+
+        assertNotNull(result);
+        assertFalse(result.isError(), result.getErrorMessage());
+
+        List<String> urls = new ArrayList<>();
+        for (Metadata metadata : result.getMetadataList()) {
+            String url = metadata.get("warc:WARC-Target-URI");
+            if (url != null) {
+                urls.add(url);
+                assertNotNull(metadata.get("warc:WARC-Date"));
+            }
+        }
+        assertEquals(List.of("https://example.com/", "https://example.org/news"), urls);
+    }
+
     @Test
     void testParseBytesRecursive() throws IOException {
         String testContent = "Recursive parsing test content from bytes.";
@@ -195,6 +233,8 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new RecursiveConfig(),
+                new ParseOptions(),
                 false
         );
 
@@ -211,6 +251,70 @@ class TikaNativeMainTest {
         assertTrue(content.contains(testContent));
     }
 
+    @Test
+    void testParseFileRecursiveMaxContainerDepth(@TempDir Path tempDir) throws IOException {
+        // outer.zip -> [top.txt, inner.zip -> [nested.txt]]
+        ByteArrayOutputStream innerBytes = new ByteArrayOutputStream();
+        try (ZipOutputStream inner = new ZipOutputStream(innerBytes)) {
+            inner.putNextEntry(new ZipEntry("nested.txt"));
+            inner.write("nested attachment text".getBytes(StandardCharsets.UTF_8));
+            inner.closeEntry();
+        }
+        Path testFile = tempDir.resolve("outer.zip");
+        try (ZipOutputStream outer = new ZipOutputStream(Files.newOutputStream(testFile))) {
+            outer.putNextEntry(new ZipEntry("top.txt"));
+            outer.write("top level attachment text".getBytes(StandardCharsets.UTF_8));
+            outer.closeEntry();
+            outer.putNextEntry(new ZipEntry("inner.zip"));
+            outer.write(innerBytes.toByteArray());
+            outer.closeEntry();
+        }
+
+        RecursiveConfig recursiveConfig = new RecursiveConfig();
+        recursiveConfig.setMaxContainerDepth(1);
+
+        RecursiveResult result = TikaNativeMain.parseFileRecursive(
+                testFile.toString(),
+                10000,
+                new PDFParserConfig(),
+                new OfficeParserConfig(),
+                new TesseractOCRConfig(),
+                recursiveConfig,
+                new ParseOptions(),
+                false
+        );
+
+        assertFalse(result.isError());
+        List<String> names = new ArrayList<>();
+        for (Metadata metadata : result.getMetadataList()) {
+            names.add(metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY));
+        }
+        assertTrue(names.contains("top.txt"));
+        assertTrue(names.contains("inner.zip"));
+        assertFalse(names.contains("nested.txt"), "Should not descend past depth 1");
+    }
+
+    @Test
+    void testGetSignatureInfoUnsigned(@TempDir Path tempDir) throws IOException {
+        Path testFile = tempDir.resolve("test.txt");
+        Files.writeString(testFile, "Not a signed document");
+
+        SignatureResult result = TikaNativeMain.getSignatureInfo(testFile.toString());
+
+        assertNotNull(result);
+        assertFalse(result.isError());
+        assertEquals(0, result.getSignatures().length);
+    }
+
+    @Test
+    void testGetSignatureInfoNonExistentFile() {
+        SignatureResult result = TikaNativeMain.getSignatureInfo("non_existent_file.pdf");
+
+        assertNotNull(result);
+        assertTrue(result.isError());
+        assertEquals(1, result.getStatus());
+    }
+
     @Test
     void testParseFileWithEmbeddedDisabled(@TempDir Path tempDir) throws IOException {
         Path testFile = tempDir.resolve("test.txt");
@@ -228,6 +332,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 false
         );
@@ -267,6 +372,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 true
         );
@@ -283,6 +389,7 @@ class TikaNativeMainTest {
                 pdfConfig,
                 officeConfig,
                 tesseractConfig,
+                new ParseOptions(),
                 false,
                 false
         );
@@ -422,6 +529,7 @@ class TikaNativeMainTest {
                     pdfConfig,
                     officeConfig,
                     tesseractConfig,
+                    new ParseOptions(),
                     false,
                     false
             );
@@ -478,6 +586,7 @@ class TikaNativeMainTest {
                     pdfConfig,
                     officeConfig,
                     tesseractConfig,
+                    new ParseOptions(),
                     false,
                     false
             );
@@ -606,4 +715,50 @@ class TikaNativeMainTest {
         // Ensure no unexpected fields
         assertEquals(4, json.size(), "JSON should have exactly 4 fields");
     }
+
+    @Test
+    void testSetMaxOcrConcurrency(@TempDir Path tempDir) throws IOException {
+        final int previous = OcrLimiter.getMaxConcurrency();
+        try {
+            TikaNativeMain.setMaxOcrConcurrency(0);
+            assertEquals(1, OcrLimiter.getMaxConcurrency(), "Limit should never go below 1");
+
+            TikaNativeMain.setMaxOcrConcurrency(3);
+            assertEquals(3, OcrLimiter.getMaxConcurrency());
+
+            // Parsing still works with the limited parser
+            Path testFile = tempDir.resolve("test.txt");
+            Files.writeString(testFile, "OCR limited parse.");
+            StringResult result = TikaNativeMain.parseFileToString(
+                    testFile.toString(), 1000, new PDFParserConfig(), new OfficeParserConfig(),
+                    new TesseractOCRConfig(), new ParseOptions(), false, false);
+            assertFalse(result.isError());
+            assertTrue(result.getContent().contains("OCR limited parse."));
+        } finally {
+            TikaNativeMain.setMaxOcrConcurrency(previous);
+        }
+    }
+
+    @Test
+    void testMaxElementDepth() {
+        final int depth = 400;
+        final String html = "<html><body>" + "<div>".repeat(depth) + "deep text"
+                + "</div>".repeat(depth) + "</body></html>";
+
+        final ParseOptions limited = new ParseOptions();
+        limited.setMaxElementDepth(100);
+        StringResult result = TikaNativeMain.parseBytesToString(
+                ByteBuffer.wrap(html.getBytes(StandardCharsets.UTF_8)), 10000, new PDFParserConfig(),
+                new OfficeParserConfig(), new TesseractOCRConfig(), limited, false, false);
+        assertTrue(result.isError());
+        assertEquals(2, result.getStatus());
+
+        final ParseOptions unlimited = new ParseOptions();
+        unlimited.setMaxElementDepth(0);
+        result = TikaNativeMain.parseBytesToString(
+                ByteBuffer.wrap(html.getBytes(StandardCharsets.UTF_8)), 10000, new PDFParserConfig(),
+                new OfficeParserConfig(), new TesseractOCRConfig(), unlimited, false, false);
+        assertFalse(result.isError());
+        assertTrue(result.getContent().contains("deep text"));
+    }
 }